@@ -170,6 +170,7 @@ fn operate_struct(ds: DataStruct, ident: Ident) -> TokenStream {
 
         #[new]
         #[doc = #new_msg]
+        #[allow(clippy::too_many_arguments)]
         fn new(#(#input_arguments),*) -> PyResult<Self>{
             #(#conversion_quotes)*
             Ok(Self{internal: #ident::new(#(#arguments),*)})
@@ -118,8 +118,10 @@ const SOURCE_FILES: &[&str] = &[
     "src/operations/single_qubit_gate_operations.rs",
     "src/operations/pragma_operations.rs",
     "src/operations/two_qubit_gate_operations.rs",
+    "src/operations/multi_qubit_gate_operations.rs",
     "src/operations/measurement_operations.rs",
     "src/operations/define_operations.rs",
+    "src/operations/classical_operations.rs",
 ];
 
 fn main() {
@@ -211,7 +213,7 @@ fn main() {
                 quote! {
                     Operation::#ident(internal) => {
                         let pyref: Py<#wrapper_ident> =
-                            Py::new(py, #wrapper_ident { internal }).unwrap();
+                            Py::new(py, #wrapper_ident { internal: internal.into() }).unwrap();
                         let pyobject: PyObject = pyref.to_object(py);
                         Ok(pyobject)
                     }
@@ -236,6 +238,9 @@ fn main() {
         use num_complex::Complex64;
 
         /// Tries to convert a [roqoqo::operations::Operation] to a PyObject
+        // Operations stored boxed in the enums are moved out of the Box with into,
+        // for all other operations into does not change the type
+        #[allow(clippy::useless_conversion)]
         pub fn convert_operation_to_pyobject(operation: Operation) -> PyResult<PyObject> {
             Python::with_gil(|py| -> PyResult<PyObject> {
             match operation {
@@ -16,6 +16,7 @@ use pyo3::exceptions::{PyIndexError, PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyByteArray;
 use pyo3::{PyIterProtocol, PyMappingProtocol, PyNumberProtocol, PyObjectProtocol};
+use roqoqo::formats::{circuit_to_text_diagram, DiagramCharset};
 use roqoqo::prelude::*;
 use roqoqo::{Circuit, OperationIterator, ROQOQO_VERSION};
 use std::collections::HashSet;
@@ -164,6 +165,23 @@ impl CircuitWrapper {
         operations
     }
 
+    /// Draw the Circuit as a text diagram with one wire per qubit and classical register.
+    ///
+    /// Args:
+    ///     unicode (Optional[bool]): Use Unicode box-drawing characters, ASCII characters are used when False. Defaults to True.
+    ///     max_width (Optional[int]): Fold the diagram into blocks of at most max_width characters. Defaults to no folding.
+    ///
+    /// Returns:
+    ///     str: The diagram of the Circuit.
+    pub fn draw(&self, unicode: Option<bool>, max_width: Option<usize>) -> String {
+        let charset = if unicode.unwrap_or(true) {
+            DiagramCharset::Unicode
+        } else {
+            DiagramCharset::Ascii
+        };
+        circuit_to_text_diagram(&self.internal, charset, max_width)
+    }
+
     /// Return a copy of the Circuit (copy here produces a deepcopy).
     ///
     /// Returns:
@@ -10,350 +10,854 @@ use qoqo_calculator_pyo3::convert_into_calculator_float;
 use roqoqo::operations::*;
 use std::collections::HashMap;
 #[doc = r" Tries to convert a [roqoqo::operations::Operation] to a PyObject"]
+#[allow(clippy::useless_conversion)]
 pub fn convert_operation_to_pyobject(operation: Operation) -> PyResult<PyObject> {
     Python::with_gil(|py| -> PyResult<PyObject> {
         match operation {
             Operation::SingleQubitGate(internal) => {
-                let pyref: Py<SingleQubitGateWrapper> =
-                    Py::new(py, SingleQubitGateWrapper { internal }).unwrap();
+                let pyref: Py<SingleQubitGateWrapper> = Py::new(
+                    py,
+                    SingleQubitGateWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::RotateX(internal) => {
-                let pyref: Py<RotateXWrapper> = Py::new(py, RotateXWrapper { internal }).unwrap();
+                let pyref: Py<RotateXWrapper> = Py::new(
+                    py,
+                    RotateXWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::RotateY(internal) => {
-                let pyref: Py<RotateYWrapper> = Py::new(py, RotateYWrapper { internal }).unwrap();
+                let pyref: Py<RotateYWrapper> = Py::new(
+                    py,
+                    RotateYWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::RotateZ(internal) => {
-                let pyref: Py<RotateZWrapper> = Py::new(py, RotateZWrapper { internal }).unwrap();
+                let pyref: Py<RotateZWrapper> = Py::new(
+                    py,
+                    RotateZWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PauliX(internal) => {
-                let pyref: Py<PauliXWrapper> = Py::new(py, PauliXWrapper { internal }).unwrap();
+                let pyref: Py<PauliXWrapper> = Py::new(
+                    py,
+                    PauliXWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PauliY(internal) => {
-                let pyref: Py<PauliYWrapper> = Py::new(py, PauliYWrapper { internal }).unwrap();
+                let pyref: Py<PauliYWrapper> = Py::new(
+                    py,
+                    PauliYWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PauliZ(internal) => {
-                let pyref: Py<PauliZWrapper> = Py::new(py, PauliZWrapper { internal }).unwrap();
+                let pyref: Py<PauliZWrapper> = Py::new(
+                    py,
+                    PauliZWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::SqrtPauliX(internal) => {
-                let pyref: Py<SqrtPauliXWrapper> =
-                    Py::new(py, SqrtPauliXWrapper { internal }).unwrap();
+                let pyref: Py<SqrtPauliXWrapper> = Py::new(
+                    py,
+                    SqrtPauliXWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::InvSqrtPauliX(internal) => {
-                let pyref: Py<InvSqrtPauliXWrapper> =
-                    Py::new(py, InvSqrtPauliXWrapper { internal }).unwrap();
+                let pyref: Py<InvSqrtPauliXWrapper> = Py::new(
+                    py,
+                    InvSqrtPauliXWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::Hadamard(internal) => {
-                let pyref: Py<HadamardWrapper> = Py::new(py, HadamardWrapper { internal }).unwrap();
+                let pyref: Py<HadamardWrapper> = Py::new(
+                    py,
+                    HadamardWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::SGate(internal) => {
-                let pyref: Py<SGateWrapper> = Py::new(py, SGateWrapper { internal }).unwrap();
+                let pyref: Py<SGateWrapper> = Py::new(
+                    py,
+                    SGateWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::TGate(internal) => {
-                let pyref: Py<TGateWrapper> = Py::new(py, TGateWrapper { internal }).unwrap();
+                let pyref: Py<TGateWrapper> = Py::new(
+                    py,
+                    TGateWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::RotateAroundSphericalAxis(internal) => {
-                let pyref: Py<RotateAroundSphericalAxisWrapper> =
-                    Py::new(py, RotateAroundSphericalAxisWrapper { internal }).unwrap();
+                let pyref: Py<RotateAroundSphericalAxisWrapper> = Py::new(
+                    py,
+                    RotateAroundSphericalAxisWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaSetNumberOfMeasurements(internal) => {
-                let pyref: Py<PragmaSetNumberOfMeasurementsWrapper> =
-                    Py::new(py, PragmaSetNumberOfMeasurementsWrapper { internal }).unwrap();
+                let pyref: Py<PragmaSetNumberOfMeasurementsWrapper> = Py::new(
+                    py,
+                    PragmaSetNumberOfMeasurementsWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaRepeatGate(internal) => {
-                let pyref: Py<PragmaRepeatGateWrapper> =
-                    Py::new(py, PragmaRepeatGateWrapper { internal }).unwrap();
+                let pyref: Py<PragmaRepeatGateWrapper> = Py::new(
+                    py,
+                    PragmaRepeatGateWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaOverrotation(internal) => {
-                let pyref: Py<PragmaOverrotationWrapper> =
-                    Py::new(py, PragmaOverrotationWrapper { internal }).unwrap();
+                let pyref: Py<PragmaOverrotationWrapper> = Py::new(
+                    py,
+                    PragmaOverrotationWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaBoostNoise(internal) => {
-                let pyref: Py<PragmaBoostNoiseWrapper> =
-                    Py::new(py, PragmaBoostNoiseWrapper { internal }).unwrap();
+                let pyref: Py<PragmaBoostNoiseWrapper> = Py::new(
+                    py,
+                    PragmaBoostNoiseWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaStopParallelBlock(internal) => {
-                let pyref: Py<PragmaStopParallelBlockWrapper> =
-                    Py::new(py, PragmaStopParallelBlockWrapper { internal }).unwrap();
+                let pyref: Py<PragmaStopParallelBlockWrapper> = Py::new(
+                    py,
+                    PragmaStopParallelBlockWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaGlobalPhase(internal) => {
-                let pyref: Py<PragmaGlobalPhaseWrapper> =
-                    Py::new(py, PragmaGlobalPhaseWrapper { internal }).unwrap();
+                let pyref: Py<PragmaGlobalPhaseWrapper> = Py::new(
+                    py,
+                    PragmaGlobalPhaseWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaSleep(internal) => {
-                let pyref: Py<PragmaSleepWrapper> =
-                    Py::new(py, PragmaSleepWrapper { internal }).unwrap();
+                let pyref: Py<PragmaSleepWrapper> = Py::new(
+                    py,
+                    PragmaSleepWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaActiveReset(internal) => {
-                let pyref: Py<PragmaActiveResetWrapper> =
-                    Py::new(py, PragmaActiveResetWrapper { internal }).unwrap();
+                let pyref: Py<PragmaActiveResetWrapper> = Py::new(
+                    py,
+                    PragmaActiveResetWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaStartDecompositionBlock(internal) => {
-                let pyref: Py<PragmaStartDecompositionBlockWrapper> =
-                    Py::new(py, PragmaStartDecompositionBlockWrapper { internal }).unwrap();
+                let pyref: Py<PragmaStartDecompositionBlockWrapper> = Py::new(
+                    py,
+                    PragmaStartDecompositionBlockWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaStopDecompositionBlock(internal) => {
-                let pyref: Py<PragmaStopDecompositionBlockWrapper> =
-                    Py::new(py, PragmaStopDecompositionBlockWrapper { internal }).unwrap();
+                let pyref: Py<PragmaStopDecompositionBlockWrapper> = Py::new(
+                    py,
+                    PragmaStopDecompositionBlockWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaDamping(internal) => {
-                let pyref: Py<PragmaDampingWrapper> =
-                    Py::new(py, PragmaDampingWrapper { internal }).unwrap();
+                let pyref: Py<PragmaDampingWrapper> = Py::new(
+                    py,
+                    PragmaDampingWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaDepolarising(internal) => {
-                let pyref: Py<PragmaDepolarisingWrapper> =
-                    Py::new(py, PragmaDepolarisingWrapper { internal }).unwrap();
+                let pyref: Py<PragmaDepolarisingWrapper> = Py::new(
+                    py,
+                    PragmaDepolarisingWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaDephasing(internal) => {
-                let pyref: Py<PragmaDephasingWrapper> =
-                    Py::new(py, PragmaDephasingWrapper { internal }).unwrap();
+                let pyref: Py<PragmaDephasingWrapper> = Py::new(
+                    py,
+                    PragmaDephasingWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaRandomNoise(internal) => {
-                let pyref: Py<PragmaRandomNoiseWrapper> =
-                    Py::new(py, PragmaRandomNoiseWrapper { internal }).unwrap();
+                let pyref: Py<PragmaRandomNoiseWrapper> = Py::new(
+                    py,
+                    PragmaRandomNoiseWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaConditional(internal) => {
-                let pyref: Py<PragmaConditionalWrapper> =
-                    Py::new(py, PragmaConditionalWrapper { internal }).unwrap();
+                let pyref: Py<PragmaConditionalWrapper> = Py::new(
+                    py,
+                    PragmaConditionalWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::PragmaConditionalPattern(internal) => {
+                let pyref: Py<PragmaConditionalPatternWrapper> = Py::new(
+                    py,
+                    PragmaConditionalPatternWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::PragmaLoop(internal) => {
+                let pyref: Py<PragmaLoopWrapper> = Py::new(
+                    py,
+                    PragmaLoopWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::PragmaRepeatUntilSuccess(internal) => {
+                let pyref: Py<PragmaRepeatUntilSuccessWrapper> = Py::new(
+                    py,
+                    PragmaRepeatUntilSuccessWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::CNOT(internal) => {
-                let pyref: Py<CNOTWrapper> = Py::new(py, CNOTWrapper { internal }).unwrap();
+                let pyref: Py<CNOTWrapper> = Py::new(
+                    py,
+                    CNOTWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::SWAP(internal) => {
-                let pyref: Py<SWAPWrapper> = Py::new(py, SWAPWrapper { internal }).unwrap();
+                let pyref: Py<SWAPWrapper> = Py::new(
+                    py,
+                    SWAPWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::ISwap(internal) => {
-                let pyref: Py<ISwapWrapper> = Py::new(py, ISwapWrapper { internal }).unwrap();
+                let pyref: Py<ISwapWrapper> = Py::new(
+                    py,
+                    ISwapWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::SqrtISwap(internal) => {
-                let pyref: Py<SqrtISwapWrapper> =
-                    Py::new(py, SqrtISwapWrapper { internal }).unwrap();
+                let pyref: Py<SqrtISwapWrapper> = Py::new(
+                    py,
+                    SqrtISwapWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::InvSqrtISwap(internal) => {
-                let pyref: Py<InvSqrtISwapWrapper> =
-                    Py::new(py, InvSqrtISwapWrapper { internal }).unwrap();
+                let pyref: Py<InvSqrtISwapWrapper> = Py::new(
+                    py,
+                    InvSqrtISwapWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::FSwap(internal) => {
-                let pyref: Py<FSwapWrapper> = Py::new(py, FSwapWrapper { internal }).unwrap();
+                let pyref: Py<FSwapWrapper> = Py::new(
+                    py,
+                    FSwapWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::MolmerSorensenXX(internal) => {
-                let pyref: Py<MolmerSorensenXXWrapper> =
-                    Py::new(py, MolmerSorensenXXWrapper { internal }).unwrap();
+                let pyref: Py<MolmerSorensenXXWrapper> = Py::new(
+                    py,
+                    MolmerSorensenXXWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::VariableMSXX(internal) => {
-                let pyref: Py<VariableMSXXWrapper> =
-                    Py::new(py, VariableMSXXWrapper { internal }).unwrap();
+                let pyref: Py<VariableMSXXWrapper> = Py::new(
+                    py,
+                    VariableMSXXWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::GivensRotation(internal) => {
-                let pyref: Py<GivensRotationWrapper> =
-                    Py::new(py, GivensRotationWrapper { internal }).unwrap();
+                let pyref: Py<GivensRotationWrapper> = Py::new(
+                    py,
+                    GivensRotationWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::GivensRotationLittleEndian(internal) => {
-                let pyref: Py<GivensRotationLittleEndianWrapper> =
-                    Py::new(py, GivensRotationLittleEndianWrapper { internal }).unwrap();
+                let pyref: Py<GivensRotationLittleEndianWrapper> = Py::new(
+                    py,
+                    GivensRotationLittleEndianWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::XY(internal) => {
-                let pyref: Py<XYWrapper> = Py::new(py, XYWrapper { internal }).unwrap();
+                let pyref: Py<XYWrapper> = Py::new(
+                    py,
+                    XYWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::ControlledPhaseShift(internal) => {
-                let pyref: Py<ControlledPhaseShiftWrapper> =
-                    Py::new(py, ControlledPhaseShiftWrapper { internal }).unwrap();
+                let pyref: Py<ControlledPhaseShiftWrapper> = Py::new(
+                    py,
+                    ControlledPhaseShiftWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::ControlledPauliY(internal) => {
-                let pyref: Py<ControlledPauliYWrapper> =
-                    Py::new(py, ControlledPauliYWrapper { internal }).unwrap();
+                let pyref: Py<ControlledPauliYWrapper> = Py::new(
+                    py,
+                    ControlledPauliYWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::ControlledPauliZ(internal) => {
-                let pyref: Py<ControlledPauliZWrapper> =
-                    Py::new(py, ControlledPauliZWrapper { internal }).unwrap();
+                let pyref: Py<ControlledPauliZWrapper> = Py::new(
+                    py,
+                    ControlledPauliZWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::Qsim(internal) => {
-                let pyref: Py<QsimWrapper> = Py::new(py, QsimWrapper { internal }).unwrap();
+                let pyref: Py<QsimWrapper> = Py::new(
+                    py,
+                    QsimWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::Fsim(internal) => {
-                let pyref: Py<FsimWrapper> = Py::new(py, FsimWrapper { internal }).unwrap();
+                let pyref: Py<FsimWrapper> = Py::new(
+                    py,
+                    FsimWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::SpinInteraction(internal) => {
-                let pyref: Py<SpinInteractionWrapper> =
-                    Py::new(py, SpinInteractionWrapper { internal }).unwrap();
+                let pyref: Py<SpinInteractionWrapper> = Py::new(
+                    py,
+                    SpinInteractionWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::Bogoliubov(internal) => {
-                let pyref: Py<BogoliubovWrapper> =
-                    Py::new(py, BogoliubovWrapper { internal }).unwrap();
+                let pyref: Py<BogoliubovWrapper> = Py::new(
+                    py,
+                    BogoliubovWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PMInteraction(internal) => {
-                let pyref: Py<PMInteractionWrapper> =
-                    Py::new(py, PMInteractionWrapper { internal }).unwrap();
+                let pyref: Py<PMInteractionWrapper> = Py::new(
+                    py,
+                    PMInteractionWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::ComplexPMInteraction(internal) => {
-                let pyref: Py<ComplexPMInteractionWrapper> =
-                    Py::new(py, ComplexPMInteractionWrapper { internal }).unwrap();
+                let pyref: Py<ComplexPMInteractionWrapper> = Py::new(
+                    py,
+                    ComplexPMInteractionWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PhaseShiftedControlledZ(internal) => {
-                let pyref: Py<PhaseShiftedControlledZWrapper> =
-                    Py::new(py, PhaseShiftedControlledZWrapper { internal }).unwrap();
+                let pyref: Py<PhaseShiftedControlledZWrapper> = Py::new(
+                    py,
+                    PhaseShiftedControlledZWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::TwoQubitGate(internal) => {
+                let pyref: Py<TwoQubitGateWrapper> = Py::new(
+                    py,
+                    TwoQubitGateWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::MultiQubitMS(internal) => {
+                let pyref: Py<MultiQubitMSWrapper> = Py::new(
+                    py,
+                    MultiQubitMSWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::Toffoli(internal) => {
+                let pyref: Py<ToffoliWrapper> = Py::new(
+                    py,
+                    ToffoliWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ControlledControlledPauliZ(internal) => {
+                let pyref: Py<ControlledControlledPauliZWrapper> = Py::new(
+                    py,
+                    ControlledControlledPauliZWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ControlledControlledPhaseShift(internal) => {
+                let pyref: Py<ControlledControlledPhaseShiftWrapper> = Py::new(
+                    py,
+                    ControlledControlledPhaseShiftWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::MultiControlledGate(internal) => {
+                let pyref: Py<MultiControlledGateWrapper> = Py::new(
+                    py,
+                    MultiControlledGateWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::PauliProductRotation(internal) => {
+                let pyref: Py<PauliProductRotationWrapper> = Py::new(
+                    py,
+                    PauliProductRotationWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::MeasureQubit(internal) => {
-                let pyref: Py<MeasureQubitWrapper> =
-                    Py::new(py, MeasureQubitWrapper { internal }).unwrap();
+                let pyref: Py<MeasureQubitWrapper> = Py::new(
+                    py,
+                    MeasureQubitWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaGetStateVector(internal) => {
-                let pyref: Py<PragmaGetStateVectorWrapper> =
-                    Py::new(py, PragmaGetStateVectorWrapper { internal }).unwrap();
+                let pyref: Py<PragmaGetStateVectorWrapper> = Py::new(
+                    py,
+                    PragmaGetStateVectorWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaGetDensityMatrix(internal) => {
-                let pyref: Py<PragmaGetDensityMatrixWrapper> =
-                    Py::new(py, PragmaGetDensityMatrixWrapper { internal }).unwrap();
+                let pyref: Py<PragmaGetDensityMatrixWrapper> = Py::new(
+                    py,
+                    PragmaGetDensityMatrixWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaGetOccupationProbability(internal) => {
-                let pyref: Py<PragmaGetOccupationProbabilityWrapper> =
-                    Py::new(py, PragmaGetOccupationProbabilityWrapper { internal }).unwrap();
+                let pyref: Py<PragmaGetOccupationProbabilityWrapper> = Py::new(
+                    py,
+                    PragmaGetOccupationProbabilityWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaGetPauliProduct(internal) => {
-                let pyref: Py<PragmaGetPauliProductWrapper> =
-                    Py::new(py, PragmaGetPauliProductWrapper { internal }).unwrap();
+                let pyref: Py<PragmaGetPauliProductWrapper> = Py::new(
+                    py,
+                    PragmaGetPauliProductWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::PragmaRepeatedMeasurement(internal) => {
-                let pyref: Py<PragmaRepeatedMeasurementWrapper> =
-                    Py::new(py, PragmaRepeatedMeasurementWrapper { internal }).unwrap();
+                let pyref: Py<PragmaRepeatedMeasurementWrapper> = Py::new(
+                    py,
+                    PragmaRepeatedMeasurementWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::DefinitionFloat(internal) => {
-                let pyref: Py<DefinitionFloatWrapper> =
-                    Py::new(py, DefinitionFloatWrapper { internal }).unwrap();
+                let pyref: Py<DefinitionFloatWrapper> = Py::new(
+                    py,
+                    DefinitionFloatWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::DefinitionComplex(internal) => {
-                let pyref: Py<DefinitionComplexWrapper> =
-                    Py::new(py, DefinitionComplexWrapper { internal }).unwrap();
+                let pyref: Py<DefinitionComplexWrapper> = Py::new(
+                    py,
+                    DefinitionComplexWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::DefinitionUsize(internal) => {
-                let pyref: Py<DefinitionUsizeWrapper> =
-                    Py::new(py, DefinitionUsizeWrapper { internal }).unwrap();
+                let pyref: Py<DefinitionUsizeWrapper> = Py::new(
+                    py,
+                    DefinitionUsizeWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::DefinitionBit(internal) => {
-                let pyref: Py<DefinitionBitWrapper> =
-                    Py::new(py, DefinitionBitWrapper { internal }).unwrap();
+                let pyref: Py<DefinitionBitWrapper> = Py::new(
+                    py,
+                    DefinitionBitWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
             Operation::InputSymbolic(internal) => {
-                let pyref: Py<InputSymbolicWrapper> =
-                    Py::new(py, InputSymbolicWrapper { internal }).unwrap();
+                let pyref: Py<InputSymbolicWrapper> = Py::new(
+                    py,
+                    InputSymbolicWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ClassicalNot(internal) => {
+                let pyref: Py<ClassicalNotWrapper> = Py::new(
+                    py,
+                    ClassicalNotWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ClassicalCopy(internal) => {
+                let pyref: Py<ClassicalCopyWrapper> = Py::new(
+                    py,
+                    ClassicalCopyWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ClassicalXor(internal) => {
+                let pyref: Py<ClassicalXorWrapper> = Py::new(
+                    py,
+                    ClassicalXorWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ClassicalAnd(internal) => {
+                let pyref: Py<ClassicalAndWrapper> = Py::new(
+                    py,
+                    ClassicalAndWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ClassicalOr(internal) => {
+                let pyref: Py<ClassicalOrWrapper> = Py::new(
+                    py,
+                    ClassicalOrWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ClassicalGreaterThan(internal) => {
+                let pyref: Py<ClassicalGreaterThanWrapper> = Py::new(
+                    py,
+                    ClassicalGreaterThanWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
+            Operation::ClassicalLessThan(internal) => {
+                let pyref: Py<ClassicalLessThanWrapper> = Py::new(
+                    py,
+                    ClassicalLessThanWrapper {
+                        internal: internal.into(),
+                    },
+                )
+                .unwrap();
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
@@ -375,6 +879,12 @@ pub fn convert_operation_to_pyobject(operation: Operation) -> PyResult<PyObject>
                 let pyobject: PyObject = pyref.to_object(py);
                 Ok(pyobject)
             }
+            Operation::MultiQubitGate(internal) => {
+                let pyref: Py<MultiQubitGateWrapper> =
+                    Py::new(py, MultiQubitGateWrapper { internal }).unwrap();
+                let pyobject: PyObject = pyref.to_object(py);
+                Ok(pyobject)
+            }
         }
     })
 }
@@ -787,6 +1297,71 @@ pub fn convert_pyany_to_operation(op: &PyAny) -> Result<Operation, QoqoError> {
                 convert_into_circuit(circuit_pyobject).map_err(|_| QoqoError::ConversionError)?;
             Ok(PragmaConditional::new(condition_register, condition_index, circuit).into())
         }
+        "PragmaConditionalPattern" => {
+            let condition_register_pyobject = op
+                .call_method0("condition_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let condition_register: String = condition_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let condition_pyobject = op
+                .call_method0("condition")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let condition: Vec<(usize, bool)> = condition_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let circuit_pyobject = op
+                .call_method0("circuit")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let circuit =
+                convert_into_circuit(circuit_pyobject).map_err(|_| QoqoError::ConversionError)?;
+            Ok(PragmaConditionalPattern::new(condition_register, condition, circuit).into())
+        }
+        "PragmaLoop" => {
+            let repetitions_pyobject = op
+                .call_method0("repetitions")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let repetitions = convert_into_calculator_float(repetitions_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let circuit_pyobject = op
+                .call_method0("circuit")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let circuit =
+                convert_into_circuit(circuit_pyobject).map_err(|_| QoqoError::ConversionError)?;
+            Ok(PragmaLoop::new(repetitions, circuit).into())
+        }
+        "PragmaRepeatUntilSuccess" => {
+            let condition_register_pyobject = op
+                .call_method0("condition_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let condition_register: String = condition_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let condition_index_pyobject = op
+                .call_method0("condition_index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let condition_index: usize = condition_index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let max_iterations_pyobject = op
+                .call_method0("max_iterations")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let max_iterations: usize = max_iterations_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let circuit_pyobject = op
+                .call_method0("circuit")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let circuit =
+                convert_into_circuit(circuit_pyobject).map_err(|_| QoqoError::ConversionError)?;
+            Ok(PragmaRepeatUntilSuccess::new(
+                condition_register,
+                condition_index,
+                max_iterations,
+                circuit,
+            )
+            .into())
+        }
         "CNOT" => {
             let control_pyobject = op
                 .call_method0("control")
@@ -1212,6 +1787,216 @@ pub fn convert_pyany_to_operation(op: &PyAny) -> Result<Operation, QoqoError> {
                 .map_err(|_| QoqoError::ConversionError)?;
             Ok(PhaseShiftedControlledZ::new(control, target, phi).into())
         }
+        "TwoQubitGate" => {
+            let control_pyobject = op
+                .call_method0("control")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control: usize = control_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_pyobject = op
+                .call_method0("target")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target: usize = target_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let phase_pyobject = op
+                .call_method0("phase")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let phase = convert_into_calculator_float(phase_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let kx_pyobject = op
+                .call_method0("kx")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let kx = convert_into_calculator_float(kx_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let ky_pyobject = op
+                .call_method0("ky")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let ky = convert_into_calculator_float(ky_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let kz_pyobject = op
+                .call_method0("kz")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let kz = convert_into_calculator_float(kz_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_before_z1_pyobject = op
+                .call_method0("control_before_z1")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_before_z1 = convert_into_calculator_float(control_before_z1_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_before_y_pyobject = op
+                .call_method0("control_before_y")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_before_y = convert_into_calculator_float(control_before_y_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_before_z2_pyobject = op
+                .call_method0("control_before_z2")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_before_z2 = convert_into_calculator_float(control_before_z2_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_before_z1_pyobject = op
+                .call_method0("target_before_z1")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_before_z1 = convert_into_calculator_float(target_before_z1_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_before_y_pyobject = op
+                .call_method0("target_before_y")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_before_y = convert_into_calculator_float(target_before_y_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_before_z2_pyobject = op
+                .call_method0("target_before_z2")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_before_z2 = convert_into_calculator_float(target_before_z2_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_after_z1_pyobject = op
+                .call_method0("control_after_z1")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_after_z1 = convert_into_calculator_float(control_after_z1_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_after_y_pyobject = op
+                .call_method0("control_after_y")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_after_y = convert_into_calculator_float(control_after_y_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_after_z2_pyobject = op
+                .call_method0("control_after_z2")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let control_after_z2 = convert_into_calculator_float(control_after_z2_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_after_z1_pyobject = op
+                .call_method0("target_after_z1")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_after_z1 = convert_into_calculator_float(target_after_z1_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_after_y_pyobject = op
+                .call_method0("target_after_y")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_after_y = convert_into_calculator_float(target_after_y_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_after_z2_pyobject = op
+                .call_method0("target_after_z2")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let target_after_z2 = convert_into_calculator_float(target_after_z2_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(TwoQubitGate::new(
+                control,
+                target,
+                phase,
+                kx,
+                ky,
+                kz,
+                control_before_z1,
+                control_before_y,
+                control_before_z2,
+                target_before_z1,
+                target_before_y,
+                target_before_z2,
+                control_after_z1,
+                control_after_y,
+                control_after_z2,
+                target_after_z1,
+                target_after_y,
+                target_after_z2,
+            )
+            .into())
+        }
+        "MultiQubitMS" => {
+            let qubits_pyobject = op
+                .call_method0("qubits")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let qubits: Vec<usize> = qubits_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let theta_pyobject = op
+                .call_method0("theta")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let theta = convert_into_calculator_float(theta_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(MultiQubitMS::new(qubits, theta).into())
+        }
+        "Toffoli" => {
+            let qubits_pyobject = op
+                .call_method0("qubits")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let qubits: Vec<usize> = qubits_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(Toffoli::new(qubits).into())
+        }
+        "ControlledControlledPauliZ" => {
+            let qubits_pyobject = op
+                .call_method0("qubits")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let qubits: Vec<usize> = qubits_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(ControlledControlledPauliZ::new(qubits).into())
+        }
+        "ControlledControlledPhaseShift" => {
+            let qubits_pyobject = op
+                .call_method0("qubits")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let qubits: Vec<usize> = qubits_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let theta_pyobject = op
+                .call_method0("theta")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let theta = convert_into_calculator_float(theta_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(ControlledControlledPhaseShift::new(qubits, theta).into())
+        }
+        "MultiControlledGate" => {
+            let qubits_pyobject = op
+                .call_method0("qubits")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let qubits: Vec<usize> = qubits_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let phase_pyobject = op
+                .call_method0("phase")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let phase = convert_into_calculator_float(phase_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let z1_pyobject = op
+                .call_method0("z1")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let z1 = convert_into_calculator_float(z1_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let y_pyobject = op
+                .call_method0("y")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let y = convert_into_calculator_float(y_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let z2_pyobject = op
+                .call_method0("z2")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let z2 = convert_into_calculator_float(z2_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(MultiControlledGate::new(qubits, phase, z1, y, z2).into())
+        }
+        "PauliProductRotation" => {
+            let qubits_pyobject = op
+                .call_method0("qubits")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let qubits: Vec<usize> = qubits_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let paulis_pyobject = op
+                .call_method0("paulis")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let paulis: Vec<usize> = paulis_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let theta_pyobject = op
+                .call_method0("theta")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let theta = convert_into_calculator_float(theta_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(PauliProductRotation::new(qubits, paulis, theta).into())
+        }
         "MeasureQubit" => {
             let qubit_pyobject = op
                 .call_method0("qubit")
@@ -1431,6 +2216,217 @@ pub fn convert_pyany_to_operation(op: &PyAny) -> Result<Operation, QoqoError> {
                 .map_err(|_| QoqoError::ConversionError)?;
             Ok(InputSymbolic::new(name, input).into())
         }
+        "ClassicalNot" => {
+            let register_pyobject = op
+                .call_method0("register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let register: String = register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let index_pyobject = op
+                .call_method0("index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let index: usize = index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register_pyobject = op
+                .call_method0("output_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register: String = output_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index_pyobject = op
+                .call_method0("output_index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index: usize = output_index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(ClassicalNot::new(register, index, output_register, output_index).into())
+        }
+        "ClassicalCopy" => {
+            let register_pyobject = op
+                .call_method0("register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let register: String = register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let index_pyobject = op
+                .call_method0("index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let index: usize = index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register_pyobject = op
+                .call_method0("output_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register: String = output_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index_pyobject = op
+                .call_method0("output_index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index: usize = output_index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(ClassicalCopy::new(register, index, output_register, output_index).into())
+        }
+        "ClassicalXor" => {
+            let register_pyobject = op
+                .call_method0("register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let register: String = register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let indices_pyobject = op
+                .call_method0("indices")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let indices: Vec<usize> = indices_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register_pyobject = op
+                .call_method0("output_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register: String = output_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index_pyobject = op
+                .call_method0("output_index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index: usize = output_index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(ClassicalXor::new(register, indices, output_register, output_index).into())
+        }
+        "ClassicalAnd" => {
+            let register_pyobject = op
+                .call_method0("register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let register: String = register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let indices_pyobject = op
+                .call_method0("indices")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let indices: Vec<usize> = indices_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register_pyobject = op
+                .call_method0("output_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register: String = output_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index_pyobject = op
+                .call_method0("output_index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index: usize = output_index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(ClassicalAnd::new(register, indices, output_register, output_index).into())
+        }
+        "ClassicalOr" => {
+            let register_pyobject = op
+                .call_method0("register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let register: String = register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let indices_pyobject = op
+                .call_method0("indices")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let indices: Vec<usize> = indices_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register_pyobject = op
+                .call_method0("output_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register: String = output_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index_pyobject = op
+                .call_method0("output_index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index: usize = output_index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(ClassicalOr::new(register, indices, output_register, output_index).into())
+        }
+        "ClassicalGreaterThan" => {
+            let register_pyobject = op
+                .call_method0("register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let register: String = register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let index_pyobject = op
+                .call_method0("index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let index: usize = index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let threshold_pyobject = op
+                .call_method0("threshold")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let threshold = convert_into_calculator_float(threshold_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register_pyobject = op
+                .call_method0("output_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register: String = output_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index_pyobject = op
+                .call_method0("output_index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index: usize = output_index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(
+                ClassicalGreaterThan::new(
+                    register,
+                    index,
+                    threshold,
+                    output_register,
+                    output_index,
+                )
+                .into(),
+            )
+        }
+        "ClassicalLessThan" => {
+            let register_pyobject = op
+                .call_method0("register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let register: String = register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let index_pyobject = op
+                .call_method0("index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let index: usize = index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let threshold_pyobject = op
+                .call_method0("threshold")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let threshold = convert_into_calculator_float(threshold_pyobject)
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register_pyobject = op
+                .call_method0("output_register")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_register: String = output_register_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index_pyobject = op
+                .call_method0("output_index")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let output_index: usize = output_index_pyobject
+                .extract()
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(
+                ClassicalLessThan::new(register, index, threshold, output_register, output_index)
+                    .into(),
+            )
+        }
         "PragmaSetStateVector" => {
             let array = op.call_method0("statevector").expect("error extracting");
             let statevec_casted: Vec<Complex64> = array.extract().unwrap();
@@ -1471,6 +2467,23 @@ pub fn convert_pyany_to_operation(op: &PyAny) -> Result<Operation, QoqoError> {
             let operators = Array::from_shape_vec((dim, dim), densmat_casted).unwrap();
             Ok(PragmaGeneralNoise::new(qubit, gate_time, rate, operators).into())
         }
+        "MultiQubitGate" => {
+            let qubits = op
+                .call_method0("qubits")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let qubits: Vec<usize> =
+                Vec::extract(qubits).map_err(|_| QoqoError::ConversionError)?;
+            let array = op
+                .call_method0("matrix")
+                .map_err(|_| QoqoError::ConversionError)?;
+            let matrix_casted: Vec<Complex64> =
+                Vec::extract(array).map_err(|_| QoqoError::ConversionError)?;
+            let length: usize = matrix_casted.len();
+            let dim: usize = (length as f64).sqrt() as usize;
+            let matrix_array = Array::from_shape_vec((dim, dim), matrix_casted)
+                .map_err(|_| QoqoError::ConversionError)?;
+            Ok(MultiQubitGate::new(qubits, matrix_array).into())
+        }
         _ => Err(QoqoError::ConversionError),
     }
 }
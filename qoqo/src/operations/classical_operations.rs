@@ -0,0 +1,147 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::PySet;
+use pyo3::PyObjectProtocol;
+use qoqo_calculator::CalculatorFloat;
+use qoqo_calculator_pyo3::{convert_into_calculator_float, CalculatorFloatWrapper};
+use qoqo_macros::*;
+use roqoqo::operations::*;
+use std::collections::HashMap;
+
+#[wrap(Operate)]
+/// The classical NOT operation.
+///
+/// Writes the negation of a bit in a classical bit register to the output bit.
+///
+/// Args:
+///     register (str): The name of the bit register containing the input bit.
+///     index (int): The index of the input bit in the register.
+///     output_register (str): The name of the bit register the result is written to.
+///     output_index (int): The index of the result in the output register.
+pub struct ClassicalNot {
+    register: String,
+    index: usize,
+    output_register: String,
+    output_index: usize,
+}
+
+#[wrap(Operate)]
+/// The classical copy operation.
+///
+/// Copies a bit in a classical bit register to the output bit.
+///
+/// Args:
+///     register (str): The name of the bit register containing the input bit.
+///     index (int): The index of the input bit in the register.
+///     output_register (str): The name of the bit register the result is written to.
+///     output_index (int): The index of the result in the output register.
+pub struct ClassicalCopy {
+    register: String,
+    index: usize,
+    output_register: String,
+    output_index: usize,
+}
+
+#[wrap(Operate)]
+/// The classical XOR operation.
+///
+/// Writes the parity of the bits at the given indices of a classical bit register to the output bit,
+/// true when an odd number of the bits is set. The parity of no bits is false.
+///
+/// Args:
+///     register (str): The name of the bit register containing the input bits.
+///     indices (List[int]): The indices of the input bits in the register.
+///     output_register (str): The name of the bit register the result is written to.
+///     output_index (int): The index of the result in the output register.
+pub struct ClassicalXor {
+    register: String,
+    indices: Vec<usize>,
+    output_register: String,
+    output_index: usize,
+}
+
+#[wrap(Operate)]
+/// The classical AND operation.
+///
+/// Writes true to the output bit when all bits at the given indices of a classical bit register are set.
+/// The AND of no bits is true.
+///
+/// Args:
+///     register (str): The name of the bit register containing the input bits.
+///     indices (List[int]): The indices of the input bits in the register.
+///     output_register (str): The name of the bit register the result is written to.
+///     output_index (int): The index of the result in the output register.
+pub struct ClassicalAnd {
+    register: String,
+    indices: Vec<usize>,
+    output_register: String,
+    output_index: usize,
+}
+
+#[wrap(Operate)]
+/// The classical OR operation.
+///
+/// Writes true to the output bit when any bit at the given indices of a classical bit register is set.
+/// The OR of no bits is false.
+///
+/// Args:
+///     register (str): The name of the bit register containing the input bits.
+///     indices (List[int]): The indices of the input bits in the register.
+///     output_register (str): The name of the bit register the result is written to.
+///     output_index (int): The index of the result in the output register.
+pub struct ClassicalOr {
+    register: String,
+    indices: Vec<usize>,
+    output_register: String,
+    output_index: usize,
+}
+
+#[wrap(Operate)]
+/// The classical greater-than comparison.
+///
+/// Writes true to the output bit when a float in a classical float register is strictly greater than the threshold.
+///
+/// Args:
+///     register (str): The name of the float register containing the input float.
+///     index (int): The index of the input float in the register.
+///     threshold (CalculatorFloat): The threshold the input float is compared to.
+///     output_register (str): The name of the bit register the result is written to.
+///     output_index (int): The index of the result in the output register.
+pub struct ClassicalGreaterThan {
+    register: String,
+    index: usize,
+    threshold: CalculatorFloat,
+    output_register: String,
+    output_index: usize,
+}
+
+#[wrap(Operate)]
+/// The classical less-than comparison.
+///
+/// Writes true to the output bit when a float in a classical float register is strictly less than the threshold.
+///
+/// Args:
+///     register (str): The name of the float register containing the input float.
+///     index (int): The index of the input float in the register.
+///     threshold (CalculatorFloat): The threshold the input float is compared to.
+///     output_register (str): The name of the bit register the result is written to.
+///     output_index (int): The index of the result in the output register.
+pub struct ClassicalLessThan {
+    register: String,
+    index: usize,
+    threshold: CalculatorFloat,
+    output_register: String,
+    output_index: usize,
+}
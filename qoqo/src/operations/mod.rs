@@ -24,6 +24,10 @@ mod measurement_operations;
 pub use measurement_operations::*;
 mod two_qubit_gate_operations;
 pub use two_qubit_gate_operations::*;
+mod multi_qubit_gate_operations;
+pub use multi_qubit_gate_operations::*;
+mod classical_operations;
+pub use classical_operations::*;
 mod _auto_generated_operation_conversion;
 pub use _auto_generated_operation_conversion::{
     convert_operation_to_pyobject, convert_pyany_to_operation,
@@ -33,7 +37,7 @@ use pyo3::prelude::*;
 
 /// Operations are the atomic instructions in any quantum program that can be represented by qoqo.
 ///
-/// Operations can be of various kinds: Definitions, GateOperations, PRAGMAs or measurement Operations.
+/// Operations can be of various kinds: Definitions, GateOperations, PRAGMAs, measurement Operations or classical Operations.
 ///
 /// Operations:
 ///     Definition operations define the classical registers and variables in the Circuit.
@@ -42,6 +46,7 @@ use pyo3::prelude::*;
 ///     PRAGMAs are operations that can be used when running a simulation of a quantum computing program.
 ///     Measurement Operations are operations that perform a measurement either on a quantum computing device (MeasuareQubit)
 ///     or on a simulation of a quantum computing program (PRAGMA measurement operations).
+///     Classical Operations process the values of classical registers, for example to compute the condition of a PragmaConditional.
 ///
 /// .. autosummary::
 ///    :toctree: generated/
@@ -87,6 +92,9 @@ use pyo3::prelude::*;
 ///    PragmaRandomNoise
 ///    PragmaGeneralNoise
 ///    PragmaConditional
+///    PragmaConditionalPattern
+///    PragmaLoop
+///    PragmaRepeatUntilSuccess
 ///    CNOT
 ///    SWAP
 ///    FSwap
@@ -107,6 +115,21 @@ use pyo3::prelude::*;
 ///    Bogoliubov
 ///    PMInteraction
 ///    ComplexPMInteraction
+///    TwoQubitGate
+///    MultiQubitMS
+///    MultiQubitGate
+///    Toffoli
+///    ControlledControlledPauliZ
+///    ControlledControlledPhaseShift
+///    MultiControlledGate
+///    PauliProductRotation
+///    ClassicalNot
+///    ClassicalCopy
+///    ClassicalXor
+///    ClassicalAnd
+///    ClassicalOr
+///    ClassicalGreaterThan
+///    ClassicalLessThan
 
 #[pymodule]
 pub fn operations(_py: Python, m: &PyModule) -> PyResult<()> {
@@ -152,6 +175,9 @@ pub fn operations(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PragmaRandomNoiseWrapper>()?;
     m.add_class::<PragmaGeneralNoiseWrapper>()?;
     m.add_class::<PragmaConditionalWrapper>()?;
+    m.add_class::<PragmaConditionalPatternWrapper>()?;
+    m.add_class::<PragmaLoopWrapper>()?;
+    m.add_class::<PragmaRepeatUntilSuccessWrapper>()?;
     m.add_class::<CNOTWrapper>()?;
     m.add_class::<SWAPWrapper>()?;
     m.add_class::<FSwapWrapper>()?;
@@ -172,6 +198,21 @@ pub fn operations(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<BogoliubovWrapper>()?;
     m.add_class::<PMInteractionWrapper>()?;
     m.add_class::<ComplexPMInteractionWrapper>()?;
+    m.add_class::<TwoQubitGateWrapper>()?;
+    m.add_class::<MultiQubitMSWrapper>()?;
+    m.add_class::<MultiQubitGateWrapper>()?;
+    m.add_class::<ToffoliWrapper>()?;
+    m.add_class::<ControlledControlledPauliZWrapper>()?;
+    m.add_class::<ControlledControlledPhaseShiftWrapper>()?;
+    m.add_class::<MultiControlledGateWrapper>()?;
+    m.add_class::<PauliProductRotationWrapper>()?;
+    m.add_class::<ClassicalNotWrapper>()?;
+    m.add_class::<ClassicalCopyWrapper>()?;
+    m.add_class::<ClassicalXorWrapper>()?;
+    m.add_class::<ClassicalAndWrapper>()?;
+    m.add_class::<ClassicalOrWrapper>()?;
+    m.add_class::<ClassicalGreaterThanWrapper>()?;
+    m.add_class::<ClassicalLessThanWrapper>()?;
     m.add_class::<PhaseShiftedControlledZWrapper>()?;
 
     Ok(())
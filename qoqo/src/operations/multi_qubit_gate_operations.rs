@@ -0,0 +1,388 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::CircuitWrapper;
+use ndarray::Array;
+use num_complex::Complex64;
+use numpy::{PyArray1, PyArray2, ToPyArray};
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PySet;
+use pyo3::PyObjectProtocol;
+use qoqo_calculator::CalculatorFloat;
+use qoqo_calculator_pyo3::{convert_into_calculator_float, CalculatorFloatWrapper};
+use qoqo_macros::*;
+use roqoqo::operations::*;
+use std::collections::HashMap;
+
+#[wrap(Operate, OperateMultiQubit, OperateGate, OperateMultiQubitGate, Rotate)]
+/// The Molmer-Sorensen gate between multiple qubits.
+///
+/// The gate applies the rotation under the product of Pauli X operators on multiple qubits.
+/// In mathematical terms the gate applies exp(-i * theta/2 * X_i0 * X_i1 * ... * X_in).
+///
+/// Args:
+///     qubits (list[int]): The qubits involved in the multi qubit Molmer-Sorensen gate.
+///     theta (CalculatorFloat): The angle of the multi qubit Molmer-Sorensen gate.
+///
+pub struct MultiQubitMS {
+    qubits: Vec<usize>,
+    theta: CalculatorFloat,
+}
+
+#[wrap(Operate, OperateMultiQubit, OperateGate, OperateMultiQubitGate)]
+/// The Toffoli gate, a PauliX gate on the target qubit controlled by two control qubits.
+///
+/// Args:
+///     qubits (list[int]): The qubits [control_0, control_1, target] of the gate.
+///
+pub struct Toffoli {
+    qubits: Vec<usize>,
+}
+
+#[wrap(Operate, OperateMultiQubit, OperateGate, OperateMultiQubitGate)]
+/// The controlled-controlled PauliZ gate, a PauliZ gate on the target qubit controlled by two control qubits.
+///
+/// The gate applies a phase of -1 when all three qubits are in the state |1>.
+///
+/// Args:
+///     qubits (list[int]): The qubits [control_0, control_1, target] of the gate.
+///
+pub struct ControlledControlledPauliZ {
+    qubits: Vec<usize>,
+}
+
+#[wrap(Operate, OperateMultiQubit, OperateGate, OperateMultiQubitGate, Rotate)]
+/// The controlled-controlled phase shift gate.
+///
+/// The gate applies the phase exp(i * theta) when all three qubits are in the state |1>.
+///
+/// Args:
+///     qubits (list[int]): The qubits [control_0, control_1, target] of the gate.
+///     theta (CalculatorFloat): The phase applied to the state |111>.
+///
+pub struct ControlledControlledPhaseShift {
+    qubits: Vec<usize>,
+    theta: CalculatorFloat,
+}
+
+#[wrap(Operate, OperateMultiQubit, OperateGate, OperateMultiQubitGate)]
+/// A general single-qubit gate on a target qubit controlled by any number of control qubits.
+///
+/// When all control qubits are in the state |1> the gate applies the single-qubit unitary
+/// exp(i * phase) * RotateZ(z2) * RotateY(y) * RotateZ(z1) to the target qubit.
+/// The circuit of the gate is a decomposition without ancilla qubits.
+///
+/// Args:
+///     qubits (list[int]): The control qubits followed by the target qubit.
+///     phase (CalculatorFloat): The phase of the single-qubit unitary.
+///     z1 (CalculatorFloat): The angle of the first rotation around the z-axis.
+///     y (CalculatorFloat): The angle of the rotation around the y-axis.
+///     z2 (CalculatorFloat): The angle of the second rotation around the z-axis.
+///
+pub struct MultiControlledGate {
+    qubits: Vec<usize>,
+    phase: CalculatorFloat,
+    z1: CalculatorFloat,
+    y: CalculatorFloat,
+    z2: CalculatorFloat,
+}
+
+#[wrap(Operate, OperateMultiQubit, OperateGate, OperateMultiQubitGate, Rotate)]
+/// The rotation exp(-i * theta/2 * P) under a product P of Pauli operators on multiple qubits.
+///
+/// The Pauli operators use the encoding of PragmaGetPauliProduct:
+/// 0 = identity, 1 = PauliX, 2 = PauliY, 3 = PauliZ.
+/// The circuit of the gate decomposes the rotation into basis changes, a CNOT ladder and a RotateZ.
+///
+/// Args:
+///     qubits (list[int]): The qubits of the Pauli product.
+///     paulis (list[int]): The Pauli operators on the qubits in the same order.
+///     theta (CalculatorFloat): The angle of the rotation.
+///
+pub struct PauliProductRotation {
+    qubits: Vec<usize>,
+    paulis: Vec<usize>,
+    theta: CalculatorFloat,
+}
+
+/// Module containing the MultiQubitGate class.
+#[pymodule]
+fn multi_qubit_gate(_py: Python, module: &PyModule) -> PyResult<()> {
+    module.add_class::<MultiQubitGateWrapper>()?;
+    Ok(())
+}
+
+#[pyclass(name = "MultiQubitGate", module = "qoqo.operations")]
+#[derive(Clone, Debug, PartialEq)]
+/// The gate applying an arbitrary unitary matrix to multiple qubits.
+///
+/// The basis states of the matrix are ordered with the first qubit in `qubits` as the most significant qubit.
+///
+/// Args:
+///     qubits (list[int]): The qubits the gate acts on in order of descending significance.
+///     matrix (array of complex numbers): The unitary matrix of the gate.
+///                                        The array needs to be flattened before input, using np.flatten.
+///
+pub struct MultiQubitGateWrapper {
+    /// MultiQubitGate to be wrapped and converted to Python.
+    pub internal: MultiQubitGate,
+}
+
+insert_pyany_to_operation!(
+    "MultiQubitGate" =>{
+        let qubits = op.call_method0("qubits")
+                      .map_err(|_| QoqoError::ConversionError)?;
+        let qubits: Vec<usize> = Vec::extract(qubits).map_err(|_| QoqoError::ConversionError)?;
+        let array = op.call_method0("matrix")
+                      .map_err(|_| QoqoError::ConversionError)?;
+        let matrix_casted: Vec<Complex64> = Vec::extract(array).map_err(|_| QoqoError::ConversionError)?;
+        let length: usize = matrix_casted.len();
+        let dim: usize = (length as f64).sqrt() as usize;
+        let matrix_array = Array::from_shape_vec((dim, dim), matrix_casted).map_err(|_| QoqoError::ConversionError)?;
+        Ok(MultiQubitGate::new(qubits, matrix_array).into())
+    }
+);
+insert_operation_to_pyobject!(
+    Operation::MultiQubitGate(internal) => {
+        {
+            let pyref: Py<MultiQubitGateWrapper> =
+                Py::new(py, MultiQubitGateWrapper { internal }).unwrap();
+            let pyobject: PyObject = pyref.to_object(py);
+            Ok(pyobject)
+        }
+    }
+);
+
+#[pymethods]
+impl MultiQubitGateWrapper {
+    /// Create a MultiQubitGate.
+    ///
+    /// Args:
+    ///     qubits (list[int]): The qubits the gate acts on in order of descending significance.
+    ///     matrix (list[complex]): The flattened unitary matrix of the gate.
+    ///
+    /// Returns:
+    ///     self: The new MultiQubitGate.
+    ///
+    /// Raises:
+    ///     TypeError: The matrix cannot be converted to a list of complex numbers.
+    ///     ValueError: The matrix is not a unitary matrix of matching dimension.
+    #[new]
+    fn new(qubits: Vec<usize>, matrix: Py<PyAny>) -> PyResult<Self> {
+        let matrix_casted: Vec<Complex64> = Python::with_gil(|py| -> PyResult<Vec<Complex64>> {
+            Vec::extract(matrix.as_ref(py)).map_err(|_| {
+                PyTypeError::new_err("matrix input cannot be converted to list of complex numbers")
+            })
+        })?;
+        let length: usize = matrix_casted.len();
+        let dim: usize = (length as f64).sqrt() as usize;
+        let matrix_array = Array::from_shape_vec((dim, dim), matrix_casted).map_err(|_| {
+            PyValueError::new_err("matrix input does not have a square number of elements")
+        })?;
+        Ok(Self {
+            internal: MultiQubitGate::from_unitary(qubits, matrix_array)
+                .map_err(|x| PyValueError::new_err(format!("{}", x)))?,
+        })
+    }
+
+    /// Return the flattened unitary matrix of the gate.
+    ///
+    /// Returns:
+    ///     np.ndarray: The flattened matrix of the gate.
+    fn matrix(&self) -> Py<PyArray1<Complex64>> {
+        let array: Vec<Complex64> = self.internal.matrix().iter().cloned().collect();
+        Python::with_gil(|py| -> Py<PyArray1<Complex64>> { array.to_pyarray(py).to_owned() })
+    }
+
+    /// Return the unitary matrix of the gate.
+    ///
+    /// Returns:
+    ///     np.ndarray: The unitary matrix of the gate.
+    ///
+    /// Raises:
+    ///     ValueError: The matrix is not a unitary matrix of matching dimension.
+    fn unitary_matrix(&self) -> PyResult<Py<PyArray2<Complex64>>> {
+        let matrix = self
+            .internal
+            .unitary_matrix()
+            .map_err(|x| PyValueError::new_err(format!("{}", x)))?;
+        Ok(Python::with_gil(|py| -> Py<PyArray2<Complex64>> {
+            matrix.to_pyarray(py).to_owned()
+        }))
+    }
+
+    /// Return the list of qubits of the gate in order of descending significance.
+    ///
+    /// Returns:
+    ///     list[int]: The qubits of the gate.
+    fn qubits(&self) -> Vec<usize> {
+        self.internal.qubits().clone()
+    }
+
+    /// Return the decomposition of the gate into single-qubit rotations and CNOT gates.
+    ///
+    /// Returns:
+    ///     Circuit: The circuit implementing the gate.
+    fn circuit(&self) -> CircuitWrapper {
+        CircuitWrapper {
+            internal: self.internal.circuit(),
+        }
+    }
+
+    /// List all involved qubits.
+    ///
+    /// Returns:
+    ///     set[int]: The involved qubits of the gate.
+    fn involved_qubits(&self) -> PyObject {
+        Python::with_gil(|py| -> PyObject {
+            PySet::new(py, self.internal.qubits())
+                .unwrap()
+                .to_object(py)
+        })
+    }
+
+    /// Return tags classifying the type of the operation.
+    ///
+    /// Used for type based dispatch in ffi interfaces.
+    ///
+    /// Returns:
+    ///     list[str]: The tags of the Operation.
+    fn tags(&self) -> Vec<String> {
+        self.internal.tags().iter().map(|s| s.to_string()).collect()
+    }
+
+    /// Return hqslang name of the operation.
+    ///
+    /// Returns:
+    ///     str: The hqslang name of the operation.
+    fn hqslang(&self) -> &'static str {
+        self.internal.hqslang()
+    }
+
+    /// Return true when the operation has symbolic parameters.
+    ///
+    /// Returns:
+    ///     bool: Always False, the matrix of the gate is numeric.
+    fn is_parametrized(&self) -> bool {
+        self.internal.is_parametrized()
+    }
+
+    /// Substitute the symbolic parameters in a clone of the gate according to the input.
+    ///
+    /// Args:
+    ///     substitution_parameters (dict[str, float]): The dictionary containing the substitutions to use in the gate.
+    ///
+    /// Returns:
+    ///     self: The gate with the parameters substituted.
+    ///
+    /// Raises:
+    ///     RuntimeError: The parameter substitution failed.
+    fn substitute_parameters(&self, substitution_parameters: HashMap<&str, f64>) -> PyResult<Self> {
+        let mut calculator = qoqo_calculator::Calculator::new();
+        for (key, val) in substitution_parameters.iter() {
+            calculator.set_variable(key, *val);
+        }
+        Ok(Self {
+            internal: self
+                .internal
+                .substitute_parameters(&mut calculator)
+                .map_err(|x| {
+                    PyRuntimeError::new_err(format!("Parameter Substitution failed: {:?}", x))
+                })?,
+        })
+    }
+
+    /// Remap qubits in a clone of the gate.
+    ///
+    /// Args:
+    ///     mapping (dict[int, int]): The dictionary containing the {qubit: qubit} mapping to use in the gate.
+    ///
+    /// Returns:
+    ///     self: The gate with the qubits remapped.
+    ///
+    /// Raises:
+    ///     RuntimeError: The qubit remapping failed.
+    fn remap_qubits(&self, mapping: HashMap<usize, usize>) -> PyResult<Self> {
+        let new_internal = self
+            .internal
+            .remap_qubits(&mapping)
+            .map_err(|_| PyRuntimeError::new_err("Qubit remapping failed: "))?;
+        Ok(Self {
+            internal: new_internal,
+        })
+    }
+
+    /// Return a copy of the gate (copy here produces a deepcopy).
+    ///
+    /// Returns:
+    ///     MultiQubitGate: A deep copy of self.
+    fn __copy__(&self) -> MultiQubitGateWrapper {
+        self.clone()
+    }
+
+    /// Return a deep copy of the gate.
+    ///
+    /// Returns:
+    ///     MultiQubitGate: A deep copy of self.
+    fn __deepcopy__(&self, _memodict: Py<PyAny>) -> MultiQubitGateWrapper {
+        self.clone()
+    }
+
+    /// Return a string containing a formatted (string) representation of the gate.
+    ///
+    /// Returns:
+    ///     str: The string representation of the operation.
+    fn __format__(&self, _format_spec: &str) -> PyResult<String> {
+        Ok(format!("{:?}", self.internal))
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for MultiQubitGateWrapper {
+    /// Return a string containing a printable representation of the gate.
+    ///
+    /// Returns:
+    ///     str: The printable string representation of the operation.
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{:?}", self.internal))
+    }
+
+    /// Return the __richcmp__ magic method to perform rich comparison operations on MultiQubitGate.
+    ///
+    /// Args:
+    ///     self: The MultiQubitGate object.
+    ///     other: The object to compare self to.
+    ///     op: Whether they should be equal or not.
+    ///
+    /// Returns:
+    ///     bool: Whether the two operations compared evaluated to True or False.
+    fn __richcmp__(&self, other: Py<PyAny>, op: pyo3::class::basic::CompareOp) -> PyResult<bool> {
+        let other: Operation = Python::with_gil(|py| -> PyResult<Operation> {
+            let other_ref = other.as_ref(py);
+            crate::operations::convert_pyany_to_operation(other_ref).map_err(|_| {
+                PyTypeError::new_err("Right hand side can not be converted to Operation")
+            })
+        })?;
+        match op {
+            pyo3::class::basic::CompareOp::Eq => {
+                Ok(Operation::from(self.internal.clone()) == other)
+            }
+            pyo3::class::basic::CompareOp::Ne => {
+                Ok(Operation::from(self.internal.clone()) != other)
+            }
+            _ => Err(pyo3::exceptions::PyNotImplementedError::new_err(
+                "Other comparison not implemented.",
+            )),
+        }
+    }
+}
@@ -1149,3 +1149,52 @@ pub struct PragmaConditional {
     condition_index: usize,
     circuit: Circuit,
 }
+
+#[wrap(Operate, OperatePragma)]
+/// The conditional PRAGMA operation with a condition on several bits.
+///
+/// This PRAGMA executes a circuit when each bit of a classical bit register listed in the condition
+/// has the value it is paired with, for example when a syndrome matches the syndrome of a correctable error.
+///
+/// Args:
+///     condition_register (str): The name of the bit register containting the condition bits.
+///     condition (List[Tuple[int, bool]]): The index of each condition bit in the bit register paired with the value it must have.
+///     circuit (Circuit): The circuit executed if the condition is met.
+pub struct PragmaConditionalPattern {
+    condition_register: String,
+    condition: Vec<(usize, bool)>,
+    circuit: Circuit,
+}
+
+#[wrap(Operate, OperatePragma)]
+/// The loop PRAGMA operation.
+///
+/// This PRAGMA executes a circuit a fixed number of times.
+/// The number of repetitions can be symbolic but must be a non-negative integer once all parameters are substituted.
+///
+/// Args:
+///     repetitions (CalculatorFloat): The number of times the circuit is executed.
+///     circuit (Circuit): The circuit executed in each repetition.
+pub struct PragmaLoop {
+    repetitions: CalculatorFloat,
+    circuit: Circuit,
+}
+
+#[wrap(Operate, OperatePragma)]
+/// The repeat-until-success PRAGMA operation.
+///
+/// This PRAGMA executes a circuit and executes it again as long as the bit at the condition index of a
+/// classical bit register is true afterwards, for example when an ancilla measurement flags a failed
+/// state preparation. The circuit is executed at most max_iterations times.
+///
+/// Args:
+///     condition_register (str): The name of the bit register containting the condition bool value.
+///     condition_index (int): The index in the bit register containting the condition bool value.
+///     max_iterations (int): The maximal number of times the circuit is executed.
+///     circuit (Circuit): The circuit executed until the condition bit is false.
+pub struct PragmaRepeatUntilSuccess {
+    condition_register: String,
+    condition_index: usize,
+    max_iterations: usize,
+    circuit: Circuit,
+}
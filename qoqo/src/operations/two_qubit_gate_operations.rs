@@ -518,3 +518,54 @@ pub struct PhaseShiftedControlledZ {
     target: usize,
     phi: CalculatorFloat,
 }
+
+#[allow(clippy::upper_case_acronyms)]
+#[wrap(Operate, OperateTwoQubit, OperateGate, OperateTwoQubitGate)]
+/// A general two-qubit gate given by the parameters of its KAK decomposition.
+///
+/// .. math::
+///     U = e^{i \phi} (A_c \otimes A_t) \exp\left(i (k_x XX + k_y YY + k_z ZZ)\right) (B_c \otimes B_t)
+///
+/// Each single-qubit unitary is given by three rotation angles as RotateZ(z1), followed by RotateY(y),
+/// followed by RotateZ(z2).
+///
+/// Args:
+///     control (int): The index of the most significant qubit in the unitary representation.
+///     target (int): The index of the least significant qubit in the unitary representation.
+///     phase (CalculatorFloat): The global phase :math:`\phi` of the gate.
+///     kx (CalculatorFloat): The prefactor of the XX interaction.
+///     ky (CalculatorFloat): The prefactor of the YY interaction.
+///     kz (CalculatorFloat): The prefactor of the ZZ interaction.
+///     control_before_z1 (CalculatorFloat): The first RotateZ angle on the control qubit before the entangling operation.
+///     control_before_y (CalculatorFloat): The RotateY angle on the control qubit before the entangling operation.
+///     control_before_z2 (CalculatorFloat): The second RotateZ angle on the control qubit before the entangling operation.
+///     target_before_z1 (CalculatorFloat): The first RotateZ angle on the target qubit before the entangling operation.
+///     target_before_y (CalculatorFloat): The RotateY angle on the target qubit before the entangling operation.
+///     target_before_z2 (CalculatorFloat): The second RotateZ angle on the target qubit before the entangling operation.
+///     control_after_z1 (CalculatorFloat): The first RotateZ angle on the control qubit after the entangling operation.
+///     control_after_y (CalculatorFloat): The RotateY angle on the control qubit after the entangling operation.
+///     control_after_z2 (CalculatorFloat): The second RotateZ angle on the control qubit after the entangling operation.
+///     target_after_z1 (CalculatorFloat): The first RotateZ angle on the target qubit after the entangling operation.
+///     target_after_y (CalculatorFloat): The RotateY angle on the target qubit after the entangling operation.
+///     target_after_z2 (CalculatorFloat): The second RotateZ angle on the target qubit after the entangling operation.
+///
+pub struct TwoQubitGate {
+    control: usize,
+    target: usize,
+    phase: CalculatorFloat,
+    kx: CalculatorFloat,
+    ky: CalculatorFloat,
+    kz: CalculatorFloat,
+    control_before_z1: CalculatorFloat,
+    control_before_y: CalculatorFloat,
+    control_before_z2: CalculatorFloat,
+    target_before_z1: CalculatorFloat,
+    target_before_y: CalculatorFloat,
+    target_before_z2: CalculatorFloat,
+    control_after_z1: CalculatorFloat,
+    control_after_y: CalculatorFloat,
+    control_after_z2: CalculatorFloat,
+    target_after_z1: CalculatorFloat,
+    target_after_y: CalculatorFloat,
+    target_after_z2: CalculatorFloat,
+}
@@ -190,6 +190,26 @@ fn test_count_occurences() {
     assert_eq!(comp_op, 0_usize);
 }
 
+/// Test draw function of Circuit
+#[test]
+fn test_draw() {
+    let added_op1 = Operation::from(Hadamard::new(0));
+    let added_op2 = Operation::from(CNOT::new(0, 1));
+    pyo3::prepare_freethreaded_python();
+    let gil = pyo3::Python::acquire_gil();
+    let py = gil.python();
+    let operation1 = convert_operation_to_pyobject(added_op1).unwrap();
+    let operation2 = convert_operation_to_pyobject(added_op2).unwrap();
+    let circuit = new_circuit(py);
+    circuit.call_method1("add", (operation1.clone(),)).unwrap();
+    circuit.call_method1("add", (operation2.clone(),)).unwrap();
+
+    let unicode = String::extract(circuit.call_method0("draw").unwrap()).unwrap();
+    assert_eq!(unicode, "q0: ─H──●──\nq1: ────⊕──\n");
+    let ascii = String::extract(circuit.call_method1("draw", (false, 10)).unwrap()).unwrap();
+    assert_eq!(ascii, "q0: -H--\nq1: ----\n\nq0: -@--\nq1: -X--\n");
+}
+
 /// Test get_operation_types function of Circuit
 #[test]
 fn test_get_operation_types() {
@@ -72,6 +72,21 @@ use test_case::test_case;
 #[test_case(Operation::from(Bogoliubov::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "Bogoliubov")]
 #[test_case(Operation::from(PMInteraction::new(0, 1, CalculatorFloat::PI)); "PMInteraction")]
 #[test_case(Operation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "ComplexPMInteraction")]
+#[test_case(Operation::from(TwoQubitGate::new(0, 1, CalculatorFloat::from(0.1), CalculatorFloat::from(0.2), CalculatorFloat::from(0.3), CalculatorFloat::from(0.4), CalculatorFloat::from(0.5), CalculatorFloat::from(0.6), CalculatorFloat::from(0.7), CalculatorFloat::from(0.8), CalculatorFloat::from(0.9), CalculatorFloat::from(1.0), CalculatorFloat::from(1.1), CalculatorFloat::from(1.2), CalculatorFloat::from(1.3), CalculatorFloat::from(1.4), CalculatorFloat::from(1.5), CalculatorFloat::from("theta"))); "TwoQubitGate")]
+#[test_case(Operation::from(MultiQubitMS::new(vec![0, 1, 2], CalculatorFloat::FRAC_PI_2)); "MultiQubitMS")]
+#[test_case(Operation::from(MultiQubitGate::new(vec![1, 0], array![[Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)], [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)]])); "MultiQubitGate")]
+#[test_case(Operation::from(Toffoli::new(vec![0, 1, 2])); "Toffoli")]
+#[test_case(Operation::from(ControlledControlledPauliZ::new(vec![2, 0, 1])); "ControlledControlledPauliZ")]
+#[test_case(Operation::from(ControlledControlledPhaseShift::new(vec![0, 1, 2], CalculatorFloat::from("theta"))); "ControlledControlledPhaseShift")]
+#[test_case(Operation::from(MultiControlledGate::new(vec![0, 1, 2, 3], CalculatorFloat::from(0.1), CalculatorFloat::from("z1"), CalculatorFloat::from(-0.3), CalculatorFloat::from(0.4))); "MultiControlledGate")]
+#[test_case(Operation::from(PauliProductRotation::new(vec![2, 0, 1], vec![1, 2, 3], CalculatorFloat::from("theta"))); "PauliProductRotation")]
+#[test_case(Operation::from(ClassicalNot::new(String::from("ro"), 1, String::from("flags"), 0)); "ClassicalNot")]
+#[test_case(Operation::from(ClassicalCopy::new(String::from("ro"), 1, String::from("flags"), 0)); "ClassicalCopy")]
+#[test_case(Operation::from(ClassicalXor::new(String::from("ro"), vec![0, 1, 3], String::from("flags"), 2)); "ClassicalXor")]
+#[test_case(Operation::from(ClassicalAnd::new(String::from("ro"), vec![0, 1], String::from("flags"), 2)); "ClassicalAnd")]
+#[test_case(Operation::from(ClassicalOr::new(String::from("ro"), vec![], String::from("flags"), 2)); "ClassicalOr")]
+#[test_case(Operation::from(ClassicalGreaterThan::new(String::from("x"), 1, CalculatorFloat::from(0.5), String::from("flags"), 0)); "ClassicalGreaterThan")]
+#[test_case(Operation::from(ClassicalLessThan::new(String::from("x"), 1, CalculatorFloat::from("threshold"), String::from("flags"), 0)); "ClassicalLessThan")]
 #[test_case(Operation::from(DefinitionFloat::new(String::from("ro"), 1, false)); "DefinitionFloat")]
 #[test_case(Operation::from(DefinitionComplex::new(String::from("ro"), 1, false)); "DefinitionComplex")]
 #[test_case(Operation::from(DefinitionUsize::new(String::from("ro"), 1, false)); "DefinitionUsize")]
@@ -101,6 +116,9 @@ use test_case::test_case;
 #[test_case(Operation::from(PragmaRandomNoise::new(0, CalculatorFloat::from(0.005), CalculatorFloat::from(0.02), CalculatorFloat::from(0.01))); "PragmaRandomNoise")]
 #[test_case(Operation::from(PragmaGeneralNoise::new(0, CalculatorFloat::from(0.005), CalculatorFloat::from(0.02), operators())); "PragmaGeneralNoise")]
 #[test_case(Operation::from(PragmaConditional::new(String::from("ro"), 1, create_circuit())); "PragmaConditional")]
+#[test_case(Operation::from(PragmaConditionalPattern::new(String::from("ro"), vec![(0, true), (2, false)], create_circuit())); "PragmaConditionalPattern")]
+#[test_case(Operation::from(PragmaLoop::new(CalculatorFloat::from("repetitions"), create_circuit())); "PragmaLoop")]
+#[test_case(Operation::from(PragmaRepeatUntilSuccess::new(String::from("ro"), 1, 5, create_circuit())); "PragmaRepeatUntilSuccess")]
 fn test_conversion(input: Operation) {
     pyo3::prepare_freethreaded_python();
     let gil = pyo3::Python::acquire_gil();
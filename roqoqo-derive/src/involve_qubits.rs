@@ -10,7 +10,7 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{extract_fields_with_types, extract_variants_with_types};
+use crate::{extract_fields_with_types, extract_variants_with_types, variant_inner};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Data, DataEnum, DataStruct, DeriveInput, Ident};
@@ -29,9 +29,10 @@ pub fn dispatch_struct_enum(input: DeriveInput) -> TokenStream {
 /// This derive delegates the invocations of the involved_qubits function to all possible variants via match arms
 fn involve_qubits_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let match_quotes = variants_with_type.map(|(vident, _, _)| {
+    let match_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {InvolveQubits::involved_qubits(&(*inner))},
+            &#ident::#vident(ref inner) => {InvolveQubits::involved_qubits(&#inner)},
         }
     });
     quote! {
@@ -14,6 +14,8 @@
 //!
 //! Derive proc-macros for the traits of roqoqo [roqoqo].
 
+use proc_macro2::TokenStream;
+use quote::quote;
 use syn::{
     parse_macro_input, DataEnum, DataStruct, DeriveInput, Fields, GenericArgument, Ident,
     PathArguments, Type, TypePath,
@@ -47,7 +49,10 @@ pub fn derive_involve_qubits(input: proc_macro::TokenStream) -> proc_macro::Toke
 }
 
 /// Derive macro for the [roqoqo::Operate] trait
-#[proc_macro_derive(Operate)]
+///
+/// Operations with the `boxed_variant` attribute are stored boxed in the operation enums
+/// and can be converted from a Box.
+#[proc_macro_derive(Operate, attributes(boxed_variant))]
 pub fn derive_operate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let parsed_input = parse_macro_input!(input as DeriveInput);
     operate::dispatch_struct_enum(parsed_input).into()
@@ -226,3 +231,42 @@ fn extract_variants_with_types(de: DataEnum) -> Vec<(Ident, Option<String>, Type
         (vident, type_string ,ty)
     }).collect()
 }
+
+/// Returns the operation type of a variant with a boxed operation `Box<Operation>`.
+fn boxed_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(TypePath { path: p, .. }) => p.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Box" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(GenericArgument::Type(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the operation type of a variant, unwrapping boxed operations.
+fn operation_type(ty: &Type) -> &Type {
+    boxed_type(ty).unwrap_or(ty)
+}
+
+/// Returns the expression of the operation bound by reference as `inner` in a variant of the type.
+fn variant_inner(ty: &Type) -> TokenStream {
+    match boxed_type(ty) {
+        Some(_) => quote! {(**inner)},
+        None => quote! {(*inner)},
+    }
+}
+
+/// Returns the expression storing an operation in a variant of the type, boxing it if necessary.
+fn variant_value(ty: &Type, value: TokenStream) -> TokenStream {
+    match boxed_type(ty) {
+        Some(_) => quote! {Box::new(#value)},
+        None => value,
+    }
+}
@@ -10,7 +10,10 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{extract_fields_with_types, extract_variants_with_types, RESERVED_FIELDS};
+use crate::{
+    boxed_type, extract_fields_with_types, extract_variants_with_types, operation_type,
+    variant_inner, variant_value, RESERVED_FIELDS,
+};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::collections::HashSet;
@@ -19,8 +22,12 @@ use syn::{Data, DataEnum, DataStruct, DeriveInput, Ident, Type, TypePath};
 /// Dispatch to derive Operate for enums and structs
 pub fn dispatch_struct_enum(input: DeriveInput) -> TokenStream {
     let ident = input.ident;
+    let boxed = input
+        .attrs
+        .iter()
+        .any(|attribute| attribute.path.is_ident("boxed_variant"));
     match input.data {
-        Data::Struct(ds) => operate_qubits_struct(ds, ident),
+        Data::Struct(ds) => operate_qubits_struct(ds, ident, boxed),
         Data::Enum(de) => operate_qubits_enum(de, ident),
         _ => panic!("InvolveQubits can only be derived on structs and enums"),
     }
@@ -30,42 +37,51 @@ pub fn dispatch_struct_enum(input: DeriveInput) -> TokenStream {
 fn operate_qubits_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
     // TokenStream iterator for matching tags function on variants
-    let tags_quotes = variants_with_type.clone().map(|(vident, _, _)| {
+    let tags_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {Operate::tags(&(*inner))},
+            &#ident::#vident(ref inner) => {Operate::tags(&#inner)},
         }
     });
     // TokenStream iterator for matching hqslang function on variants
-    let hqslang_quotes = variants_with_type.clone().map(|(vident, _, _)| {
+    let hqslang_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {Operate::hqslang(&(*inner))},
+            &#ident::#vident(ref inner) => {Operate::hqslang(&#inner)},
         }
     });
     // TokenStream iterator for matching From implementation on variants
     let from_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let operation_ty = operation_type(&ty);
+        let value = variant_value(&ty, quote! {v});
         quote! {
             /// Implements the [From] trait $\textit{from}$ this Operation.
             #[automatically_derived]
-            impl From<#ty> for #ident{
+            impl From<#operation_ty> for #ident{
                 /// Performs the conversion.
-                fn from(v: #ty) -> Self{
-                    #ident::#vident(v)
+                fn from(v: #operation_ty) -> Self{
+                    #ident::#vident(#value)
                 }
             }
         }
     });
     // TokenStream iterator for matching TryFrom implementation on variants
     let try_from_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let operation_ty = operation_type(&ty);
+        let value = match boxed_type(&ty) {
+            Some(_) => quote! {*v},
+            None => quote! {v},
+        };
         quote! {
             #[automatically_derived]
             /// Implements the [core::convert::TryFrom] trait $\textit{into}$ this Operation.
-            impl core::convert::TryFrom<#ident> for #ty{
+            impl core::convert::TryFrom<#ident> for #operation_ty{
                 /// The type returned in case of a conversion error.
                 type Error = &'static str;
                 /// Performs the conversion.
                 fn try_from(e: #ident) -> Result<Self, Self::Error>{
                     match e{
-                        #ident::#vident(v) => Ok(v),
+                        #ident::#vident(v) => Ok(#value),
                         _ => Err("Type can not be created from variant")
                     }
 
@@ -73,9 +89,10 @@ fn operate_qubits_enum(de: DataEnum, ident: Ident) -> TokenStream {
             }
         }
     });
-    let is_parametrized_quotes = variants_with_type.map(|(vident, _, _)| {
+    let is_parametrized_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {Operate::is_parametrized(&(*inner))},
+            &#ident::#vident(ref inner) => {Operate::is_parametrized(&#inner)},
         }
     });
     let qtags = quote! {
@@ -120,7 +137,7 @@ fn operate_qubits_enum(de: DataEnum, ident: Ident) -> TokenStream {
 }
 
 /// Generates the TokenStream of the implementation of Operate for structs
-fn operate_qubits_struct(ds: DataStruct, ident: Ident) -> TokenStream {
+fn operate_qubits_struct(ds: DataStruct, ident: Ident, boxed: bool) -> TokenStream {
     let reserved_fields: HashSet<&str> = RESERVED_FIELDS.iter().cloned().collect();
     let fields_with_type = extract_fields_with_types(ds).into_iter();
 
@@ -195,6 +212,19 @@ fn operate_qubits_struct(ds: DataStruct, ident: Ident) -> TokenStream {
     let formated_tags = format_ident!("TAGS_{}", ident);
     let formated_hqslang = format!("{}", ident);
     let msg = format!("Creates a new instance of `{}`.\n\n", ident);
+    let from_box_quote = if boxed {
+        quote! {
+            #[automatically_derived]
+            /// Moves the Operation out of the Box it is stored in in the operation enums.
+            impl From<Box<#ident>> for #ident{
+                fn from(operation: Box<#ident>) -> Self{
+                    *operation
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
     quote! {
         #[automatically_derived]
         impl #ident{
@@ -202,6 +232,7 @@ fn operate_qubits_struct(ds: DataStruct, ident: Ident) -> TokenStream {
 
             #[doc = #msg]
             #[inline]
+            #[allow(clippy::too_many_arguments)]
             pub fn new(#(#input_arguments),*) -> Self{
                 Self{#(#arguments),*}
             }
@@ -215,7 +246,7 @@ fn operate_qubits_struct(ds: DataStruct, ident: Ident) -> TokenStream {
             #[inline]
             fn is_parametrized(&self) -> bool {
                     // Leading false is necessary for constant operations that are never parametrized
-                    (#(#is_parametrized_fields)&&*)
+                    (#(#is_parametrized_fields)||*)
             }
             /// Returns tags classifying the type of the Operation.
             #[inline]
@@ -229,6 +260,7 @@ fn operate_qubits_struct(ds: DataStruct, ident: Ident) -> TokenStream {
             }
         }
 
+        #from_box_quote
     }
 }
 
@@ -10,6 +10,7 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::variant_inner;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Data, DataEnum, DeriveInput, Fields, Ident};
@@ -52,8 +53,9 @@ fn operate_single_qubit_enum(de: DataEnum, ident: Ident) -> TokenStream {
                 "OperateSingleQubit can only be derived for enums with newtype structs as variants"
             )
         }
+        let inner = variant_inner(&fields.unnamed[0].ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateSingleQubit::qubit(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateSingleQubit::qubit(&#inner)},
         }
     });
     quote! {
@@ -116,8 +118,9 @@ fn operate_two_qubit_enum(de: DataEnum, ident: Ident) -> TokenStream {
                 "OperateSingleQubit can only be derived for enums with newtype structs as variants"
             )
         }
+        let inner = variant_inner(&fields.unnamed[0].ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateTwoQubit::control(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateTwoQubit::control(&#inner)},
         }
     });
     let target_quotes = variants.into_iter().map(|v| {
@@ -133,8 +136,9 @@ fn operate_two_qubit_enum(de: DataEnum, ident: Ident) -> TokenStream {
                 "OperateSingleQubit can only be derived for enums with newtype structs as variants"
             )
         }
+        let inner = variant_inner(&fields.unnamed[0].ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateTwoQubit::target(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateTwoQubit::target(&#inner)},
         }
     });
     quote! {
@@ -202,8 +206,9 @@ fn operate_multi_qubit_enum(de: DataEnum, ident: Ident) -> TokenStream {
                 "OperateMultiQubit can only be derived for enums with newtype structs as variants"
             )
         }
+        let inner = variant_inner(&fields.unnamed[0].ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateMultiQubit::qubits(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateMultiQubit::qubits(&#inner)},
         }
     });
     quote! {
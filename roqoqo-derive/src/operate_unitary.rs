@@ -10,7 +10,7 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::extract_variants_with_types;
+use crate::{extract_variants_with_types, variant_inner, variant_value};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Data, DataEnum, DeriveInput, Ident};
@@ -26,9 +26,10 @@ pub fn dispatch_struct_enum_operate_gate(input: DeriveInput) -> TokenStream {
 
 fn operate_gate_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let match_quotes = variants_with_type.map(|(vident, _, _)| {
+    let match_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateGate::unitary_matrix(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateGate::unitary_matrix(&#inner)},
         }
     });
     quote! {
@@ -67,29 +68,34 @@ pub fn dispatch_struct_enum_single_qubit_gate(input: DeriveInput) -> TokenStream
 
 fn single_qubit_gate_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let alpha_r_quotes = variants_with_type.clone().map(|(vident, _, _)| {
+    let alpha_r_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateSingleQubitGate::alpha_r(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateSingleQubitGate::alpha_r(&#inner)},
         }
     });
-    let beta_i_quotes = variants_with_type.clone().map(|(vident, _, _)| {
+    let beta_i_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateSingleQubitGate::beta_i(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateSingleQubitGate::beta_i(&#inner)},
         }
     });
-    let alpha_i_quotes = variants_with_type.clone().map(|(vident, _, _)| {
+    let alpha_i_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateSingleQubitGate::alpha_i(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateSingleQubitGate::alpha_i(&#inner)},
         }
     });
-    let beta_r_quotes = variants_with_type.clone().map(|(vident, _, _)| {
+    let beta_r_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateSingleQubitGate::beta_r(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateSingleQubitGate::beta_r(&#inner)},
         }
     });
-    let phase_quotes = variants_with_type.map(|(vident, _, _)| {
+    let phase_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateSingleQubitGate::global_phase(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateSingleQubitGate::global_phase(&#inner)},
         }
     });
     quote! {
@@ -169,9 +175,10 @@ pub fn dispatch_struct_enum_constant_gate(input: DeriveInput) -> TokenStream {
 
 fn constant_gate_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let match_quotes = variants_with_type.map(|(vident, _, _)| {
+    let match_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => { OperateConstantGate::inverse(&(*inner)).into()},
+            &#ident::#vident(ref inner) => { OperateConstantGate::inverse(&#inner).into()},
         }
     });
     quote! {
@@ -198,9 +205,10 @@ pub fn dispatch_struct_enum_two_qubit_gate(input: DeriveInput) -> TokenStream {
 
 fn two_qubit_gate_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let match_quotes = variants_with_type.map(|(vident, _, _)| {
+    let match_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateTwoQubitGate::kak_decomposition(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateTwoQubitGate::kak_decomposition(&#inner)},
         }
     });
     quote! {
@@ -238,9 +246,10 @@ pub fn dispatch_struct_enum_multi_qubit_gate(input: DeriveInput) -> TokenStream
 
 fn multi_qubit_gate_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let match_quotes = variants_with_type.map(|(vident, _, _)| {
+    let match_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperateMultiQubitGate::circuit(&(*inner))},
+            &#ident::#vident(ref inner) => {OperateMultiQubitGate::circuit(&#inner)},
         }
     });
     quote! {
@@ -278,15 +287,21 @@ pub fn dispatch_struct_enum_rotate(input: DeriveInput) -> TokenStream {
 
 fn rotate_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let match_quotes = variants_with_type.clone().map(|(vident, _, _)| {
+    let match_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {Rotate::theta(&(*inner))},
+            &#ident::#vident(ref inner) => {Rotate::theta(&#inner)},
         }
     });
     let overrotate_quote = if cfg!(feature = "overrotate") {
-        let overrotate_match_quotes = variants_with_type.clone().map(|(vident, _, _)|  {
+        let overrotate_match_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+            let inner = variant_inner(&ty);
+            let value = variant_value(
+                &ty,
+                quote! {Rotate::overrotate(&#inner, amplitude, variance)},
+            );
             quote! {
-                &#ident::#vident(ref inner) => {#ident::#vident(Rotate::overrotate(&(*inner), amplitude, variance))}
+                &#ident::#vident(ref inner) => {#ident::#vident(#value)}
             }
         });
         quote! {
@@ -300,9 +315,11 @@ fn rotate_enum(de: DataEnum, ident: Ident) -> TokenStream {
     } else {
         quote! {}
     };
-    let match_pow_quotes = variants_with_type.map(|(vident, _, _)| {
+    let match_pow_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
+        let value = variant_value(&ty, quote! {Rotate::powercf(&#inner, power)});
         quote! {
-            &#ident::#vident(ref inner) => #ident::#vident(Rotate::powercf(&(*inner), power)),
+            &#ident::#vident(ref inner) => #ident::#vident(#value),
         }
     });
 
@@ -368,9 +385,10 @@ pub fn dispatch_struct_enum_define(input: DeriveInput) -> TokenStream {
 
 fn define_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let match_quotes = variants_with_type.map(|(vident, _, _)| {
+    let match_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {Define::name(&(*inner))},
+            &#ident::#vident(ref inner) => {Define::name(&#inner)},
         }
     });
     quote! {
@@ -436,9 +454,10 @@ pub fn dispatch_struct_enum_operate_noise_pragma(input: DeriveInput) -> TokenStr
 
 fn operate_noise_pragma_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
-    let match_quotes = variants_with_type.clone().map(|(vident, _, _)| {
+    let match_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
         quote! {
-            &#ident::#vident(ref inner) => {OperatePragmaNoise::superoperator(&(*inner))},
+            &#ident::#vident(ref inner) => {OperatePragmaNoise::superoperator(&#inner)},
         }
     });
     let match_proba_quotes = variants_with_type.clone().map(|(vident, _, _)| {
@@ -446,9 +465,11 @@ fn operate_noise_pragma_enum(de: DataEnum, ident: Ident) -> TokenStream {
             &#ident::#vident(ref inner) => inner.probability(),
         }
     });
-    let match_pow_quotes = variants_with_type.map(|(vident, _, _)| {
+    let match_pow_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
+        let value = variant_value(&ty, quote! {OperatePragmaNoise::powercf(&#inner, power)});
         quote! {
-            &#ident::#vident(ref inner) => #ident::#vident(OperatePragmaNoise::powercf(&(*inner), power)),
+            &#ident::#vident(ref inner) => #ident::#vident(#value),
         }
     });
     let q = quote! {
@@ -10,7 +10,7 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{extract_fields_with_types, extract_variants_with_types};
+use crate::{extract_fields_with_types, extract_variants_with_types, variant_inner, variant_value};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Data, DataEnum, DataStruct, DeriveInput, Ident};
@@ -29,14 +29,27 @@ pub fn dispatch_struct_enum(input: DeriveInput) -> TokenStream {
 fn substitute_enum(de: DataEnum, ident: Ident) -> TokenStream {
     let variants_with_type = extract_variants_with_types(de).into_iter();
 
-    let substitute_quotes = variants_with_type.clone().map(|(vident, _ ,_)| {
+    let substitute_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
+        let value = variant_value(
+            &ty,
+            quote! {Substitute::substitute_parameters(&#inner, calculator)?},
+        );
         quote! {
-            &#ident::#vident(ref inner) => {Ok(#ident::#vident(Substitute::substitute_parameters(&(*inner), calculator)?))},
+            &#ident::#vident(ref inner) => {Ok(#ident::#vident(#value))},
         }
     });
-    let remap_quotes = variants_with_type.map(|(vident, _ ,_)| {
+    let remap_quotes = variants_with_type.clone().map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
+        let value = variant_value(&ty, quote! {Substitute::remap_qubits(&#inner, mapping)?});
         quote! {
-            &#ident::#vident(ref inner) => {Ok(#ident::#vident(Substitute::remap_qubits(&(*inner), mapping)?))},
+            &#ident::#vident(ref inner) => {Ok(#ident::#vident(#value))},
+        }
+    });
+    let free_parameters_quotes = variants_with_type.map(|(vident, _, ty)| {
+        let inner = variant_inner(&ty);
+        quote! {
+            &#ident::#vident(ref inner) => {Substitute::free_parameters(&#inner)},
         }
     });
 
@@ -58,12 +71,22 @@ fn substitute_enum(de: DataEnum, ident: Ident) -> TokenStream {
             }
         }
     };
+    let qfree_parameters = quote! {
+        /// Returns the symbols in the symbolic parameters of the operation.
+        fn free_parameters(&self) -> std::collections::HashSet<String> {
+            match self{
+                #(#free_parameters_quotes)*
+                _ => panic!("Unexpectedly cannot match variant")
+            }
+        }
+    };
     quote! {
         /// Implements [Substitute] trait allowing to replace symbolic parameters and to perform qubit mappings.
         #[automatically_derived]
         impl Substitute for #ident{
             #qsubstitute
             #qremap
+            #qfree_parameters
         }
     }
 }
@@ -84,6 +107,21 @@ fn substitute_struct(ds: DataStruct, ident: Ident) -> TokenStream {
             },
             _ => quote! {(self).#id.clone()},
         });
+    let free_parameters_quote =
+        fields_with_type
+            .clone()
+            .filter_map(|(id, type_string, _)| match type_string.as_deref() {
+                Some("CalculatorFloat") => Some(
+                    quote! {parameters.extend(crate::parameters::expression_symbols(&self.#id));},
+                ),
+                Some("Circuit") => Some(quote! {parameters.extend(self.#id.free_parameters());}),
+                Some("Option<Circuit>") => Some(quote! {
+                    if let Some(circuit) = self.#id.as_ref() {
+                        parameters.extend(circuit.free_parameters());
+                    }
+                }),
+                _ => None,
+            });
     let mut contains_qubits = false;
     let remap_quote = fields_with_type.clone().map(|(fid, _, _)|  {
             match fid.to_string().as_str() {
@@ -122,6 +160,13 @@ fn substitute_struct(ds: DataStruct, ident: Ident) -> TokenStream {
                 #new_qubits_quote
                 Ok(Self::new(#(#remap_quote),*))
             }
+            /// Returns the symbols in the symbolic parameters of the operation.
+            #[allow(unused_mut)]
+            fn free_parameters(&self) -> std::collections::HashSet<String> {
+                let mut parameters: std::collections::HashSet<String> = std::collections::HashSet::new();
+                #(#free_parameters_quote)*
+                parameters
+            }
         }
     }
 }
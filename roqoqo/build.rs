@@ -48,6 +48,8 @@ struct Visitor {
     two_qubit_gate_operations: Vec<Ident>,
     // Identifiers of structs belonging to MultiQubitGateOperation enum
     multi_qubit_gate_operations: Vec<Ident>,
+    // Names of structs with the boxed_variant attribute, stored boxed in the enums
+    boxed_operations: HashSet<String>,
 }
 
 impl Visitor {
@@ -66,6 +68,7 @@ impl Visitor {
             single_qubit_gate_operations: Vec::new(),
             two_qubit_gate_operations: Vec::new(),
             multi_qubit_gate_operations: Vec::new(),
+            boxed_operations: HashSet::new(),
         }
     }
 }
@@ -178,6 +181,13 @@ impl<'ast> Visit<'ast> for Visitor {
             }
         }
 
+        if i.attrs
+            .iter()
+            .any(|att| matches!(att.style, AttrStyle::Outer) && att.path.is_ident("boxed_variant"))
+        {
+            self.boxed_operations.insert(i.ident.to_string());
+        }
+
         visit::visit_item_struct(self, i);
     }
 
@@ -220,7 +230,7 @@ impl<'ast> Visit<'ast> for Visitor {
                     self.pragma_noise_operations.push(id.clone());
                 }
                 if trait_name.as_str() == "OperateMultiQubitGate" {
-                    self.two_qubit_gate_operations.push(id);
+                    self.multi_qubit_gate_operations.push(id);
                 }
             }
         }
@@ -228,12 +238,25 @@ impl<'ast> Visit<'ast> for Visitor {
     }
 }
 
+/// Returns the type of the variant of an operation in the enums
+///
+/// Operations with the boxed_variant attribute are stored boxed, so that their size does not increase the size of the enums.
+fn variant_type(operation: &Ident, boxed_operations: &HashSet<String>) -> proc_macro2::TokenStream {
+    if boxed_operations.contains(&operation.to_string()) {
+        quote! {Box<#operation>}
+    } else {
+        quote! {#operation}
+    }
+}
+
 const SOURCE_FILES: &[&str] = &[
     "src/operations/single_qubit_gate_operations.rs",
     "src/operations/pragma_operations.rs",
     "src/operations/two_qubit_gate_operations.rs",
     "src/operations/measurement_operations.rs",
     "src/operations/define_operations.rs",
+    "src/operations/multi_qubit_gate_operations.rs",
+    "src/operations/classical_operations.rs",
 ];
 
 fn main() {
@@ -247,103 +270,117 @@ fn main() {
         let syntax_tree: File = syn::parse2(code).unwrap();
         vis.visit_file(&syntax_tree);
     }
+    let boxed_operations = vis.boxed_operations.clone();
 
     // Construct TokenStreams for variants of operation enum
     let operations_quotes = vis.operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[allow(clippy::upper_case_acronyms)]
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of operation enum
     let single_qubit_operations_quotes = vis.single_qubit_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of operation enum
     let two_qubit_operations_quotes = vis.two_qubit_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[allow(clippy::upper_case_acronyms)]
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of operation enum
     let multi_qubit_operations_quotes = vis.multi_qubit_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of pragma enum
     let pragma_operations_quotes = vis.pragma_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of pragma enum
     let pragma_noise_operations_quotes = vis.pragma_noise_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of pragma enum
     let gate_operations_quotes = vis.gate_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[allow(clippy::upper_case_acronyms)]
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of definition enum
     let rotations_quotes = vis.rotations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
             #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of definition enum
     let definitions_quotes = vis.definitions.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
             #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of operation enum
     let constant_gate_operations_quote = vis.constant_gate_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[allow(clippy::upper_case_acronyms)]
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of operation enum
     let single_qubit_gate_operations_quote =
         vis.single_qubit_gate_operations.into_iter().map(|v| {
             let msg = format!("Variant for {}", v);
+            let ty = variant_type(&v, &boxed_operations);
             quote! {
                 #[doc = #msg]
-            #v(#v)}
+            #v(#ty)}
         });
     // Construct TokenStreams for variants of operation enum
     let two_qubit_gate_operations_quote = vis.two_qubit_gate_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
         #[allow(clippy::upper_case_acronyms)]
         #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStreams for variants of operation enum
     let multi_qubit_gate_operations_quote = vis.multi_qubit_gate_operations.into_iter().map(|v| {
         let msg = format!("Variant for {}", v);
+        let ty = variant_type(&v, &boxed_operations);
         quote! {
             #[doc = #msg]
-        #v(#v)}
+        #v(#ty)}
     });
     // Construct TokenStream for auto-generated rust file containing the enums
     let final_quote = quote! {
@@ -353,6 +390,7 @@ fn main() {
         /// Enum of all Operations implementing [Operate]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, Substitute)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum Operation {
             #(#operations_quotes),*
         }
@@ -360,6 +398,7 @@ fn main() {
         /// Enum of all Operations implementing [OperateSingleQubit]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateSingleQubit)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum SingleQubitOperation {
             #(#single_qubit_operations_quotes),*
         }
@@ -367,6 +406,7 @@ fn main() {
         /// Enum of all Operations implementing [OperateTwoQubit]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateTwoQubit)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum TwoQubitOperation {
             #(#two_qubit_operations_quotes),*
         }
@@ -374,6 +414,7 @@ fn main() {
         /// Enum of all Operations implementing [OperateMultiQubit]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateMultiQubit)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum MultiQubitOperation {
             #(#multi_qubit_operations_quotes),*
         }
@@ -381,6 +422,7 @@ fn main() {
         /// Enum of all Operations implementing [OperatePragma]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperatePragma)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum PragmaOperation {
             #(#pragma_operations_quotes),*
         }
@@ -388,6 +430,7 @@ fn main() {
         /// Enum of all Operations implementing [OperatePragmaNoise]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperatePragma, OperatePragmaNoise)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum PragmaNoiseOperation {
             #(#pragma_noise_operations_quotes),*
         }
@@ -395,6 +438,7 @@ fn main() {
         /// Enum of all Operations implementing [OperateGate]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateGate)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum GateOperation {
             #(#gate_operations_quotes),*
         }
@@ -403,6 +447,7 @@ fn main() {
         #[allow(clippy::upper_case_acronyms)]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateGate, Rotate)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum Rotation {
             #(#rotations_quotes),*
         }
@@ -410,6 +455,7 @@ fn main() {
         /// Enum of all Operations implementing [Define]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, Define)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum Definition {
             #(#definitions_quotes),*
         }
@@ -417,6 +463,7 @@ fn main() {
         /// Enum of all Operations implementing [OperateConstantGate]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateGate, OperateConstantGate)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum ConstantGateOperation {
             #(#constant_gate_operations_quote),*
         }
@@ -424,6 +471,7 @@ fn main() {
         /// Enum of all Operations implementing [OperateSingleQubitGate]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateGate, OperateSingleQubit, OperateSingleQubitGate)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum SingleQubitGateOperation {
             #(#single_qubit_gate_operations_quote),*
         }
@@ -431,6 +479,7 @@ fn main() {
         /// Enum of all Operations implementing [OperateTwoQubitGate]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateGate, OperateTwoQubit, OperateTwoQubitGate)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum TwoQubitGateOperation {
             #(#two_qubit_gate_operations_quote),*
         }
@@ -438,6 +487,7 @@ fn main() {
         /// Enum of all Operations implementing [OperateMultiQubitGate]
         #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateGate, OperateMultiQubit, OperateMultiQubitGate)]
         #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
         pub enum MultiQubitGateOperation {
             #(#multi_qubit_gate_operations_quote),*
         }
@@ -31,7 +31,9 @@
 use std::collections::HashMap;
 
 use crate::operations::Operation;
-use crate::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister};
+use crate::registers::{
+    BitOutputRegister, ComplexOutputRegister, FloatOutputRegister, OutputRegisters,
+};
 use crate::Circuit;
 use crate::{
     measurements::{Measure, MeasureExpectationValues},
@@ -39,6 +41,8 @@ use crate::{
 };
 
 /// Result of functions running a full circuit and producing output registers.
+///
+/// The registers can be converted into [OutputRegisters] with `OutputRegisters::from`.
 pub type RegisterResult = Result<
     (
         HashMap<String, BitOutputRegister>,
@@ -118,40 +122,15 @@ pub trait EvaluatingBackend: Sized {
     where
         T: Measure,
     {
-        let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
-        let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
-        let mut complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
-
+        let mut registers = OutputRegisters::new();
         for circuit in measurement.circuits() {
-            let (tmp_bit_reg, tmp_float_reg, tmp_complex_reg) = match measurement.constant_circuit()
-            {
+            let run = match measurement.constant_circuit() {
                 Some(x) => self.run_circuit_iterator(x.iter().chain(circuit.iter()))?,
                 None => self.run_circuit_iterator(circuit.iter())?,
             };
-
-            for (key, mut val) in tmp_bit_reg.into_iter() {
-                if let Some(x) = bit_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = bit_registers.insert(key, val);
-                }
-            }
-            for (key, mut val) in tmp_float_reg.into_iter() {
-                if let Some(x) = float_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = float_registers.insert(key, val);
-                }
-            }
-            for (key, mut val) in tmp_complex_reg.into_iter() {
-                if let Some(x) = complex_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = complex_registers.insert(key, val);
-                }
-            }
+            registers.append(OutputRegisters::from(run));
         }
-        Ok((bit_registers, float_registers, complex_registers))
+        Ok(registers.into())
     }
     /// Evaluates expectation values of a measurement with the backend.
     ///
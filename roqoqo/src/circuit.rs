@@ -13,6 +13,7 @@
 use crate::operations::{Define, InvolveQubits, InvolvedQubits, Operate, Operation, Substitute};
 #[cfg(feature = "overrotate")]
 use crate::operations::{Rotate, Rotation};
+use crate::parameters::check_parameter_binding;
 use crate::RoqoqoError;
 use qoqo_calculator::Calculator;
 #[cfg(feature = "overrotate")]
@@ -81,6 +82,7 @@ use std::{
 ///
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct Circuit {
     /// Definitions in the quantum circuit, must be unique.
     definitions: Vec<Operation>,
@@ -187,6 +189,62 @@ impl Circuit {
             || self.definitions.iter().any(|o| o.is_parametrized())
     }
 
+    /// Returns the names of all free symbolic parameters in the Circuit.
+    ///
+    /// The symbols are parsed from the symbolic expressions of all operations and definitions,
+    /// including the Circuits nested in operations.
+    /// Parameters with a default value given by an [crate::operations::InputSymbolic] definition are included.
+    ///
+    /// # Returns
+    ///
+    /// * `HashSet<String>` - The names of the symbolic parameters.
+    pub fn free_parameters(&self) -> HashSet<String> {
+        self.iter()
+            .flat_map(|operation| operation.free_parameters())
+            .collect()
+    }
+
+    /// Returns the default values of symbolic parameters defined by [crate::operations::InputSymbolic] definitions.
+    ///
+    /// # Returns
+    ///
+    /// * `HashMap<String, f64>` - The default values with the parameter names as keys.
+    pub fn default_parameters(&self) -> HashMap<String, f64> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Operation::InputSymbolic(x) => Some((x.name().clone(), *x.input())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Substitutes the symbolic parameters in a clone of Circuit after checking that all of them are bound.
+    ///
+    /// Parameters with a default value given by an [crate::operations::InputSymbolic] definition
+    /// do not need to be bound, values in `parameters` take precedence over the defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The values of the symbolic parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The Circuit with the parameters substituted.
+    /// * `Err(RoqoqoError::ParameterBindingError)` - Free parameters are not bound or unknown parameters are given.
+    /// * `Err(RoqoqoError)` - The substitution failed.
+    pub fn bind_parameters(&self, parameters: &HashMap<String, f64>) -> Result<Self, RoqoqoError> {
+        check_parameter_binding(
+            &[(self.free_parameters(), self.default_parameters())],
+            parameters,
+        )?;
+        let mut calculator = Calculator::new();
+        for (name, value) in parameters.iter() {
+            calculator.set_variable(name, *value);
+        }
+        self.substitute_parameters(&mut calculator)
+    }
+
     /// Returns the length of the Circuit.
     ///
     /// # Returns
@@ -253,6 +311,9 @@ impl Circuit {
 
     /// Substitutes the symbolic parameters in a clone of Circuit according to the calculator input.
     ///
+    /// [crate::operations::InputSymbolic] definitions set default values for parameters
+    /// that are not set in the calculator.
+    ///
     /// # Arguments
     ///
     /// * ``calculator` - The Calculator containing the substitutions to use in the Circuit.
@@ -266,7 +327,9 @@ impl Circuit {
         for def in self.definitions.iter() {
             let tmp_op = def.substitute_parameters(calculator)?;
             if let Operation::InputSymbolic(x) = &tmp_op {
-                calculator.set_variable(x.name(), *x.input())
+                if calculator.get_variable(x.name()).is_err() {
+                    calculator.set_variable(x.name(), *x.input())
+                }
             }
             tmp_def.push(tmp_op);
         }
@@ -0,0 +1,509 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::operations::{InvolveQubits, InvolvedQubits, Operation};
+use crate::Circuit;
+use std::collections::HashMap;
+
+/// Represents a quantum circuit as a directed acyclic graph (DAG) of operations.
+///
+/// Every operation of the circuit is a node of the graph, identified by its index in the
+/// original operation sequence.
+/// An edge from node `a` to node `b` means that `a` has to be executed before `b`.
+/// Edges are created when two operations act on a common qubit
+/// or when they access the same classical register and at least one of the accesses is a write
+/// (for example a [crate::operations::MeasureQubit] writing to a register that is read by a
+/// [crate::operations::PragmaConditional]).
+/// Operations acting on all qubits ([InvolvedQubits::All]) depend on every preceding operation acting on qubits.
+///
+/// Definitions are not part of the graph and are stored separately.
+///
+/// Since edges always point from lower to higher node indices, the node indices are a valid topological order
+/// and converting a CircuitDag back to a [Circuit] reproduces the original circuit.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::{Circuit, CircuitDag};
+/// use roqoqo::operations::{CNOT, Hadamard, PauliX};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += Hadamard::new(0);
+/// circuit += PauliX::new(1);
+/// circuit += CNOT::new(0, 1);
+///
+/// let dag = CircuitDag::from(&circuit);
+/// assert_eq!(dag.front_layer(), vec![0, 1]);
+/// assert_eq!(dag.predecessors(2), &[0, 1]);
+/// assert_eq!(dag.layers(), vec![vec![0, 1], vec![2]]);
+/// assert_eq!(Circuit::from(dag), circuit);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct CircuitDag {
+    /// Definitions of the quantum circuit, not part of the graph.
+    definitions: Vec<Operation>,
+    /// Operations of the quantum circuit, the nodes of the graph.
+    operations: Vec<Operation>,
+    /// Sorted indices of the direct predecessors of each node.
+    predecessors: Vec<Vec<usize>>,
+    /// Sorted indices of the direct successors of each node.
+    successors: Vec<Vec<usize>>,
+    /// The last node acting on each qubit.
+    last_on_qubit: HashMap<usize, usize>,
+    /// The last node acting on all qubits.
+    last_on_all_qubits: Option<usize>,
+    /// The last node writing to each classical register.
+    last_register_write: HashMap<String, usize>,
+    /// The nodes reading each classical register since the last write.
+    register_reads: HashMap<String, Vec<usize>>,
+}
+
+impl CircuitDag {
+    /// Creates an empty CircuitDag.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The empty CircuitDag.
+    pub fn new() -> Self {
+        CircuitDag {
+            definitions: Vec::new(),
+            operations: Vec::new(),
+            predecessors: Vec::new(),
+            successors: Vec::new(),
+            last_on_qubit: HashMap::new(),
+            last_on_all_qubits: None,
+            last_register_write: HashMap::new(),
+            register_reads: HashMap::new(),
+        }
+    }
+
+    /// Adds an Operation to the end of the CircuitDag.
+    ///
+    /// Definitions are stored separately and do not become nodes of the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - The Operation to add to the CircuitDag.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The index of the new node.
+    /// * `None` - The Operation is a definition and was not added as a node.
+    pub fn add_operation<T>(&mut self, op: T) -> Option<usize>
+    where
+        T: Into<Operation>,
+    {
+        let input: Operation = op.into();
+        match &input {
+            Operation::DefinitionBit(_)
+            | Operation::DefinitionFloat(_)
+            | Operation::DefinitionComplex(_)
+            | Operation::DefinitionUsize(_)
+            | Operation::InputSymbolic(_) => {
+                self.definitions.push(input);
+                return None;
+            }
+            _ => (),
+        }
+        let node = self.operations.len();
+        let mut predecessors: Vec<usize> = Vec::new();
+
+        match input.involved_qubits() {
+            InvolvedQubits::None => (),
+            InvolvedQubits::All => {
+                predecessors.extend(self.last_on_qubit.values());
+                predecessors.extend(self.last_on_all_qubits);
+                self.last_on_qubit.clear();
+                self.last_on_all_qubits = Some(node);
+            }
+            InvolvedQubits::Set(qubits) => {
+                for qubit in qubits {
+                    predecessors.extend(
+                        self.last_on_qubit
+                            .get(&qubit)
+                            .copied()
+                            .or(self.last_on_all_qubits),
+                    );
+                    self.last_on_qubit.insert(qubit, node);
+                }
+            }
+        }
+
+        let (reads, writes) = register_access(&input);
+        for register in reads {
+            predecessors.extend(self.last_register_write.get(&register));
+            self.register_reads.entry(register).or_default().push(node);
+        }
+        for register in writes {
+            predecessors.extend(self.last_register_write.get(&register));
+            if let Some(readers) = self.register_reads.remove(&register) {
+                predecessors.extend(readers.into_iter().filter(|reader| *reader != node));
+            }
+            self.last_register_write.insert(register, node);
+        }
+
+        predecessors.sort_unstable();
+        predecessors.dedup();
+        for predecessor in predecessors.iter() {
+            self.successors[*predecessor].push(node);
+        }
+        self.operations.push(input);
+        self.predecessors.push(predecessors);
+        self.successors.push(Vec::new());
+        Some(node)
+    }
+
+    /// Returns a reference to the operation at a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the node.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Operation)` - The operation at the node.
+    /// * `None` - The node does not exist.
+    pub fn get(&self, index: usize) -> Option<&Operation> {
+        self.operations.get(index)
+    }
+
+    /// Returns the number of nodes in the CircuitDag.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of operations (not including definitions).
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Returns true if the CircuitDag contains no nodes.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the CircuitDag contains no operations (definitions are not counted).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns reference to the vector of definitions in the CircuitDag.
+    ///
+    /// # Returns
+    ///
+    /// * `&Vec<Operation>` - A vector of the definitions.
+    pub fn definitions(&self) -> &Vec<Operation> {
+        &self.definitions
+    }
+
+    /// Returns reference to the vector of operations (nodes) in the CircuitDag.
+    ///
+    /// # Returns
+    ///
+    /// * `&Vec<Operation>` - A vector of the operations ordered by node index.
+    pub fn operations(&self) -> &Vec<Operation> {
+        &self.operations
+    }
+
+    /// Returns the direct predecessors of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the node.
+    ///
+    /// # Returns
+    ///
+    /// * `&[usize]` - The sorted indices of the nodes that need to be executed directly before the node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    pub fn predecessors(&self, index: usize) -> &[usize] {
+        &self.predecessors[index]
+    }
+
+    /// Returns the direct successors of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the node.
+    ///
+    /// # Returns
+    ///
+    /// * `&[usize]` - The sorted indices of the nodes that need to be executed directly after the node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    pub fn successors(&self, index: usize) -> &[usize] {
+        &self.successors[index]
+    }
+
+    /// Returns the front layer of the CircuitDag.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The sorted indices of all nodes without predecessors.
+    pub fn front_layer(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|index| self.predecessors[*index].is_empty())
+            .collect()
+    }
+
+    /// Returns the back layer of the CircuitDag.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The sorted indices of all nodes without successors.
+    pub fn back_layer(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|index| self.successors[*index].is_empty())
+            .collect()
+    }
+
+    /// Returns the topological layers of the CircuitDag.
+    ///
+    /// Every node is placed in the earliest possible layer,
+    /// one layer after the latest of its predecessors.
+    /// Operations within one layer do not depend on each other.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Vec<usize>>` - The sorted node indices of each layer.
+    pub fn layers(&self) -> Vec<Vec<usize>> {
+        let mut layers: Vec<Vec<usize>> = Vec::new();
+        let mut node_layer: Vec<usize> = Vec::with_capacity(self.len());
+        for predecessors in self.predecessors.iter() {
+            let layer = predecessors
+                .iter()
+                .map(|predecessor| node_layer[*predecessor] + 1)
+                .max()
+                .unwrap_or(0);
+            if layer == layers.len() {
+                layers.push(Vec::new());
+            }
+            layers[layer].push(node_layer.len());
+            node_layer.push(layer);
+        }
+        layers
+    }
+
+    /// Returns the longest path through the CircuitDag.
+    ///
+    /// The length of the path is the number of nodes it contains
+    /// and is equal to the number of layers returned by [CircuitDag::layers].
+    /// When several paths have the same length, the one ending at the lowest node index is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The node indices along the longest path, from the first to the last executed node.
+    pub fn longest_path(&self) -> Vec<usize> {
+        let mut length: Vec<usize> = Vec::with_capacity(self.len());
+        let mut previous: Vec<Option<usize>> = Vec::with_capacity(self.len());
+        for predecessors in self.predecessors.iter() {
+            let longest =
+                predecessors
+                    .iter()
+                    .copied()
+                    .fold(None, |best: Option<usize>, predecessor| match best {
+                        Some(b) if length[b] >= length[predecessor] => Some(b),
+                        _ => Some(predecessor),
+                    });
+            length.push(longest.map(|b| length[b]).unwrap_or(0) + 1);
+            previous.push(longest);
+        }
+        let mut end: Option<usize> = None;
+        for (index, l) in length.iter().enumerate() {
+            if end.map(|e| length[e] < *l).unwrap_or(true) {
+                end = Some(index);
+            }
+        }
+        let mut path: Vec<usize> = Vec::new();
+        while let Some(node) = end {
+            path.push(node);
+            end = previous[node];
+        }
+        path.reverse();
+        path
+    }
+
+    /// Returns the sub-graph containing only the selected nodes.
+    ///
+    /// The selected operations are inserted into a new CircuitDag in the order of their node indices,
+    /// the definitions are copied.
+    /// Dependencies that only existed via nodes that are not selected are not part of the sub-graph.
+    /// Node indices of the returned CircuitDag are positions in the sorted selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - The indices of the nodes in the sub-graph. Indices that do not exist are ignored.
+    ///
+    /// # Returns
+    ///
+    /// * `CircuitDag` - The sub-graph.
+    pub fn sub_dag(&self, nodes: &[usize]) -> CircuitDag {
+        let mut selected: Vec<usize> = nodes
+            .iter()
+            .copied()
+            .filter(|node| *node < self.len())
+            .collect();
+        selected.sort_unstable();
+        selected.dedup();
+        let mut sub_dag = CircuitDag::new();
+        sub_dag.definitions = self.definitions.clone();
+        for node in selected {
+            sub_dag.add_operation(self.operations[node].clone());
+        }
+        sub_dag
+    }
+
+    /// Converts the CircuitDag to a [Circuit].
+    ///
+    /// # Returns
+    ///
+    /// * `Circuit` - The Circuit containing the definitions and all operations in node order.
+    pub fn to_circuit(&self) -> Circuit {
+        let mut circuit = Circuit::new();
+        for definition in self.definitions.iter() {
+            circuit.add_operation(definition.clone());
+        }
+        for operation in self.operations.iter() {
+            circuit.add_operation(operation.clone());
+        }
+        circuit
+    }
+}
+
+impl Default for CircuitDag {
+    /// Creates an empty CircuitDag.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Circuit> for CircuitDag {
+    /// Creates a CircuitDag from a Circuit.
+    fn from(circuit: &Circuit) -> Self {
+        let mut dag = CircuitDag::new();
+        for definition in circuit.definitions().iter() {
+            dag.add_operation(definition.clone());
+        }
+        for operation in circuit.operations().iter() {
+            dag.add_operation(operation.clone());
+        }
+        dag
+    }
+}
+
+impl From<Circuit> for CircuitDag {
+    /// Creates a CircuitDag from a Circuit.
+    fn from(circuit: Circuit) -> Self {
+        CircuitDag::from(&circuit)
+    }
+}
+
+impl From<CircuitDag> for Circuit {
+    /// Creates a Circuit from a CircuitDag.
+    fn from(dag: CircuitDag) -> Self {
+        dag.to_circuit()
+    }
+}
+
+/// Returns the classical registers read and written by an operation.
+///
+/// Operations containing a circuit that is executed conditionally also access
+/// the registers accessed in that circuit.
+///
+/// # Arguments
+///
+/// * `operation` - The operation.
+///
+/// # Returns
+///
+/// * `(Vec<String>, Vec<String>)` - The names of the registers read and the names of the registers written.
+pub(crate) fn register_access(operation: &Operation) -> (Vec<String>, Vec<String>) {
+    let mut reads: Vec<String> = Vec::new();
+    let mut writes: Vec<String> = Vec::new();
+    match operation {
+        Operation::MeasureQubit(op) => writes.push(op.readout().clone()),
+        Operation::PragmaRepeatedMeasurement(op) => writes.push(op.readout().clone()),
+        Operation::PragmaGetStateVector(op) => writes.push(op.readout().clone()),
+        Operation::PragmaGetDensityMatrix(op) => writes.push(op.readout().clone()),
+        Operation::PragmaGetOccupationProbability(op) => writes.push(op.readout().clone()),
+        Operation::PragmaGetPauliProduct(op) => writes.push(op.readout().clone()),
+        Operation::PragmaSetNumberOfMeasurements(op) => writes.push(op.readout().clone()),
+        Operation::PragmaConditional(op) => {
+            reads.push(op.condition_register().clone());
+            for inner in op.circuit().iter() {
+                let (inner_reads, inner_writes) = register_access(inner);
+                reads.extend(inner_reads);
+                writes.extend(inner_writes);
+            }
+        }
+        Operation::PragmaConditionalPattern(op) => {
+            reads.push(op.condition_register().clone());
+            for inner in op.circuit().iter() {
+                let (inner_reads, inner_writes) = register_access(inner);
+                reads.extend(inner_reads);
+                writes.extend(inner_writes);
+            }
+        }
+        Operation::PragmaLoop(op) => {
+            for inner in op.circuit().iter() {
+                let (inner_reads, inner_writes) = register_access(inner);
+                reads.extend(inner_reads);
+                writes.extend(inner_writes);
+            }
+        }
+        Operation::PragmaRepeatUntilSuccess(op) => {
+            reads.push(op.condition_register().clone());
+            for inner in op.circuit().iter() {
+                let (inner_reads, inner_writes) = register_access(inner);
+                reads.extend(inner_reads);
+                writes.extend(inner_writes);
+            }
+        }
+        Operation::ClassicalNot(op) => {
+            reads.push(op.register().clone());
+            writes.push(op.output_register().clone());
+        }
+        Operation::ClassicalCopy(op) => {
+            reads.push(op.register().clone());
+            writes.push(op.output_register().clone());
+        }
+        Operation::ClassicalXor(op) => {
+            reads.push(op.register().clone());
+            writes.push(op.output_register().clone());
+        }
+        Operation::ClassicalAnd(op) => {
+            reads.push(op.register().clone());
+            writes.push(op.output_register().clone());
+        }
+        Operation::ClassicalOr(op) => {
+            reads.push(op.register().clone());
+            writes.push(op.output_register().clone());
+        }
+        Operation::ClassicalGreaterThan(op) => {
+            reads.push(op.register().clone());
+            writes.push(op.output_register().clone());
+        }
+        Operation::ClassicalLessThan(op) => {
+            reads.push(op.register().clone());
+            writes.push(op.output_register().clone());
+        }
+        _ => (),
+    }
+    reads.sort();
+    reads.dedup();
+    writes.sort();
+    writes.dedup();
+    (reads, writes)
+}
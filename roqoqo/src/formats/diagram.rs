@@ -0,0 +1,906 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::drawing::{decimal, pi_fraction, GREEK_LETTERS};
+use super::qasm_export::number_qubits;
+use crate::operations::*;
+use crate::Circuit;
+use qoqo_calculator::CalculatorFloat;
+
+/// Maximal number of characters of a symbolic parameter in a diagram.
+const MAX_EXPRESSION_LENGTH: usize = 12;
+
+/// Character set used to draw circuit diagrams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramCharset {
+    /// Only ASCII characters.
+    Ascii,
+    /// Unicode box-drawing characters and symbols.
+    Unicode,
+}
+
+/// Characters and symbols of a charset.
+#[derive(Clone, Copy)]
+struct Glyphs {
+    /// Horizontal line of a qubit wire.
+    wire: char,
+    /// Horizontal line of a classical register.
+    classical: char,
+    /// Single vertical connector crossing a qubit wire.
+    cross: char,
+    /// Double vertical connector crossing a qubit wire.
+    double_cross: char,
+    /// Double vertical connector crossing a classical register.
+    double_cross_classical: char,
+    /// Control of a controlled gate.
+    control: &'static str,
+    /// Target of a CNOT.
+    target: &'static str,
+    /// Ends of a SWAP.
+    swap: &'static str,
+    /// Readout of an operation in a classical register.
+    readout: &'static str,
+    /// Symbol for repetitions.
+    times: &'static str,
+    /// Symbol of pi.
+    pi: &'static str,
+    /// Symbol of truncated expressions.
+    ellipsis: &'static str,
+    /// Square root in gate names.
+    sqrt: &'static str,
+    /// Suffix of inverse gates.
+    dagger: &'static str,
+    /// Label of a reset.
+    reset: &'static str,
+    /// Brackets of expectation values.
+    expectation: (&'static str, &'static str),
+}
+
+impl Glyphs {
+    /// Returns the glyphs of a charset.
+    fn new(charset: DiagramCharset) -> Self {
+        match charset {
+            DiagramCharset::Ascii => Glyphs {
+                wire: '-',
+                classical: '=',
+                cross: '+',
+                double_cross: '#',
+                double_cross_classical: '#',
+                control: "@",
+                target: "X",
+                swap: "x",
+                readout: "v",
+                times: "x",
+                pi: "pi",
+                ellipsis: "...",
+                sqrt: "sqrt",
+                dagger: "dg",
+                reset: "|0>",
+                expectation: ("<", ">"),
+            },
+            DiagramCharset::Unicode => Glyphs {
+                wire: '─',
+                classical: '═',
+                cross: '┼',
+                double_cross: '╫',
+                double_cross_classical: '╬',
+                control: "●",
+                target: "⊕",
+                swap: "×",
+                readout: "╩",
+                times: "×",
+                pi: "π",
+                ellipsis: "…",
+                sqrt: "√",
+                dagger: "†",
+                reset: "|0⟩",
+                expectation: ("⟨", "⟩"),
+            },
+        }
+    }
+}
+
+/// Draws a circuit as a text diagram.
+///
+/// Every qubit is drawn as a horizontal wire and every classical register as a double line below the qubits.
+/// Operations are placed in columns as early as possible.
+/// Operations acting on several qubits or on classical registers are connected by vertical lines,
+/// measurements are drawn as `M` with the index of the readout in the classical register.
+/// Parameters are shown compactly as multiples of pi where possible and symbolic parameters are truncated.
+///
+/// When `max_width` is given, the diagram is folded into several blocks of columns,
+/// which are at most `max_width` characters wide unless a single column is wider.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to draw.
+/// * `charset` - The characters used to draw the diagram.
+/// * `max_width` - The maximal width of the lines of the diagram, None for no folding.
+///
+/// # Returns
+///
+/// * `String` - The diagram of the circuit.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::formats::{circuit_to_text_diagram, DiagramCharset};
+/// use roqoqo::operations::{CNOT, DefinitionBit, Hadamard, MeasureQubit};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += DefinitionBit::new("ro".to_string(), 1, true);
+/// circuit += Hadamard::new(0);
+/// circuit += CNOT::new(0, 1);
+/// circuit += MeasureQubit::new(1, "ro".to_string(), 0);
+///
+/// let diagram = circuit_to_text_diagram(&circuit, DiagramCharset::Ascii, None);
+/// assert_eq!(
+///     diagram,
+///     "q0: -H--@-----\n\
+///      q1: ----X--M--\n\
+///      ro: =======0==\n"
+/// );
+/// ```
+pub fn circuit_to_text_diagram(
+    circuit: &Circuit,
+    charset: DiagramCharset,
+    max_width: Option<usize>,
+) -> String {
+    let mut builder = DiagramBuilder {
+        charset,
+        glyphs: Glyphs::new(charset),
+        number_qubits: number_qubits(circuit),
+        registers: Vec::new(),
+    };
+    let elements: Vec<Element> = circuit
+        .iter()
+        .filter_map(|operation| builder.element(operation))
+        .collect();
+    builder.render(elements, max_width)
+}
+
+/// An operation drawn in a single column of the diagram.
+struct Element {
+    /// The rows of the labels of the operation and the labels, sorted by row.
+    labels: Vec<(usize, String)>,
+    /// True if the vertical connector is drawn as a double line.
+    double: bool,
+}
+
+impl Element {
+    /// Returns an element with a single label.
+    fn single(row: usize, label: String) -> Self {
+        Element {
+            labels: vec![(row, label)],
+            double: false,
+        }
+    }
+
+    /// Returns an element with labels on two rows connected by a single line.
+    fn pair(first: (usize, String), second: (usize, String)) -> Self {
+        let mut labels = vec![first, second];
+        labels.sort();
+        Element {
+            labels,
+            double: false,
+        }
+    }
+
+    /// Returns the first and last row of the element.
+    fn span(&self) -> (usize, usize) {
+        let first = self.labels.first().map(|(row, _)| *row).unwrap_or(0);
+        let last = self.labels.last().map(|(row, _)| *row).unwrap_or(0);
+        (first, last)
+    }
+}
+
+/// A column of the diagram.
+struct Column {
+    /// The elements in the column, which have disjoint spans.
+    elements: Vec<Element>,
+    /// The width of the column in characters.
+    width: usize,
+}
+
+/// State of the drawing of a circuit.
+struct DiagramBuilder {
+    /// The charset of the diagram.
+    charset: DiagramCharset,
+    /// The characters used in the diagram.
+    glyphs: Glyphs,
+    /// The number of qubit wires.
+    number_qubits: usize,
+    /// The names of the classical registers in the order of their rows.
+    registers: Vec<String>,
+}
+
+impl DiagramBuilder {
+    /// Returns the row of a classical register, adding the register if it is not known yet.
+    fn register_row(&mut self, name: &str) -> usize {
+        let index = match self.registers.iter().position(|register| register == name) {
+            Some(index) => index,
+            None => {
+                self.registers.push(name.to_string());
+                self.registers.len() - 1
+            }
+        };
+        self.number_qubits + index
+    }
+
+    /// Returns the compact text of a parameter.
+    fn parameter(&self, value: &CalculatorFloat) -> String {
+        match value {
+            CalculatorFloat::Float(value) => self.number(*value),
+            CalculatorFloat::Str(expression) => self.expression(expression),
+        }
+    }
+
+    /// Returns the comma separated texts of parameters.
+    fn parameters(&self, values: &[&CalculatorFloat]) -> String {
+        let texts: Vec<String> = values.iter().map(|value| self.parameter(value)).collect();
+        texts.join(",")
+    }
+
+    /// Returns a number as a multiple of pi with a small denominator or with three decimals.
+    fn number(&self, value: f64) -> String {
+        match pi_fraction(value) {
+            Some((numerator, denominator)) => {
+                let sign = if numerator < 0 { "-" } else { "" };
+                let factor = match numerator.abs() {
+                    1 => self.glyphs.pi.to_string(),
+                    n => format!("{}{}", n, self.glyphs.pi),
+                };
+                match denominator {
+                    1 => format!("{}{}", sign, factor),
+                    _ => format!("{}{}/{}", sign, factor, denominator),
+                }
+            }
+            None => decimal(value),
+        }
+    }
+
+    /// Returns a symbolic expression without whitespace, with Greek letters in Unicode and truncated.
+    fn expression(&self, expression: &str) -> String {
+        let mut text = String::new();
+        let mut word = String::new();
+        for c in expression.chars().chain(std::iter::once(' ')) {
+            if c.is_alphanumeric() || c == '_' {
+                word.push(c);
+                continue;
+            }
+            let letter = GREEK_LETTERS
+                .iter()
+                .find(|(name, _)| *name == word)
+                .filter(|_| self.charset == DiagramCharset::Unicode);
+            match letter {
+                Some((_, letter)) => text.push_str(letter),
+                None => text.push_str(&word),
+            }
+            word.clear();
+            if !c.is_whitespace() {
+                text.push(c);
+            }
+        }
+        if text.chars().count() > MAX_EXPRESSION_LENGTH {
+            let keep = MAX_EXPRESSION_LENGTH - self.glyphs.ellipsis.chars().count();
+            text = text.chars().take(keep).collect();
+            text.push_str(self.glyphs.ellipsis);
+        }
+        text
+    }
+
+    /// Returns an element with the same label on all qubit wires.
+    fn all_qubits(&self, label: String) -> Option<Element> {
+        if self.number_qubits == 0 {
+            return None;
+        }
+        Some(Element {
+            labels: (0..self.number_qubits)
+                .map(|qubit| (qubit, label.clone()))
+                .collect(),
+            double: false,
+        })
+    }
+
+    /// Returns an element with the same label on the given qubit wires.
+    fn qubits(&self, qubits: &[usize], label: String) -> Option<Element> {
+        let mut qubits = qubits.to_vec();
+        qubits.sort_unstable();
+        qubits.dedup();
+        if qubits.is_empty() {
+            return None;
+        }
+        Some(Element {
+            labels: qubits
+                .into_iter()
+                .map(|qubit| (qubit, label.clone()))
+                .collect(),
+            double: false,
+        })
+    }
+
+    /// Returns an element with controls on all but the last qubit and a label on the last qubit.
+    fn controlled(&self, qubits: &[usize], label: String) -> Option<Element> {
+        let (target, controls) = qubits.split_last()?;
+        let mut labels: Vec<(usize, String)> = controls
+            .iter()
+            .map(|control| (*control, self.glyphs.control.to_string()))
+            .collect();
+        labels.push((*target, label));
+        labels.sort();
+        Some(Element {
+            labels,
+            double: false,
+        })
+    }
+
+    /// Returns an element for a measurement-like operation writing to a classical register.
+    fn readout(&mut self, labels: Vec<(usize, String)>, readout: &str, mark: String) -> Element {
+        let row = self.register_row(readout);
+        let mut labels = labels;
+        labels.push((row, mark));
+        labels.sort();
+        Element {
+            labels,
+            double: true,
+        }
+    }
+
+    /// Returns the labels on the qubit wires of the operations in the circuit of a conditional or loop operation.
+    fn block_labels(&self, circuit: &Circuit, prefix: &str) -> Vec<(usize, String)> {
+        let mut inner = DiagramBuilder {
+            charset: self.charset,
+            glyphs: self.glyphs,
+            number_qubits: self.number_qubits,
+            registers: Vec::new(),
+        };
+        let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+        for operation in circuit.iter() {
+            let element = match inner.element(operation) {
+                Some(element) => element,
+                None => continue,
+            };
+            for (row, label) in element.labels {
+                if row >= self.number_qubits {
+                    continue;
+                }
+                match rows.iter_mut().find(|(existing, _)| *existing == row) {
+                    Some((_, labels)) => labels.push(label),
+                    None => rows.push((row, vec![label])),
+                }
+            }
+        }
+        rows.into_iter()
+            .map(|(row, labels)| (row, format!("{}:{}", prefix, labels.join(" "))))
+            .collect()
+    }
+
+    /// Returns an element for a classical operation writing the result to an index of a classical register.
+    fn classical(
+        &mut self,
+        register: &str,
+        label: String,
+        output_register: &str,
+        output_index: usize,
+    ) -> Element {
+        let row = self.register_row(register);
+        let output_row = self.register_row(output_register);
+        let labels = if row == output_row {
+            vec![(row, format!("{}:{}", label, output_index))]
+        } else {
+            let mut labels = vec![(row, label), (output_row, output_index.to_string())];
+            labels.sort();
+            labels
+        };
+        Element {
+            labels,
+            double: true,
+        }
+    }
+
+    /// Returns the element of an operation, None if the operation is not drawn.
+    fn element(&mut self, operation: &Operation) -> Option<Element> {
+        let g = self.glyphs;
+        let element = match operation {
+            Operation::DefinitionFloat(op) => {
+                self.register_row(op.name());
+                return None;
+            }
+            Operation::DefinitionComplex(op) => {
+                self.register_row(op.name());
+                return None;
+            }
+            Operation::DefinitionUsize(op) => {
+                self.register_row(op.name());
+                return None;
+            }
+            Operation::DefinitionBit(op) => {
+                self.register_row(op.name());
+                return None;
+            }
+            Operation::InputSymbolic(_) => return None,
+            Operation::SingleQubitGate(op) => Element::single(*op.qubit(), "U".to_string()),
+            Operation::RotateZ(op) => {
+                Element::single(*op.qubit(), format!("Rz({})", self.parameter(op.theta())))
+            }
+            Operation::RotateX(op) => {
+                Element::single(*op.qubit(), format!("Rx({})", self.parameter(op.theta())))
+            }
+            Operation::RotateY(op) => {
+                Element::single(*op.qubit(), format!("Ry({})", self.parameter(op.theta())))
+            }
+            Operation::PauliX(op) => Element::single(*op.qubit(), "X".to_string()),
+            Operation::PauliY(op) => Element::single(*op.qubit(), "Y".to_string()),
+            Operation::PauliZ(op) => Element::single(*op.qubit(), "Z".to_string()),
+            Operation::SqrtPauliX(op) => Element::single(*op.qubit(), format!("{}X", g.sqrt)),
+            Operation::InvSqrtPauliX(op) => {
+                Element::single(*op.qubit(), format!("{}X{}", g.sqrt, g.dagger))
+            }
+            Operation::Hadamard(op) => Element::single(*op.qubit(), "H".to_string()),
+            Operation::SGate(op) => Element::single(*op.qubit(), "S".to_string()),
+            Operation::TGate(op) => Element::single(*op.qubit(), "T".to_string()),
+            Operation::RotateAroundSphericalAxis(op) => Element::single(
+                *op.qubit(),
+                format!(
+                    "RSph({})",
+                    self.parameters(&[op.theta(), op.spherical_theta(), op.spherical_phi()])
+                ),
+            ),
+            Operation::CNOT(op) => Element::pair(
+                (*op.control(), g.control.to_string()),
+                (*op.target(), g.target.to_string()),
+            ),
+            Operation::ControlledPauliY(op) => Element::pair(
+                (*op.control(), g.control.to_string()),
+                (*op.target(), "Y".to_string()),
+            ),
+            Operation::ControlledPauliZ(op) => Element::pair(
+                (*op.control(), g.control.to_string()),
+                (*op.target(), g.control.to_string()),
+            ),
+            Operation::ControlledPhaseShift(op) => Element::pair(
+                (*op.control(), g.control.to_string()),
+                (*op.target(), format!("P({})", self.parameter(op.theta()))),
+            ),
+            Operation::SWAP(op) => Element::pair(
+                (*op.control(), g.swap.to_string()),
+                (*op.target(), g.swap.to_string()),
+            ),
+            Operation::ISwap(op) => two_qubit(*op.control(), *op.target(), "iSWAP".to_string()),
+            Operation::FSwap(op) => two_qubit(*op.control(), *op.target(), "fSWAP".to_string()),
+            Operation::SqrtISwap(op) => {
+                two_qubit(*op.control(), *op.target(), format!("{}iSWAP", g.sqrt))
+            }
+            Operation::InvSqrtISwap(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("{}iSWAP{}", g.sqrt, g.dagger),
+            ),
+            Operation::XY(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("XY({})", self.parameter(op.theta())),
+            ),
+            Operation::MolmerSorensenXX(op) => {
+                two_qubit(*op.control(), *op.target(), "MS".to_string())
+            }
+            Operation::VariableMSXX(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("MS({})", self.parameter(op.theta())),
+            ),
+            Operation::GivensRotation(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("Givens({})", self.parameters(&[op.theta(), op.phi()])),
+            ),
+            Operation::GivensRotationLittleEndian(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("GivensLE({})", self.parameters(&[op.theta(), op.phi()])),
+            ),
+            Operation::Qsim(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("Qsim({})", self.parameters(&[op.x(), op.y(), op.z()])),
+            ),
+            Operation::Fsim(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("Fsim({})", self.parameters(&[op.t(), op.u(), op.delta()])),
+            ),
+            Operation::SpinInteraction(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("Spin({})", self.parameters(&[op.x(), op.y(), op.z()])),
+            ),
+            Operation::Bogoliubov(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!(
+                    "Bogoliubov({})",
+                    self.parameters(&[op.delta_real(), op.delta_imag()])
+                ),
+            ),
+            Operation::PMInteraction(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("PM({})", self.parameter(op.t())),
+            ),
+            Operation::ComplexPMInteraction(op) => two_qubit(
+                *op.control(),
+                *op.target(),
+                format!("PM({})", self.parameters(&[op.t_real(), op.t_imag()])),
+            ),
+            Operation::PhaseShiftedControlledZ(op) => Element::pair(
+                (*op.control(), g.control.to_string()),
+                (*op.target(), format!("PCZ({})", self.parameter(op.phi()))),
+            ),
+            Operation::TwoQubitGate(op) => two_qubit(*op.control(), *op.target(), "U".to_string()),
+            Operation::MultiQubitMS(op) => {
+                self.qubits(op.qubits(), format!("MS({})", self.parameter(op.theta())))?
+            }
+            Operation::MultiQubitGate(op) => self.qubits(op.qubits(), "U".to_string())?,
+            Operation::Toffoli(op) => self.controlled(op.qubits(), g.target.to_string())?,
+            Operation::ControlledControlledPauliZ(op) => {
+                self.controlled(op.qubits(), g.control.to_string())?
+            }
+            Operation::ControlledControlledPhaseShift(op) => {
+                self.controlled(op.qubits(), format!("P({})", self.parameter(op.theta())))?
+            }
+            Operation::MultiControlledGate(op) => self.controlled(op.qubits(), "U".to_string())?,
+            Operation::PauliProductRotation(op) => {
+                let theta = self.parameter(op.theta());
+                let mut labels: Vec<(usize, String)> = op
+                    .qubits()
+                    .iter()
+                    .zip(op.paulis().iter())
+                    .map(|(qubit, pauli)| {
+                        let name = ["I", "X", "Y", "Z"].get(*pauli).unwrap_or(&"?");
+                        (*qubit, format!("R{}({})", name, theta))
+                    })
+                    .collect();
+                if labels.is_empty() {
+                    return None;
+                }
+                labels.sort();
+                Element {
+                    labels,
+                    double: false,
+                }
+            }
+            Operation::PragmaActiveReset(op) => Element::single(*op.qubit(), g.reset.to_string()),
+            Operation::PragmaDamping(op) => Element::single(
+                *op.qubit(),
+                format!("Damping({})", self.parameters(&[op.gate_time(), op.rate()])),
+            ),
+            Operation::PragmaDepolarising(op) => Element::single(
+                *op.qubit(),
+                format!(
+                    "Depolarising({})",
+                    self.parameters(&[op.gate_time(), op.rate()])
+                ),
+            ),
+            Operation::PragmaDephasing(op) => Element::single(
+                *op.qubit(),
+                format!(
+                    "Dephasing({})",
+                    self.parameters(&[op.gate_time(), op.rate()])
+                ),
+            ),
+            Operation::PragmaRandomNoise(op) => Element::single(
+                *op.qubit(),
+                format!(
+                    "RandomNoise({})",
+                    self.parameters(&[op.gate_time(), op.depolarising_rate(), op.dephasing_rate()])
+                ),
+            ),
+            Operation::PragmaGeneralNoise(op) => Element::single(
+                *op.qubit(),
+                format!(
+                    "GeneralNoise({})",
+                    self.parameters(&[op.gate_time(), op.rate()])
+                ),
+            ),
+            Operation::PragmaSleep(op) => self.qubits(
+                op.qubits(),
+                format!("Sleep({})", self.parameter(op.sleep_time())),
+            )?,
+            Operation::PragmaStopParallelBlock(op) => self.qubits(
+                op.qubits(),
+                format!("StopParallelBlock({})", self.parameter(op.execution_time())),
+            )?,
+            Operation::PragmaStartDecompositionBlock(op) => {
+                self.qubits(op.qubits(), "StartDecompositionBlock".to_string())?
+            }
+            Operation::PragmaStopDecompositionBlock(op) => {
+                self.qubits(op.qubits(), "StopDecompositionBlock".to_string())?
+            }
+            Operation::PragmaOverrotation(op) => {
+                self.qubits(op.qubits(), format!("Overrotation({})", op.gate_hqslang()))?
+            }
+            Operation::PragmaSetStateVector(_) => self.all_qubits("SetStateVector".to_string())?,
+            Operation::PragmaSetDensityMatrix(_) => {
+                self.all_qubits("SetDensityMatrix".to_string())?
+            }
+            Operation::PragmaRepeatGate(op) => {
+                self.all_qubits(format!("RepeatGate({})", op.repetition_coefficient()))?
+            }
+            Operation::PragmaBoostNoise(op) => self.all_qubits(format!(
+                "BoostNoise({})",
+                self.parameter(op.noise_coefficient())
+            ))?,
+            Operation::PragmaGlobalPhase(op) => {
+                self.all_qubits(format!("GlobalPhase({})", self.parameter(op.phase())))?
+            }
+            Operation::PragmaSetNumberOfMeasurements(op) => {
+                let mark = format!("{}{}", g.times, op.number_measurements());
+                self.readout(Vec::new(), op.readout(), mark)
+            }
+            Operation::MeasureQubit(op) => {
+                let labels = vec![(*op.qubit(), "M".to_string())];
+                let mark = op.readout_index().to_string();
+                self.readout(labels, op.readout(), mark)
+            }
+            Operation::PragmaRepeatedMeasurement(op) => {
+                let mut qubits: Vec<usize> = match op.qubit_mapping() {
+                    Some(mapping) => mapping.keys().copied().collect(),
+                    None => (0..self.number_qubits).collect(),
+                };
+                qubits.sort_unstable();
+                let labels = qubits
+                    .into_iter()
+                    .map(|qubit| (qubit, "M".to_string()))
+                    .collect();
+                let mark = format!("{}{}", g.times, op.number_measurements());
+                self.readout(labels, op.readout(), mark)
+            }
+            Operation::PragmaGetStateVector(op) => {
+                let labels = (0..self.number_qubits)
+                    .map(|qubit| (qubit, "StateVector".to_string()))
+                    .collect();
+                let mark = g.readout.to_string();
+                self.readout(labels, op.readout(), mark)
+            }
+            Operation::PragmaGetDensityMatrix(op) => {
+                let labels = (0..self.number_qubits)
+                    .map(|qubit| (qubit, "DensityMatrix".to_string()))
+                    .collect();
+                let mark = g.readout.to_string();
+                self.readout(labels, op.readout(), mark)
+            }
+            Operation::PragmaGetOccupationProbability(op) => {
+                let labels = (0..self.number_qubits)
+                    .map(|qubit| (qubit, "Occupation".to_string()))
+                    .collect();
+                let mark = g.readout.to_string();
+                self.readout(labels, op.readout(), mark)
+            }
+            Operation::PragmaGetPauliProduct(op) => {
+                let mut paulis: Vec<(&usize, &usize)> = op.qubit_paulis().iter().collect();
+                paulis.sort_unstable();
+                let labels = paulis
+                    .into_iter()
+                    .map(|(qubit, pauli)| {
+                        let name = ["I", "X", "Y", "Z"].get(*pauli).unwrap_or(&"?");
+                        (
+                            *qubit,
+                            format!("{}{}{}", g.expectation.0, name, g.expectation.1),
+                        )
+                    })
+                    .collect();
+                let mark = g.readout.to_string();
+                self.readout(labels, op.readout(), mark)
+            }
+            Operation::PragmaConditional(op) => {
+                let labels = self.block_labels(op.circuit(), "if");
+                let mark = op.condition_index().to_string();
+                self.readout(labels, op.condition_register(), mark)
+            }
+            Operation::PragmaConditionalPattern(op) => {
+                let labels = self.block_labels(op.circuit(), "if");
+                let mark = op
+                    .condition()
+                    .iter()
+                    .map(|(index, value)| format!("{}={}", index, *value as u8))
+                    .collect::<Vec<String>>()
+                    .join(",");
+                self.readout(labels, op.condition_register(), mark)
+            }
+            Operation::PragmaLoop(op) => {
+                let prefix = format!("loop({})", self.parameter(op.repetitions()));
+                let labels = self.block_labels(op.circuit(), &prefix);
+                if labels.is_empty() {
+                    return None;
+                }
+                Element {
+                    labels,
+                    double: false,
+                }
+            }
+            Operation::PragmaRepeatUntilSuccess(op) => {
+                let prefix = format!("rus({})", op.max_iterations());
+                let labels = self.block_labels(op.circuit(), &prefix);
+                let mark = op.condition_index().to_string();
+                self.readout(labels, op.condition_register(), mark)
+            }
+            Operation::ClassicalNot(op) => self.classical(
+                op.register(),
+                format!("NOT({})", op.index()),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalCopy(op) => self.classical(
+                op.register(),
+                format!("COPY({})", op.index()),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalXor(op) => self.classical(
+                op.register(),
+                format!("XOR({})", indices_text(op.indices())),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalAnd(op) => self.classical(
+                op.register(),
+                format!("AND({})", indices_text(op.indices())),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalOr(op) => self.classical(
+                op.register(),
+                format!("OR({})", indices_text(op.indices())),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalGreaterThan(op) => self.classical(
+                op.register(),
+                format!("GT({},{})", op.index(), self.parameter(op.threshold())),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalLessThan(op) => self.classical(
+                op.register(),
+                format!("LT({},{})", op.index(), self.parameter(op.threshold())),
+                op.output_register(),
+                *op.output_index(),
+            ),
+        };
+        Some(element)
+    }
+
+    /// Places the elements in columns and renders the diagram.
+    fn render(&self, elements: Vec<Element>, max_width: Option<usize>) -> String {
+        let number_rows = self.number_qubits + self.registers.len();
+        if number_rows == 0 {
+            return String::new();
+        }
+        let mut columns: Vec<Column> = Vec::new();
+        let mut next_free = vec![0; number_rows];
+        for element in elements {
+            let (first, last) = element.span();
+            let index = next_free[first..=last].iter().copied().max().unwrap_or(0);
+            for free in next_free[first..=last].iter_mut() {
+                *free = index + 1;
+            }
+            if index == columns.len() {
+                columns.push(Column {
+                    elements: Vec::new(),
+                    width: 3,
+                });
+            }
+            let label_width = element
+                .labels
+                .iter()
+                .map(|(_, label)| label.chars().count())
+                .max()
+                .unwrap_or(1);
+            columns[index].width = columns[index].width.max(label_width + 2);
+            columns[index].elements.push(element);
+        }
+
+        let names: Vec<String> = (0..self.number_qubits)
+            .map(|qubit| format!("q{}", qubit))
+            .chain(self.registers.iter().cloned())
+            .collect();
+        let name_width = names
+            .iter()
+            .map(|name| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let prefix_width = name_width + 2;
+
+        let mut blocks: Vec<Vec<&Column>> = vec![Vec::new()];
+        let mut block_width = prefix_width + 1;
+        for column in columns.iter() {
+            let full = matches!(max_width, Some(max) if block_width + column.width > max);
+            if full && !blocks[blocks.len() - 1].is_empty() {
+                blocks.push(Vec::new());
+                block_width = prefix_width + 1;
+            }
+            block_width += column.width;
+            let last = blocks.len() - 1;
+            blocks[last].push(column);
+        }
+
+        let mut text = String::new();
+        for (block_index, block) in blocks.iter().enumerate() {
+            if block_index > 0 {
+                text.push('\n');
+            }
+            for (row, name) in names.iter().enumerate() {
+                let classical = row >= self.number_qubits;
+                let line = if classical {
+                    self.glyphs.classical
+                } else {
+                    self.glyphs.wire
+                };
+                text.push_str(&format!("{:>width$}: ", name, width = name_width));
+                for column in block.iter() {
+                    text.push_str(&self.cell(column, row, classical, line));
+                }
+                text.push(line);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// Returns the text of a column on a row.
+    fn cell(&self, column: &Column, row: usize, classical: bool, line: char) -> String {
+        let center = (column.width - 1) / 2;
+        let mut cell: Vec<char> = vec![line; column.width];
+        for element in column.elements.iter() {
+            let (first, last) = element.span();
+            if row < first || row > last {
+                continue;
+            }
+            match element
+                .labels
+                .iter()
+                .find(|(label_row, _)| *label_row == row)
+            {
+                Some((_, label)) => {
+                    let length = label.chars().count();
+                    let start = center - length.saturating_sub(1) / 2;
+                    for (index, c) in label.chars().enumerate() {
+                        cell[start + index] = c;
+                    }
+                }
+                None => {
+                    cell[center] = match (element.double, classical) {
+                        (false, _) => self.glyphs.cross,
+                        (true, false) => self.glyphs.double_cross,
+                        (true, true) => self.glyphs.double_cross_classical,
+                    };
+                }
+            }
+        }
+        cell.into_iter().collect()
+    }
+}
+
+/// Returns an element with the same label on the two qubits of a two-qubit gate.
+fn two_qubit(control: usize, target: usize, label: String) -> Element {
+    Element::pair((control, label.clone()), (target, label))
+}
+
+/// Returns the comma separated indices of the input bits of a classical operation.
+fn indices_text(indices: &[usize]) -> String {
+    indices
+        .iter()
+        .map(|index| index.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
@@ -0,0 +1,874 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::qasm_export::number_qubits;
+use crate::operations::*;
+use crate::Circuit;
+use qoqo_calculator::CalculatorFloat;
+use std::f64::consts::PI;
+
+/// Greek letters replacing parameter names in Unicode drawings, the names are also the LaTeX commands.
+pub(crate) const GREEK_LETTERS: &[(&str, &str)] = &[
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("theta", "θ"),
+    ("lambda", "λ"),
+    ("phi", "φ"),
+    ("pi", "π"),
+];
+
+/// Short symbols of operations as LaTeX math and as Unicode text, operations without a symbol are labeled by name.
+const SYMBOLS: &[(&str, &str, &str)] = &[
+    ("SingleQubitGate", "U", "U"),
+    ("RotateX", "R_x", "Rx"),
+    ("RotateY", "R_y", "Ry"),
+    ("RotateZ", "R_z", "Rz"),
+    ("RotateAroundSphericalAxis", "R_{\\hat{n}}", "Rn"),
+    ("PauliX", "X", "X"),
+    ("PauliY", "Y", "Y"),
+    ("PauliZ", "Z", "Z"),
+    ("SqrtPauliX", "\\sqrt{X}", "√X"),
+    ("InvSqrtPauliX", "\\sqrt{X}^\\dagger", "√X†"),
+    ("Hadamard", "H", "H"),
+    ("SGate", "S", "S"),
+    ("TGate", "T", "T"),
+    ("ControlledPauliY", "Y", "Y"),
+    ("ControlledPhaseShift", "P", "P"),
+    ("PhaseShiftedControlledZ", "Z_\\phi", "Zφ"),
+    ("ISwap", "i\\mathrm{SWAP}", "iSWAP"),
+    ("FSwap", "\\mathrm{FSWAP}", "FSWAP"),
+    ("SqrtISwap", "\\sqrt{i\\mathrm{SWAP}}", "√iSWAP"),
+    (
+        "InvSqrtISwap",
+        "\\sqrt{i\\mathrm{SWAP}}^\\dagger",
+        "√iSWAP†",
+    ),
+    ("MolmerSorensenXX", "\\mathrm{MS}", "MS"),
+    ("VariableMSXX", "\\mathrm{MS}", "MS"),
+    ("XY", "\\mathrm{XY}", "XY"),
+    ("GivensRotation", "G", "G"),
+    ("GivensRotationLittleEndian", "G_{\\mathrm{LE}}", "G LE"),
+    ("Qsim", "\\mathrm{Qsim}", "Qsim"),
+    ("Fsim", "\\mathrm{Fsim}", "Fsim"),
+    ("SpinInteraction", "\\mathrm{SI}", "SI"),
+    ("Bogoliubov", "\\mathrm{Bog}", "Bog"),
+    ("PMInteraction", "\\mathrm{PM}", "PM"),
+    ("ComplexPMInteraction", "\\mathrm{PM}_c", "PMc"),
+    ("TwoQubitGate", "U", "U"),
+    ("MultiQubitMS", "\\mathrm{MS}", "MS"),
+    ("MultiQubitGate", "U", "U"),
+    ("ControlledControlledPhaseShift", "P", "P"),
+    ("MultiControlledGate", "U", "U"),
+    ("PauliProductRotation", "R_{P}", "RP"),
+    ("ClassicalNot", "\\neg", "¬"),
+    ("ClassicalCopy", "\\mathrm{Copy}", "Copy"),
+    ("ClassicalXor", "\\oplus", "⊕"),
+    ("ClassicalAnd", "\\wedge", "∧"),
+    ("ClassicalOr", "\\vee", "∨"),
+    ("ClassicalGreaterThan", ">", ">"),
+    ("ClassicalLessThan", "<", "<"),
+    ("PragmaActiveReset", "|0\\rangle", "|0⟩"),
+    ("PragmaSleep", "\\mathrm{Sleep}", "Sleep"),
+    ("PragmaStopParallelBlock", "\\mathrm{Parallel}", "Parallel"),
+    ("PragmaGlobalPhase", "e^{i\\phi}", "e^iφ"),
+    ("PragmaSetStateVector", "\\psi", "ψ"),
+    ("PragmaSetDensityMatrix", "\\rho", "ρ"),
+    ("PragmaRepeatGate", "\\mathrm{Repeat}", "Repeat"),
+    ("PragmaLoop", "\\mathrm{Loop}", "Loop"),
+    ("PragmaRepeatUntilSuccess", "\\mathrm{RUS}", "RUS"),
+    ("PragmaGetStateVector", "\\langle\\psi|", "⟨ψ|"),
+    ("PragmaGetDensityMatrix", "\\langle\\rho|", "⟨ρ|"),
+    ("PragmaGetOccupationProbability", "\\langle n|", "⟨n|"),
+    ("PragmaDamping", "\\mathrm{Damp}", "Damp"),
+    ("PragmaDepolarising", "\\mathrm{Dep}", "Dep"),
+    ("PragmaDephasing", "\\mathrm{Deph}", "Deph"),
+    ("PragmaRandomNoise", "\\mathrm{Noise}", "Noise"),
+    ("PragmaGeneralNoise", "\\mathrm{Noise}", "Noise"),
+    ("PragmaOverrotation", "\\mathrm{Over}", "Over"),
+    ("PragmaBoostNoise", "\\mathrm{Boost}", "Boost"),
+];
+
+/// Returns the short LaTeX and Unicode symbols of an operation.
+pub(crate) fn short_symbol(hqslang: &str) -> Option<(&'static str, &'static str)> {
+    SYMBOLS
+        .iter()
+        .find(|(name, _, _)| *name == hqslang)
+        .map(|(_, latex, text)| (*latex, *text))
+}
+
+/// Returns the rows of the runs of equal gate symbols on consecutive rows of an element.
+///
+/// Every run is drawn as a single box spanning its rows.
+pub(crate) fn gate_runs(element: &Element) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let mut previous: Option<&(usize, Symbol)> = None;
+    for entry in element.symbols.iter() {
+        if let Symbol::Gate { .. } = entry.1 {
+            match (previous, runs.last_mut()) {
+                (Some((row, symbol)), Some(run)) if *row + 1 == entry.0 && *symbol == entry.1 => {
+                    run.1 = entry.0;
+                }
+                _ => runs.push((entry.0, entry.0)),
+            }
+        }
+        previous = Some(entry);
+    }
+    runs
+}
+
+/// Labels of the gates in circuit drawings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateLabels {
+    /// The hqslang name of the operation, e.g. `RotateX`.
+    Hqslang,
+    /// A short symbol of the operation, e.g. `Rx` or `H`.
+    Symbol,
+}
+
+/// Options of the LaTeX and SVG drawings of circuits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawingOptions {
+    /// The labels of the gates.
+    pub gate_labels: GateLabels,
+    /// Show the parameters of gates and pragmas in the labels.
+    pub show_parameters: bool,
+    /// Draw noise pragmas, which are omitted otherwise.
+    pub show_noise: bool,
+}
+
+impl Default for DrawingOptions {
+    fn default() -> Self {
+        DrawingOptions {
+            gate_labels: GateLabels::Symbol,
+            show_parameters: true,
+            show_noise: false,
+        }
+    }
+}
+
+/// Returns the numerator and denominator of a value that is a multiple of pi with a denominator up to 8.
+pub(crate) fn pi_fraction(value: f64) -> Option<(i64, i64)> {
+    if value == 0.0 {
+        return None;
+    }
+    (1..=8).find_map(|denominator| {
+        let numerator = value * (denominator as f64) / PI;
+        if (numerator - numerator.round()).abs() < 1e-9 {
+            Some((numerator.round() as i64, denominator))
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns a value with three decimals without trailing zeros, or in scientific notation if it is too small.
+pub(crate) fn decimal(value: f64) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if (text == "0" || text == "-0") && value != 0.0 {
+        format!("{:.1e}", value)
+    } else if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// A symbol drawn on a wire in a circuit drawing.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Symbol {
+    /// A box with the label of an operation.
+    Gate {
+        /// The hqslang name of the operation.
+        hqslang: &'static str,
+        /// The parameters shown in the label.
+        parameters: Vec<CalculatorFloat>,
+        /// True for noise pragmas, which are drawn with dashed boxes.
+        noise: bool,
+    },
+    /// The control of a controlled gate.
+    Control,
+    /// The target of a CNOT.
+    Target,
+    /// An end of a SWAP.
+    Swap,
+    /// A measurement of a qubit.
+    Meter,
+    /// The readout in a classical register with the optional index in the register.
+    Readout(Option<usize>),
+    /// The classical condition of a conditional operation with the label of the condition bits in the register.
+    Condition(String),
+}
+
+/// An operation drawn in a single column.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Element {
+    /// The rows of the symbols of the operation and the symbols, sorted by row.
+    pub(crate) symbols: Vec<(usize, Symbol)>,
+    /// True if the vertical connection is classical.
+    pub(crate) classical: bool,
+}
+
+impl Element {
+    /// Returns the first and last row of the element.
+    pub(crate) fn span(&self) -> (usize, usize) {
+        let first = self.symbols.first().map(|(row, _)| *row).unwrap_or(0);
+        let last = self.symbols.last().map(|(row, _)| *row).unwrap_or(0);
+        (first, last)
+    }
+
+    /// Returns the symbol on a row.
+    pub(crate) fn symbol(&self, row: usize) -> Option<&Symbol> {
+        self.symbols
+            .iter()
+            .find(|(symbol_row, _)| *symbol_row == row)
+            .map(|(_, symbol)| symbol)
+    }
+}
+
+/// A boxed group of columns and rows, drawn for decomposition blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Group {
+    /// The first row of the group.
+    pub(crate) first_row: usize,
+    /// The last row of the group.
+    pub(crate) last_row: usize,
+    /// The first column of the group.
+    pub(crate) first_column: usize,
+    /// The last column of the group.
+    pub(crate) last_column: usize,
+}
+
+/// The placement of the operations of a circuit in columns.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Layout {
+    /// The number of qubit wires, the classical registers follow the qubit wires.
+    pub(crate) number_qubits: usize,
+    /// The names of the classical registers.
+    pub(crate) registers: Vec<String>,
+    /// The elements of every column.
+    pub(crate) columns: Vec<Vec<Element>>,
+    /// The decomposition blocks.
+    pub(crate) groups: Vec<Group>,
+}
+
+impl Layout {
+    /// Returns the number of rows.
+    pub(crate) fn number_rows(&self) -> usize {
+        self.number_qubits + self.registers.len()
+    }
+
+    /// Returns the names of the rows.
+    pub(crate) fn row_names(&self) -> Vec<String> {
+        (0..self.number_qubits)
+            .map(|qubit| format!("q{}", qubit))
+            .chain(self.registers.iter().cloned())
+            .collect()
+    }
+}
+
+/// Places the operations of a circuit in columns.
+///
+/// Operations are placed in the first column after all operations on the rows they span.
+/// Decomposition blocks start in a new column on their rows and end when the block is stopped.
+pub(crate) fn layout(circuit: &Circuit, options: &DrawingOptions) -> Layout {
+    let mut builder = LayoutBuilder {
+        options: *options,
+        layout: Layout {
+            number_qubits: number_qubits(circuit),
+            registers: Vec::new(),
+            columns: Vec::new(),
+            groups: Vec::new(),
+        },
+        next_free: Vec::new(),
+        open_groups: Vec::new(),
+    };
+    for operation in circuit.iter() {
+        builder.add(operation);
+    }
+    while let Some((qubits, first_column)) = builder.open_groups.pop() {
+        builder.close_group(&qubits, first_column);
+    }
+    builder.layout
+}
+
+/// State of the layout of a circuit.
+struct LayoutBuilder {
+    /// The drawing options.
+    options: DrawingOptions,
+    /// The layout.
+    layout: Layout,
+    /// The first free column of every row.
+    next_free: Vec<usize>,
+    /// The qubits and first columns of the decomposition blocks that have not been stopped.
+    open_groups: Vec<(Vec<usize>, usize)>,
+}
+
+impl LayoutBuilder {
+    /// Returns the row of a classical register, adding the register if it is not known yet.
+    fn register_row(&mut self, name: &str) -> usize {
+        let index = match self.layout.registers.iter().position(|r| r == name) {
+            Some(index) => index,
+            None => {
+                self.layout.registers.push(name.to_string());
+                self.layout.registers.len() - 1
+            }
+        };
+        self.layout.number_qubits + index
+    }
+
+    /// Returns the first free column of the rows from first to last.
+    fn free_column(&mut self, first: usize, last: usize) -> usize {
+        if self.next_free.len() <= last {
+            self.next_free.resize(last + 1, 0);
+        }
+        self.next_free[first..=last]
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Marks the columns up to the given column as used on the rows from first to last.
+    fn occupy(&mut self, first: usize, last: usize, column: usize) {
+        for free in self.next_free[first..=last].iter_mut() {
+            *free = column + 1;
+        }
+    }
+
+    /// Adds an operation to the layout.
+    fn add(&mut self, operation: &Operation) {
+        match operation {
+            Operation::PragmaStartDecompositionBlock(op) => {
+                if let (Some(first), Some(last)) =
+                    (op.qubits().iter().min(), op.qubits().iter().max())
+                {
+                    let column = self.free_column(*first, *last);
+                    for free in self.next_free[*first..=*last].iter_mut() {
+                        *free = column;
+                    }
+                    self.open_groups.push((op.qubits().clone(), column));
+                }
+            }
+            Operation::PragmaStopDecompositionBlock(op) => {
+                let mut qubits = op.qubits().clone();
+                qubits.sort_unstable();
+                let position = self.open_groups.iter().rposition(|(open, _)| {
+                    let mut open = open.clone();
+                    open.sort_unstable();
+                    open == qubits
+                });
+                if let Some(position) = position {
+                    let (qubits, first_column) = self.open_groups.remove(position);
+                    self.close_group(&qubits, first_column);
+                }
+            }
+            _ => {
+                for element in self.elements(operation) {
+                    self.place(element);
+                }
+            }
+        }
+    }
+
+    /// Places an element in the first free column of its rows.
+    fn place(&mut self, element: Element) {
+        let (first, last) = element.span();
+        let column = self.free_column(first, last);
+        self.occupy(first, last, column);
+        if self.layout.columns.len() <= column {
+            self.layout.columns.resize(column + 1, Vec::new());
+        }
+        self.layout.columns[column].push(element);
+    }
+
+    /// Closes a decomposition block, which ends after the last operation on its rows.
+    fn close_group(&mut self, qubits: &[usize], first_column: usize) {
+        let first_row = qubits.iter().copied().min().unwrap_or(0);
+        let last_row = qubits.iter().copied().max().unwrap_or(0);
+        let end = self.free_column(first_row, last_row).max(first_column + 1);
+        self.occupy(first_row, last_row, end - 1);
+        if self.layout.columns.len() < end {
+            self.layout.columns.resize(end, Vec::new());
+        }
+        self.layout.groups.push(Group {
+            first_row,
+            last_row,
+            first_column,
+            last_column: end - 1,
+        });
+    }
+
+    /// Returns a gate symbol.
+    fn gate(&self, operation: &Operation, parameters: &[&CalculatorFloat]) -> Symbol {
+        Symbol::Gate {
+            hqslang: operation.hqslang(),
+            parameters: if self.options.show_parameters {
+                parameters.iter().map(|p| (*p).clone()).collect()
+            } else {
+                Vec::new()
+            },
+            noise: false,
+        }
+    }
+
+    /// Returns an element with the same gate symbol on the given qubits.
+    fn on_qubits(&self, qubits: &[usize], symbol: Symbol) -> Vec<Element> {
+        let mut qubits = qubits.to_vec();
+        qubits.sort_unstable();
+        qubits.dedup();
+        if qubits.is_empty() {
+            return Vec::new();
+        }
+        vec![Element {
+            symbols: qubits.into_iter().map(|q| (q, symbol.clone())).collect(),
+            classical: false,
+        }]
+    }
+
+    /// Returns an element with the same gate symbol on all qubits.
+    fn on_all_qubits(&self, symbol: Symbol) -> Vec<Element> {
+        let qubits: Vec<usize> = (0..self.layout.number_qubits).collect();
+        self.on_qubits(&qubits, symbol)
+    }
+
+    /// Returns an element with the same gate symbol on the qubits involved in a circuit.
+    fn on_circuit_qubits(&self, circuit: &Circuit, symbol: Symbol) -> Vec<Element> {
+        match circuit.involved_qubits() {
+            InvolvedQubits::All => self.on_all_qubits(symbol),
+            InvolvedQubits::None => Vec::new(),
+            InvolvedQubits::Set(qubits) => {
+                self.on_qubits(&qubits.into_iter().collect::<Vec<usize>>(), symbol)
+            }
+        }
+    }
+
+    /// Returns an element of two symbols on two qubits.
+    fn pair(&self, first: (usize, Symbol), second: (usize, Symbol)) -> Vec<Element> {
+        let mut symbols = vec![first, second];
+        symbols.sort_by_key(|(row, _)| *row);
+        vec![Element {
+            symbols,
+            classical: false,
+        }]
+    }
+
+    /// Returns an element with controls on all but the last qubit and a symbol on the last qubit.
+    fn controlled(&self, qubits: &[usize], symbol: Symbol) -> Vec<Element> {
+        let (target, controls) = match qubits.split_last() {
+            Some(split) => split,
+            None => return Vec::new(),
+        };
+        let mut symbols: Vec<(usize, Symbol)> = controls
+            .iter()
+            .map(|control| (*control, Symbol::Control))
+            .collect();
+        symbols.push((*target, symbol));
+        symbols.sort_by_key(|(row, _)| *row);
+        vec![Element {
+            symbols,
+            classical: false,
+        }]
+    }
+
+    /// Returns an element of symbols on qubits connected to a readout in a classical register.
+    fn readout(
+        &mut self,
+        symbols: Vec<(usize, Symbol)>,
+        register: &str,
+        readout: Symbol,
+    ) -> Vec<Element> {
+        let row = self.register_row(register);
+        let mut symbols = symbols;
+        symbols.push((row, readout));
+        symbols.sort_by_key(|(row, _)| *row);
+        vec![Element {
+            symbols,
+            classical: true,
+        }]
+    }
+
+    /// Returns a noise symbol if noise is drawn.
+    fn noise(&self, operation: &Operation, parameters: &[&CalculatorFloat]) -> Option<Symbol> {
+        if !self.options.show_noise {
+            return None;
+        }
+        match self.gate(operation, parameters) {
+            Symbol::Gate {
+                hqslang,
+                parameters,
+                ..
+            } => Some(Symbol::Gate {
+                hqslang,
+                parameters,
+                noise: true,
+            }),
+            symbol => Some(symbol),
+        }
+    }
+
+    /// Returns the elements of an operation.
+    fn elements(&mut self, operation: &Operation) -> Vec<Element> {
+        match operation {
+            Operation::DefinitionFloat(op) => {
+                self.register_row(op.name());
+                Vec::new()
+            }
+            Operation::DefinitionComplex(op) => {
+                self.register_row(op.name());
+                Vec::new()
+            }
+            Operation::DefinitionUsize(op) => {
+                self.register_row(op.name());
+                Vec::new()
+            }
+            Operation::DefinitionBit(op) => {
+                self.register_row(op.name());
+                Vec::new()
+            }
+            Operation::InputSymbolic(_) | Operation::PragmaSetNumberOfMeasurements(_) => Vec::new(),
+            Operation::PragmaStartDecompositionBlock(_)
+            | Operation::PragmaStopDecompositionBlock(_) => Vec::new(),
+            Operation::SingleQubitGate(op) => self.on_qubits(
+                &[*op.qubit()],
+                self.gate(
+                    operation,
+                    &[
+                        &op.alpha_r(),
+                        &op.alpha_i(),
+                        &op.beta_r(),
+                        &op.beta_i(),
+                        &op.global_phase(),
+                    ],
+                ),
+            ),
+            Operation::RotateZ(op) => {
+                self.on_qubits(&[*op.qubit()], self.gate(operation, &[op.theta()]))
+            }
+            Operation::RotateX(op) => {
+                self.on_qubits(&[*op.qubit()], self.gate(operation, &[op.theta()]))
+            }
+            Operation::RotateY(op) => {
+                self.on_qubits(&[*op.qubit()], self.gate(operation, &[op.theta()]))
+            }
+            Operation::RotateAroundSphericalAxis(op) => self.on_qubits(
+                &[*op.qubit()],
+                self.gate(
+                    operation,
+                    &[op.theta(), op.spherical_theta(), op.spherical_phi()],
+                ),
+            ),
+            Operation::PauliX(_)
+            | Operation::PauliY(_)
+            | Operation::PauliZ(_)
+            | Operation::SqrtPauliX(_)
+            | Operation::InvSqrtPauliX(_)
+            | Operation::Hadamard(_)
+            | Operation::SGate(_)
+            | Operation::TGate(_)
+            | Operation::PragmaActiveReset(_) => match operation.involved_qubits() {
+                InvolvedQubits::Set(qubits) => {
+                    let qubits: Vec<usize> = qubits.into_iter().collect();
+                    self.on_qubits(&qubits, self.gate(operation, &[]))
+                }
+                _ => Vec::new(),
+            },
+            Operation::CNOT(op) => self.pair(
+                (*op.control(), Symbol::Control),
+                (*op.target(), Symbol::Target),
+            ),
+            Operation::ControlledPauliZ(op) => self.pair(
+                (*op.control(), Symbol::Control),
+                (*op.target(), Symbol::Control),
+            ),
+            Operation::SWAP(op) => {
+                self.pair((*op.control(), Symbol::Swap), (*op.target(), Symbol::Swap))
+            }
+            Operation::ControlledPauliY(op) => self.pair(
+                (*op.control(), Symbol::Control),
+                (*op.target(), self.gate(operation, &[])),
+            ),
+            Operation::ControlledPhaseShift(op) => self.pair(
+                (*op.control(), Symbol::Control),
+                (*op.target(), self.gate(operation, &[op.theta()])),
+            ),
+            Operation::PhaseShiftedControlledZ(op) => self.pair(
+                (*op.control(), Symbol::Control),
+                (*op.target(), self.gate(operation, &[op.phi()])),
+            ),
+            Operation::MultiQubitMS(op) => {
+                self.on_qubits(op.qubits(), self.gate(operation, &[op.theta()]))
+            }
+            Operation::MultiQubitGate(op) => self.on_qubits(op.qubits(), self.gate(operation, &[])),
+            Operation::Toffoli(op) => self.controlled(op.qubits(), Symbol::Target),
+            Operation::ControlledControlledPauliZ(op) => {
+                self.controlled(op.qubits(), Symbol::Control)
+            }
+            Operation::ControlledControlledPhaseShift(op) => {
+                self.controlled(op.qubits(), self.gate(operation, &[op.theta()]))
+            }
+            Operation::PauliProductRotation(op) => {
+                self.on_qubits(op.qubits(), self.gate(operation, &[op.theta()]))
+            }
+            Operation::MultiControlledGate(op) => self.controlled(
+                op.qubits(),
+                self.gate(operation, &[op.phase(), op.z1(), op.y(), op.z2()]),
+            ),
+            Operation::ISwap(_)
+            | Operation::FSwap(_)
+            | Operation::SqrtISwap(_)
+            | Operation::InvSqrtISwap(_)
+            | Operation::MolmerSorensenXX(_) => self.two_qubit(operation, &[]),
+            Operation::XY(op) => self.two_qubit(operation, &[op.theta()]),
+            Operation::VariableMSXX(op) => self.two_qubit(operation, &[op.theta()]),
+            Operation::GivensRotation(op) => self.two_qubit(operation, &[op.theta(), op.phi()]),
+            Operation::GivensRotationLittleEndian(op) => {
+                self.two_qubit(operation, &[op.theta(), op.phi()])
+            }
+            Operation::Qsim(op) => self.two_qubit(operation, &[op.x(), op.y(), op.z()]),
+            Operation::Fsim(op) => self.two_qubit(operation, &[op.t(), op.u(), op.delta()]),
+            Operation::SpinInteraction(op) => self.two_qubit(operation, &[op.x(), op.y(), op.z()]),
+            Operation::Bogoliubov(op) => {
+                self.two_qubit(operation, &[op.delta_real(), op.delta_imag()])
+            }
+            Operation::PMInteraction(op) => self.two_qubit(operation, &[op.t()]),
+            Operation::TwoQubitGate(_) => self.two_qubit(operation, &[]),
+            Operation::ComplexPMInteraction(op) => {
+                self.two_qubit(operation, &[op.t_real(), op.t_imag()])
+            }
+            Operation::PragmaDamping(op) => self
+                .noise(operation, &[op.gate_time(), op.rate()])
+                .map(|symbol| self.on_qubits(&[*op.qubit()], symbol))
+                .unwrap_or_default(),
+            Operation::PragmaDepolarising(op) => self
+                .noise(operation, &[op.gate_time(), op.rate()])
+                .map(|symbol| self.on_qubits(&[*op.qubit()], symbol))
+                .unwrap_or_default(),
+            Operation::PragmaDephasing(op) => self
+                .noise(operation, &[op.gate_time(), op.rate()])
+                .map(|symbol| self.on_qubits(&[*op.qubit()], symbol))
+                .unwrap_or_default(),
+            Operation::PragmaRandomNoise(op) => self
+                .noise(
+                    operation,
+                    &[op.gate_time(), op.depolarising_rate(), op.dephasing_rate()],
+                )
+                .map(|symbol| self.on_qubits(&[*op.qubit()], symbol))
+                .unwrap_or_default(),
+            Operation::PragmaGeneralNoise(op) => self
+                .noise(operation, &[op.gate_time(), op.rate()])
+                .map(|symbol| self.on_qubits(&[*op.qubit()], symbol))
+                .unwrap_or_default(),
+            Operation::PragmaOverrotation(op) => self
+                .noise(operation, &[])
+                .map(|symbol| self.on_qubits(op.qubits(), symbol))
+                .unwrap_or_default(),
+            Operation::PragmaBoostNoise(op) => self
+                .noise(operation, &[op.noise_coefficient()])
+                .map(|symbol| self.on_all_qubits(symbol))
+                .unwrap_or_default(),
+            Operation::PragmaSleep(op) => {
+                self.on_qubits(op.qubits(), self.gate(operation, &[op.sleep_time()]))
+            }
+            Operation::PragmaStopParallelBlock(op) => {
+                self.on_qubits(op.qubits(), self.gate(operation, &[op.execution_time()]))
+            }
+            Operation::PragmaGlobalPhase(op) => {
+                self.on_all_qubits(self.gate(operation, &[op.phase()]))
+            }
+            Operation::PragmaSetStateVector(_)
+            | Operation::PragmaSetDensityMatrix(_)
+            | Operation::PragmaRepeatGate(_) => self.on_all_qubits(self.gate(operation, &[])),
+            Operation::MeasureQubit(op) => self.readout(
+                vec![(*op.qubit(), Symbol::Meter)],
+                op.readout(),
+                Symbol::Readout(Some(*op.readout_index())),
+            ),
+            Operation::PragmaRepeatedMeasurement(op) => {
+                let qubits: Vec<usize> = match op.qubit_mapping() {
+                    Some(mapping) => mapping.keys().copied().collect(),
+                    None => (0..self.layout.number_qubits).collect(),
+                };
+                let symbols = qubits.into_iter().map(|q| (q, Symbol::Meter)).collect();
+                self.readout(symbols, op.readout(), Symbol::Readout(None))
+            }
+            Operation::PragmaGetStateVector(op) => {
+                self.whole_register_readout(operation, op.readout())
+            }
+            Operation::PragmaGetDensityMatrix(op) => {
+                self.whole_register_readout(operation, op.readout())
+            }
+            Operation::PragmaGetOccupationProbability(op) => {
+                self.whole_register_readout(operation, op.readout())
+            }
+            Operation::PragmaGetPauliProduct(op) => {
+                let symbols = op
+                    .qubit_paulis()
+                    .iter()
+                    .filter_map(|(qubit, pauli)| {
+                        let hqslang = match pauli {
+                            1 => "PauliX",
+                            2 => "PauliY",
+                            3 => "PauliZ",
+                            _ => return None,
+                        };
+                        Some((
+                            *qubit,
+                            Symbol::Gate {
+                                hqslang,
+                                parameters: Vec::new(),
+                                noise: false,
+                            },
+                        ))
+                    })
+                    .collect();
+                self.readout(symbols, op.readout(), Symbol::Readout(None))
+            }
+            Operation::PragmaConditional(op) => self.conditional(
+                op.circuit(),
+                op.condition_register(),
+                op.condition_index().to_string(),
+            ),
+            Operation::PragmaConditionalPattern(op) => {
+                let condition = op
+                    .condition()
+                    .iter()
+                    .map(|(index, value)| format!("{}={}", index, *value as u8))
+                    .collect::<Vec<String>>()
+                    .join(",");
+                self.conditional(op.circuit(), op.condition_register(), condition)
+            }
+            Operation::PragmaLoop(op) => {
+                self.on_circuit_qubits(op.circuit(), self.gate(operation, &[op.repetitions()]))
+            }
+            Operation::PragmaRepeatUntilSuccess(op) => {
+                let row = self.register_row(op.condition_register());
+                let condition = Symbol::Condition(op.condition_index().to_string());
+                let mut elements = self.on_circuit_qubits(op.circuit(), self.gate(operation, &[]));
+                for element in elements.iter_mut() {
+                    element.symbols.push((row, condition.clone()));
+                    element.classical = true;
+                }
+                elements
+            }
+            Operation::ClassicalNot(op) => self.classical(
+                self.gate(operation, &[]),
+                op.register(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalCopy(op) => self.classical(
+                self.gate(operation, &[]),
+                op.register(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalXor(op) => self.classical(
+                self.gate(operation, &[]),
+                op.register(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalAnd(op) => self.classical(
+                self.gate(operation, &[]),
+                op.register(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalOr(op) => self.classical(
+                self.gate(operation, &[]),
+                op.register(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalGreaterThan(op) => self.classical(
+                self.gate(operation, &[op.threshold()]),
+                op.register(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalLessThan(op) => self.classical(
+                self.gate(operation, &[op.threshold()]),
+                op.register(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+        }
+    }
+
+    /// Returns the elements of the circuit of a conditional operation connected to the condition in a classical register.
+    fn conditional(
+        &mut self,
+        circuit: &Circuit,
+        register: &str,
+        condition: String,
+    ) -> Vec<Element> {
+        let row = self.register_row(register);
+        let mut elements = Vec::new();
+        for inner in circuit.iter() {
+            for mut element in self.elements(inner) {
+                if element.symbol(row).is_none() {
+                    element
+                        .symbols
+                        .push((row, Symbol::Condition(condition.clone())));
+                    element.symbols.sort_by_key(|(row, _)| *row);
+                }
+                element.classical = true;
+                elements.push(element);
+            }
+        }
+        elements
+    }
+
+    /// Returns an element of a classical operation reading a register and writing to an index of a classical register.
+    fn classical(
+        &mut self,
+        symbol: Symbol,
+        register: &str,
+        output_register: &str,
+        output_index: usize,
+    ) -> Vec<Element> {
+        let row = self.register_row(register);
+        let output_row = self.register_row(output_register);
+        let mut symbols = vec![(row, symbol)];
+        if output_row != row {
+            symbols.push((output_row, Symbol::Readout(Some(output_index))));
+            symbols.sort_by_key(|(row, _)| *row);
+        }
+        vec![Element {
+            symbols,
+            classical: true,
+        }]
+    }
+
+    /// Returns an element with the same gate symbol on both qubits of a two-qubit gate.
+    fn two_qubit(&self, operation: &Operation, parameters: &[&CalculatorFloat]) -> Vec<Element> {
+        match operation.involved_qubits() {
+            InvolvedQubits::Set(qubits) => {
+                let qubits: Vec<usize> = qubits.into_iter().collect();
+                self.on_qubits(&qubits, self.gate(operation, parameters))
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns an element of a pragma reading out the state of all qubits into a classical register.
+    fn whole_register_readout(&mut self, operation: &Operation, register: &str) -> Vec<Element> {
+        let symbol = self.gate(operation, &[]);
+        let symbols = (0..self.layout.number_qubits)
+            .map(|qubit| (qubit, symbol.clone()))
+            .collect();
+        self.readout(symbols, register, Symbol::Readout(None))
+    }
+}
@@ -0,0 +1,1408 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::operations::*;
+use crate::{Circuit, RoqoqoError};
+use ndarray::{Array1, Array2};
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorFloat;
+use std::collections::HashMap;
+
+/// Indentation of the operations of nested circuits.
+const INDENTATION: &str = "    ";
+
+/// Writes a circuit in the hqslang text format.
+///
+/// Every operation is written on its own line, starting with the hqslang name of the operation
+/// followed by the fields of the operation in the order of the arguments of its `new` function:
+///
+/// * Qubits, indices and lengths are written as integers, floats as float literals and booleans as `true` or `false`.
+/// * [CalculatorFloat] values are written as float literals when they are numeric and as quoted strings when they are symbolic, e.g. `"2 * theta"`.
+/// * Register names are written as identifiers, or as quoted strings when they are not valid identifiers.
+/// * Lists of qubits are written as `[0, 1]`, qubit mappings as `{0: 1, 1: 0}` and missing optional values as `None`.
+/// * Complex numbers are written as `(re, im)`, complex vectors and matrices as (nested) lists of complex numbers.
+/// * Circuits (e.g. in [PragmaConditional] and [PragmaGetPauliProduct]) are written as a block in braces with one indented operation per line.
+///
+/// Comments start with `#` and extend to the end of the line. The text can be parsed back into the circuit with [hqslang_to_circuit].
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to write.
+///
+/// # Returns
+///
+/// * `String` - The circuit in the hqslang text format.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::formats::circuit_to_hqslang;
+/// use roqoqo::operations::{DefinitionBit, MeasureQubit, PauliX, PragmaConditional, RotateX};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += DefinitionBit::new("ro".to_string(), 1, true);
+/// circuit += RotateX::new(0, "theta".into());
+/// circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+/// let mut conditional = Circuit::new();
+/// conditional += PauliX::new(1);
+/// circuit += PragmaConditional::new("ro".to_string(), 0, conditional);
+///
+/// assert_eq!(
+///     circuit_to_hqslang(&circuit),
+///     "DefinitionBit ro 1 true\nRotateX 0 \"theta\"\nMeasureQubit 0 ro 0\nPragmaConditional ro 0 {\n    PauliX 1\n}\n"
+/// );
+/// ```
+pub fn circuit_to_hqslang(circuit: &Circuit) -> String {
+    let mut text = String::new();
+    for operation in circuit.iter() {
+        text.push_str(&operation_to_hqslang(operation));
+        text.push('\n');
+    }
+    text
+}
+
+/// Writes a single operation in the hqslang text format.
+///
+/// The operation is written without a trailing line break,
+/// the format of the arguments is described in [circuit_to_hqslang].
+///
+/// # Arguments
+///
+/// * `operation` - The operation to write.
+///
+/// # Returns
+///
+/// * `String` - The operation in the hqslang text format.
+pub fn operation_to_hqslang(operation: &Operation) -> String {
+    let arguments: Vec<String> = match operation {
+        Operation::SingleQubitGate(op) => vec![
+            op.qubit().to_hqslang(),
+            op.alpha_r().to_hqslang(),
+            op.alpha_i().to_hqslang(),
+            op.beta_r().to_hqslang(),
+            op.beta_i().to_hqslang(),
+            op.global_phase().to_hqslang(),
+        ],
+        Operation::RotateZ(op) => vec![op.qubit().to_hqslang(), op.theta().to_hqslang()],
+        Operation::RotateX(op) => vec![op.qubit().to_hqslang(), op.theta().to_hqslang()],
+        Operation::RotateY(op) => vec![op.qubit().to_hqslang(), op.theta().to_hqslang()],
+        Operation::PauliX(op) => vec![op.qubit().to_hqslang()],
+        Operation::PauliY(op) => vec![op.qubit().to_hqslang()],
+        Operation::PauliZ(op) => vec![op.qubit().to_hqslang()],
+        Operation::SqrtPauliX(op) => vec![op.qubit().to_hqslang()],
+        Operation::InvSqrtPauliX(op) => vec![op.qubit().to_hqslang()],
+        Operation::Hadamard(op) => vec![op.qubit().to_hqslang()],
+        Operation::SGate(op) => vec![op.qubit().to_hqslang()],
+        Operation::TGate(op) => vec![op.qubit().to_hqslang()],
+        Operation::RotateAroundSphericalAxis(op) => vec![
+            op.qubit().to_hqslang(),
+            op.theta().to_hqslang(),
+            op.spherical_theta().to_hqslang(),
+            op.spherical_phi().to_hqslang(),
+        ],
+        Operation::PragmaSetNumberOfMeasurements(op) => vec![
+            op.number_measurements().to_hqslang(),
+            op.readout().to_hqslang(),
+        ],
+        Operation::PragmaSetStateVector(op) => vec![op.statevector().to_hqslang()],
+        Operation::PragmaSetDensityMatrix(op) => vec![op.density_matrix().to_hqslang()],
+        Operation::PragmaRepeatGate(op) => vec![op.repetition_coefficient().to_hqslang()],
+        Operation::PragmaOverrotation(op) => vec![
+            op.gate_hqslang().to_hqslang(),
+            op.qubits().to_hqslang(),
+            op.amplitude().to_hqslang(),
+            op.variance().to_hqslang(),
+        ],
+        Operation::PragmaBoostNoise(op) => vec![op.noise_coefficient().to_hqslang()],
+        Operation::PragmaStopParallelBlock(op) => {
+            vec![op.qubits().to_hqslang(), op.execution_time().to_hqslang()]
+        }
+        Operation::PragmaGlobalPhase(op) => vec![op.phase().to_hqslang()],
+        Operation::PragmaSleep(op) => vec![op.qubits().to_hqslang(), op.sleep_time().to_hqslang()],
+        Operation::PragmaActiveReset(op) => vec![op.qubit().to_hqslang()],
+        Operation::PragmaStartDecompositionBlock(op) => vec![
+            op.qubits().to_hqslang(),
+            op.reordering_dictionary().to_hqslang(),
+        ],
+        Operation::PragmaStopDecompositionBlock(op) => vec![op.qubits().to_hqslang()],
+        Operation::PragmaDamping(op) => vec![
+            op.qubit().to_hqslang(),
+            op.gate_time().to_hqslang(),
+            op.rate().to_hqslang(),
+        ],
+        Operation::PragmaDepolarising(op) => vec![
+            op.qubit().to_hqslang(),
+            op.gate_time().to_hqslang(),
+            op.rate().to_hqslang(),
+        ],
+        Operation::PragmaDephasing(op) => vec![
+            op.qubit().to_hqslang(),
+            op.gate_time().to_hqslang(),
+            op.rate().to_hqslang(),
+        ],
+        Operation::PragmaRandomNoise(op) => vec![
+            op.qubit().to_hqslang(),
+            op.gate_time().to_hqslang(),
+            op.depolarising_rate().to_hqslang(),
+            op.dephasing_rate().to_hqslang(),
+        ],
+        Operation::PragmaGeneralNoise(op) => vec![
+            op.qubit().to_hqslang(),
+            op.gate_time().to_hqslang(),
+            op.rate().to_hqslang(),
+            op.operators().to_hqslang(),
+        ],
+        Operation::PragmaConditional(op) => vec![
+            op.condition_register().to_hqslang(),
+            op.condition_index().to_hqslang(),
+            op.circuit().to_hqslang(),
+        ],
+        Operation::PragmaConditionalPattern(op) => vec![
+            op.condition_register().to_hqslang(),
+            op.condition().to_hqslang(),
+            op.circuit().to_hqslang(),
+        ],
+        Operation::PragmaLoop(op) => vec![
+            op.repetitions().to_hqslang(),
+            op.circuit().to_hqslang(),
+        ],
+        Operation::PragmaRepeatUntilSuccess(op) => vec![
+            op.condition_register().to_hqslang(),
+            op.condition_index().to_hqslang(),
+            op.max_iterations().to_hqslang(),
+            op.circuit().to_hqslang(),
+        ],
+        Operation::ClassicalNot(op) => vec![
+            op.register().to_hqslang(),
+            op.index().to_hqslang(),
+            op.output_register().to_hqslang(),
+            op.output_index().to_hqslang(),
+        ],
+        Operation::ClassicalCopy(op) => vec![
+            op.register().to_hqslang(),
+            op.index().to_hqslang(),
+            op.output_register().to_hqslang(),
+            op.output_index().to_hqslang(),
+        ],
+        Operation::ClassicalXor(op) => vec![
+            op.register().to_hqslang(),
+            op.indices().to_hqslang(),
+            op.output_register().to_hqslang(),
+            op.output_index().to_hqslang(),
+        ],
+        Operation::ClassicalAnd(op) => vec![
+            op.register().to_hqslang(),
+            op.indices().to_hqslang(),
+            op.output_register().to_hqslang(),
+            op.output_index().to_hqslang(),
+        ],
+        Operation::ClassicalOr(op) => vec![
+            op.register().to_hqslang(),
+            op.indices().to_hqslang(),
+            op.output_register().to_hqslang(),
+            op.output_index().to_hqslang(),
+        ],
+        Operation::ClassicalGreaterThan(op) => vec![
+            op.register().to_hqslang(),
+            op.index().to_hqslang(),
+            op.threshold().to_hqslang(),
+            op.output_register().to_hqslang(),
+            op.output_index().to_hqslang(),
+        ],
+        Operation::ClassicalLessThan(op) => vec![
+            op.register().to_hqslang(),
+            op.index().to_hqslang(),
+            op.threshold().to_hqslang(),
+            op.output_register().to_hqslang(),
+            op.output_index().to_hqslang(),
+        ],
+        Operation::CNOT(op) => vec![op.control().to_hqslang(), op.target().to_hqslang()],
+        Operation::SWAP(op) => vec![op.control().to_hqslang(), op.target().to_hqslang()],
+        Operation::ISwap(op) => vec![op.control().to_hqslang(), op.target().to_hqslang()],
+        Operation::FSwap(op) => vec![op.control().to_hqslang(), op.target().to_hqslang()],
+        Operation::SqrtISwap(op) => vec![op.control().to_hqslang(), op.target().to_hqslang()],
+        Operation::InvSqrtISwap(op) => vec![op.control().to_hqslang(), op.target().to_hqslang()],
+        Operation::XY(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.theta().to_hqslang(),
+        ],
+        Operation::ControlledPhaseShift(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.theta().to_hqslang(),
+        ],
+        Operation::ControlledPauliY(op) => {
+            vec![op.control().to_hqslang(), op.target().to_hqslang()]
+        }
+        Operation::ControlledPauliZ(op) => {
+            vec![op.control().to_hqslang(), op.target().to_hqslang()]
+        }
+        Operation::MolmerSorensenXX(op) => {
+            vec![op.control().to_hqslang(), op.target().to_hqslang()]
+        }
+        Operation::VariableMSXX(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.theta().to_hqslang(),
+        ],
+        Operation::GivensRotation(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.theta().to_hqslang(),
+            op.phi().to_hqslang(),
+        ],
+        Operation::GivensRotationLittleEndian(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.theta().to_hqslang(),
+            op.phi().to_hqslang(),
+        ],
+        Operation::Qsim(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.x().to_hqslang(),
+            op.y().to_hqslang(),
+            op.z().to_hqslang(),
+        ],
+        Operation::Fsim(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.t().to_hqslang(),
+            op.u().to_hqslang(),
+            op.delta().to_hqslang(),
+        ],
+        Operation::SpinInteraction(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.x().to_hqslang(),
+            op.y().to_hqslang(),
+            op.z().to_hqslang(),
+        ],
+        Operation::Bogoliubov(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.delta_real().to_hqslang(),
+            op.delta_imag().to_hqslang(),
+        ],
+        Operation::PMInteraction(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.t().to_hqslang(),
+        ],
+        Operation::ComplexPMInteraction(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.t_real().to_hqslang(),
+            op.t_imag().to_hqslang(),
+        ],
+        Operation::PhaseShiftedControlledZ(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.phi().to_hqslang(),
+        ],
+        Operation::TwoQubitGate(op) => vec![
+            op.control().to_hqslang(),
+            op.target().to_hqslang(),
+            op.phase().to_hqslang(),
+            op.kx().to_hqslang(),
+            op.ky().to_hqslang(),
+            op.kz().to_hqslang(),
+            op.control_before_z1().to_hqslang(),
+            op.control_before_y().to_hqslang(),
+            op.control_before_z2().to_hqslang(),
+            op.target_before_z1().to_hqslang(),
+            op.target_before_y().to_hqslang(),
+            op.target_before_z2().to_hqslang(),
+            op.control_after_z1().to_hqslang(),
+            op.control_after_y().to_hqslang(),
+            op.control_after_z2().to_hqslang(),
+            op.target_after_z1().to_hqslang(),
+            op.target_after_y().to_hqslang(),
+            op.target_after_z2().to_hqslang(),
+        ],
+        Operation::MultiQubitMS(op) => vec![op.qubits().to_hqslang(), op.theta().to_hqslang()],
+        Operation::MultiQubitGate(op) => vec![op.qubits().to_hqslang(), op.matrix().to_hqslang()],
+        Operation::Toffoli(op) => vec![op.qubits().to_hqslang()],
+        Operation::ControlledControlledPauliZ(op) => vec![op.qubits().to_hqslang()],
+        Operation::ControlledControlledPhaseShift(op) => {
+            vec![op.qubits().to_hqslang(), op.theta().to_hqslang()]
+        }
+        Operation::PauliProductRotation(op) => vec![
+            op.qubits().to_hqslang(),
+            op.paulis().to_hqslang(),
+            op.theta().to_hqslang(),
+        ],
+        Operation::MultiControlledGate(op) => vec![
+            op.qubits().to_hqslang(),
+            op.phase().to_hqslang(),
+            op.z1().to_hqslang(),
+            op.y().to_hqslang(),
+            op.z2().to_hqslang(),
+        ],
+        Operation::MeasureQubit(op) => vec![
+            op.qubit().to_hqslang(),
+            op.readout().to_hqslang(),
+            op.readout_index().to_hqslang(),
+        ],
+        Operation::PragmaGetStateVector(op) => {
+            vec![op.readout().to_hqslang(), op.circuit().to_hqslang()]
+        }
+        Operation::PragmaGetDensityMatrix(op) => {
+            vec![op.readout().to_hqslang(), op.circuit().to_hqslang()]
+        }
+        Operation::PragmaGetOccupationProbability(op) => {
+            vec![op.readout().to_hqslang(), op.circuit().to_hqslang()]
+        }
+        Operation::PragmaGetPauliProduct(op) => vec![
+            op.qubit_paulis().to_hqslang(),
+            op.readout().to_hqslang(),
+            op.circuit().to_hqslang(),
+        ],
+        Operation::PragmaRepeatedMeasurement(op) => vec![
+            op.readout().to_hqslang(),
+            op.qubit_mapping().to_hqslang(),
+            op.number_measurements().to_hqslang(),
+        ],
+        Operation::DefinitionFloat(op) => vec![
+            op.name().to_hqslang(),
+            op.length().to_hqslang(),
+            op.is_output().to_hqslang(),
+        ],
+        Operation::DefinitionComplex(op) => vec![
+            op.name().to_hqslang(),
+            op.length().to_hqslang(),
+            op.is_output().to_hqslang(),
+        ],
+        Operation::DefinitionUsize(op) => vec![
+            op.name().to_hqslang(),
+            op.length().to_hqslang(),
+            op.is_output().to_hqslang(),
+        ],
+        Operation::DefinitionBit(op) => vec![
+            op.name().to_hqslang(),
+            op.length().to_hqslang(),
+            op.is_output().to_hqslang(),
+        ],
+        Operation::InputSymbolic(op) => vec![op.name().to_hqslang(), op.input().to_hqslang()],
+    };
+    let mut text = operation.hqslang().to_string();
+    for argument in arguments {
+        text.push(' ');
+        text.push_str(&argument);
+    }
+    text
+}
+
+/// Parses a circuit in the hqslang text format.
+///
+/// The format is described in [circuit_to_hqslang], which writes circuits that are parsed back into the same circuit.
+/// Lists, mappings and matrices may be split over several lines and may end with a trailing comma.
+///
+/// # Arguments
+///
+/// * `source` - The circuit in the hqslang text format.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The parsed circuit.
+/// * `Err(RoqoqoError::HqslangParseError)` - The text is not valid, with the line and column of the error.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::formats::hqslang_to_circuit;
+/// use roqoqo::operations::{CNOT, Hadamard, PragmaGetPauliProduct};
+/// use roqoqo::Circuit;
+/// use std::collections::HashMap;
+///
+/// let source = "# Bell state
+/// Hadamard 0
+/// CNOT 0 1
+/// PragmaGetPauliProduct {0: 3, 1: 3} zz {
+///     Hadamard 1
+/// }
+/// ";
+/// let mut measurement = Circuit::new();
+/// measurement += Hadamard::new(1);
+/// let mut paulis = HashMap::new();
+/// paulis.insert(0, 3);
+/// paulis.insert(1, 3);
+/// let mut expected = Circuit::new();
+/// expected += Hadamard::new(0);
+/// expected += CNOT::new(0, 1);
+/// expected += PragmaGetPauliProduct::new(paulis, "zz".to_string(), measurement);
+/// assert_eq!(hqslang_to_circuit(source).unwrap(), expected);
+/// ```
+pub fn hqslang_to_circuit(source: &str) -> Result<Circuit, RoqoqoError> {
+    let mut parser = HqslangParser {
+        tokens: tokenize(source)?,
+        position: 0,
+        operation: String::new(),
+        context: String::new(),
+    };
+    parser.block(None)
+}
+
+/// Returns a parse error at the given position.
+fn parse_error(line: usize, column: usize, msg: String) -> RoqoqoError {
+    RoqoqoError::HqslangParseError { line, column, msg }
+}
+
+/// Returns true if the text can be written as an identifier without quotes.
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => chars.all(|c| c.is_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+/// Returns the text as a quoted string, escaping quotes, backslashes and line breaks.
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Kinds of tokens of the hqslang text format.
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// An identifier or keyword.
+    Identifier(String),
+    /// A number literal.
+    Number(String),
+    /// A quoted string without the quotes and with resolved escape sequences.
+    Text(String),
+    /// A delimiter.
+    Symbol(char),
+    /// The end of a line.
+    LineBreak,
+    /// The end of the text.
+    End,
+}
+
+/// A token of the hqslang text format with its position.
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    /// The kind and content of the token.
+    kind: TokenKind,
+    /// The line of the token (starting at 1).
+    line: usize,
+    /// The column of the token (starting at 1).
+    column: usize,
+}
+
+/// Returns a description of a token for error messages.
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Identifier(name) => format!("'{}'", name),
+        TokenKind::Number(literal) => format!("'{}'", literal),
+        TokenKind::Text(text) => quote(text),
+        TokenKind::Symbol(symbol) => format!("'{}'", symbol),
+        TokenKind::LineBreak => "end of line".to_string(),
+        TokenKind::End => "end of text".to_string(),
+    }
+}
+
+/// Splits a text in the hqslang format into tokens, skipping comments.
+fn tokenize(source: &str) -> Result<Vec<Token>, RoqoqoError> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut end = (1, 1);
+    for (line_index, text) in source.lines().enumerate() {
+        let line = line_index + 1;
+        let chars: Vec<char> = text.chars().collect();
+        let mut index = 0;
+        while index < chars.len() {
+            let c = chars[index];
+            let column = index + 1;
+            if c == '#' {
+                break;
+            } else if c.is_whitespace() {
+                index += 1;
+                continue;
+            }
+            let kind = if c == '"' {
+                let mut text = String::new();
+                index += 1;
+                loop {
+                    match chars.get(index) {
+                        None => {
+                            return Err(parse_error(
+                                line,
+                                column,
+                                "Missing closing '\"' of string".to_string(),
+                            ))
+                        }
+                        Some('"') => break,
+                        Some('\\') => {
+                            let escaped = match chars.get(index + 1) {
+                                Some('"') => '"',
+                                Some('\\') => '\\',
+                                Some('n') => '\n',
+                                _ => {
+                                    return Err(parse_error(
+                                        line,
+                                        index + 1,
+                                        "Invalid escape sequence in string".to_string(),
+                                    ))
+                                }
+                            };
+                            text.push(escaped);
+                            index += 2;
+                        }
+                        Some(d) => {
+                            text.push(*d);
+                            index += 1;
+                        }
+                    }
+                }
+                index += 1;
+                TokenKind::Text(text)
+            } else if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' {
+                let mut end = index + 1;
+                while end < chars.len()
+                    && (chars[end].is_alphanumeric()
+                        || chars[end] == '.'
+                        || ((chars[end] == '+' || chars[end] == '-')
+                            && (chars[end - 1] == 'e' || chars[end - 1] == 'E')))
+                {
+                    end += 1;
+                }
+                let literal: String = chars[index..end].iter().collect();
+                index = end;
+                TokenKind::Number(literal)
+            } else if c.is_alphabetic() || c == '_' {
+                let mut end = index + 1;
+                while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+                let word: String = chars[index..end].iter().collect();
+                index = end;
+                TokenKind::Identifier(word)
+            } else if "[]{}(),:".contains(c) {
+                index += 1;
+                TokenKind::Symbol(c)
+            } else {
+                return Err(parse_error(
+                    line,
+                    column,
+                    format!("Unexpected character '{}'", c),
+                ));
+            };
+            tokens.push(Token { kind, line, column });
+        }
+        tokens.push(Token {
+            kind: TokenKind::LineBreak,
+            line,
+            column: chars.len() + 1,
+        });
+        end = (line, chars.len() + 1);
+    }
+    tokens.push(Token {
+        kind: TokenKind::End,
+        line: end.0,
+        column: end.1,
+    });
+    Ok(tokens)
+}
+
+/// Parser state of a text in the hqslang format.
+struct HqslangParser {
+    /// The tokens of the text, ending with a token of kind [TokenKind::End].
+    tokens: Vec<Token>,
+    /// The index of the next token.
+    position: usize,
+    /// The hqslang name of the operation that is parsed.
+    operation: String,
+    /// Description of the argument that is parsed, used in error messages.
+    context: String,
+}
+
+impl HqslangParser {
+    /// Returns the next token without consuming it.
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position]
+    }
+
+    /// Consumes the next token unless it is the end of the text.
+    fn advance(&mut self) {
+        if self.peek().kind != TokenKind::End {
+            self.position += 1;
+        }
+    }
+
+    /// Returns an error at the given token.
+    fn error_at(&self, token: &Token, msg: String) -> RoqoqoError {
+        parse_error(token.line, token.column, msg)
+    }
+
+    /// Returns an error for an unexpected next token.
+    fn unexpected(&self, expected: &str) -> RoqoqoError {
+        let token = self.peek();
+        let msg = if self.context.is_empty() {
+            format!("Expected {} but found {}", expected, describe(&token.kind))
+        } else {
+            format!(
+                "Expected {} for {} but found {}",
+                expected,
+                self.context,
+                describe(&token.kind)
+            )
+        };
+        self.error_at(token, msg)
+    }
+
+    /// Skips line breaks, which are allowed inside of lists and mappings.
+    fn skip_line_breaks(&mut self) {
+        while self.peek().kind == TokenKind::LineBreak {
+            self.advance();
+        }
+    }
+
+    /// Consumes the next token if it is the given symbol.
+    fn accept_symbol(&mut self, symbol: char) -> bool {
+        let found = self.peek().kind == TokenKind::Symbol(symbol);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    /// Consumes the next token, which has to be the given symbol.
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), RoqoqoError> {
+        if self.accept_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", symbol)))
+        }
+    }
+
+    /// Parses the comma separated items of a list or mapping up to the closing symbol, allowing a trailing comma.
+    fn items<T>(
+        &mut self,
+        closing: char,
+        mut item: impl FnMut(&mut Self) -> Result<T, RoqoqoError>,
+    ) -> Result<Vec<T>, RoqoqoError> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_line_breaks();
+            if self.accept_symbol(closing) {
+                return Ok(items);
+            }
+            items.push(item(self)?);
+            self.skip_line_breaks();
+            if self.accept_symbol(closing) {
+                return Ok(items);
+            }
+            if !self.accept_symbol(',') {
+                return Err(self.unexpected(&format!("',' or '{}'", closing)));
+            }
+        }
+    }
+
+    /// Parses the next argument of the current operation.
+    fn argument<T: HqslangArgument>(&mut self, name: &str) -> Result<T, RoqoqoError> {
+        self.context = format!("argument `{}` of {}", name, self.operation);
+        T::parse(self)
+    }
+
+    /// Parses operations up to the end of the text or up to the closing brace of a nested circuit.
+    fn block(&mut self, opening: Option<&Token>) -> Result<Circuit, RoqoqoError> {
+        let mut circuit = Circuit::new();
+        loop {
+            self.context.clear();
+            let token = self.peek().clone();
+            match &token.kind {
+                TokenKind::LineBreak => self.advance(),
+                TokenKind::End => {
+                    return match opening {
+                        None => Ok(circuit),
+                        Some(open) => Err(self.error_at(
+                            &token,
+                            format!(
+                                "Missing '}}' closing the circuit opened at line {}, column {}",
+                                open.line, open.column
+                            ),
+                        )),
+                    }
+                }
+                TokenKind::Symbol('}') if opening.is_some() => {
+                    self.advance();
+                    return Ok(circuit);
+                }
+                TokenKind::Identifier(name) => {
+                    self.advance();
+                    circuit.add_operation(self.operation(name, &token)?);
+                }
+                _ => return Err(self.unexpected("the hqslang name of an operation")),
+            }
+        }
+    }
+
+    /// Parses the arguments of the operation with the given hqslang name.
+    fn operation(&mut self, name: &str, token: &Token) -> Result<Operation, RoqoqoError> {
+        self.operation = name.to_string();
+        let operation = match name {
+            "SingleQubitGate" => Operation::from(SingleQubitGate::new(
+                self.argument("qubit")?,
+                self.argument("alpha_r")?,
+                self.argument("alpha_i")?,
+                self.argument("beta_r")?,
+                self.argument("beta_i")?,
+                self.argument("global_phase")?,
+            )),
+            "RotateZ" => Operation::from(RotateZ::new(
+                self.argument("qubit")?,
+                self.argument("theta")?,
+            )),
+            "RotateX" => Operation::from(RotateX::new(
+                self.argument("qubit")?,
+                self.argument("theta")?,
+            )),
+            "RotateY" => Operation::from(RotateY::new(
+                self.argument("qubit")?,
+                self.argument("theta")?,
+            )),
+            "PauliX" => Operation::from(PauliX::new(self.argument("qubit")?)),
+            "PauliY" => Operation::from(PauliY::new(self.argument("qubit")?)),
+            "PauliZ" => Operation::from(PauliZ::new(self.argument("qubit")?)),
+            "SqrtPauliX" => Operation::from(SqrtPauliX::new(self.argument("qubit")?)),
+            "InvSqrtPauliX" => Operation::from(InvSqrtPauliX::new(self.argument("qubit")?)),
+            "Hadamard" => Operation::from(Hadamard::new(self.argument("qubit")?)),
+            "SGate" => Operation::from(SGate::new(self.argument("qubit")?)),
+            "TGate" => Operation::from(TGate::new(self.argument("qubit")?)),
+            "RotateAroundSphericalAxis" => Operation::from(RotateAroundSphericalAxis::new(
+                self.argument("qubit")?,
+                self.argument("theta")?,
+                self.argument("spherical_theta")?,
+                self.argument("spherical_phi")?,
+            )),
+            "PragmaSetNumberOfMeasurements" => Operation::from(PragmaSetNumberOfMeasurements::new(
+                self.argument("number_measurements")?,
+                self.argument("readout")?,
+            )),
+            "PragmaSetStateVector" => {
+                Operation::from(PragmaSetStateVector::new(self.argument("statevector")?))
+            }
+            "PragmaSetDensityMatrix" => Operation::from(PragmaSetDensityMatrix::new(
+                self.argument("density_matrix")?,
+            )),
+            "PragmaRepeatGate" => Operation::from(PragmaRepeatGate::new(
+                self.argument("repetition_coefficient")?,
+            )),
+            "PragmaOverrotation" => Operation::from(PragmaOverrotation::new(
+                self.argument("gate_hqslang")?,
+                self.argument("qubits")?,
+                self.argument("amplitude")?,
+                self.argument("variance")?,
+            )),
+            "PragmaBoostNoise" => {
+                Operation::from(PragmaBoostNoise::new(self.argument("noise_coefficient")?))
+            }
+            "PragmaStopParallelBlock" => Operation::from(PragmaStopParallelBlock::new(
+                self.argument("qubits")?,
+                self.argument("execution_time")?,
+            )),
+            "PragmaGlobalPhase" => Operation::from(PragmaGlobalPhase::new(self.argument("phase")?)),
+            "PragmaSleep" => Operation::from(PragmaSleep::new(
+                self.argument("qubits")?,
+                self.argument("sleep_time")?,
+            )),
+            "PragmaActiveReset" => Operation::from(PragmaActiveReset::new(self.argument("qubit")?)),
+            "PragmaStartDecompositionBlock" => Operation::from(PragmaStartDecompositionBlock::new(
+                self.argument("qubits")?,
+                self.argument("reordering_dictionary")?,
+            )),
+            "PragmaStopDecompositionBlock" => {
+                Operation::from(PragmaStopDecompositionBlock::new(self.argument("qubits")?))
+            }
+            "PragmaDamping" => Operation::from(PragmaDamping::new(
+                self.argument("qubit")?,
+                self.argument("gate_time")?,
+                self.argument("rate")?,
+            )),
+            "PragmaDepolarising" => Operation::from(PragmaDepolarising::new(
+                self.argument("qubit")?,
+                self.argument("gate_time")?,
+                self.argument("rate")?,
+            )),
+            "PragmaDephasing" => Operation::from(PragmaDephasing::new(
+                self.argument("qubit")?,
+                self.argument("gate_time")?,
+                self.argument("rate")?,
+            )),
+            "PragmaRandomNoise" => Operation::from(PragmaRandomNoise::new(
+                self.argument("qubit")?,
+                self.argument("gate_time")?,
+                self.argument("depolarising_rate")?,
+                self.argument("dephasing_rate")?,
+            )),
+            "PragmaGeneralNoise" => Operation::from(PragmaGeneralNoise::new(
+                self.argument("qubit")?,
+                self.argument("gate_time")?,
+                self.argument("rate")?,
+                self.argument("operators")?,
+            )),
+            "PragmaConditional" => Operation::from(PragmaConditional::new(
+                self.argument("condition_register")?,
+                self.argument("condition_index")?,
+                self.argument("circuit")?,
+            )),
+            "PragmaConditionalPattern" => Operation::from(PragmaConditionalPattern::new(
+                self.argument("condition_register")?,
+                self.argument("condition")?,
+                self.argument("circuit")?,
+            )),
+            "PragmaLoop" => Operation::from(PragmaLoop::new(
+                self.argument("repetitions")?,
+                self.argument("circuit")?,
+            )),
+            "PragmaRepeatUntilSuccess" => Operation::from(PragmaRepeatUntilSuccess::new(
+                self.argument("condition_register")?,
+                self.argument("condition_index")?,
+                self.argument("max_iterations")?,
+                self.argument("circuit")?,
+            )),
+            "ClassicalNot" => Operation::from(ClassicalNot::new(
+                self.argument("register")?,
+                self.argument("index")?,
+                self.argument("output_register")?,
+                self.argument("output_index")?,
+            )),
+            "ClassicalCopy" => Operation::from(ClassicalCopy::new(
+                self.argument("register")?,
+                self.argument("index")?,
+                self.argument("output_register")?,
+                self.argument("output_index")?,
+            )),
+            "ClassicalXor" => Operation::from(ClassicalXor::new(
+                self.argument("register")?,
+                self.argument("indices")?,
+                self.argument("output_register")?,
+                self.argument("output_index")?,
+            )),
+            "ClassicalAnd" => Operation::from(ClassicalAnd::new(
+                self.argument("register")?,
+                self.argument("indices")?,
+                self.argument("output_register")?,
+                self.argument("output_index")?,
+            )),
+            "ClassicalOr" => Operation::from(ClassicalOr::new(
+                self.argument("register")?,
+                self.argument("indices")?,
+                self.argument("output_register")?,
+                self.argument("output_index")?,
+            )),
+            "ClassicalGreaterThan" => Operation::from(ClassicalGreaterThan::new(
+                self.argument("register")?,
+                self.argument("index")?,
+                self.argument("threshold")?,
+                self.argument("output_register")?,
+                self.argument("output_index")?,
+            )),
+            "ClassicalLessThan" => Operation::from(ClassicalLessThan::new(
+                self.argument("register")?,
+                self.argument("index")?,
+                self.argument("threshold")?,
+                self.argument("output_register")?,
+                self.argument("output_index")?,
+            )),
+            "CNOT" => Operation::from(CNOT::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "SWAP" => Operation::from(SWAP::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "ISwap" => Operation::from(ISwap::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "FSwap" => Operation::from(FSwap::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "SqrtISwap" => Operation::from(SqrtISwap::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "InvSqrtISwap" => Operation::from(InvSqrtISwap::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "XY" => Operation::from(XY::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("theta")?,
+            )),
+            "ControlledPhaseShift" => Operation::from(ControlledPhaseShift::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("theta")?,
+            )),
+            "ControlledPauliY" => Operation::from(ControlledPauliY::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "ControlledPauliZ" => Operation::from(ControlledPauliZ::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "MolmerSorensenXX" => Operation::from(MolmerSorensenXX::new(
+                self.argument("control")?,
+                self.argument("target")?,
+            )),
+            "VariableMSXX" => Operation::from(VariableMSXX::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("theta")?,
+            )),
+            "GivensRotation" => Operation::from(GivensRotation::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("theta")?,
+                self.argument("phi")?,
+            )),
+            "GivensRotationLittleEndian" => Operation::from(GivensRotationLittleEndian::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("theta")?,
+                self.argument("phi")?,
+            )),
+            "Qsim" => Operation::from(Qsim::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("x")?,
+                self.argument("y")?,
+                self.argument("z")?,
+            )),
+            "Fsim" => Operation::from(Fsim::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("t")?,
+                self.argument("u")?,
+                self.argument("delta")?,
+            )),
+            "SpinInteraction" => Operation::from(SpinInteraction::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("x")?,
+                self.argument("y")?,
+                self.argument("z")?,
+            )),
+            "Bogoliubov" => Operation::from(Bogoliubov::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("delta_real")?,
+                self.argument("delta_imag")?,
+            )),
+            "PMInteraction" => Operation::from(PMInteraction::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("t")?,
+            )),
+            "ComplexPMInteraction" => Operation::from(ComplexPMInteraction::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("t_real")?,
+                self.argument("t_imag")?,
+            )),
+            "PhaseShiftedControlledZ" => Operation::from(PhaseShiftedControlledZ::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("phi")?,
+            )),
+            "TwoQubitGate" => Operation::from(TwoQubitGate::new(
+                self.argument("control")?,
+                self.argument("target")?,
+                self.argument("phase")?,
+                self.argument("kx")?,
+                self.argument("ky")?,
+                self.argument("kz")?,
+                self.argument("control_before_z1")?,
+                self.argument("control_before_y")?,
+                self.argument("control_before_z2")?,
+                self.argument("target_before_z1")?,
+                self.argument("target_before_y")?,
+                self.argument("target_before_z2")?,
+                self.argument("control_after_z1")?,
+                self.argument("control_after_y")?,
+                self.argument("control_after_z2")?,
+                self.argument("target_after_z1")?,
+                self.argument("target_after_y")?,
+                self.argument("target_after_z2")?,
+            )),
+            "MultiQubitMS" => Operation::from(MultiQubitMS::new(
+                self.argument("qubits")?,
+                self.argument("theta")?,
+            )),
+            "MultiQubitGate" => Operation::from(MultiQubitGate::new(
+                self.argument("qubits")?,
+                self.argument("matrix")?,
+            )),
+            "Toffoli" => Operation::from(Toffoli::new(self.argument("qubits")?)),
+            "ControlledControlledPauliZ" => {
+                Operation::from(ControlledControlledPauliZ::new(self.argument("qubits")?))
+            }
+            "ControlledControlledPhaseShift" => {
+                Operation::from(ControlledControlledPhaseShift::new(
+                    self.argument("qubits")?,
+                    self.argument("theta")?,
+                ))
+            }
+            "PauliProductRotation" => Operation::from(PauliProductRotation::new(
+                self.argument("qubits")?,
+                self.argument("paulis")?,
+                self.argument("theta")?,
+            )),
+            "MultiControlledGate" => Operation::from(MultiControlledGate::new(
+                self.argument("qubits")?,
+                self.argument("phase")?,
+                self.argument("z1")?,
+                self.argument("y")?,
+                self.argument("z2")?,
+            )),
+            "MeasureQubit" => Operation::from(MeasureQubit::new(
+                self.argument("qubit")?,
+                self.argument("readout")?,
+                self.argument("readout_index")?,
+            )),
+            "PragmaGetStateVector" => Operation::from(PragmaGetStateVector::new(
+                self.argument("readout")?,
+                self.argument("circuit")?,
+            )),
+            "PragmaGetDensityMatrix" => Operation::from(PragmaGetDensityMatrix::new(
+                self.argument("readout")?,
+                self.argument("circuit")?,
+            )),
+            "PragmaGetOccupationProbability" => {
+                Operation::from(PragmaGetOccupationProbability::new(
+                    self.argument("readout")?,
+                    self.argument("circuit")?,
+                ))
+            }
+            "PragmaGetPauliProduct" => Operation::from(PragmaGetPauliProduct::new(
+                self.argument("qubit_paulis")?,
+                self.argument("readout")?,
+                self.argument("circuit")?,
+            )),
+            "PragmaRepeatedMeasurement" => Operation::from(PragmaRepeatedMeasurement::new(
+                self.argument("readout")?,
+                self.argument("qubit_mapping")?,
+                self.argument("number_measurements")?,
+            )),
+            "DefinitionFloat" => Operation::from(DefinitionFloat::new(
+                self.argument("name")?,
+                self.argument("length")?,
+                self.argument("is_output")?,
+            )),
+            "DefinitionComplex" => Operation::from(DefinitionComplex::new(
+                self.argument("name")?,
+                self.argument("length")?,
+                self.argument("is_output")?,
+            )),
+            "DefinitionUsize" => Operation::from(DefinitionUsize::new(
+                self.argument("name")?,
+                self.argument("length")?,
+                self.argument("is_output")?,
+            )),
+            "DefinitionBit" => Operation::from(DefinitionBit::new(
+                self.argument("name")?,
+                self.argument("length")?,
+                self.argument("is_output")?,
+            )),
+            "InputSymbolic" => Operation::from(InputSymbolic::new(
+                self.argument("name")?,
+                self.argument("input")?,
+            )),
+            _ => {
+                return Err(self.error_at(token, format!("Unknown operation '{}'", name)));
+            }
+        };
+        self.context = format!("the arguments of {}", name);
+        match self.peek().kind {
+            TokenKind::LineBreak | TokenKind::End => Ok(operation),
+            _ => Err(self.unexpected("end of line")),
+        }
+    }
+}
+
+/// Value that can be written and parsed as an argument of an operation in the hqslang text format.
+trait HqslangArgument: Sized {
+    /// Returns the hqslang text of the value.
+    fn to_hqslang(&self) -> String;
+
+    /// Parses the value from the next tokens.
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError>;
+}
+
+impl HqslangArgument for usize {
+    fn to_hqslang(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        if let TokenKind::Number(literal) = &parser.peek().kind {
+            if let Ok(value) = literal.parse::<usize>() {
+                parser.advance();
+                return Ok(value);
+            }
+        }
+        Err(parser.unexpected("non-negative integer"))
+    }
+}
+
+impl HqslangArgument for f64 {
+    fn to_hqslang(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        if let TokenKind::Number(literal) | TokenKind::Identifier(literal) = &parser.peek().kind {
+            if let Ok(value) = literal.parse::<f64>() {
+                parser.advance();
+                return Ok(value);
+            }
+        }
+        Err(parser.unexpected("number"))
+    }
+}
+
+impl HqslangArgument for bool {
+    fn to_hqslang(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        let value = match &parser.peek().kind {
+            TokenKind::Identifier(word) if word == "true" => true,
+            TokenKind::Identifier(word) if word == "false" => false,
+            _ => return Err(parser.unexpected("'true' or 'false'")),
+        };
+        parser.advance();
+        Ok(value)
+    }
+}
+
+impl HqslangArgument for String {
+    fn to_hqslang(&self) -> String {
+        if is_identifier(self) {
+            self.clone()
+        } else {
+            quote(self)
+        }
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        match &parser.peek().kind {
+            TokenKind::Identifier(text) | TokenKind::Text(text) => {
+                let text = text.clone();
+                parser.advance();
+                Ok(text)
+            }
+            _ => Err(parser.unexpected("name")),
+        }
+    }
+}
+
+impl HqslangArgument for CalculatorFloat {
+    fn to_hqslang(&self) -> String {
+        match self {
+            CalculatorFloat::Float(value) => value.to_hqslang(),
+            CalculatorFloat::Str(expression) => quote(expression),
+        }
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        if let TokenKind::Text(expression) = &parser.peek().kind {
+            let expression = expression.clone();
+            parser.advance();
+            return Ok(CalculatorFloat::Str(expression));
+        }
+        f64::parse(parser)
+            .map(CalculatorFloat::Float)
+            .map_err(|_| parser.unexpected("number or quoted symbolic expression"))
+    }
+}
+
+impl HqslangArgument for Complex64 {
+    fn to_hqslang(&self) -> String {
+        format!("({}, {})", self.re.to_hqslang(), self.im.to_hqslang())
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        if parser.peek().kind != TokenKind::Symbol('(') {
+            return Err(parser.unexpected("complex number '(re, im)'"));
+        }
+        parser.advance();
+        let re = f64::parse(parser)?;
+        parser.expect_symbol(',')?;
+        let im = f64::parse(parser)?;
+        parser.expect_symbol(')')?;
+        Ok(Complex64::new(re, im))
+    }
+}
+
+impl HqslangArgument for (usize, bool) {
+    fn to_hqslang(&self) -> String {
+        format!("({}, {})", self.0.to_hqslang(), self.1.to_hqslang())
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        if parser.peek().kind != TokenKind::Symbol('(') {
+            return Err(parser.unexpected("pair '(index, value)'"));
+        }
+        parser.advance();
+        let index = usize::parse(parser)?;
+        parser.expect_symbol(',')?;
+        let value = bool::parse(parser)?;
+        parser.expect_symbol(')')?;
+        Ok((index, value))
+    }
+}
+
+impl<T: HqslangArgument> HqslangArgument for Vec<T> {
+    fn to_hqslang(&self) -> String {
+        let items: Vec<String> = self.iter().map(|item| item.to_hqslang()).collect();
+        format!("[{}]", items.join(", "))
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        if parser.peek().kind != TokenKind::Symbol('[') {
+            return Err(parser.unexpected("list '[...]'"));
+        }
+        parser.advance();
+        parser.items(']', T::parse)
+    }
+}
+
+impl HqslangArgument for HashMap<usize, usize> {
+    fn to_hqslang(&self) -> String {
+        let mut entries: Vec<(&usize, &usize)> = self.iter().collect();
+        entries.sort();
+        let items: Vec<String> = entries
+            .into_iter()
+            .map(|(key, value)| format!("{}: {}", key, value))
+            .collect();
+        format!("{{{}}}", items.join(", "))
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        if parser.peek().kind != TokenKind::Symbol('{') {
+            return Err(parser.unexpected("mapping '{...}'"));
+        }
+        parser.advance();
+        let entries = parser.items('}', |parser| {
+            let token = parser.peek().clone();
+            let key = usize::parse(parser)?;
+            parser.expect_symbol(':')?;
+            let value = usize::parse(parser)?;
+            Ok((token, key, value))
+        })?;
+        let mut mapping = HashMap::new();
+        for (token, key, value) in entries {
+            if mapping.insert(key, value).is_some() {
+                return Err(parser.error_at(&token, format!("Duplicate key {} in mapping", key)));
+            }
+        }
+        Ok(mapping)
+    }
+}
+
+impl<T: HqslangArgument> HqslangArgument for Option<T> {
+    fn to_hqslang(&self) -> String {
+        match self {
+            Some(value) => value.to_hqslang(),
+            None => "None".to_string(),
+        }
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        if parser.peek().kind == TokenKind::Identifier("None".to_string()) {
+            parser.advance();
+            Ok(None)
+        } else {
+            T::parse(parser).map(Some)
+        }
+    }
+}
+
+impl HqslangArgument for Array1<Complex64> {
+    fn to_hqslang(&self) -> String {
+        self.to_vec().to_hqslang()
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        Vec::<Complex64>::parse(parser).map(Array1::from)
+    }
+}
+
+impl HqslangArgument for Array2<Complex64> {
+    fn to_hqslang(&self) -> String {
+        let rows: Vec<Vec<Complex64>> = self.outer_iter().map(|row| row.to_vec()).collect();
+        rows.to_hqslang()
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        let token = parser.peek().clone();
+        let rows = Vec::<Vec<Complex64>>::parse(parser)?;
+        let number_columns = rows.first().map(|row| row.len()).unwrap_or(0);
+        if rows.iter().any(|row| row.len() != number_columns) {
+            return Err(parser.error_at(
+                &token,
+                "All rows of a matrix must have the same length".to_string(),
+            ));
+        }
+        let number_rows = rows.len();
+        Array2::from_shape_vec(
+            (number_rows, number_columns),
+            rows.into_iter().flatten().collect(),
+        )
+        .map_err(|err| parser.error_at(&token, err.to_string()))
+    }
+}
+
+impl HqslangArgument for Circuit {
+    fn to_hqslang(&self) -> String {
+        let mut text = String::from("{\n");
+        for line in circuit_to_hqslang(self).lines() {
+            text.push_str(INDENTATION);
+            text.push_str(line);
+            text.push('\n');
+        }
+        text.push('}');
+        text
+    }
+
+    fn parse(parser: &mut HqslangParser) -> Result<Self, RoqoqoError> {
+        let token = parser.peek().clone();
+        if token.kind != TokenKind::Symbol('{') {
+            return Err(parser.unexpected("circuit '{...}'"));
+        }
+        parser.advance();
+        let operation = parser.operation.clone();
+        let circuit = parser.block(Some(&token))?;
+        parser.operation = operation;
+        Ok(circuit)
+    }
+}
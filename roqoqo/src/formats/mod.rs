@@ -0,0 +1,84 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of roqoqo circuits to and from text formats of other quantum computing tools.
+
+#[doc(hidden)]
+mod diagram;
+pub use diagram::*;
+#[doc(hidden)]
+mod drawing;
+pub use drawing::{DrawingOptions, GateLabels};
+#[doc(hidden)]
+mod hqslang;
+pub use hqslang::*;
+#[doc(hidden)]
+mod qasm_export;
+pub use qasm_export::*;
+#[doc(hidden)]
+mod qasm_import;
+pub use qasm_import::*;
+#[doc(hidden)]
+mod quil;
+pub use quil::*;
+#[doc(hidden)]
+mod quantikz;
+pub use quantikz::*;
+#[doc(hidden)]
+mod svg;
+pub use svg::*;
+
+use crate::operations::{
+    OperateSingleQubit, OperateTwoQubit, Operation, SingleQubitGateOperation, Substitute,
+    TwoQubitGateOperation,
+};
+use crate::RoqoqoError;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// A single- or two-qubit gate moved to qubit 0 (and 1), used to define a custom gate once for all qubits.
+pub(crate) struct CanonicalGate {
+    /// The qubits the gate acts on, with the control before the target.
+    pub qubits: Vec<usize>,
+    /// The gate acting on qubit 0 (and 1).
+    pub gate: Operation,
+    /// The hqslang text of the moved gate, identifying the custom gate by its hqslang name and parameter values.
+    pub key: String,
+}
+
+/// Moves a single- or two-qubit gate to qubit 0 (and 1).
+///
+/// # Arguments
+///
+/// * `operation` - The operation to move.
+///
+/// # Returns
+///
+/// * `Ok(Some(CanonicalGate))` - The moved gate.
+/// * `Ok(None)` - The operation is not a single- or two-qubit gate.
+/// * `Err(RoqoqoError)` - Remapping the qubits of the operation failed.
+pub(crate) fn canonical_gate(operation: &Operation) -> Result<Option<CanonicalGate>, RoqoqoError> {
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    let qubits = if let Ok(gate) = SingleQubitGateOperation::try_from(operation) {
+        vec![*gate.qubit()]
+    } else if let Ok(gate) = TwoQubitGateOperation::try_from(operation) {
+        vec![*gate.control(), *gate.target()]
+    } else {
+        return Ok(None);
+    };
+    for (index, qubit) in qubits.iter().enumerate() {
+        mapping.insert(*qubit, index);
+    }
+    let gate = operation.remap_qubits(&mapping)?;
+    let key = operation_to_hqslang(&gate);
+    Ok(Some(CanonicalGate { qubits, gate, key }))
+}
@@ -0,0 +1,564 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{canonical_gate, operation_to_hqslang, CanonicalGate};
+use crate::operations::*;
+use crate::{Circuit, RoqoqoError};
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorFloat;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::f64::consts::FRAC_PI_2;
+
+/// Amplitudes below this threshold are treated as zero when decomposing single-qubit gates.
+const AMPLITUDE_TOLERANCE: f64 = 1e-12;
+
+/// Names of gates defined in commonly used versions of qelib1.inc that must not be redefined.
+const RESERVED_GATE_NAMES: &[&str] = &[
+    "u3", "u2", "u1", "u0", "u", "p", "cx", "id", "x", "y", "z", "h", "s", "sdg", "t", "tdg", "rx",
+    "ry", "rz", "sx", "sxdg", "cz", "cy", "swap", "ch", "ccx", "cswap", "crx", "cry", "crz", "cu1",
+    "cp", "cu3", "csx", "cu", "rxx", "rzz", "rccx", "rc3x", "c3x", "c3sqrtx", "c4x",
+];
+
+/// Handling of operations that cannot be represented in OpenQASM 2.0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QasmStrictness {
+    /// Return an error for operations that cannot be represented.
+    Error,
+    /// Write operations that cannot be represented as comments.
+    Comment,
+    /// Drop operations that cannot be represented.
+    Drop,
+}
+
+/// Exports a circuit to OpenQASM 2.0.
+///
+/// All qubits are placed in a single quantum register `q`
+/// with the size given by the largest qubit index in the circuit.
+///
+/// * Gates with a counterpart in `qelib1.inc` are written directly,
+///   all other gates are written as custom `gate` definitions obtained from [qelib1_decomposition].
+/// * [DefinitionBit] is translated into `creg`.
+/// * [MeasureQubit] and [PragmaRepeatedMeasurement] are translated into `measure`.
+/// * [PragmaActiveReset] is translated into `reset`.
+/// * [PragmaConditional] is translated into `if` statements when the condition register has length one.
+/// * [PragmaConditionalPattern] is translated into `if` statements when the condition covers every bit of the condition register.
+/// * [PragmaLoop] is unrolled when the number of repetitions is a constant.
+///
+/// All other operations are handled according to `strictness`.
+/// Symbolic parameters cannot be exported and have to be substituted beforehand.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to export.
+/// * `strictness` - The handling of operations that cannot be represented in OpenQASM 2.0.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The OpenQASM 2.0 program.
+/// * `Err(RoqoqoError::QasmExportError)` - The circuit contains symbolic parameters or an operation that cannot be represented with [QasmStrictness::Error].
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::formats::{circuit_to_qasm, QasmStrictness};
+/// use roqoqo::operations::{CNOT, DefinitionBit, Hadamard, MeasureQubit};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += DefinitionBit::new("ro".to_string(), 2, true);
+/// circuit += Hadamard::new(0);
+/// circuit += CNOT::new(0, 1);
+/// circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+/// circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+///
+/// let qasm = circuit_to_qasm(&circuit, QasmStrictness::Error).unwrap();
+/// assert!(qasm.contains("cx q[0],q[1];"));
+/// ```
+pub fn circuit_to_qasm(
+    circuit: &Circuit,
+    strictness: QasmStrictness,
+) -> Result<String, RoqoqoError> {
+    let mut writer = QasmWriter {
+        strictness,
+        number_qubits: number_qubits(circuit),
+        bit_registers: HashMap::new(),
+        gate_definitions: Vec::new(),
+        gate_names: HashMap::new(),
+        gate_name_counts: HashMap::new(),
+    };
+    let mut statements: Vec<String> = Vec::new();
+    for operation in circuit.iter() {
+        match writer.statements(operation)? {
+            Some(operation_statements) => statements.extend(operation_statements),
+            None => writer.not_representable(operation, &mut statements)?,
+        }
+    }
+
+    let mut qasm = String::from("OPENQASM 2.0;\ninclude \"qelib1.inc\";\n");
+    for definition in writer.gate_definitions.iter() {
+        qasm.push_str(definition);
+    }
+    if writer.number_qubits > 0 {
+        qasm.push_str(&format!("qreg q[{}];\n", writer.number_qubits));
+    }
+    for statement in statements {
+        qasm.push_str(&statement);
+        qasm.push('\n');
+    }
+    Ok(qasm)
+}
+
+/// Decomposes a gate into gates with a counterpart in the OpenQASM 2.0 `qelib1.inc`.
+///
+/// Gates with a counterpart are returned unchanged.
+/// Other single-qubit gates are decomposed into [RotateZ], [RotateY] and [RotateZ] rotations.
+/// Other two-qubit gates are decomposed using their [KakDecomposition],
+/// where the entangling part is constructed from [CNOT], [Hadamard] and [RotateZ] gates.
+/// The decomposition is equal to the gate up to a global phase.
+///
+/// # Arguments
+///
+/// * `operation` - The gate to decompose.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The decomposition of the gate.
+/// * `Err(RoqoqoError::OperationNotUnitary)` - The operation is not a single- or two-qubit gate.
+/// * `Err(RoqoqoError::CalculatorError)` - The gate has symbolic parameters.
+pub fn qelib1_decomposition(operation: &Operation) -> Result<Circuit, RoqoqoError> {
+    let mut circuit = Circuit::new();
+    if is_qelib1_gate(operation) {
+        circuit.add_operation(operation.clone());
+    } else if let Ok(gate) = SingleQubitGateOperation::try_from(operation) {
+        circuit += single_qubit_decomposition(*gate.qubit(), &gate.unitary_matrix()?);
+    } else if let Ok(gate) = TwoQubitGateOperation::try_from(operation) {
+        let (control, target) = (*gate.control(), *gate.target());
+        let kak = gate.kak_decomposition();
+        if let Some(before) = kak.circuit_before {
+            for operation in before.iter() {
+                circuit += qelib1_decomposition(operation)?;
+            }
+        }
+        let [kx, ky, kz] = kak.k_vector;
+        circuit += xx_interaction(control, target, *kx.float()?);
+        circuit += yy_interaction(control, target, *ky.float()?);
+        circuit += zz_interaction(control, target, *kz.float()?);
+        if let Some(after) = kak.circuit_after {
+            for operation in after.iter() {
+                circuit += qelib1_decomposition(operation)?;
+            }
+        }
+    } else {
+        return Err(RoqoqoError::OperationNotUnitary {
+            hqslang: operation.hqslang(),
+        });
+    }
+    Ok(circuit)
+}
+
+/// Returns true if a gate has a counterpart in `qelib1.inc`.
+fn is_qelib1_gate(operation: &Operation) -> bool {
+    matches!(
+        operation,
+        Operation::RotateX(_)
+            | Operation::RotateY(_)
+            | Operation::RotateZ(_)
+            | Operation::PauliX(_)
+            | Operation::PauliY(_)
+            | Operation::PauliZ(_)
+            | Operation::Hadamard(_)
+            | Operation::SGate(_)
+            | Operation::TGate(_)
+            | Operation::CNOT(_)
+            | Operation::ControlledPauliY(_)
+            | Operation::ControlledPauliZ(_)
+            | Operation::ControlledPhaseShift(_)
+            | Operation::Toffoli(_)
+    )
+}
+
+/// Returns the `qelib1.inc` statement of a gate with a counterpart.
+///
+/// # Arguments
+///
+/// * `operation` - The gate.
+/// * `qubit` - Returns the name of a qubit in the statement.
+fn qelib1_statement(
+    operation: &Operation,
+    qubit: &dyn Fn(usize) -> String,
+) -> Result<Option<String>, RoqoqoError> {
+    Ok(Some(match operation {
+        Operation::RotateX(op) => format!("rx({}) {};", angle(op.theta())?, qubit(*op.qubit())),
+        Operation::RotateY(op) => format!("ry({}) {};", angle(op.theta())?, qubit(*op.qubit())),
+        Operation::RotateZ(op) => format!("rz({}) {};", angle(op.theta())?, qubit(*op.qubit())),
+        Operation::PauliX(op) => format!("x {};", qubit(*op.qubit())),
+        Operation::PauliY(op) => format!("y {};", qubit(*op.qubit())),
+        Operation::PauliZ(op) => format!("z {};", qubit(*op.qubit())),
+        Operation::Hadamard(op) => format!("h {};", qubit(*op.qubit())),
+        Operation::SGate(op) => format!("s {};", qubit(*op.qubit())),
+        Operation::TGate(op) => format!("t {};", qubit(*op.qubit())),
+        Operation::CNOT(op) => format!("cx {},{};", qubit(*op.control()), qubit(*op.target())),
+        Operation::ControlledPauliY(op) => {
+            format!("cy {},{};", qubit(*op.control()), qubit(*op.target()))
+        }
+        Operation::ControlledPauliZ(op) => {
+            format!("cz {},{};", qubit(*op.control()), qubit(*op.target()))
+        }
+        Operation::ControlledPhaseShift(op) => format!(
+            "cu1({}) {},{};",
+            angle(op.theta())?,
+            qubit(*op.control()),
+            qubit(*op.target())
+        ),
+        Operation::Toffoli(op) => match op.qubits().as_slice() {
+            [control_0, control_1, target] => format!(
+                "ccx {},{},{};",
+                qubit(*control_0),
+                qubit(*control_1),
+                qubit(*target)
+            ),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    }))
+}
+
+/// Returns the OpenQASM representation of an angle.
+fn angle(value: &CalculatorFloat) -> Result<String, RoqoqoError> {
+    match value {
+        CalculatorFloat::Float(x) => Ok(format!("{}", x)),
+        CalculatorFloat::Str(expression) => Err(RoqoqoError::QasmExportError {
+            msg: format!(
+                "Symbolic parameter {} can not be exported, substitute parameters first",
+                expression
+            ),
+        }),
+    }
+}
+
+/// Returns the number of qubits up to the largest qubit index in the circuit.
+pub(crate) fn number_qubits(circuit: &Circuit) -> usize {
+    let mut number_qubits = 0;
+    for operation in circuit.iter() {
+        if let InvolvedQubits::Set(qubits) = operation.involved_qubits() {
+            if let Some(max) = qubits.iter().max() {
+                number_qubits = number_qubits.max(max + 1);
+            }
+        }
+        if let Operation::PragmaRepeatedMeasurement(op) = operation {
+            if let Some(max) = op.qubit_mapping().as_ref().and_then(|m| m.keys().max()) {
+                number_qubits = number_qubits.max(max + 1);
+            }
+        }
+    }
+    number_qubits
+}
+
+/// Decomposes a single-qubit unitary into Z-Y-Z rotations.
+fn single_qubit_decomposition(qubit: usize, unitary: &ndarray::Array2<Complex64>) -> Circuit {
+    // Up to a global phase the unitary is
+    // [[cos(θ/2), -exp(iλ) sin(θ/2)], [exp(iφ) sin(θ/2), exp(i(φ+λ)) cos(θ/2)]] = RZ(φ) RY(θ) RZ(λ)
+    let (u00, u01, u10, u11) = (
+        unitary[(0, 0)],
+        unitary[(0, 1)],
+        unitary[(1, 0)],
+        unitary[(1, 1)],
+    );
+    let theta = 2.0 * u10.norm().atan2(u00.norm());
+    let (phi, lambda) = if u10.norm() < AMPLITUDE_TOLERANCE {
+        (0.0, u11.arg() - u00.arg())
+    } else if u00.norm() < AMPLITUDE_TOLERANCE {
+        (u10.arg(), (-u01).arg())
+    } else {
+        (u10.arg() - u00.arg(), (-u01).arg() - u00.arg())
+    };
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(qubit, lambda.into());
+    circuit += RotateY::new(qubit, theta.into());
+    circuit += RotateZ::new(qubit, phi.into());
+    circuit
+}
+
+/// Returns exp(i k Z⊗Z) built from CNOT and RotateZ gates.
+fn zz_interaction(control: usize, target: usize, k: f64) -> Circuit {
+    let mut circuit = Circuit::new();
+    if k != 0.0 {
+        circuit += CNOT::new(control, target);
+        circuit += RotateZ::new(target, (-2.0 * k).into());
+        circuit += CNOT::new(control, target);
+    }
+    circuit
+}
+
+/// Returns exp(i k X⊗X) built from Hadamard, CNOT and RotateZ gates.
+fn xx_interaction(control: usize, target: usize, k: f64) -> Circuit {
+    let mut circuit = Circuit::new();
+    if k != 0.0 {
+        circuit += Hadamard::new(control);
+        circuit += Hadamard::new(target);
+        circuit += zz_interaction(control, target, k);
+        circuit += Hadamard::new(control);
+        circuit += Hadamard::new(target);
+    }
+    circuit
+}
+
+/// Returns exp(i k Y⊗Y) built from Hadamard, CNOT and RotateZ gates.
+fn yy_interaction(control: usize, target: usize, k: f64) -> Circuit {
+    let mut circuit = Circuit::new();
+    if k != 0.0 {
+        for qubit in [control, target].iter() {
+            circuit += RotateZ::new(*qubit, (-FRAC_PI_2).into());
+            circuit += Hadamard::new(*qubit);
+        }
+        circuit += zz_interaction(control, target, k);
+        for qubit in [control, target].iter() {
+            circuit += Hadamard::new(*qubit);
+            circuit += RotateZ::new(*qubit, FRAC_PI_2.into());
+        }
+    }
+    circuit
+}
+
+/// State of the export of a circuit.
+struct QasmWriter {
+    /// The handling of operations that cannot be represented.
+    strictness: QasmStrictness,
+    /// The number of qubits in the quantum register.
+    number_qubits: usize,
+    /// The lengths of the classical registers.
+    bit_registers: HashMap<String, usize>,
+    /// The custom gate definitions.
+    gate_definitions: Vec<String>,
+    /// The names of the custom gates by the debug representation of the gate acting on qubits 0 and 1.
+    gate_names: HashMap<String, String>,
+    /// The number of custom gates defined for each hqslang name.
+    gate_name_counts: HashMap<&'static str, usize>,
+}
+
+impl QasmWriter {
+    /// Returns the statements representing an operation or None when it cannot be represented.
+    fn statements(&mut self, operation: &Operation) -> Result<Option<Vec<String>>, RoqoqoError> {
+        let qubit = |index: usize| format!("q[{}]", index);
+        if let Some(statement) = qelib1_statement(operation, &qubit)? {
+            return Ok(Some(vec![statement]));
+        }
+        let statements = match operation {
+            Operation::DefinitionBit(op) => {
+                self.bit_registers.insert(op.name().clone(), *op.length());
+                vec![format!("creg {}[{}];", op.name(), op.length())]
+            }
+            Operation::MeasureQubit(op) => vec![format!(
+                "measure q[{}] -> {}[{}];",
+                op.qubit(),
+                op.readout(),
+                op.readout_index()
+            )],
+            Operation::PragmaRepeatedMeasurement(op) => {
+                let mut mapping: Vec<(usize, usize)> = match op.qubit_mapping() {
+                    Some(mapping) => mapping.iter().map(|(q, b)| (*q, *b)).collect(),
+                    None => {
+                        let length = self
+                            .bit_registers
+                            .get(op.readout())
+                            .copied()
+                            .unwrap_or(self.number_qubits);
+                        (0..length.min(self.number_qubits))
+                            .map(|q| (q, q))
+                            .collect()
+                    }
+                };
+                mapping.sort_unstable();
+                mapping
+                    .into_iter()
+                    .map(|(q, b)| format!("measure q[{}] -> {}[{}];", q, op.readout(), b))
+                    .collect()
+            }
+            Operation::PragmaActiveReset(op) => vec![format!("reset q[{}];", op.qubit())],
+            Operation::PragmaConditional(op) => {
+                if self.bit_registers.get(op.condition_register()) != Some(&1) {
+                    return Ok(None);
+                }
+                return self.conditional_statements(op.circuit(), op.condition_register(), 1);
+            }
+            Operation::PragmaConditionalPattern(op) => {
+                let length = self.bit_registers.get(op.condition_register()).copied();
+                let mut indices: Vec<usize> =
+                    op.condition().iter().map(|(index, _)| *index).collect();
+                indices.sort_unstable();
+                if Some(indices.len()) != length
+                    || indices.iter().enumerate().any(|(i, index)| i != *index)
+                    || indices.len() >= usize::BITS as usize
+                {
+                    return Ok(None);
+                }
+                let value = op
+                    .condition()
+                    .iter()
+                    .filter(|(_, value)| *value)
+                    .map(|(index, _)| 1_usize << index)
+                    .sum();
+                return self.conditional_statements(op.circuit(), op.condition_register(), value);
+            }
+            Operation::PragmaLoop(op) => {
+                if let CalculatorFloat::Str(expression) = op.repetitions() {
+                    return Err(RoqoqoError::QasmExportError {
+                        msg: format!(
+                            "Symbolic parameter {} can not be exported, substitute parameters first",
+                            expression
+                        ),
+                    });
+                }
+                let mut statements: Vec<String> = Vec::new();
+                for inner in op.unroll()?.iter() {
+                    if inner.tags().contains(&"Definition") {
+                        return Ok(None);
+                    }
+                    match self.statements(inner)? {
+                        Some(inner_statements) => statements.extend(inner_statements),
+                        None => return Ok(None),
+                    }
+                }
+                statements
+            }
+            _ => match self.custom_gate(operation)? {
+                Some(statement) => vec![statement],
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(statements))
+    }
+
+    /// Returns the statements of the circuit of a conditional operation, each executed if the register has the value.
+    fn conditional_statements(
+        &mut self,
+        circuit: &Circuit,
+        register: &str,
+        value: usize,
+    ) -> Result<Option<Vec<String>>, RoqoqoError> {
+        let mut statements: Vec<String> = Vec::new();
+        for inner in circuit.iter() {
+            if matches!(
+                inner,
+                Operation::PragmaConditional(_) | Operation::PragmaConditionalPattern(_)
+            ) || inner.tags().contains(&"Definition")
+            {
+                return Ok(None);
+            }
+            match self.statements(inner)? {
+                Some(inner_statements) if !inner_statements.iter().any(|s| s.starts_with("if(")) => {
+                    statements.extend(
+                        inner_statements
+                            .into_iter()
+                            .map(|s| format!("if({}=={}) {}", register, value, s)),
+                    )
+                }
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(statements))
+    }
+
+    /// Returns the statement applying a custom gate, adding the gate definition if necessary.
+    fn custom_gate(&mut self, operation: &Operation) -> Result<Option<String>, RoqoqoError> {
+        let CanonicalGate {
+            qubits,
+            gate: canonical,
+            key,
+        } = match canonical_gate(operation)? {
+            Some(canonical) => canonical,
+            None => return Ok(None),
+        };
+        let name = match self.gate_names.get(&key) {
+            Some(name) => name.clone(),
+            None => {
+                let name = self.new_gate_name(operation.hqslang());
+                let formal = |index: usize| ["a", "b"][index].to_string();
+                let mut definition = format!(
+                    "gate {} {} {{\n",
+                    name,
+                    (0..qubits.len())
+                        .map(formal)
+                        .collect::<Vec<String>>()
+                        .join(",")
+                );
+                let decomposition = qelib1_decomposition(&canonical).map_err(|error| {
+                    match error {
+                        RoqoqoError::CalculatorError(_) => RoqoqoError::QasmExportError {
+                            msg: format!(
+                                "Symbolic parameters of operation {} can not be exported, substitute parameters first",
+                                operation.hqslang()
+                            ),
+                        },
+                        _ => error,
+                    }
+                })?;
+                for gate in decomposition.iter() {
+                    if let Some(statement) = qelib1_statement(gate, &formal)? {
+                        definition.push_str(&format!("  {}\n", statement));
+                    }
+                }
+                definition.push_str("}\n");
+                self.gate_definitions.push(definition);
+                self.gate_names.insert(key, name.clone());
+                name
+            }
+        };
+        Ok(Some(format!(
+            "{} {};",
+            name,
+            qubits
+                .iter()
+                .map(|q| format!("q[{}]", q))
+                .collect::<Vec<String>>()
+                .join(",")
+        )))
+    }
+
+    /// Returns a new unique name for a custom gate.
+    fn new_gate_name(&mut self, hqslang: &'static str) -> String {
+        let mut name = hqslang.to_lowercase();
+        if RESERVED_GATE_NAMES.contains(&name.as_str()) {
+            name.push_str("_gate");
+        }
+        let count = self.gate_name_counts.entry(hqslang).or_insert(0);
+        if *count > 0 {
+            name = format!("{}_{}", name, count);
+        }
+        *count += 1;
+        name
+    }
+
+    /// Handles an operation that cannot be represented according to the strictness.
+    fn not_representable(
+        &self,
+        operation: &Operation,
+        statements: &mut Vec<String>,
+    ) -> Result<(), RoqoqoError> {
+        match self.strictness {
+            QasmStrictness::Error => Err(RoqoqoError::QasmExportError {
+                msg: format!(
+                    "Operation {} can not be represented in OpenQASM 2.0",
+                    operation.hqslang()
+                ),
+            }),
+            QasmStrictness::Comment => {
+                let text = operation_to_hqslang(operation);
+                let lines: Vec<&str> = text.lines().map(str::trim).collect();
+                statements.push(format!("// {}", lines.join(" ")));
+                Ok(())
+            }
+            QasmStrictness::Drop => Ok(()),
+        }
+    }
+}
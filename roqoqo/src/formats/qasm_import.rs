@@ -0,0 +1,1065 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::operations::*;
+use crate::{Circuit, RoqoqoError};
+use qoqo_calculator::{Calculator, CalculatorFloat};
+use std::collections::{HashMap, HashSet};
+
+/// Definitions of the composite gates of qelib1.inc and stdgates.inc in terms of the primitive gates.
+const COMPOSITE_GATES: &str = "
+gate u2(phi, lambda) q { U(pi / 2, phi, lambda) q; }
+gate sdg a { rz(-pi / 2) a; }
+gate tdg a { rz(-pi / 4) a; }
+gate ch a, b { ry(-pi / 4) b; cz a, b; ry(pi / 4) b; }
+gate crz(lambda) a, b { rz(lambda / 2) b; cx a, b; rz(-lambda / 2) b; cx a, b; }
+gate cry(lambda) a, b { ry(lambda / 2) b; cx a, b; ry(-lambda / 2) b; cx a, b; }
+gate crx(lambda) a, b { h b; crz(lambda) a, b; h b; }
+gate cu3(theta, phi, lambda) c, t {
+    u1((lambda + phi) / 2) c; u1((lambda - phi) / 2) t; cx c, t;
+    u3(-theta / 2, 0, -(phi + lambda) / 2) t; cx c, t; u3(theta / 2, phi, 0) t;
+}
+gate rzz(theta) a, b { cx a, b; rz(theta) b; cx a, b; }
+gate rxx(theta) a, b { h a; h b; rzz(theta) a, b; h a; h b; }
+gate ryy(theta) a, b { rx(pi / 2) a; rx(pi / 2) b; rzz(theta) a, b; rx(-pi / 2) a; rx(-pi / 2) b; }
+gate ccx a, b, c {
+    h c; cx b, c; tdg c; cx a, c; t c; cx b, c; tdg c; cx a, c;
+    t b; t c; h c; cx a, b; t a; tdg b; cx a, b;
+}
+gate cswap a, b, c { cx c, b; ccx a, b, c; cx c, b; }
+";
+
+/// Imports an OpenQASM 2.0 program or a program in a subset of OpenQASM 3.0.
+///
+/// All quantum registers are placed consecutively on the qubits of the circuit in the order of their declaration.
+/// The gates of `qelib1.inc` and `stdgates.inc` are mapped onto roqoqo gates or sequences of roqoqo gates,
+/// user `gate` definitions are inlined.
+/// The gates of the circuit are equal to the gates in the program up to a global phase.
+///
+/// * `qreg` and `qubit` declarations define qubits.
+/// * `creg` and `bit` declarations are translated into [DefinitionBit].
+/// * `measure` is translated into [MeasureQubit].
+/// * `reset` is translated into [PragmaActiveReset].
+/// * `barrier` is translated into [PragmaStopParallelBlock].
+/// * `if` statements conditioned on a single bit being one are translated into [PragmaConditional].
+/// * `input` parameters of OpenQASM 3.0 are translated into symbolic parameters.
+/// * `gphase` is translated into [PragmaGlobalPhase].
+///
+/// # Arguments
+///
+/// * `source` - The OpenQASM program.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The imported circuit.
+/// * `Err(RoqoqoError::QasmImportError)` - The program is invalid or uses unsupported features, with the line and column of the error.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::formats::qasm_to_circuit;
+/// use roqoqo::operations::{DefinitionBit, Hadamard, MeasureQubit, CNOT};
+/// use roqoqo::Circuit;
+///
+/// let source = "OPENQASM 2.0;
+/// include \"qelib1.inc\";
+/// qreg q[2];
+/// creg c[2];
+/// h q[0];
+/// cx q[0], q[1];
+/// measure q -> c;
+/// ";
+/// let mut expected = Circuit::new();
+/// expected += DefinitionBit::new("c".to_string(), 2, true);
+/// expected += Hadamard::new(0);
+/// expected += CNOT::new(0, 1);
+/// expected += MeasureQubit::new(0, "c".to_string(), 0);
+/// expected += MeasureQubit::new(1, "c".to_string(), 1);
+/// assert_eq!(qasm_to_circuit(source).unwrap(), expected);
+/// ```
+pub fn qasm_to_circuit(source: &str) -> Result<Circuit, RoqoqoError> {
+    let mut parser = QasmParser::new(tokenize(COMPOSITE_GATES)?);
+    while !parser.at_end() {
+        parser.gate_definition()?;
+    }
+    parser.tokens = tokenize(source)?;
+    parser.position = 0;
+    let mut circuit = Circuit::new();
+    while !parser.at_end() {
+        parser.statement(&mut circuit, false)?;
+    }
+    Ok(circuit)
+}
+
+/// Kinds of tokens of an OpenQASM program.
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// An identifier or keyword.
+    Identifier(String),
+    /// An integer or floating point literal.
+    Number(String),
+    /// A string literal.
+    Text(String),
+    /// An operator or delimiter.
+    Symbol(&'static str),
+}
+
+/// A token of an OpenQASM program with its position.
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    /// The kind and content of the token.
+    kind: TokenKind,
+    /// The line of the token (starting at 1).
+    line: usize,
+    /// The column of the token (starting at 1).
+    column: usize,
+}
+
+/// Operators and delimiters, longer symbols first.
+const SYMBOLS: &[&str] = &[
+    "->", "==", "**", ";", ",", "(", ")", "[", "]", "{", "}", "+", "-", "*", "/", "^", "=",
+];
+
+/// Returns an import error at the given position.
+fn import_error(line: usize, column: usize, msg: String) -> RoqoqoError {
+    RoqoqoError::QasmImportError { line, column, msg }
+}
+
+/// Splits an OpenQASM program into tokens, skipping whitespace and comments.
+fn tokenize(source: &str) -> Result<Vec<Token>, RoqoqoError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let (mut index, mut line, mut column) = (0, 1, 1);
+    // Advances the position by n characters, updating line and column
+    let advance = |index: &mut usize, line: &mut usize, column: &mut usize, n: usize| {
+        for _ in 0..n {
+            if chars[*index] == '\n' {
+                *line += 1;
+                *column = 1;
+            } else {
+                *column += 1;
+            }
+            *index += 1;
+        }
+    };
+    while index < chars.len() {
+        let c = chars[index];
+        let rest: String = chars[index..chars.len().min(index + 2)].iter().collect();
+        let (start_line, start_column) = (line, column);
+        if c.is_whitespace() {
+            advance(&mut index, &mut line, &mut column, 1);
+        } else if rest == "//" {
+            while index < chars.len() && chars[index] != '\n' {
+                advance(&mut index, &mut line, &mut column, 1);
+            }
+        } else if rest == "/*" {
+            let end = (index + 2..chars.len().saturating_sub(1))
+                .find(|&i| chars[i] == '*' && chars[i + 1] == '/')
+                .ok_or_else(|| import_error(line, column, "Unterminated comment".to_string()))?;
+            let n = end + 2 - index;
+            advance(&mut index, &mut line, &mut column, n);
+        } else if c == '"' {
+            let end = (index + 1..chars.len())
+                .find(|&i| chars[i] == '"' || chars[i] == '\n')
+                .filter(|&i| chars[i] == '"')
+                .ok_or_else(|| import_error(line, column, "Unterminated string".to_string()))?;
+            let text: String = chars[index + 1..end].iter().collect();
+            let n = end + 1 - index;
+            advance(&mut index, &mut line, &mut column, n);
+            tokens.push(Token {
+                kind: TokenKind::Text(text),
+                line: start_line,
+                column: start_column,
+            });
+        } else if c.is_ascii_digit()
+            || (c == '.' && matches!(chars.get(index + 1), Some(d) if d.is_ascii_digit()))
+        {
+            let mut end = index;
+            while end < chars.len()
+                && (chars[end].is_ascii_digit()
+                    || chars[end] == '.'
+                    || ((chars[end] == 'e' || chars[end] == 'E')
+                        && end + 1 < chars.len()
+                        && (chars[end + 1].is_ascii_digit()
+                            || chars[end + 1] == '+'
+                            || chars[end + 1] == '-'))
+                    || ((chars[end] == '+' || chars[end] == '-')
+                        && (chars[end - 1] == 'e' || chars[end - 1] == 'E')))
+            {
+                end += 1;
+            }
+            let number: String = chars[index..end].iter().collect();
+            let n = end - index;
+            advance(&mut index, &mut line, &mut column, n);
+            tokens.push(Token {
+                kind: TokenKind::Number(number),
+                line: start_line,
+                column: start_column,
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = index;
+            while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                end += 1;
+            }
+            let identifier: String = chars[index..end].iter().collect();
+            let n = end - index;
+            advance(&mut index, &mut line, &mut column, n);
+            tokens.push(Token {
+                kind: TokenKind::Identifier(identifier),
+                line: start_line,
+                column: start_column,
+            });
+        } else {
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(*symbol))
+                .ok_or_else(|| {
+                    import_error(line, column, format!("Unexpected character '{}'", c))
+                })?;
+            advance(&mut index, &mut line, &mut column, symbol.len());
+            tokens.push(Token {
+                kind: TokenKind::Symbol(symbol),
+                line: start_line,
+                column: start_column,
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+/// A gate defined with a `gate` statement.
+#[derive(Debug, Clone)]
+struct GateDefinition {
+    /// The names of the parameters of the gate.
+    parameters: Vec<String>,
+    /// The names of the qubit arguments of the gate.
+    qubits: Vec<String>,
+    /// The gates applied in the body of the definition.
+    body: Vec<GateCall>,
+}
+
+/// A gate applied in the body of a gate definition.
+#[derive(Debug, Clone)]
+struct GateCall {
+    /// The name token of the gate.
+    name: Token,
+    /// The tokens of the parameter expressions.
+    parameters: Vec<Vec<Token>>,
+    /// The tokens of the qubit arguments.
+    qubits: Vec<Token>,
+}
+
+/// An expression translated into the syntax of the qoqo_calculator.
+#[derive(Debug, Clone)]
+struct Expression {
+    /// The translated expression.
+    text: String,
+    /// True if the expression contains input parameters.
+    symbolic: bool,
+}
+
+/// Parser state of an OpenQASM program.
+struct QasmParser {
+    /// The tokens of the program.
+    tokens: Vec<Token>,
+    /// The index of the next token.
+    position: usize,
+    /// The first qubit and the length of each quantum register.
+    qubit_registers: HashMap<String, (usize, usize)>,
+    /// The total number of declared qubits.
+    number_qubits: usize,
+    /// The length of each classical register.
+    bit_registers: HashMap<String, usize>,
+    /// The composite and user defined gates.
+    gates: HashMap<String, GateDefinition>,
+    /// The names of input parameters.
+    inputs: HashSet<String>,
+}
+
+impl QasmParser {
+    /// Creates a new parser for the given tokens.
+    fn new(tokens: Vec<Token>) -> Self {
+        QasmParser {
+            tokens,
+            position: 0,
+            qubit_registers: HashMap::new(),
+            number_qubits: 0,
+            bit_registers: HashMap::new(),
+            gates: HashMap::new(),
+            inputs: HashSet::new(),
+        }
+    }
+
+    /// Returns true if all tokens have been consumed.
+    fn at_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    /// Returns the next token without consuming it.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    /// Returns an error at the next token or at the end of the program.
+    fn error(&self, msg: String) -> RoqoqoError {
+        match self.peek().or_else(|| self.tokens.last()) {
+            Some(token) => import_error(token.line, token.column, msg),
+            None => import_error(1, 1, msg),
+        }
+    }
+
+    /// Consumes and returns the next token.
+    fn next(&mut self, expected: &str) -> Result<Token, RoqoqoError> {
+        match self.tokens.get(self.position) {
+            Some(token) => {
+                self.position += 1;
+                Ok(token.clone())
+            }
+            None => Err(self.error(format!("Expected {} but reached end of program", expected))),
+        }
+    }
+
+    /// Returns true if the next token is the given symbol.
+    fn peek_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Some(Token { kind: TokenKind::Symbol(s), .. }) if *s == symbol)
+    }
+
+    /// Consumes the next token if it is the given symbol.
+    fn accept_symbol(&mut self, symbol: &str) -> bool {
+        let found = self.peek_symbol(symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    /// Consumes the next token, which has to be the given symbol.
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), RoqoqoError> {
+        if self.accept_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", symbol)))
+        }
+    }
+
+    /// Consumes the next token, which has to be an identifier, and returns the identifier.
+    fn expect_identifier(&mut self) -> Result<String, RoqoqoError> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Identifier(name),
+                ..
+            }) => {
+                let name = name.clone();
+                self.position += 1;
+                Ok(name)
+            }
+            _ => Err(self.unexpected("identifier")),
+        }
+    }
+
+    /// Consumes the next token, which has to be a non-negative integer, and returns the integer.
+    fn expect_integer(&mut self) -> Result<usize, RoqoqoError> {
+        let integer = match self.peek() {
+            Some(Token {
+                kind: TokenKind::Number(number),
+                ..
+            }) => number.parse::<usize>().ok(),
+            _ => None,
+        };
+        match integer {
+            Some(integer) => {
+                self.position += 1;
+                Ok(integer)
+            }
+            None => Err(self.unexpected("integer")),
+        }
+    }
+
+    /// Returns an error for an unexpected next token.
+    fn unexpected(&self, expected: &str) -> RoqoqoError {
+        match self.peek() {
+            Some(token) => self.error(format!(
+                "Expected {} but found {}",
+                expected,
+                describe(&token.kind)
+            )),
+            None => self.error(format!("Expected {} but reached end of program", expected)),
+        }
+    }
+
+    /// Parses a statement, adding the resulting operations to the circuit.
+    fn statement(&mut self, circuit: &mut Circuit, nested: bool) -> Result<(), RoqoqoError> {
+        let token = self.next("statement")?;
+        let keyword = match &token.kind {
+            TokenKind::Identifier(keyword) => keyword.clone(),
+            TokenKind::Symbol("{") if nested => {
+                while !self.accept_symbol("}") {
+                    self.statement(circuit, true)?;
+                }
+                return Ok(());
+            }
+            kind => {
+                self.position -= 1;
+                return Err(self.error(format!("Expected statement but found {}", describe(kind))));
+            }
+        };
+        let declaration = matches!(
+            keyword.as_str(),
+            "OPENQASM" | "include" | "qreg" | "creg" | "qubit" | "bit" | "input" | "gate"
+        );
+        if nested && declaration {
+            self.position -= 1;
+            return Err(self.error(format!(
+                "'{}' is not allowed inside an if statement",
+                keyword
+            )));
+        }
+        match keyword.as_str() {
+            "OPENQASM" => {
+                let version = self.next("version")?;
+                match &version.kind {
+                    TokenKind::Number(number)
+                        if number.starts_with('2') || number.starts_with('3') => {}
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.error("Unsupported OpenQASM version".to_string()));
+                    }
+                }
+                self.expect_symbol(";")
+            }
+            "include" => {
+                match self.peek().map(|t| &t.kind) {
+                    Some(TokenKind::Text(file))
+                        if file == "qelib1.inc" || file == "stdgates.inc" =>
+                    {
+                        self.position += 1;
+                    }
+                    Some(TokenKind::Text(file)) => {
+                        return Err(self.error(format!("Unsupported include file \"{}\"", file)))
+                    }
+                    _ => return Err(self.unexpected("file name")),
+                }
+                self.expect_symbol(";")
+            }
+            "qreg" | "creg" => {
+                let name = self.expect_identifier()?;
+                self.expect_symbol("[")?;
+                let length = self.expect_integer()?;
+                self.expect_symbol("]")?;
+                self.expect_symbol(";")?;
+                self.declare(&token, name, length, keyword == "qreg", circuit)
+            }
+            "qubit" | "bit" => {
+                let length = if self.accept_symbol("[") {
+                    let length = self.expect_integer()?;
+                    self.expect_symbol("]")?;
+                    length
+                } else {
+                    1
+                };
+                let name = self.expect_identifier()?;
+                self.expect_symbol(";")?;
+                self.declare(&token, name, length, keyword == "qubit", circuit)
+            }
+            "input" => {
+                self.expect_identifier()?;
+                if self.accept_symbol("[") {
+                    self.expect_integer()?;
+                    self.expect_symbol("]")?;
+                }
+                let name = self.expect_identifier()?;
+                self.expect_symbol(";")?;
+                self.inputs.insert(name);
+                Ok(())
+            }
+            "gate" => {
+                self.position -= 1;
+                self.gate_definition()
+            }
+            "measure" => {
+                let qubits = self.qubit_argument()?;
+                self.expect_symbol("->")?;
+                let bits = self.bit_argument()?;
+                self.expect_symbol(";")?;
+                self.measure(&token, qubits, bits, circuit)
+            }
+            "reset" => {
+                let qubits = self.qubit_argument()?;
+                self.expect_symbol(";")?;
+                for qubit in qubits {
+                    circuit.add_operation(PragmaActiveReset::new(qubit));
+                }
+                Ok(())
+            }
+            "barrier" => {
+                let mut qubits: Vec<usize> = Vec::new();
+                loop {
+                    qubits.extend(self.qubit_argument()?);
+                    if !self.accept_symbol(",") {
+                        break;
+                    }
+                }
+                self.expect_symbol(";")?;
+                circuit.add_operation(PragmaStopParallelBlock::new(qubits, 0.0.into()));
+                Ok(())
+            }
+            "if" => {
+                self.expect_symbol("(")?;
+                let (register, index) = self.condition()?;
+                self.expect_symbol(")")?;
+                let mut body = Circuit::new();
+                self.statement(&mut body, true)?;
+                circuit.add_operation(PragmaConditional::new(register, index, body));
+                Ok(())
+            }
+            "opaque" => {
+                self.position -= 1;
+                Err(self.error("Opaque gates are not supported".to_string()))
+            }
+            _ if self.peek_symbol("=") || self.peek_symbol("[") => {
+                self.position -= 1;
+                let bits = self.bit_argument()?;
+                self.expect_symbol("=")?;
+                match self.next("measure")?.kind {
+                    TokenKind::Identifier(measure) if measure == "measure" => {}
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.error("Only measurements can be assigned".to_string()));
+                    }
+                }
+                let qubits = self.qubit_argument()?;
+                self.expect_symbol(";")?;
+                self.measure(&token, qubits, bits, circuit)
+            }
+            _ => self.gate_statement(&token, keyword, circuit),
+        }
+    }
+
+    /// Declares a quantum or classical register.
+    fn declare(
+        &mut self,
+        token: &Token,
+        name: String,
+        length: usize,
+        quantum: bool,
+        circuit: &mut Circuit,
+    ) -> Result<(), RoqoqoError> {
+        if self.qubit_registers.contains_key(&name) || self.bit_registers.contains_key(&name) {
+            return Err(import_error(
+                token.line,
+                token.column,
+                format!("Register {} is already declared", name),
+            ));
+        }
+        if quantum {
+            self.qubit_registers
+                .insert(name, (self.number_qubits, length));
+            self.number_qubits += length;
+        } else {
+            self.bit_registers.insert(name.clone(), length);
+            circuit.add_operation(DefinitionBit::new(name, length, true));
+        }
+        Ok(())
+    }
+
+    /// Parses a qubit argument, returning the qubits of a whole register or of a single element.
+    fn qubit_argument(&mut self) -> Result<Vec<usize>, RoqoqoError> {
+        let start = self.position;
+        let name = self.expect_identifier()?;
+        let (offset, length) = match self.qubit_registers.get(&name) {
+            Some(register) => *register,
+            None => {
+                self.position = start;
+                return Err(self.error(format!("Unknown quantum register {}", name)));
+            }
+        };
+        match self.element_index(length)? {
+            Some(index) => Ok(vec![offset + index]),
+            None => Ok((offset..offset + length).collect()),
+        }
+    }
+
+    /// Parses a bit argument, returning the register and the indices of the whole register or of a single element.
+    fn bit_argument(&mut self) -> Result<(String, Vec<usize>), RoqoqoError> {
+        let start = self.position;
+        let name = self.expect_identifier()?;
+        let length = match self.bit_registers.get(&name) {
+            Some(length) => *length,
+            None => {
+                self.position = start;
+                return Err(self.error(format!("Unknown classical register {}", name)));
+            }
+        };
+        match self.element_index(length)? {
+            Some(index) => Ok((name, vec![index])),
+            None => Ok((name, (0..length).collect())),
+        }
+    }
+
+    /// Parses an optional element index of a register with the given length.
+    fn element_index(&mut self, length: usize) -> Result<Option<usize>, RoqoqoError> {
+        if !self.accept_symbol("[") {
+            return Ok(None);
+        }
+        let index = self.expect_integer()?;
+        if index >= length {
+            self.position -= 1;
+            return Err(self.error(format!(
+                "Index {} is out of range for register of length {}",
+                index, length
+            )));
+        }
+        self.expect_symbol("]")?;
+        Ok(Some(index))
+    }
+
+    /// Parses the condition of an if statement, returning the register and the index of the bit.
+    fn condition(&mut self) -> Result<(String, usize), RoqoqoError> {
+        let start = self.position;
+        let (register, indices) = self.bit_argument()?;
+        let value = if self.accept_symbol("==") {
+            Some(self.expect_integer()?)
+        } else {
+            None
+        };
+        match (indices.as_slice(), value) {
+            ([index], None) | ([index], Some(1)) => Ok((register, *index)),
+            _ => {
+                self.position = start;
+                Err(self
+                    .error("Only conditions on a single bit being one are supported".to_string()))
+            }
+        }
+    }
+
+    /// Adds the measurements of qubits into bits, broadcasting over registers.
+    fn measure(
+        &mut self,
+        token: &Token,
+        qubits: Vec<usize>,
+        bits: (String, Vec<usize>),
+        circuit: &mut Circuit,
+    ) -> Result<(), RoqoqoError> {
+        let (register, indices) = bits;
+        if qubits.len() != indices.len() {
+            return Err(import_error(
+                token.line,
+                token.column,
+                format!(
+                    "Measurement of {} qubits into {} bits",
+                    qubits.len(),
+                    indices.len()
+                ),
+            ));
+        }
+        for (qubit, index) in qubits.into_iter().zip(indices) {
+            circuit.add_operation(MeasureQubit::new(qubit, register.clone(), index));
+        }
+        Ok(())
+    }
+
+    /// Parses a gate definition.
+    fn gate_definition(&mut self) -> Result<(), RoqoqoError> {
+        match self.next("gate")?.kind {
+            TokenKind::Identifier(gate) if gate == "gate" => {}
+            _ => {
+                self.position -= 1;
+                return Err(self.unexpected("gate definition"));
+            }
+        }
+        let name = self.expect_identifier()?;
+        let mut parameters: Vec<String> = Vec::new();
+        if self.accept_symbol("(") && !self.accept_symbol(")") {
+            loop {
+                parameters.push(self.expect_identifier()?);
+                if !self.accept_symbol(",") {
+                    break;
+                }
+            }
+            self.expect_symbol(")")?;
+        }
+        let mut qubits: Vec<String> = Vec::new();
+        loop {
+            qubits.push(self.expect_identifier()?);
+            if !self.accept_symbol(",") {
+                break;
+            }
+        }
+        self.expect_symbol("{")?;
+        let mut body: Vec<GateCall> = Vec::new();
+        while !self.accept_symbol("}") {
+            let token = self.next("gate")?;
+            let gate = match &token.kind {
+                TokenKind::Identifier(gate) => gate.clone(),
+                kind => {
+                    self.position -= 1;
+                    return Err(self.error(format!("Expected gate but found {}", describe(kind))));
+                }
+            };
+            let call_parameters = self.parameter_tokens()?;
+            let mut call_qubits: Vec<Token> = Vec::new();
+            loop {
+                let qubit = self.next("qubit")?;
+                match &qubit.kind {
+                    TokenKind::Identifier(q) if qubits.contains(q) => call_qubits.push(qubit),
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.error("Expected qubit argument of the gate".to_string()));
+                    }
+                }
+                if !self.accept_symbol(",") {
+                    break;
+                }
+            }
+            self.expect_symbol(";")?;
+            if gate != "barrier" {
+                body.push(GateCall {
+                    name: token,
+                    parameters: call_parameters,
+                    qubits: call_qubits,
+                });
+            }
+        }
+        self.gates.insert(
+            name,
+            GateDefinition {
+                parameters,
+                qubits,
+                body,
+            },
+        );
+        Ok(())
+    }
+
+    /// Parses the optional parameter list of a gate, returning the tokens of each expression.
+    fn parameter_tokens(&mut self) -> Result<Vec<Vec<Token>>, RoqoqoError> {
+        let mut parameters: Vec<Vec<Token>> = Vec::new();
+        if !self.accept_symbol("(") || self.accept_symbol(")") {
+            return Ok(parameters);
+        }
+        let mut expression: Vec<Token> = Vec::new();
+        let mut depth = 0;
+        loop {
+            let token = self.next("')'")?;
+            match token.kind {
+                TokenKind::Symbol("(") => depth += 1,
+                TokenKind::Symbol(")") if depth == 0 => {
+                    parameters.push(expression);
+                    return Ok(parameters);
+                }
+                TokenKind::Symbol(")") => depth -= 1,
+                TokenKind::Symbol(",") if depth == 0 => {
+                    parameters.push(std::mem::take(&mut expression));
+                    continue;
+                }
+                TokenKind::Symbol(";") => {
+                    self.position -= 1;
+                    return Err(self.unexpected("')'"));
+                }
+                _ => {}
+            }
+            expression.push(token);
+        }
+    }
+
+    /// Parses a gate statement, broadcasting the gate over register arguments.
+    fn gate_statement(
+        &mut self,
+        token: &Token,
+        name: String,
+        circuit: &mut Circuit,
+    ) -> Result<(), RoqoqoError> {
+        let parameters = self
+            .parameter_tokens()?
+            .iter()
+            .map(|tokens| self.expression(tokens, &HashMap::new(), token))
+            .collect::<Result<Vec<Expression>, RoqoqoError>>()?;
+        let mut arguments: Vec<Vec<usize>> = Vec::new();
+        if !self.peek_symbol(";") {
+            loop {
+                arguments.push(self.qubit_argument()?);
+                if !self.accept_symbol(",") {
+                    break;
+                }
+            }
+        }
+        self.expect_symbol(";")?;
+        let broadcast = arguments
+            .iter()
+            .map(|argument| argument.len())
+            .filter(|length| *length > 1)
+            .max()
+            .unwrap_or(1);
+        if arguments
+            .iter()
+            .any(|argument| argument.len() != 1 && argument.len() != broadcast)
+        {
+            return Err(import_error(
+                token.line,
+                token.column,
+                "Registers of different lengths in gate arguments".to_string(),
+            ));
+        }
+        for index in 0..broadcast {
+            let qubits: Vec<usize> = arguments
+                .iter()
+                .map(|argument| argument[if argument.len() == 1 { 0 } else { index }])
+                .collect();
+            self.apply_gate(token, &name, &parameters, &qubits, circuit, 0)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a gate to the circuit, inlining composite and user defined gates.
+    fn apply_gate(
+        &self,
+        token: &Token,
+        name: &str,
+        parameters: &[Expression],
+        qubits: &[usize],
+        circuit: &mut Circuit,
+        depth: usize,
+    ) -> Result<(), RoqoqoError> {
+        let error = |msg: String| import_error(token.line, token.column, msg);
+        if depth > 100 {
+            return Err(error(format!("Recursive definition of gate {}", name)));
+        }
+        if qubits
+            .iter()
+            .enumerate()
+            .any(|(i, qubit)| qubits[..i].contains(qubit))
+        {
+            return Err(error(format!("Repeated qubit argument of gate {}", name)));
+        }
+        if let Some(definition) = self.gates.get(name) {
+            if definition.parameters.len() != parameters.len()
+                || definition.qubits.len() != qubits.len()
+            {
+                return Err(error(format!(
+                    "Gate {} takes {} parameters and {} qubits",
+                    name,
+                    definition.parameters.len(),
+                    definition.qubits.len()
+                )));
+            }
+            let bindings: HashMap<String, Expression> = definition
+                .parameters
+                .iter()
+                .cloned()
+                .zip(parameters.iter().cloned())
+                .collect();
+            let qubit_bindings: HashMap<&String, usize> = definition
+                .qubits
+                .iter()
+                .zip(qubits.iter().copied())
+                .collect();
+            for call in definition.body.iter() {
+                let call_parameters = call
+                    .parameters
+                    .iter()
+                    .map(|tokens| self.expression(tokens, &bindings, &call.name))
+                    .collect::<Result<Vec<Expression>, RoqoqoError>>()?;
+                let call_qubits: Vec<usize> = call
+                    .qubits
+                    .iter()
+                    .map(|qubit| match &qubit.kind {
+                        TokenKind::Identifier(q) => qubit_bindings[q],
+                        _ => unreachable!(),
+                    })
+                    .collect();
+                let call_name = match &call.name.kind {
+                    TokenKind::Identifier(call_name) => call_name.as_str(),
+                    _ => unreachable!(),
+                };
+                self.apply_gate(
+                    token,
+                    call_name,
+                    &call_parameters,
+                    &call_qubits,
+                    circuit,
+                    depth + 1,
+                )?;
+            }
+            return Ok(());
+        }
+        let (number_parameters, number_qubits) = match name {
+            "U" | "u3" | "u" => (3, 1),
+            "u1" | "p" | "phase" | "rx" | "ry" | "rz" | "u0" => (1, 1),
+            "id" | "x" | "y" | "z" | "h" | "s" | "t" | "sx" | "sxdg" => (0, 1),
+            "CX" | "cx" | "cnot" | "cy" | "cz" | "swap" | "iswap" => (0, 2),
+            "cu1" | "cp" | "cphase" => (1, 2),
+            "gphase" => (1, 0),
+            _ => return Err(error(format!("Unknown gate {}", name))),
+        };
+        if parameters.len() != number_parameters || qubits.len() != number_qubits {
+            return Err(error(format!(
+                "Gate {} takes {} parameters and {} qubits",
+                name, number_parameters, number_qubits
+            )));
+        }
+        let parameters = parameters
+            .iter()
+            .map(|expression| evaluate(expression, token))
+            .collect::<Result<Vec<CalculatorFloat>, RoqoqoError>>()?;
+        match name {
+            "U" | "u3" | "u" => {
+                circuit.add_operation(RotateZ::new(qubits[0], parameters[2].clone()));
+                circuit.add_operation(RotateY::new(qubits[0], parameters[0].clone()));
+                circuit.add_operation(RotateZ::new(qubits[0], parameters[1].clone()));
+            }
+            "u1" | "p" | "phase" | "rz" => {
+                circuit.add_operation(RotateZ::new(qubits[0], parameters[0].clone()))
+            }
+            "rx" => circuit.add_operation(RotateX::new(qubits[0], parameters[0].clone())),
+            "ry" => circuit.add_operation(RotateY::new(qubits[0], parameters[0].clone())),
+            "id" | "u0" => {}
+            "x" => circuit.add_operation(PauliX::new(qubits[0])),
+            "y" => circuit.add_operation(PauliY::new(qubits[0])),
+            "z" => circuit.add_operation(PauliZ::new(qubits[0])),
+            "h" => circuit.add_operation(Hadamard::new(qubits[0])),
+            "s" => circuit.add_operation(SGate::new(qubits[0])),
+            "t" => circuit.add_operation(TGate::new(qubits[0])),
+            "sx" => circuit.add_operation(SqrtPauliX::new(qubits[0])),
+            "sxdg" => circuit.add_operation(InvSqrtPauliX::new(qubits[0])),
+            "CX" | "cx" | "cnot" => circuit.add_operation(CNOT::new(qubits[0], qubits[1])),
+            "cy" => circuit.add_operation(ControlledPauliY::new(qubits[0], qubits[1])),
+            "cz" => circuit.add_operation(ControlledPauliZ::new(qubits[0], qubits[1])),
+            "swap" => circuit.add_operation(SWAP::new(qubits[0], qubits[1])),
+            "iswap" => circuit.add_operation(ISwap::new(qubits[0], qubits[1])),
+            "cu1" | "cp" | "cphase" => circuit.add_operation(ControlledPhaseShift::new(
+                qubits[0],
+                qubits[1],
+                parameters[0].clone(),
+            )),
+            "gphase" => circuit.add_operation(PragmaGlobalPhase::new(parameters[0].clone())),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Translates the tokens of an expression into the syntax of the qoqo_calculator.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokens` - The tokens of the expression.
+    /// * `bindings` - The expressions of the parameters of the enclosing gate definition.
+    /// * `token` - The token the error is reported at when the expression is empty.
+    fn expression(
+        &self,
+        tokens: &[Token],
+        bindings: &HashMap<String, Expression>,
+        token: &Token,
+    ) -> Result<Expression, RoqoqoError> {
+        if tokens.is_empty() {
+            return Err(import_error(
+                token.line,
+                token.column,
+                "Empty parameter expression".to_string(),
+            ));
+        }
+        let mut text = String::new();
+        let mut symbolic = false;
+        for (index, token) in tokens.iter().enumerate() {
+            let function_call = matches!(
+                tokens.get(index + 1),
+                Some(Token {
+                    kind: TokenKind::Symbol("("),
+                    ..
+                })
+            );
+            match &token.kind {
+                TokenKind::Number(number) => text.push_str(number),
+                TokenKind::Symbol(symbol) => text.push_str(symbol),
+                TokenKind::Identifier(name) if function_call => {
+                    text.push_str(match name.as_str() {
+                        "sin" | "cos" | "tan" | "exp" | "sqrt" => name.as_str(),
+                        "ln" => "log",
+                        "arcsin" => "asin",
+                        "arccos" => "acos",
+                        "arctan" => "atan",
+                        _ => {
+                            return Err(import_error(
+                                token.line,
+                                token.column,
+                                format!("Unknown function {}", name),
+                            ))
+                        }
+                    })
+                }
+                TokenKind::Identifier(name) => match name.as_str() {
+                    "pi" | "π" => text.push_str(&format!("{:?}", std::f64::consts::PI)),
+                    "tau" | "τ" => text.push_str(&format!("{:?}", std::f64::consts::TAU)),
+                    "euler" | "ℯ" => text.push_str(&format!("{:?}", std::f64::consts::E)),
+                    _ => match bindings.get(name) {
+                        Some(binding) => {
+                            text.push_str(&format!("({})", binding.text));
+                            symbolic |= binding.symbolic;
+                        }
+                        None if self.inputs.contains(name) => {
+                            text.push_str(name);
+                            symbolic = true;
+                        }
+                        None => {
+                            return Err(import_error(
+                                token.line,
+                                token.column,
+                                format!("Unknown parameter {}", name),
+                            ))
+                        }
+                    },
+                },
+                TokenKind::Text(_) => {
+                    return Err(import_error(
+                        token.line,
+                        token.column,
+                        "Unexpected string in parameter expression".to_string(),
+                    ))
+                }
+            }
+            text.push(' ');
+        }
+        Ok(Expression {
+            text: text.trim_end().to_string(),
+            symbolic,
+        })
+    }
+}
+
+/// Evaluates an expression without input parameters to a float.
+fn evaluate(expression: &Expression, token: &Token) -> Result<CalculatorFloat, RoqoqoError> {
+    if expression.symbolic {
+        return Ok(CalculatorFloat::Str(expression.text.clone()));
+    }
+    Calculator::new()
+        .parse_str(&expression.text)
+        .map(CalculatorFloat::Float)
+        .map_err(|error| {
+            import_error(
+                token.line,
+                token.column,
+                format!(
+                    "Invalid parameter expression {}: {}",
+                    expression.text, error
+                ),
+            )
+        })
+}
+
+/// Returns a description of a token for error messages.
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Identifier(name) => format!("'{}'", name),
+        TokenKind::Number(number) => format!("'{}'", number),
+        TokenKind::Text(text) => format!("\"{}\"", text),
+        TokenKind::Symbol(symbol) => format!("'{}'", symbol),
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::drawing::{
+    decimal, gate_runs, layout, pi_fraction, short_symbol, DrawingOptions, Element, GateLabels,
+    Symbol, GREEK_LETTERS,
+};
+use crate::Circuit;
+use qoqo_calculator::CalculatorFloat;
+
+/// Draws a circuit as LaTeX source using the quantikz package.
+///
+/// The returned `quantikz` environment can be included in a document loading quantikz
+/// with `\usepackage{tikz}` and `\usetikzlibrary{quantikz}`.
+/// Every qubit is drawn as a quantum wire and every classical register as a classical wire below the qubits.
+/// Operations are placed in columns as early as possible.
+/// Decomposition blocks started by `PragmaStartDecompositionBlock` and stopped by
+/// `PragmaStopDecompositionBlock` are drawn as dashed boxes around the operations of the block.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to draw.
+/// * `options` - The labels of the gates and the operations shown.
+///
+/// # Returns
+///
+/// * `String` - The LaTeX source of the drawing.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::formats::{circuit_to_quantikz, DrawingOptions};
+/// use roqoqo::operations::{CNOT, Hadamard};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += Hadamard::new(0);
+/// circuit += CNOT::new(0, 1);
+///
+/// let latex = circuit_to_quantikz(&circuit, &DrawingOptions::default());
+/// assert_eq!(
+///     latex,
+///     "\\begin{quantikz}\n\
+///      \\lstick{q_{0}} & \\gate{H} & \\control{} \\vqw{1} & \\qw \\\\\n\
+///      \\lstick{q_{1}} & \\qw & \\targ{} & \\qw\n\
+///      \\end{quantikz}\n"
+/// );
+/// ```
+pub fn circuit_to_quantikz(circuit: &Circuit, options: &DrawingOptions) -> String {
+    let layout = layout(circuit, options);
+    let number_rows = layout.number_rows();
+    let mut cells: Vec<Vec<Vec<String>>> =
+        vec![vec![Vec::new(); layout.columns.len()]; number_rows];
+    for (column, elements) in layout.columns.iter().enumerate() {
+        for element in elements {
+            add_element(&mut cells, column, element, options);
+        }
+    }
+    for group in layout.groups.iter() {
+        cells[group.first_row][group.first_column].push(format!(
+            "\\gategroup[wires={},steps={},style={{dashed,rounded corners}}]{{}}",
+            group.last_row - group.first_row + 1,
+            group.last_column - group.first_column + 1
+        ));
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for (row, name) in layout.row_names().iter().enumerate() {
+        let wire = if row < layout.number_qubits {
+            "\\qw"
+        } else {
+            "\\cw"
+        };
+        let mut line = format!("\\lstick{{{}}}", row_name(name, row < layout.number_qubits));
+        for cell in cells[row].iter() {
+            line.push_str(" & ");
+            if cell.is_empty() {
+                line.push_str(wire);
+            } else {
+                line.push_str(&cell.join(" "));
+            }
+        }
+        line.push_str(" & ");
+        line.push_str(wire);
+        lines.push(line);
+    }
+    let mut latex = "\\begin{quantikz}\n".to_string();
+    latex.push_str(&lines.join(" \\\\\n"));
+    if !lines.is_empty() {
+        latex.push('\n');
+    }
+    latex.push_str("\\end{quantikz}\n");
+    latex
+}
+
+/// Adds the commands of an element to the cells of its column.
+fn add_element(
+    cells: &mut [Vec<Vec<String>>],
+    column: usize,
+    element: &Element,
+    options: &DrawingOptions,
+) {
+    let runs = gate_runs(element);
+    for (row, symbol) in element.symbols.iter() {
+        let command = match symbol {
+            Symbol::Gate {
+                hqslang,
+                parameters,
+                noise,
+            } => match runs
+                .iter()
+                .find(|(first, last)| first <= row && row <= last)
+            {
+                Some((first, last)) if first == row => {
+                    let mut settings: Vec<String> = Vec::new();
+                    if last > first {
+                        settings.push(format!("wires={}", last - first + 1));
+                    }
+                    if *noise {
+                        settings.push("style={dashed}".to_string());
+                    }
+                    let settings = if settings.is_empty() {
+                        String::new()
+                    } else {
+                        format!("[{}]", settings.join(","))
+                    };
+                    format!(
+                        "\\gate{}{{{}}}",
+                        settings,
+                        label(hqslang, parameters, options)
+                    )
+                }
+                _ => "\\qw".to_string(),
+            },
+            Symbol::Control => "\\control{}".to_string(),
+            Symbol::Target => "\\targ{}".to_string(),
+            Symbol::Swap => "\\targX{}".to_string(),
+            Symbol::Meter => "\\meter{}".to_string(),
+            Symbol::Readout(Some(index)) => format!("\\push{{{}}} \\cw", index),
+            Symbol::Readout(None) => "\\control{} \\cw".to_string(),
+            Symbol::Condition(index) => format!("\\push{{{}}} \\cw", index),
+        };
+        cells[*row][column].push(command);
+    }
+    let (first, last) = element.span();
+    let single_box = runs.len() == 1 && runs[0] == (first, last);
+    if last > first && !single_box {
+        let connector = if element.classical { "\\vcw" } else { "\\vqw" };
+        cells[first][column].push(format!("{}{{{}}}", connector, last - first));
+    }
+}
+
+/// Returns the LaTeX math label of a gate.
+fn label(hqslang: &str, parameters: &[CalculatorFloat], options: &DrawingOptions) -> String {
+    let mut label = match (options.gate_labels, short_symbol(hqslang)) {
+        (GateLabels::Symbol, Some((symbol, _))) => symbol.to_string(),
+        _ => format!("\\mathrm{{{}}}", hqslang),
+    };
+    if !parameters.is_empty() {
+        let texts: Vec<String> = parameters.iter().map(parameter).collect();
+        label.push_str(&format!("({})", texts.join(", ")));
+    }
+    label
+}
+
+/// Returns the LaTeX math of a parameter.
+fn parameter(value: &CalculatorFloat) -> String {
+    match value {
+        CalculatorFloat::Float(value) => number(*value),
+        CalculatorFloat::Str(expression) => expression_to_latex(expression),
+    }
+}
+
+/// Returns a number as a multiple of pi with a small denominator or with three decimals.
+fn number(value: f64) -> String {
+    match pi_fraction(value) {
+        Some((numerator, denominator)) => {
+            let sign = if numerator < 0 { "-" } else { "" };
+            let factor = match numerator.abs() {
+                1 => "\\pi".to_string(),
+                n => format!("{}\\pi", n),
+            };
+            match denominator {
+                1 => format!("{}{}", sign, factor),
+                _ => format!("{}{}/{}", sign, factor, denominator),
+            }
+        }
+        None => decimal(value),
+    }
+}
+
+/// Returns a symbolic expression as LaTeX math.
+///
+/// Greek letter names become the LaTeX commands, other names with several characters are set upright.
+fn expression_to_latex(expression: &str) -> String {
+    let mut latex = String::new();
+    let mut word = String::new();
+    for c in expression.chars().chain(std::iter::once(' ')) {
+        if c.is_alphanumeric() || c == '_' || c == '.' {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            let is_name = word.starts_with(|c: char| c.is_alphabetic() || c == '_');
+            if GREEK_LETTERS.iter().any(|(name, _)| *name == word) {
+                latex.push_str(&format!("\\{} ", word));
+            } else if is_name && word.chars().count() > 1 {
+                latex.push_str(&format!("\\mathrm{{{}}}", escape(&word)));
+            } else {
+                latex.push_str(&escape(&word));
+            }
+            word.clear();
+        }
+        match c {
+            '*' => latex.push_str("\\cdot "),
+            c if c.is_whitespace() => (),
+            c => latex.push(c),
+        }
+    }
+    latex.trim_end().to_string()
+}
+
+/// Returns the LaTeX math of the name of a row.
+fn row_name(name: &str, qubit: bool) -> String {
+    if qubit {
+        format!("q_{{{}}}", name.trim_start_matches('q'))
+    } else {
+        format!("\\mathrm{{{}}}", escape(name))
+    }
+}
+
+/// Escapes the characters with a special meaning in LaTeX.
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '_' | '%' | '&' | '#' | '$' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\\' => escaped.push_str("\\backslash "),
+            '^' => escaped.push_str("\\hat{}"),
+            '~' => escaped.push_str("\\sim "),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,1264 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::qasm_export::number_qubits;
+use super::{canonical_gate, CanonicalGate};
+use crate::operations::*;
+use crate::{Circuit, RoqoqoError};
+use ndarray::Array2;
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorFloat;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::f64::consts::FRAC_PI_4;
+
+/// Tolerance for the unitarity of DEFGATE matrices and for real gate parameters.
+const IMPORT_TOLERANCE: f64 = 1e-8;
+
+/// Names of the standard gates of Quil that must not be redefined.
+const STANDARD_GATE_NAMES: &[&str] = &[
+    "I", "X", "Y", "Z", "H", "S", "T", "RX", "RY", "RZ", "PHASE", "CNOT", "CZ", "SWAP", "ISWAP",
+    "CPHASE", "CPHASE00", "CPHASE01", "CPHASE10", "XY", "PSWAP", "CCNOT", "CSWAP",
+];
+
+/// Exports a circuit to Quil.
+///
+/// * Gates with a counterpart among the standard gates of Quil are written directly,
+///   all other single- and two-qubit gates are written as `DEFGATE` definitions with the unitary matrix of the gate.
+/// * [DefinitionBit], [DefinitionFloat] and [DefinitionUsize] are translated into `DECLARE` with the types `BIT`, `REAL` and `INTEGER`.
+/// * [MeasureQubit] and [PragmaRepeatedMeasurement] are translated into `MEASURE`.
+/// * [PragmaActiveReset] is translated into `RESET`.
+/// * [PragmaConditional] is translated into a `JUMP-UNLESS` over the conditional instructions.
+/// * [PragmaConditionalPattern] is translated into a `JUMP-UNLESS` or `JUMP-WHEN` for every condition bit.
+/// * [PragmaLoop] is unrolled when the number of repetitions is a constant.
+/// * [PragmaRepeatUntilSuccess] is unrolled into nested conditionals, one for each iteration after the first.
+/// * Classical operations are translated into the classical instructions `MOVE`, `NOT`, `AND`, `IOR`, `XOR`, `GT` and `LT`.
+///
+/// Symbolic parameters cannot be exported and have to be substituted beforehand.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to export.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The Quil program.
+/// * `Err(RoqoqoError::QuilExportError)` - The circuit contains symbolic parameters or an operation that cannot be represented in Quil.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::formats::circuit_to_quil;
+/// use roqoqo::operations::{CNOT, DefinitionBit, Hadamard, MeasureQubit};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += DefinitionBit::new("ro".to_string(), 1, true);
+/// circuit += Hadamard::new(0);
+/// circuit += CNOT::new(0, 1);
+/// circuit += MeasureQubit::new(1, "ro".to_string(), 0);
+///
+/// let quil = circuit_to_quil(&circuit).unwrap();
+/// assert_eq!(quil, "DECLARE ro BIT[1]\nH 0\nCNOT 0 1\nMEASURE 1 ro[0]\n");
+/// ```
+pub fn circuit_to_quil(circuit: &Circuit) -> Result<String, RoqoqoError> {
+    let mut writer = QuilWriter {
+        number_qubits: number_qubits(circuit),
+        bit_registers: HashMap::new(),
+        gate_definitions: Vec::new(),
+        gate_names: HashMap::new(),
+        gate_name_counts: HashMap::new(),
+        number_labels: 0,
+    };
+    let mut instructions: Vec<String> = Vec::new();
+    for operation in circuit.iter() {
+        instructions.extend(writer.instructions(operation)?);
+    }
+    let mut quil = String::new();
+    for definition in writer.gate_definitions.iter() {
+        quil.push_str(definition);
+    }
+    for instruction in instructions {
+        quil.push_str(&instruction);
+        quil.push('\n');
+    }
+    Ok(quil)
+}
+
+/// Returns the Quil representation of a parameter.
+fn parameter(value: &CalculatorFloat) -> Result<String, RoqoqoError> {
+    match value {
+        CalculatorFloat::Float(x) => Ok(format!("{}", x)),
+        CalculatorFloat::Str(expression) => Err(RoqoqoError::QuilExportError {
+            msg: format!(
+                "Symbolic parameter {} can not be exported, substitute parameters first",
+                expression
+            ),
+        }),
+    }
+}
+
+/// Returns the Quil instruction of a gate with a counterpart among the standard gates.
+fn standard_gate(operation: &Operation) -> Result<Option<String>, RoqoqoError> {
+    Ok(Some(match operation {
+        Operation::PauliX(op) => format!("X {}", op.qubit()),
+        Operation::PauliY(op) => format!("Y {}", op.qubit()),
+        Operation::PauliZ(op) => format!("Z {}", op.qubit()),
+        Operation::Hadamard(op) => format!("H {}", op.qubit()),
+        Operation::SGate(op) => format!("S {}", op.qubit()),
+        Operation::TGate(op) => format!("T {}", op.qubit()),
+        Operation::RotateX(op) => format!("RX({}) {}", parameter(op.theta())?, op.qubit()),
+        Operation::RotateY(op) => format!("RY({}) {}", parameter(op.theta())?, op.qubit()),
+        Operation::RotateZ(op) => format!("RZ({}) {}", parameter(op.theta())?, op.qubit()),
+        Operation::CNOT(op) => format!("CNOT {} {}", op.control(), op.target()),
+        Operation::ControlledPauliZ(op) => format!("CZ {} {}", op.control(), op.target()),
+        Operation::SWAP(op) => format!("SWAP {} {}", op.control(), op.target()),
+        Operation::ISwap(op) => format!("ISWAP {} {}", op.control(), op.target()),
+        Operation::ControlledPhaseShift(op) => format!(
+            "CPHASE({}) {} {}",
+            parameter(op.theta())?,
+            op.control(),
+            op.target()
+        ),
+        Operation::XY(op) => format!(
+            "XY({}) {} {}",
+            parameter(op.theta())?,
+            op.control(),
+            op.target()
+        ),
+        Operation::Toffoli(op) => match op.qubits().as_slice() {
+            [control_0, control_1, target] => {
+                format!("CCNOT {} {} {}", control_0, control_1, target)
+            }
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    }))
+}
+
+/// Returns the Quil literal of a real number compared with a REAL memory region, always with a decimal point.
+fn real_literal(value: &CalculatorFloat) -> Result<String, RoqoqoError> {
+    match value {
+        CalculatorFloat::Float(x) => Ok(format!("{:?}", x)),
+        CalculatorFloat::Str(_) => parameter(value),
+    }
+}
+
+/// Returns the instructions writing a logical operation of bits in a register to an output bit.
+///
+/// The output bit is initialized with the first input bit, or with the neutral value of the operation
+/// when there are no input bits, and combined with the remaining input bits by the in-place Quil instruction.
+/// When the output bit is also an input bit it is used in place to avoid overwriting it.
+fn logic_instructions(
+    instruction: &str,
+    neutral: u8,
+    register: &str,
+    indices: &[usize],
+    output_register: &str,
+    output_index: usize,
+) -> Vec<String> {
+    let output = format!("{}[{}]", output_register, output_index);
+    let mut inputs: Vec<String> = indices
+        .iter()
+        .map(|index| format!("{}[{}]", register, index))
+        .collect();
+    let mut instructions: Vec<String> = Vec::new();
+    match inputs.iter().position(|input| *input == output) {
+        Some(position) => {
+            inputs.remove(position);
+        }
+        None if inputs.is_empty() => instructions.push(format!("MOVE {} {}", output, neutral)),
+        None => instructions.push(format!("MOVE {} {}", output, inputs.remove(0))),
+    }
+    instructions.extend(
+        inputs
+            .into_iter()
+            .map(|input| format!("{} {} {}", instruction, output, input)),
+    );
+    instructions
+}
+
+/// Returns the Quil literal of a complex number.
+fn complex_literal(value: Complex64) -> String {
+    format!("{}{:+}i", value.re, value.im)
+}
+
+/// State of the export of a circuit.
+struct QuilWriter {
+    /// The number of qubits up to the largest qubit index in the circuit.
+    number_qubits: usize,
+    /// The lengths of the classical bit registers.
+    bit_registers: HashMap<String, usize>,
+    /// The DEFGATE definitions.
+    gate_definitions: Vec<String>,
+    /// The names of the defined gates by the debug representation of the gate acting on qubits 0 and 1.
+    gate_names: HashMap<String, String>,
+    /// The number of defined gates for each hqslang name.
+    gate_name_counts: HashMap<&'static str, usize>,
+    /// The number of labels used for conditional instructions.
+    number_labels: usize,
+}
+
+impl QuilWriter {
+    /// Returns the instructions representing an operation.
+    fn instructions(&mut self, operation: &Operation) -> Result<Vec<String>, RoqoqoError> {
+        if let Some(instruction) = standard_gate(operation)? {
+            return Ok(vec![instruction]);
+        }
+        Ok(match operation {
+            Operation::DefinitionBit(op) => {
+                self.bit_registers.insert(op.name().clone(), *op.length());
+                vec![format!("DECLARE {} BIT[{}]", op.name(), op.length())]
+            }
+            Operation::DefinitionFloat(op) => {
+                vec![format!("DECLARE {} REAL[{}]", op.name(), op.length())]
+            }
+            Operation::DefinitionUsize(op) => {
+                vec![format!("DECLARE {} INTEGER[{}]", op.name(), op.length())]
+            }
+            Operation::MeasureQubit(op) => vec![format!(
+                "MEASURE {} {}[{}]",
+                op.qubit(),
+                op.readout(),
+                op.readout_index()
+            )],
+            Operation::PragmaRepeatedMeasurement(op) => {
+                let mut mapping: Vec<(usize, usize)> = match op.qubit_mapping() {
+                    Some(mapping) => mapping.iter().map(|(q, b)| (*q, *b)).collect(),
+                    None => {
+                        let length = self
+                            .bit_registers
+                            .get(op.readout())
+                            .copied()
+                            .unwrap_or(self.number_qubits);
+                        (0..length.min(self.number_qubits))
+                            .map(|q| (q, q))
+                            .collect()
+                    }
+                };
+                mapping.sort_unstable();
+                mapping
+                    .into_iter()
+                    .map(|(q, b)| format!("MEASURE {} {}[{}]", q, op.readout(), b))
+                    .collect()
+            }
+            Operation::PragmaActiveReset(op) => vec![format!("RESET {}", op.qubit())],
+            Operation::PragmaConditional(op) => self.conditional(
+                operation,
+                &[(op.condition_register(), *op.condition_index(), true)],
+                op.circuit(),
+            )?,
+            Operation::PragmaConditionalPattern(op) => {
+                let condition: Vec<(&String, usize, bool)> = op
+                    .condition()
+                    .iter()
+                    .map(|(index, value)| (op.condition_register(), *index, *value))
+                    .collect();
+                self.conditional(operation, &condition, op.circuit())?
+            }
+            Operation::PragmaLoop(op) => {
+                parameter(op.repetitions())?;
+                let mut instructions: Vec<String> = Vec::new();
+                for inner in op.unroll()?.iter() {
+                    if inner.tags().contains(&"Definition") {
+                        return Err(self.not_representable(operation));
+                    }
+                    instructions.extend(self.instructions(inner)?);
+                }
+                instructions
+            }
+            Operation::PragmaRepeatUntilSuccess(op) => {
+                let mut instructions: Vec<String> = Vec::new();
+                for inner in op.unroll().iter() {
+                    if inner.tags().contains(&"Definition") {
+                        return Err(self.not_representable(operation));
+                    }
+                    instructions.extend(self.instructions(inner)?);
+                }
+                instructions
+            }
+            Operation::ClassicalNot(op) => {
+                let input = format!("{}[{}]", op.register(), op.index());
+                let output = format!("{}[{}]", op.output_register(), op.output_index());
+                let mut instructions = Vec::new();
+                if input != output {
+                    instructions.push(format!("MOVE {} {}", output, input));
+                }
+                instructions.push(format!("NOT {}", output));
+                instructions
+            }
+            Operation::ClassicalCopy(op) => {
+                let input = format!("{}[{}]", op.register(), op.index());
+                let output = format!("{}[{}]", op.output_register(), op.output_index());
+                if input == output {
+                    Vec::new()
+                } else {
+                    vec![format!("MOVE {} {}", output, input)]
+                }
+            }
+            Operation::ClassicalXor(op) => logic_instructions(
+                "XOR",
+                0,
+                op.register(),
+                op.indices(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalAnd(op) => logic_instructions(
+                "AND",
+                1,
+                op.register(),
+                op.indices(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalOr(op) => logic_instructions(
+                "IOR",
+                0,
+                op.register(),
+                op.indices(),
+                op.output_register(),
+                *op.output_index(),
+            ),
+            Operation::ClassicalGreaterThan(op) => vec![format!(
+                "GT {}[{}] {}[{}] {}",
+                op.output_register(),
+                op.output_index(),
+                op.register(),
+                op.index(),
+                real_literal(op.threshold())?
+            )],
+            Operation::ClassicalLessThan(op) => vec![format!(
+                "LT {}[{}] {}[{}] {}",
+                op.output_register(),
+                op.output_index(),
+                op.register(),
+                op.index(),
+                real_literal(op.threshold())?
+            )],
+            _ => vec![self.defined_gate(operation)?],
+        })
+    }
+
+    /// Returns the instructions of a conditional operation jumping over the instructions of the circuit
+    /// unless every condition bit given by register, index and value has its value.
+    fn conditional(
+        &mut self,
+        operation: &Operation,
+        condition: &[(&String, usize, bool)],
+        circuit: &Circuit,
+    ) -> Result<Vec<String>, RoqoqoError> {
+        let label = format!("@conditional_{}", self.number_labels);
+        self.number_labels += 1;
+        let mut instructions: Vec<String> = condition
+            .iter()
+            .map(|(register, index, value)| {
+                let jump = if *value { "JUMP-UNLESS" } else { "JUMP-WHEN" };
+                format!("{} {} {}[{}]", jump, label, register, index)
+            })
+            .collect();
+        for inner in circuit.iter() {
+            if inner.tags().contains(&"Definition") {
+                return Err(self.not_representable(operation));
+            }
+            instructions.extend(self.instructions(inner)?);
+        }
+        instructions.push(format!("LABEL {}", label));
+        Ok(instructions)
+    }
+
+    /// Returns the instruction applying a DEFGATE gate, adding the definition if necessary.
+    fn defined_gate(&mut self, operation: &Operation) -> Result<String, RoqoqoError> {
+        let CanonicalGate { qubits, key, .. } = match canonical_gate(operation)? {
+            Some(canonical) => canonical,
+            None => return Err(self.not_representable(operation)),
+        };
+        let name = match self.gate_names.get(&key) {
+            Some(name) => name.clone(),
+            None => {
+                let unitary = GateOperation::try_from(operation)?
+                    .unitary_matrix()
+                    .map_err(|_| RoqoqoError::QuilExportError {
+                        msg: format!(
+                            "Symbolic parameters of operation {} can not be exported, substitute parameters first",
+                            operation.hqslang()
+                        ),
+                    })?;
+                let name = self.new_gate_name(operation.hqslang());
+                let mut definition = format!("DEFGATE {}:\n", name);
+                for row in unitary.rows() {
+                    let entries: Vec<String> = row.iter().map(|c| complex_literal(*c)).collect();
+                    definition.push_str(&format!("    {}\n", entries.join(", ")));
+                }
+                definition.push('\n');
+                self.gate_definitions.push(definition);
+                self.gate_names.insert(key, name.clone());
+                name
+            }
+        };
+        let qubits: Vec<String> = qubits.iter().map(|q| q.to_string()).collect();
+        Ok(format!("{} {}", name, qubits.join(" ")))
+    }
+
+    /// Returns a new unique name for a DEFGATE gate.
+    fn new_gate_name(&mut self, hqslang: &'static str) -> String {
+        let mut name = hqslang.to_uppercase();
+        if STANDARD_GATE_NAMES.contains(&name.as_str()) {
+            name.push_str("_GATE");
+        }
+        let count = self.gate_name_counts.entry(hqslang).or_insert(0);
+        if *count > 0 {
+            name = format!("{}_{}", name, count);
+        }
+        *count += 1;
+        name
+    }
+
+    /// Returns the error for an operation that cannot be represented.
+    fn not_representable(&self, operation: &Operation) -> RoqoqoError {
+        RoqoqoError::QuilExportError {
+            msg: format!(
+                "Operation {} can not be represented in Quil",
+                operation.hqslang()
+            ),
+        }
+    }
+}
+
+/// Imports a Quil program.
+///
+/// * The standard gates of Quil are mapped onto roqoqo gates or sequences of roqoqo gates,
+///   which are equal to the Quil gates up to a global phase.
+/// * `DEFGATE` definitions of single-qubit gates are translated into [SingleQubitGate].
+/// * `DECLARE` with the types `BIT`, `REAL` and `INTEGER` is translated into [DefinitionBit], [DefinitionFloat] and [DefinitionUsize].
+/// * `MEASURE` is translated into [MeasureQubit].
+/// * `RESET` of a qubit is translated into [PragmaActiveReset].
+/// * A `JUMP-UNLESS` to a later `LABEL` is translated into a [PragmaConditional] on the instructions in between.
+/// * `PRAGMA` and `NOP` instructions are ignored.
+///
+/// # Arguments
+///
+/// * `source` - The Quil program.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The imported circuit.
+/// * `Err(RoqoqoError::QuilImportError)` - The program is invalid or uses unsupported features, with the line and column of the error.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::formats::quil_to_circuit;
+/// use roqoqo::operations::{DefinitionBit, Hadamard, MeasureQubit, RotateX};
+/// use roqoqo::Circuit;
+///
+/// let source = "DECLARE ro BIT[1]\nH 0\nRX(0.5) 0\nMEASURE 0 ro[0]\n";
+/// let mut expected = Circuit::new();
+/// expected += DefinitionBit::new("ro".to_string(), 1, true);
+/// expected += Hadamard::new(0);
+/// expected += RotateX::new(0, 0.5.into());
+/// expected += MeasureQubit::new(0, "ro".to_string(), 0);
+/// assert_eq!(quil_to_circuit(source).unwrap(), expected);
+/// ```
+pub fn quil_to_circuit(source: &str) -> Result<Circuit, RoqoqoError> {
+    let mut parser = QuilParser {
+        lines: tokenize(source)?,
+        position: 0,
+        registers: HashMap::new(),
+        gates: HashMap::new(),
+    };
+    let mut circuit = Circuit::new();
+    parser.block(&mut circuit, None)?;
+    Ok(circuit)
+}
+
+/// Kinds of tokens of a Quil program.
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// An identifier or keyword.
+    Identifier(String),
+    /// A real number literal.
+    Number(f64),
+    /// An imaginary number literal.
+    Imaginary(f64),
+    /// A label starting with `@`.
+    Label(String),
+    /// A parameter starting with `%`.
+    Parameter(String),
+    /// An operator or delimiter.
+    Symbol(char),
+}
+
+/// A token of a Quil program with its column.
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    /// The kind and content of the token.
+    kind: TokenKind,
+    /// The column of the token (starting at 1).
+    column: usize,
+    /// The column after the end of the token.
+    end_column: usize,
+}
+
+/// A non-empty line of a Quil program.
+#[derive(Debug, Clone)]
+struct Line {
+    /// The line number (starting at 1).
+    number: usize,
+    /// True if the line is indented.
+    indented: bool,
+    /// The tokens of the line.
+    tokens: Vec<Token>,
+}
+
+/// Returns an import error at the given position.
+fn import_error(line: usize, column: usize, msg: String) -> RoqoqoError {
+    RoqoqoError::QuilImportError { line, column, msg }
+}
+
+/// Splits a Quil program into lines of tokens, skipping empty lines and comments.
+fn tokenize(source: &str) -> Result<Vec<Line>, RoqoqoError> {
+    let mut lines: Vec<Line> = Vec::new();
+    for (line_index, text) in source.lines().enumerate() {
+        let number = line_index + 1;
+        let chars: Vec<char> = text.chars().collect();
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut index = 0;
+        while index < chars.len() {
+            let c = chars[index];
+            let column = index + 1;
+            if c == '#' {
+                break;
+            } else if c.is_whitespace() {
+                index += 1;
+                continue;
+            }
+            let word_end = |start: usize| {
+                let mut end = start;
+                while end < chars.len()
+                    && (chars[end].is_alphanumeric()
+                        || chars[end] == '_'
+                        || (chars[end] == '-'
+                            && end + 1 < chars.len()
+                            && chars[end + 1].is_alphabetic()))
+                {
+                    end += 1;
+                }
+                end
+            };
+            let kind = if c.is_ascii_digit() || c == '.' {
+                let mut end = index;
+                while end < chars.len()
+                    && (chars[end].is_ascii_digit()
+                        || chars[end] == '.'
+                        || ((chars[end] == 'e' || chars[end] == 'E')
+                            && end + 1 < chars.len()
+                            && (chars[end + 1].is_ascii_digit()
+                                || chars[end + 1] == '+'
+                                || chars[end + 1] == '-'))
+                        || ((chars[end] == '+' || chars[end] == '-')
+                            && (chars[end - 1] == 'e' || chars[end - 1] == 'E')))
+                {
+                    end += 1;
+                }
+                let literal: String = chars[index..end].iter().collect();
+                let value = literal.parse::<f64>().map_err(|_| {
+                    import_error(number, column, format!("Invalid number {}", literal))
+                })?;
+                let imaginary = end < chars.len()
+                    && chars[end] == 'i'
+                    && !matches!(chars.get(end + 1), Some(d) if d.is_alphanumeric() || *d == '_');
+                if imaginary {
+                    index = end + 1;
+                    TokenKind::Imaginary(value)
+                } else {
+                    index = end;
+                    TokenKind::Number(value)
+                }
+            } else if c.is_alphabetic() || c == '_' {
+                let end = word_end(index);
+                let word: String = chars[index..end].iter().collect();
+                index = end;
+                TokenKind::Identifier(word)
+            } else if c == '@' || c == '%' {
+                let end = word_end(index + 1);
+                if end == index + 1 {
+                    return Err(import_error(
+                        number,
+                        column,
+                        format!("Expected name after '{}'", c),
+                    ));
+                }
+                let word: String = chars[index + 1..end].iter().collect();
+                index = end;
+                if c == '@' {
+                    TokenKind::Label(word)
+                } else {
+                    TokenKind::Parameter(word)
+                }
+            } else if "()[],:+-*/^".contains(c) {
+                index += 1;
+                TokenKind::Symbol(c)
+            } else if c == '"' {
+                // String literals only appear in PRAGMA instructions, which are ignored
+                index = chars.len();
+                TokenKind::Symbol('"')
+            } else {
+                return Err(import_error(
+                    number,
+                    column,
+                    format!("Unexpected character '{}'", c),
+                ));
+            };
+            tokens.push(Token {
+                kind,
+                column,
+                end_column: index + 1,
+            });
+        }
+        if !tokens.is_empty() {
+            lines.push(Line {
+                number,
+                indented: text.starts_with(char::is_whitespace),
+                tokens,
+            });
+        }
+    }
+    Ok(lines)
+}
+
+/// Types of classical memory in Quil.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MemoryType {
+    /// Memory of type BIT.
+    Bit,
+    /// Memory of type REAL.
+    Real,
+    /// Memory of type INTEGER.
+    Integer,
+}
+
+/// Parser state of a Quil program.
+struct QuilParser {
+    /// The lines of the program.
+    lines: Vec<Line>,
+    /// The index of the next line.
+    position: usize,
+    /// The type and length of the declared memory regions.
+    registers: HashMap<String, (MemoryType, usize)>,
+    /// The matrices of the single-qubit DEFGATE gates.
+    gates: HashMap<String, Array2<Complex64>>,
+}
+
+/// Cursor over the tokens of a line.
+struct LineCursor<'a> {
+    /// The line.
+    line: &'a Line,
+    /// The index of the next token.
+    position: usize,
+}
+
+impl<'a> LineCursor<'a> {
+    /// Returns the next token without consuming it.
+    fn peek(&self) -> Option<&'a Token> {
+        self.line.tokens.get(self.position)
+    }
+
+    /// Returns an error at the next token or at the end of the line.
+    fn error(&self, msg: String) -> RoqoqoError {
+        let column = match self.peek() {
+            Some(token) => token.column,
+            None => self
+                .line
+                .tokens
+                .last()
+                .map(|token| token.end_column)
+                .unwrap_or(1),
+        };
+        import_error(self.line.number, column, msg)
+    }
+
+    /// Returns an error for an unexpected next token.
+    fn unexpected(&self, expected: &str) -> RoqoqoError {
+        match self.peek() {
+            Some(token) => self.error(format!(
+                "Expected {} but found {}",
+                expected,
+                describe(&token.kind)
+            )),
+            None => self.error(format!("Expected {} but reached end of line", expected)),
+        }
+    }
+
+    /// Consumes the next token if it is the given symbol.
+    fn accept_symbol(&mut self, symbol: char) -> bool {
+        let found =
+            matches!(self.peek(), Some(Token { kind: TokenKind::Symbol(s), .. }) if *s == symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    /// Consumes the next token, which has to be the given symbol.
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), RoqoqoError> {
+        if self.accept_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", symbol)))
+        }
+    }
+
+    /// Consumes the next token, which has to be an identifier, and returns the identifier.
+    fn expect_identifier(&mut self) -> Result<String, RoqoqoError> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Identifier(name),
+                ..
+            }) => {
+                self.position += 1;
+                Ok(name.clone())
+            }
+            _ => Err(self.unexpected("identifier")),
+        }
+    }
+
+    /// Consumes the next token, which has to be a non-negative integer, and returns the integer.
+    fn expect_integer(&mut self) -> Result<usize, RoqoqoError> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Number(value),
+                ..
+            }) if value.fract() == 0.0 && *value >= 0.0 => {
+                self.position += 1;
+                Ok(*value as usize)
+            }
+            _ => Err(self.unexpected("integer")),
+        }
+    }
+
+    /// Returns an error if there are tokens left on the line.
+    fn expect_end(&self) -> Result<(), RoqoqoError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected("end of line")),
+        }
+    }
+
+    /// Parses an expression: a sum of terms.
+    fn expression(&mut self) -> Result<Complex64, RoqoqoError> {
+        let mut value = self.term()?;
+        loop {
+            if self.accept_symbol('+') {
+                value += self.term()?;
+            } else if self.accept_symbol('-') {
+                value -= self.term()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Parses a product of factors.
+    fn term(&mut self) -> Result<Complex64, RoqoqoError> {
+        let mut value = self.factor()?;
+        loop {
+            if self.accept_symbol('*') {
+                value *= self.factor()?;
+            } else if self.accept_symbol('/') {
+                value /= self.factor()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Parses a signed power.
+    fn factor(&mut self) -> Result<Complex64, RoqoqoError> {
+        if self.accept_symbol('-') {
+            return Ok(-self.factor()?);
+        }
+        if self.accept_symbol('+') {
+            return self.factor();
+        }
+        let base = self.atom()?;
+        if self.accept_symbol('^') {
+            let exponent = self.factor()?;
+            Ok(base.powc(exponent))
+        } else {
+            Ok(base)
+        }
+    }
+
+    /// Parses a literal, a constant, a function call or a parenthesized expression.
+    fn atom(&mut self) -> Result<Complex64, RoqoqoError> {
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return Err(self.unexpected("expression")),
+        };
+        let value = match &token.kind {
+            TokenKind::Number(value) => Complex64::new(*value, 0.0),
+            TokenKind::Imaginary(value) => Complex64::new(0.0, *value),
+            TokenKind::Symbol('(') => {
+                self.position += 1;
+                let value = self.expression()?;
+                self.expect_symbol(')')?;
+                return Ok(value);
+            }
+            TokenKind::Identifier(name) if name == "pi" => {
+                Complex64::new(std::f64::consts::PI, 0.0)
+            }
+            TokenKind::Identifier(name) if name == "i" => Complex64::new(0.0, 1.0),
+            TokenKind::Identifier(name) => {
+                let function: fn(Complex64) -> Complex64 = match name.as_str() {
+                    "sin" => |x| x.sin(),
+                    "cos" => |x| x.cos(),
+                    "sqrt" => |x| x.sqrt(),
+                    "exp" => |x| x.exp(),
+                    "cis" => |x| (Complex64::new(0.0, 1.0) * x).exp(),
+                    _ => {
+                        return Err(self.error(format!(
+                        "Unknown identifier {} in expression, memory references are not supported",
+                        name
+                    )))
+                    }
+                };
+                self.position += 1;
+                self.expect_symbol('(')?;
+                let argument = self.expression()?;
+                self.expect_symbol(')')?;
+                return Ok(function(argument));
+            }
+            TokenKind::Parameter(_) => {
+                return Err(
+                    self.error("Parametric DEFGATE definitions are not supported".to_string())
+                )
+            }
+            _ => return Err(self.unexpected("expression")),
+        };
+        self.position += 1;
+        Ok(value)
+    }
+
+    /// Parses a real expression.
+    fn real_expression(&mut self) -> Result<f64, RoqoqoError> {
+        let start = self.position;
+        let value = self.expression()?;
+        if value.im.abs() > IMPORT_TOLERANCE {
+            self.position = start;
+            return Err(self.error("Gate parameters have to be real".to_string()));
+        }
+        Ok(value.re)
+    }
+}
+
+impl QuilParser {
+    /// Parses instructions until the end of the program or until the given label.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit the operations are added to.
+    /// * `end_label` - The label token ending the block and the line of the jump to the label.
+    fn block(
+        &mut self,
+        circuit: &mut Circuit,
+        end_label: Option<(&Token, usize)>,
+    ) -> Result<(), RoqoqoError> {
+        while self.position < self.lines.len() {
+            let line = self.lines[self.position].clone();
+            self.position += 1;
+            let mut cursor = LineCursor {
+                line: &line,
+                position: 0,
+            };
+            let keyword = cursor.expect_identifier()?;
+            match keyword.as_str() {
+                "DECLARE" => {
+                    let name = cursor.expect_identifier()?;
+                    let type_start = cursor.position;
+                    let memory_type = match cursor.expect_identifier()?.as_str() {
+                        "BIT" => MemoryType::Bit,
+                        "REAL" => MemoryType::Real,
+                        "INTEGER" => MemoryType::Integer,
+                        memory_type => {
+                            cursor.position = type_start;
+                            return Err(
+                                cursor.error(format!("Unsupported memory type {}", memory_type))
+                            );
+                        }
+                    };
+                    let length = if cursor.accept_symbol('[') {
+                        let length = cursor.expect_integer()?;
+                        cursor.expect_symbol(']')?;
+                        length
+                    } else {
+                        1
+                    };
+                    cursor.expect_end()?;
+                    if self.registers.contains_key(&name) {
+                        return Err(import_error(
+                            line.number,
+                            line.tokens[1].column,
+                            format!("Memory region {} is already declared", name),
+                        ));
+                    }
+                    self.registers.insert(name.clone(), (memory_type, length));
+                    circuit.add_operation(match memory_type {
+                        MemoryType::Bit => Operation::from(DefinitionBit::new(name, length, true)),
+                        MemoryType::Real => {
+                            Operation::from(DefinitionFloat::new(name, length, true))
+                        }
+                        MemoryType::Integer => {
+                            Operation::from(DefinitionUsize::new(name, length, true))
+                        }
+                    });
+                }
+                "DEFGATE" => self.gate_definition(&mut cursor)?,
+                "MEASURE" => {
+                    let qubit = cursor.expect_integer()?;
+                    if cursor.peek().is_none() {
+                        return Err(cursor.error(
+                            "Measurements without a memory reference are not supported".to_string(),
+                        ));
+                    }
+                    let (register, index) = self.bit_reference(&mut cursor)?;
+                    cursor.expect_end()?;
+                    circuit.add_operation(MeasureQubit::new(qubit, register, index));
+                }
+                "RESET" => {
+                    if cursor.peek().is_none() {
+                        return Err(cursor.error(
+                            "Reset of all qubits is not supported, reset single qubits instead"
+                                .to_string(),
+                        ));
+                    }
+                    let qubit = cursor.expect_integer()?;
+                    cursor.expect_end()?;
+                    circuit.add_operation(PragmaActiveReset::new(qubit));
+                }
+                "JUMP-UNLESS" => {
+                    let label = match cursor.peek() {
+                        Some(token) if matches!(token.kind, TokenKind::Label(_)) => token.clone(),
+                        _ => return Err(cursor.unexpected("label")),
+                    };
+                    cursor.position += 1;
+                    let (register, index) = self.bit_reference(&mut cursor)?;
+                    cursor.expect_end()?;
+                    let mut body = Circuit::new();
+                    self.block(&mut body, Some((&label, line.number)))?;
+                    circuit.add_operation(PragmaConditional::new(register, index, body));
+                }
+                "LABEL" => {
+                    let found = cursor.peek().map(|token| token.kind.clone());
+                    match (found, end_label) {
+                        (Some(kind), Some((end, _))) if kind == end.kind => {
+                            cursor.position += 1;
+                            cursor.expect_end()?;
+                            return Ok(());
+                        }
+                        _ => {
+                            return Err(import_error(
+                                line.number,
+                                line.tokens[0].column,
+                                "Labels are only supported as targets of JUMP-UNLESS".to_string(),
+                            ))
+                        }
+                    }
+                }
+                "PRAGMA" | "NOP" => {}
+                "DAGGER" | "CONTROLLED" | "FORKED" => {
+                    return Err(import_error(
+                        line.number,
+                        line.tokens[0].column,
+                        "Gate modifiers are not supported".to_string(),
+                    ))
+                }
+                "JUMP" | "JUMP-WHEN" | "HALT" | "WAIT" | "DEFCIRCUIT" | "INCLUDE" | "MOVE"
+                | "EXCHANGE" | "CONVERT" | "LOAD" | "STORE" | "NEG" | "NOT" | "AND" | "IOR"
+                | "XOR" | "ADD" | "SUB" | "MUL" | "DIV" | "EQ" | "GT" | "GE" | "LT" | "LE" => {
+                    return Err(import_error(
+                        line.number,
+                        line.tokens[0].column,
+                        format!("Unsupported instruction {}", keyword),
+                    ))
+                }
+                _ => self.gate(&mut cursor, &keyword, circuit)?,
+            }
+        }
+        match end_label {
+            Some((label, line)) => Err(import_error(
+                line,
+                label.column,
+                "JUMP-UNLESS without a later LABEL".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Parses a reference to an element of a declared BIT memory region.
+    fn bit_reference(&self, cursor: &mut LineCursor) -> Result<(String, usize), RoqoqoError> {
+        let start = cursor.position;
+        let name = cursor.expect_identifier()?;
+        let index = if cursor.accept_symbol('[') {
+            let index = cursor.expect_integer()?;
+            cursor.expect_symbol(']')?;
+            index
+        } else {
+            0
+        };
+        match self.registers.get(&name) {
+            Some((MemoryType::Bit, length)) if index < *length => Ok((name, index)),
+            Some((MemoryType::Bit, length)) => {
+                cursor.position = start;
+                Err(cursor.error(format!(
+                    "Index {} is out of range for memory region of length {}",
+                    index, length
+                )))
+            }
+            Some(_) => {
+                cursor.position = start;
+                Err(cursor.error(format!("Memory region {} is not of type BIT", name)))
+            }
+            None => {
+                cursor.position = start;
+                Err(cursor.error(format!("Unknown memory region {}", name)))
+            }
+        }
+    }
+
+    /// Parses a DEFGATE definition of a single-qubit gate.
+    fn gate_definition(&mut self, cursor: &mut LineCursor) -> Result<(), RoqoqoError> {
+        let name = cursor.expect_identifier()?;
+        if matches!(
+            cursor.peek(),
+            Some(Token {
+                kind: TokenKind::Symbol('('),
+                ..
+            })
+        ) {
+            return Err(
+                cursor.error("Parametric DEFGATE definitions are not supported".to_string())
+            );
+        }
+        if STANDARD_GATE_NAMES.contains(&name.as_str()) {
+            cursor.position -= 1;
+            return Err(cursor.error(format!("Standard gate {} can not be redefined", name)));
+        }
+        if let Some(Token {
+            kind: TokenKind::Identifier(keyword),
+            ..
+        }) = cursor.peek()
+        {
+            if keyword != "AS" {
+                return Err(cursor.unexpected("':'"));
+            }
+            cursor.position += 1;
+            let start = cursor.position;
+            if cursor.expect_identifier()? != "MATRIX" {
+                cursor.position = start;
+                return Err(cursor.error("Only DEFGATE AS MATRIX is supported".to_string()));
+            }
+        }
+        cursor.expect_symbol(':')?;
+        cursor.expect_end()?;
+        let mut rows: Vec<Vec<Complex64>> = Vec::new();
+        while self.position < self.lines.len() && self.lines[self.position].indented {
+            let line = &self.lines[self.position];
+            self.position += 1;
+            let mut row_cursor = LineCursor { line, position: 0 };
+            let mut row: Vec<Complex64> = Vec::new();
+            loop {
+                row.push(row_cursor.expression()?);
+                if !row_cursor.accept_symbol(',') {
+                    break;
+                }
+            }
+            row_cursor.expect_end()?;
+            rows.push(row);
+        }
+        let error =
+            |msg: String| import_error(cursor.line.number, cursor.line.tokens[0].column, msg);
+        if rows.len() != 2 || rows.iter().any(|row| row.len() != 2) {
+            return Err(error(
+                "Only DEFGATE definitions of single-qubit gates with a 2x2 matrix are supported"
+                    .to_string(),
+            ));
+        }
+        let matrix = Array2::from_shape_fn((2, 2), |(i, j)| rows[i][j]);
+        let product = matrix.dot(&matrix.t().mapv(|x| x.conj()));
+        if product
+            .indexed_iter()
+            .any(|((i, j), x)| (x - if i == j { 1.0 } else { 0.0 }).norm() > IMPORT_TOLERANCE)
+        {
+            return Err(error(format!("Matrix of gate {} is not unitary", name)));
+        }
+        self.gates.insert(name, matrix);
+        Ok(())
+    }
+
+    /// Parses a gate instruction and adds the corresponding operations to the circuit.
+    fn gate(
+        &self,
+        cursor: &mut LineCursor,
+        name: &str,
+        circuit: &mut Circuit,
+    ) -> Result<(), RoqoqoError> {
+        let name_token = cursor.line.tokens[0].clone();
+        let line_number = cursor.line.number;
+        let error = |msg: String| import_error(line_number, name_token.column, msg);
+        let mut parameters: Vec<f64> = Vec::new();
+        if cursor.accept_symbol('(') {
+            loop {
+                parameters.push(cursor.real_expression()?);
+                if !cursor.accept_symbol(',') {
+                    break;
+                }
+            }
+            cursor.expect_symbol(')')?;
+        }
+        let mut qubits: Vec<usize> = Vec::new();
+        while cursor.peek().is_some() {
+            qubits.push(cursor.expect_integer()?);
+        }
+        if qubits
+            .iter()
+            .enumerate()
+            .any(|(i, qubit)| qubits[..i].contains(qubit))
+        {
+            return Err(error(format!("Repeated qubit argument of gate {}", name)));
+        }
+        if let Some(matrix) = self.gates.get(name) {
+            if !parameters.is_empty() || qubits.len() != 1 {
+                return Err(error(format!(
+                    "Gate {} takes 0 parameters and 1 qubits",
+                    name
+                )));
+            }
+            circuit.add_operation(single_qubit_gate(qubits[0], matrix));
+            return Ok(());
+        }
+        let (number_parameters, number_qubits) = match name {
+            "I" | "X" | "Y" | "Z" | "H" | "S" | "T" => (0, 1),
+            "RX" | "RY" | "RZ" | "PHASE" => (1, 1),
+            "CNOT" | "CZ" | "SWAP" | "ISWAP" => (0, 2),
+            "CPHASE" | "CPHASE00" | "CPHASE01" | "CPHASE10" | "XY" | "PSWAP" => (1, 2),
+            "CCNOT" | "CSWAP" => (0, 3),
+            _ => return Err(error(format!("Unknown gate {}", name))),
+        };
+        if parameters.len() != number_parameters || qubits.len() != number_qubits {
+            return Err(error(format!(
+                "Gate {} takes {} parameters and {} qubits",
+                name, number_parameters, number_qubits
+            )));
+        }
+        let theta = || CalculatorFloat::from(parameters[0]);
+        match name {
+            "I" => {}
+            "X" => circuit.add_operation(PauliX::new(qubits[0])),
+            "Y" => circuit.add_operation(PauliY::new(qubits[0])),
+            "Z" => circuit.add_operation(PauliZ::new(qubits[0])),
+            "H" => circuit.add_operation(Hadamard::new(qubits[0])),
+            "S" => circuit.add_operation(SGate::new(qubits[0])),
+            "T" => circuit.add_operation(TGate::new(qubits[0])),
+            "RX" => circuit.add_operation(RotateX::new(qubits[0], theta())),
+            "RY" => circuit.add_operation(RotateY::new(qubits[0], theta())),
+            "RZ" | "PHASE" => circuit.add_operation(RotateZ::new(qubits[0], theta())),
+            "CNOT" => circuit.add_operation(CNOT::new(qubits[0], qubits[1])),
+            "CZ" => circuit.add_operation(ControlledPauliZ::new(qubits[0], qubits[1])),
+            "SWAP" => circuit.add_operation(SWAP::new(qubits[0], qubits[1])),
+            "ISWAP" => circuit.add_operation(ISwap::new(qubits[0], qubits[1])),
+            "XY" => circuit.add_operation(XY::new(qubits[0], qubits[1], theta())),
+            "CPHASE" | "CPHASE00" | "CPHASE01" | "CPHASE10" => {
+                // CPHASEab applies the phase to the state where the qubits are in a and b
+                let flipped: Vec<usize> = match name {
+                    "CPHASE00" => vec![qubits[0], qubits[1]],
+                    "CPHASE01" => vec![qubits[0]],
+                    "CPHASE10" => vec![qubits[1]],
+                    _ => vec![],
+                };
+                for qubit in flipped.iter() {
+                    circuit.add_operation(PauliX::new(*qubit));
+                }
+                circuit.add_operation(ControlledPhaseShift::new(qubits[0], qubits[1], theta()));
+                for qubit in flipped.iter() {
+                    circuit.add_operation(PauliX::new(*qubit));
+                }
+            }
+            "PSWAP" => {
+                circuit.add_operation(SWAP::new(qubits[0], qubits[1]));
+                circuit.add_operation(CNOT::new(qubits[0], qubits[1]));
+                circuit.add_operation(RotateZ::new(qubits[1], theta()));
+                circuit.add_operation(CNOT::new(qubits[0], qubits[1]));
+            }
+            "CCNOT" => *circuit += toffoli(qubits[0], qubits[1], qubits[2]),
+            "CSWAP" => {
+                circuit.add_operation(CNOT::new(qubits[2], qubits[1]));
+                *circuit += toffoli(qubits[0], qubits[1], qubits[2]);
+                circuit.add_operation(CNOT::new(qubits[2], qubits[1]));
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+}
+
+/// Returns the SingleQubitGate with the given unitary matrix.
+fn single_qubit_gate(qubit: usize, matrix: &Array2<Complex64>) -> SingleQubitGate {
+    // The matrix is exp(iφ) [[α, -β*], [β, α*]] with determinant exp(2iφ)
+    let determinant = matrix[(0, 0)] * matrix[(1, 1)] - matrix[(0, 1)] * matrix[(1, 0)];
+    let global_phase = determinant.arg() / 2.0;
+    let phase = Complex64::from_polar(1.0, -global_phase);
+    let alpha = phase * matrix[(0, 0)];
+    let beta = phase * matrix[(1, 0)];
+    SingleQubitGate::new(
+        qubit,
+        alpha.re.into(),
+        alpha.im.into(),
+        beta.re.into(),
+        beta.im.into(),
+        global_phase.into(),
+    )
+}
+
+/// Returns the decomposition of the Toffoli gate into Hadamard, T and CNOT gates.
+fn toffoli(control_0: usize, control_1: usize, target: usize) -> Circuit {
+    let tdg = |qubit: usize| RotateZ::new(qubit, (-FRAC_PI_4).into());
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(target);
+    circuit += CNOT::new(control_1, target);
+    circuit += tdg(target);
+    circuit += CNOT::new(control_0, target);
+    circuit += TGate::new(target);
+    circuit += CNOT::new(control_1, target);
+    circuit += tdg(target);
+    circuit += CNOT::new(control_0, target);
+    circuit += TGate::new(control_1);
+    circuit += TGate::new(target);
+    circuit += Hadamard::new(target);
+    circuit += CNOT::new(control_0, control_1);
+    circuit += TGate::new(control_0);
+    circuit += tdg(control_1);
+    circuit += CNOT::new(control_0, control_1);
+    circuit
+}
+
+/// Returns a description of a token for error messages.
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Identifier(name) => format!("'{}'", name),
+        TokenKind::Number(value) => format!("'{}'", value),
+        TokenKind::Imaginary(value) => format!("'{}i'", value),
+        TokenKind::Label(label) => format!("'@{}'", label),
+        TokenKind::Parameter(parameter) => format!("'%{}'", parameter),
+        TokenKind::Symbol(symbol) => format!("'{}'", symbol),
+    }
+}
@@ -0,0 +1,350 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::drawing::{
+    decimal, gate_runs, layout, pi_fraction, short_symbol, DrawingOptions, Element, GateLabels,
+    Symbol, GREEK_LETTERS,
+};
+use crate::Circuit;
+use qoqo_calculator::CalculatorFloat;
+
+/// Vertical distance between two wires.
+const ROW_HEIGHT: f64 = 40.0;
+/// Width of a character of the labels.
+const CHARACTER_WIDTH: f64 = 8.0;
+/// Minimal width of a column.
+const MIN_COLUMN_WIDTH: f64 = 40.0;
+/// Horizontal space between the label and the border of a gate box.
+const PADDING: f64 = 6.0;
+/// Height of a gate box on a single wire.
+const BOX_HEIGHT: f64 = 28.0;
+/// Margin around the drawing.
+const MARGIN: f64 = 10.0;
+/// Font size of the labels.
+const FONT_SIZE: f64 = 14.0;
+
+/// Draws a circuit as a standalone SVG image.
+///
+/// Every qubit is drawn as a single wire and every classical register as a double wire below the qubits.
+/// Operations are placed in columns as early as possible.
+/// Decomposition blocks started by `PragmaStartDecompositionBlock` and stopped by
+/// `PragmaStopDecompositionBlock` are drawn as dashed boxes around the operations of the block.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to draw.
+/// * `options` - The labels of the gates and the operations shown.
+///
+/// # Returns
+///
+/// * `String` - The SVG document of the drawing.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::formats::{circuit_to_svg, DrawingOptions};
+/// use roqoqo::operations::{CNOT, Hadamard};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += Hadamard::new(0);
+/// circuit += CNOT::new(0, 1);
+///
+/// let svg = circuit_to_svg(&circuit, &DrawingOptions::default());
+/// assert!(svg.starts_with("<?xml"));
+/// assert!(svg.contains(">H</text>"));
+/// ```
+pub fn circuit_to_svg(circuit: &Circuit, options: &DrawingOptions) -> String {
+    let layout = layout(circuit, options);
+    let names = layout.row_names();
+    let name_width = names
+        .iter()
+        .map(|name| name.chars().count() as f64 * CHARACTER_WIDTH)
+        .fold(0.0, f64::max)
+        + PADDING;
+    let widths: Vec<f64> = layout
+        .columns
+        .iter()
+        .map(|elements| column_width(elements, options))
+        .collect();
+    let mut column_starts: Vec<f64> = Vec::new();
+    let mut x = MARGIN + name_width + PADDING;
+    for width in widths.iter() {
+        column_starts.push(x);
+        x += width;
+    }
+    let wire_end = x + PADDING;
+    let width = wire_end + MARGIN;
+    let height = 2.0 * MARGIN + ROW_HEIGHT * (layout.number_rows() as f64);
+
+    let mut drawing = SvgDrawing {
+        content: Vec::new(),
+    };
+    for (row, name) in names.iter().enumerate() {
+        let y = row_y(row);
+        drawing.text(MARGIN + name_width, y, "end", name);
+        let start = MARGIN + name_width + PADDING;
+        if row < layout.number_qubits {
+            drawing.line(start, y, wire_end, y);
+        } else {
+            drawing.line(start, y - 1.5, wire_end, y - 1.5);
+            drawing.line(start, y + 1.5, wire_end, y + 1.5);
+        }
+    }
+    for (column, elements) in layout.columns.iter().enumerate() {
+        let center = column_starts[column] + widths[column] / 2.0;
+        for element in elements {
+            draw_element(&mut drawing, center, widths[column], element, options);
+        }
+    }
+    for group in layout.groups.iter() {
+        let left = column_starts[group.first_column] + 2.0;
+        let right = column_starts[group.last_column] + widths[group.last_column] - 2.0;
+        let top = row_y(group.first_row) - ROW_HEIGHT / 2.0 + 2.0;
+        let bottom = row_y(group.last_row) + ROW_HEIGHT / 2.0 - 2.0;
+        drawing.content.push(format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"none\" stroke=\"gray\" stroke-dasharray=\"4 3\"/>",
+            left,
+            top,
+            right - left,
+            bottom - top
+        ));
+    }
+
+    let mut svg = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n".to_string();
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"{f}\">\n",
+        w = width,
+        h = height,
+        f = FONT_SIZE
+    ));
+    svg.push_str(&format!(
+        "<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n",
+        width, height
+    ));
+    for item in drawing.content {
+        svg.push_str(&item);
+        svg.push('\n');
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The SVG elements of a drawing.
+struct SvgDrawing {
+    /// The SVG elements.
+    content: Vec<String>,
+}
+
+impl SvgDrawing {
+    /// Adds a black line.
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.content.push(format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>",
+            x1, y1, x2, y2
+        ));
+    }
+
+    /// Adds a text vertically centered on y.
+    fn text(&mut self, x: f64, y: f64, anchor: &str, text: &str) {
+        self.content.push(format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"{}\" dominant-baseline=\"central\">{}</text>",
+            x,
+            y,
+            anchor,
+            escape(text)
+        ));
+    }
+
+    /// Adds a circle.
+    fn circle(&mut self, x: f64, y: f64, radius: f64, fill: &str) {
+        self.content.push(format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"black\"/>",
+            x, y, radius, fill
+        ));
+    }
+
+    /// Adds a white box with a black border.
+    fn rectangle(&mut self, x: f64, y: f64, width: f64, height: f64, dashed: bool) {
+        let dash = if dashed {
+            " stroke-dasharray=\"4 3\""
+        } else {
+            ""
+        };
+        self.content.push(format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\" stroke=\"black\"{}/>",
+            x, y, width, height, dash
+        ));
+    }
+}
+
+/// Returns the vertical position of the wire of a row.
+fn row_y(row: usize) -> f64 {
+    MARGIN + ROW_HEIGHT * (row as f64 + 0.5)
+}
+
+/// Returns the width of a column fitting the labels of its gates.
+fn column_width(elements: &[Element], options: &DrawingOptions) -> f64 {
+    elements
+        .iter()
+        .flat_map(|element| element.symbols.iter())
+        .map(|(_, symbol)| match symbol {
+            Symbol::Gate {
+                hqslang,
+                parameters,
+                ..
+            } => {
+                label(hqslang, parameters, options).chars().count() as f64 * CHARACTER_WIDTH
+                    + 4.0 * PADDING
+            }
+            _ => MIN_COLUMN_WIDTH,
+        })
+        .fold(MIN_COLUMN_WIDTH, f64::max)
+}
+
+/// Draws an element centered on x in a column.
+fn draw_element(
+    drawing: &mut SvgDrawing,
+    x: f64,
+    width: f64,
+    element: &Element,
+    options: &DrawingOptions,
+) {
+    let (first, last) = element.span();
+    let runs = gate_runs(element);
+    let single_box = runs.len() == 1 && runs[0] == (first, last);
+    if last > first && !single_box {
+        if element.classical {
+            drawing.line(x - 1.5, row_y(first), x - 1.5, row_y(last));
+            drawing.line(x + 1.5, row_y(first), x + 1.5, row_y(last));
+        } else {
+            drawing.line(x, row_y(first), x, row_y(last));
+        }
+    }
+    for (row, symbol) in element.symbols.iter() {
+        let y = row_y(*row);
+        match symbol {
+            Symbol::Gate {
+                hqslang,
+                parameters,
+                noise,
+            } => {
+                if let Some((_, last_row)) = runs.iter().find(|(first_row, _)| first_row == row) {
+                    let box_width = width - 2.0 * PADDING;
+                    let bottom = row_y(*last_row) + BOX_HEIGHT / 2.0;
+                    let top = y - BOX_HEIGHT / 2.0;
+                    drawing.rectangle(x - box_width / 2.0, top, box_width, bottom - top, *noise);
+                    drawing.text(
+                        x,
+                        (top + bottom) / 2.0,
+                        "middle",
+                        &label(hqslang, parameters, options),
+                    );
+                }
+            }
+            Symbol::Control => drawing.circle(x, y, 4.0, "black"),
+            Symbol::Target => {
+                drawing.circle(x, y, 10.0, "white");
+                drawing.line(x - 10.0, y, x + 10.0, y);
+                drawing.line(x, y - 10.0, x, y + 10.0);
+            }
+            Symbol::Swap => {
+                drawing.line(x - 6.0, y - 6.0, x + 6.0, y + 6.0);
+                drawing.line(x - 6.0, y + 6.0, x + 6.0, y - 6.0);
+            }
+            Symbol::Meter => {
+                let half = BOX_HEIGHT / 2.0;
+                drawing.rectangle(x - half, y - half, BOX_HEIGHT, BOX_HEIGHT, false);
+                drawing.content.push(format!(
+                    "<path d=\"M {} {} A {} {} 0 0 1 {} {}\" fill=\"none\" stroke=\"black\"/>",
+                    x - 9.0,
+                    y + 5.0,
+                    9.0,
+                    9.0,
+                    x + 9.0,
+                    y + 5.0
+                ));
+                drawing.line(x, y + 5.0, x + 7.0, y - 8.0);
+            }
+            Symbol::Readout(index) => {
+                drawing.circle(x, y, 3.0, "black");
+                if let Some(index) = index {
+                    drawing.text(x + 5.0, y - 9.0, "start", &index.to_string());
+                }
+            }
+            Symbol::Condition(index) => {
+                drawing.circle(x, y, 4.0, "white");
+                drawing.text(x + 6.0, y - 9.0, "start", index);
+            }
+        }
+    }
+}
+
+/// Returns the text label of a gate.
+fn label(hqslang: &str, parameters: &[CalculatorFloat], options: &DrawingOptions) -> String {
+    let mut label = match (options.gate_labels, short_symbol(hqslang)) {
+        (GateLabels::Symbol, Some((_, symbol))) => symbol.to_string(),
+        _ => hqslang.to_string(),
+    };
+    if !parameters.is_empty() {
+        let texts: Vec<String> = parameters.iter().map(parameter).collect();
+        label.push_str(&format!("({})", texts.join(", ")));
+    }
+    label
+}
+
+/// Returns the text of a parameter.
+fn parameter(value: &CalculatorFloat) -> String {
+    match value {
+        CalculatorFloat::Float(value) => match pi_fraction(*value) {
+            Some((numerator, denominator)) => {
+                let sign = if numerator < 0 { "-" } else { "" };
+                let factor = match numerator.abs() {
+                    1 => "π".to_string(),
+                    n => format!("{}π", n),
+                };
+                match denominator {
+                    1 => format!("{}{}", sign, factor),
+                    _ => format!("{}{}/{}", sign, factor, denominator),
+                }
+            }
+            None => decimal(*value),
+        },
+        CalculatorFloat::Str(expression) => {
+            let mut text = String::new();
+            let mut word = String::new();
+            for c in expression.chars().chain(std::iter::once(' ')) {
+                if c.is_alphanumeric() || c == '_' {
+                    word.push(c);
+                    continue;
+                }
+                match GREEK_LETTERS.iter().find(|(name, _)| *name == word) {
+                    Some((_, letter)) => text.push_str(letter),
+                    None => text.push_str(&word),
+                }
+                word.clear();
+                if !c.is_whitespace() {
+                    text.push(c);
+                }
+            }
+            text
+        }
+    }
+}
+
+/// Escapes the characters with a special meaning in XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,420 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bit-string histograms of measured bit registers.
+//!
+//! [BitStringHistogram] turns a [BitOutputRegister] into counts of measured bit strings
+//! that can be marginalised to subsets of bits and used to estimate expectation values
+//! of products of Pauli Z operators, as measured with [ClassicalRegister](crate::measurements::ClassicalRegister)
+//! or [BasisRotation](crate::measurements::BasisRotation) measurements.
+
+use crate::registers::BitOutputRegister;
+use crate::RoqoqoError;
+use std::collections::{BTreeMap, HashMap};
+
+/// Order of the bits of a register in a bit string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitOrder {
+    /// The first bit of the register is the leftmost character of the bit string.
+    #[default]
+    FirstBitLeft,
+    /// The first bit of the register is the rightmost character of the bit string,
+    /// so the bit string reads as the binary number with the first bit as least significant bit.
+    FirstBitRight,
+}
+
+/// Histogram of the bit strings measured in the runs of a bit register.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::histogram::{BitOrder, BitStringHistogram};
+///
+/// let register = vec![vec![true, false], vec![true, false], vec![false, false]];
+/// let histogram = BitStringHistogram::from_register(&register).unwrap();
+/// assert_eq!(histogram.number_shots(), 3);
+/// assert_eq!(histogram.bit_string_counts(BitOrder::FirstBitRight)["01"], 2);
+/// assert_eq!(histogram.parity_expectation_value(&[0]).unwrap(), -1.0 / 3.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BitStringHistogram {
+    /// Number of bits in each measured bit string.
+    number_bits: usize,
+    /// Number of runs for each measured bit string, with bits in register order.
+    counts: BTreeMap<Vec<bool>, usize>,
+}
+
+impl BitStringHistogram {
+    /// Creates a new empty histogram.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_bits` - The number of bits in each bit string.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The new histogram.
+    pub fn new(number_bits: usize) -> Self {
+        BitStringHistogram {
+            number_bits,
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// Creates the histogram of the runs of a bit register.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The bit register with one entry for each run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The histogram of the register.
+    /// * `Err(RoqoqoError::BitStringHistogramError)` - The runs of the register have different lengths.
+    pub fn from_register(register: &[Vec<bool>]) -> Result<Self, RoqoqoError> {
+        let mut histogram =
+            BitStringHistogram::new(register.first().map(|run| run.len()).unwrap_or(0));
+        for run in register.iter() {
+            histogram.add(run, 1)?;
+        }
+        Ok(histogram)
+    }
+
+    /// Creates a histogram from counts of bit strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `counts` - The number of runs for each bit string of '0' and '1' characters.
+    /// * `bit_order` - The order of the bits in the bit strings.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The histogram of the counts.
+    /// * `Err(RoqoqoError::BitStringHistogramError)` - A bit string contains other characters or the bit strings have different lengths.
+    pub fn from_counts(
+        counts: &HashMap<String, usize>,
+        bit_order: BitOrder,
+    ) -> Result<Self, RoqoqoError> {
+        let mut histogram =
+            BitStringHistogram::new(counts.keys().next().map(|key| key.len()).unwrap_or(0));
+        for (bit_string, count) in counts.iter() {
+            let mut bits = bit_string
+                .chars()
+                .map(|character| match character {
+                    '0' => Ok(false),
+                    '1' => Ok(true),
+                    _ => Err(RoqoqoError::BitStringHistogramError {
+                        msg: format!(
+                            "Bit string {} contains characters other than 0 and 1",
+                            bit_string
+                        ),
+                    }),
+                })
+                .collect::<Result<Vec<bool>, RoqoqoError>>()?;
+            if bit_order == BitOrder::FirstBitRight {
+                bits.reverse();
+            }
+            histogram.add(&bits, *count)?;
+        }
+        Ok(histogram)
+    }
+
+    /// Adds runs with a measured bit string to the histogram.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - The measured bits in register order.
+    /// * `count` - The number of runs measuring the bits.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The runs were added.
+    /// * `Err(RoqoqoError::BitStringHistogramError)` - The number of bits does not match the histogram.
+    pub fn add(&mut self, bits: &[bool], count: usize) -> Result<(), RoqoqoError> {
+        if bits.len() != self.number_bits {
+            return Err(RoqoqoError::BitStringHistogramError {
+                msg: format!(
+                    "Bit string has {} bits but the histogram has {} bits",
+                    bits.len(),
+                    self.number_bits
+                ),
+            });
+        }
+        if count > 0 {
+            *self.counts.entry(bits.to_vec()).or_insert(0) += count;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bits in each bit string.
+    pub fn number_bits(&self) -> usize {
+        self.number_bits
+    }
+
+    /// Returns the total number of runs (shots) in the histogram.
+    pub fn number_shots(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Returns the number of runs measuring a bit string.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - The bits in register order.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of runs measuring the bits.
+    pub fn count(&self, bits: &[bool]) -> usize {
+        self.counts.get(bits).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of runs for each measured bit string, with bits in register order.
+    pub fn counts(&self) -> &BTreeMap<Vec<bool>, usize> {
+        &self.counts
+    }
+
+    /// Returns the number of runs for each measured bit string as strings of '0' and '1' characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `bit_order` - The order of the bits in the bit strings.
+    ///
+    /// # Returns
+    ///
+    /// * `BTreeMap<String, usize>` - The number of runs by bit string.
+    pub fn bit_string_counts(&self, bit_order: BitOrder) -> BTreeMap<String, usize> {
+        self.counts
+            .iter()
+            .map(|(bits, count)| (bit_string(bits, bit_order), *count))
+            .collect()
+    }
+
+    /// Returns the relative frequency of each measured bit string.
+    ///
+    /// # Arguments
+    ///
+    /// * `bit_order` - The order of the bits in the bit strings.
+    ///
+    /// # Returns
+    ///
+    /// * `BTreeMap<String, f64>` - The probability by bit string, empty if the histogram contains no runs.
+    pub fn probabilities(&self, bit_order: BitOrder) -> BTreeMap<String, f64> {
+        let number_shots = self.number_shots() as f64;
+        self.counts
+            .iter()
+            .map(|(bits, count)| (bit_string(bits, bit_order), *count as f64 / number_shots))
+            .collect()
+    }
+
+    /// Returns the histogram of a subset of the bits, summing over all other bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - The indices of the bits that are kept, in the order of the new histogram.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The marginal histogram.
+    /// * `Err(RoqoqoError::BitStringHistogramError)` - An index exceeds the number of bits.
+    pub fn marginal(&self, bits: &[usize]) -> Result<Self, RoqoqoError> {
+        self.check_indices(bits)?;
+        let mut marginal = BitStringHistogram::new(bits.len());
+        for (measured, count) in self.counts.iter() {
+            let kept: Vec<bool> = bits.iter().map(|index| measured[*index]).collect();
+            *marginal.counts.entry(kept).or_insert(0) += count;
+        }
+        Ok(marginal)
+    }
+
+    /// Returns the expectation value of the product of Pauli Z operators on a subset of the bits.
+    ///
+    /// A run contributes 1 when an even number of the bits in the mask were measured as true
+    /// and -1 otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - The indices of the bits in the product.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - The expectation value.
+    /// * `Err(RoqoqoError::BitStringHistogramError)` - An index exceeds the number of bits or the histogram contains no runs.
+    pub fn parity_expectation_value(&self, mask: &[usize]) -> Result<f64, RoqoqoError> {
+        self.check_indices(mask)?;
+        let number_shots = self.number_shots();
+        if number_shots == 0 {
+            return Err(RoqoqoError::BitStringHistogramError {
+                msg: "Histogram contains no runs".to_string(),
+            });
+        }
+        let sum: f64 = self
+            .counts
+            .iter()
+            .map(|(bits, count)| parity_value(bits, mask) * *count as f64)
+            .sum();
+        Ok(sum / number_shots as f64)
+    }
+
+    /// Returns the expectation value of a product of Pauli Z operators with a bootstrapped error bar.
+    ///
+    /// The runs are resampled with replacement and the error bar is the standard deviation
+    /// of the expectation values of the resampled histograms.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - The indices of the bits in the product.
+    /// * `number_resamples` - The number of resampled histograms.
+    /// * `seed` - The seed of the random number generator, resampling with the same seed gives the same result.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((f64, f64))` - The expectation value and its standard error.
+    /// * `Err(RoqoqoError::BitStringHistogramError)` - An index exceeds the number of bits or the histogram contains no runs.
+    pub fn bootstrap_parity_expectation_value(
+        &self,
+        mask: &[usize],
+        number_resamples: usize,
+        seed: u64,
+    ) -> Result<(f64, f64), RoqoqoError> {
+        let expectation_value = self.parity_expectation_value(mask)?;
+        let number_shots = self.number_shots();
+        let (values, cumulative_counts): (Vec<f64>, Vec<usize>) = self
+            .counts
+            .iter()
+            .scan(0, |total, (bits, count)| {
+                *total += count;
+                Some((parity_value(bits, mask), *total))
+            })
+            .unzip();
+        let mut rng = SplitMix64::new(seed);
+        let resampled: Vec<f64> = (0..number_resamples)
+            .map(|_| {
+                let sum: f64 = (0..number_shots)
+                    .map(|_| values[outcome_index(&cumulative_counts, rng.below(number_shots))])
+                    .sum();
+                sum / number_shots as f64
+            })
+            .collect();
+        if resampled.is_empty() {
+            return Ok((expectation_value, 0.0));
+        }
+        let mean = resampled.iter().sum::<f64>() / resampled.len() as f64;
+        let variance = resampled
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / resampled.len() as f64;
+        Ok((expectation_value, variance.sqrt()))
+    }
+
+    /// Returns a bit register containing the runs of the histogram, ordered by bit string.
+    ///
+    /// # Returns
+    ///
+    /// * `BitOutputRegister` - One entry for each run.
+    pub fn to_register(&self) -> BitOutputRegister {
+        self.counts
+            .iter()
+            .flat_map(|(bits, count)| std::iter::repeat_n(bits.clone(), *count))
+            .collect()
+    }
+
+    /// Samples a bit register from the relative frequencies of the histogram.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_shots` - The number of sampled runs.
+    /// * `seed` - The seed of the random number generator, sampling with the same seed gives the same register.
+    ///
+    /// # Returns
+    ///
+    /// * `BitOutputRegister` - One entry for each sampled run, empty if the histogram contains no runs.
+    pub fn sample(&self, number_shots: usize, seed: u64) -> BitOutputRegister {
+        let total = self.number_shots();
+        if total == 0 {
+            return Vec::new();
+        }
+        let (outcomes, cumulative_counts): (Vec<&Vec<bool>>, Vec<usize>) = self
+            .counts
+            .iter()
+            .scan(0, |sum, (bits, count)| {
+                *sum += count;
+                Some((bits, *sum))
+            })
+            .unzip();
+        let mut rng = SplitMix64::new(seed);
+        (0..number_shots)
+            .map(|_| outcomes[outcome_index(&cumulative_counts, rng.below(total))].clone())
+            .collect()
+    }
+
+    /// Checks that bit indices do not exceed the number of bits.
+    fn check_indices(&self, indices: &[usize]) -> Result<(), RoqoqoError> {
+        match indices.iter().find(|index| **index >= self.number_bits) {
+            Some(index) => Err(RoqoqoError::BitStringHistogramError {
+                msg: format!(
+                    "Bit index {} exceeds the number of bits {}",
+                    index, self.number_bits
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes bits as a string of '0' and '1' characters.
+fn bit_string(bits: &[bool], bit_order: BitOrder) -> String {
+    let characters = bits.iter().map(|bit| if *bit { '1' } else { '0' });
+    match bit_order {
+        BitOrder::FirstBitLeft => characters.collect(),
+        BitOrder::FirstBitRight => characters.rev().collect(),
+    }
+}
+
+/// Maps the parity of the masked bits to 1 (even) or -1 (odd).
+fn parity_value(bits: &[bool], mask: &[usize]) -> f64 {
+    if mask.iter().filter(|index| bits[**index]).count() % 2 == 0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Returns the index of the outcome a run belongs to, given the cumulative counts of the outcomes.
+fn outcome_index(cumulative_counts: &[usize], run: usize) -> usize {
+    cumulative_counts.partition_point(|total| *total <= run)
+}
+
+/// Small seedable pseudo random number generator for resampling, without an additional dependency.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in 0..upper.
+    fn below(&mut self, upper: usize) -> usize {
+        ((self.next_u64() as u128 * upper as u128) >> 64) as usize
+    }
+}
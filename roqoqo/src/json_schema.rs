@@ -0,0 +1,98 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON Schemas of the serialized roqoqo types.
+//!
+//! The schemas are derived with [schemars] alongside the serde implementations,
+//! so they follow the JSON representation of [Operation](crate::operations::Operation),
+//! [Circuit](crate::Circuit) and the measurements.
+//! Fields of types from other crates are described by the schema-only types of this module.
+//! The schemas of these types are checked against serialized values of every operation in the tests.
+//! Dynamic operations of external crates are described by a schema accepting any JSON value.
+
+use schemars::gen::SchemaGenerator;
+pub use schemars::schema::RootSchema;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+pub use schemars::JsonSchema;
+
+/// Returns the JSON Schema of a type.
+///
+/// # Returns
+///
+/// * `RootSchema` - The schema with the schemas of all nested types in its definitions.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::json_schema::json_schema;
+///
+/// let schema = json_schema::<Circuit>();
+/// assert!(schema.definitions.contains_key("Operation"));
+/// ```
+pub fn json_schema<T: JsonSchema>() -> RootSchema {
+    schemars::schema_for!(T)
+}
+
+/// Returns the JSON Schema of a type as pretty printed JSON.
+///
+/// # Returns
+///
+/// * `String` - The JSON of the schema.
+pub fn json_schema_string<T: JsonSchema>() -> String {
+    serde_json::to_string_pretty(&json_schema::<T>())
+        .expect("Serializing a JSON Schema to JSON can not fail")
+}
+
+/// Schema of a [CalculatorFloat](qoqo_calculator::CalculatorFloat), serialized as a number or a symbolic expression.
+#[derive(Debug)]
+pub struct CalculatorFloat;
+
+impl JsonSchema for CalculatorFloat {
+    fn schema_name() -> String {
+        "CalculatorFloat".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        Schema::Object(SchemaObject {
+            instance_type: Some(SingleOrVec::Vec(vec![
+                InstanceType::Number,
+                InstanceType::String,
+            ])),
+            ..Default::default()
+        })
+    }
+}
+
+/// Schema of a one-dimensional [ndarray::Array1], serialized with its version, shape and elements.
+#[derive(Debug, JsonSchema)]
+#[schemars(rename = "Array1_of_{T}")]
+pub struct Array1<T> {
+    /// The version of the serialization format of ndarray.
+    pub v: u8,
+    /// The length of the array.
+    pub dim: [usize; 1],
+    /// The elements of the array.
+    pub data: Vec<T>,
+}
+
+/// Schema of a two-dimensional [ndarray::Array2], serialized with its version, shape and elements in row-major order.
+#[derive(Debug, JsonSchema)]
+#[schemars(rename = "Array2_of_{T}")]
+pub struct Array2<T> {
+    /// The version of the serialization format of ndarray.
+    pub v: u8,
+    /// The number of rows and columns of the array.
+    pub dim: [usize; 2],
+    /// The elements of the array.
+    pub data: Vec<T>,
+}
@@ -46,6 +46,28 @@ pub enum RoqoqoError {
         /// Norm of (not) unitary matrix.
         norm: f64,
     },
+    /// Error when a matrix given for a gate is not unitary.
+    #[error("Matrix is not unitary. The maximal deviation of U U^dagger from the identity is {deviation:?}.")]
+    NonUnitaryMatrix {
+        /// Maximal absolute deviation of an element of U U^dagger from the identity matrix.
+        deviation: f64,
+    },
+    /// Error when the dimension of a matrix given for a gate does not match the number of qubits of the gate.
+    #[error("Matrix of dimension {rows}x{columns} does not act on {number_qubits} qubits.")]
+    MismatchedUnitaryDimension {
+        /// Number of rows of the matrix.
+        rows: usize,
+        /// Number of columns of the matrix.
+        columns: usize,
+        /// Number of qubits of the gate.
+        number_qubits: usize,
+    },
+    /// Error when the numerical decomposition of a unitary matrix fails.
+    #[error("Decomposition of the unitary matrix failed. {msg}")]
+    DecompositionError {
+        /// Error message.
+        msg: String,
+    },
     /// Error when remapping qubits fails because qubit in operation is not in keys of HashMap/dict.
     #[error("Mapping of qubit {qubit:?} failed")]
     QubitMappingError {
@@ -111,12 +133,140 @@ pub enum RoqoqoError {
         /// Name of the missing register.
         name: String,
     },
+    /// Error when output registers do not match the register definitions of a circuit.
+    #[error("OutputRegister {name} does not match the circuit. {msg}")]
+    OutputRegisterMismatch {
+        /// Name of the register.
+        name: String,
+        /// Error message.
+        msg: String,
+    },
+    /// Error when bit registers or bit strings can not be turned into a histogram.
+    #[error("Bit string histogram error. {msg}")]
+    BitStringHistogramError {
+        /// Error message.
+        msg: String,
+    },
     /// Error occured in basis rotation measurement.
     #[error("Error occured in basis rotation measurement. {msg}")]
     BasisRotationMeasurementError {
         /// Error message.
         msg: String,
     },
+    /// Error when an operation without a unitary matrix representation is used in a unitary context.
+    #[error("Operation {hqslang} has no unitary matrix representation.")]
+    OperationNotUnitary {
+        /// hqslang name of the operation.
+        hqslang: &'static str,
+    },
+    /// Error when the qubits of an operation do not fit the operation.
+    #[error("Invalid qubits for operation {hqslang}. {msg}")]
+    InvalidQubits {
+        /// hqslang name of the operation.
+        hqslang: &'static str,
+        /// Error message.
+        msg: String,
+    },
+    /// Error when classical register values can not be processed by a classical operation or condition.
+    #[error("Classical register {name} can not be processed. {msg}")]
+    ClassicalRegisterError {
+        /// Name of the register.
+        name: String,
+        /// Error message.
+        msg: String,
+    },
+    /// Error when the number of repetitions of a loop is not a non-negative integer.
+    #[error("Number of repetitions {repetitions} is not a non-negative integer.")]
+    InvalidRepetitions {
+        /// Number of repetitions of the loop.
+        repetitions: f64,
+    },
+    /// Error when a circuit can not be exported to OpenQASM.
+    #[error("Export to OpenQASM failed. {msg}")]
+    QasmExportError {
+        /// Error message.
+        msg: String,
+    },
+    /// Error when an OpenQASM program can not be imported.
+    #[error("Import of OpenQASM failed at line {line}, column {column}. {msg}")]
+    QasmImportError {
+        /// Line of the error in the program.
+        line: usize,
+        /// Column of the error in the program.
+        column: usize,
+        /// Error message.
+        msg: String,
+    },
+    /// Error when a circuit can not be exported to Quil.
+    #[error("Export to Quil failed. {msg}")]
+    QuilExportError {
+        /// Error message.
+        msg: String,
+    },
+    /// Error when a Quil program can not be imported.
+    #[error("Import of Quil failed at line {line}, column {column}. {msg}")]
+    QuilImportError {
+        /// Line of the error in the program.
+        line: usize,
+        /// Column of the error in the program.
+        column: usize,
+        /// Error message.
+        msg: String,
+    },
+    /// Error when a circuit in the hqslang text format can not be parsed.
+    #[error("Parsing of hqslang text failed at line {line}, column {column}. {msg}")]
+    HqslangParseError {
+        /// Line of the error in the text.
+        line: usize,
+        /// Column of the error in the text.
+        column: usize,
+        /// Error message.
+        msg: String,
+    },
+    /// Error when a rewrite rule is not valid.
+    #[error("Invalid rewrite rule. {msg}")]
+    RewriteRuleError {
+        /// Error message.
+        msg: String,
+    },
+    /// Error when binding parameters leaves free parameters unbound or binds unknown parameters.
+    #[error("Parameters could not be bound. Unbound parameters: {unbound:?}, unknown parameters: {extra:?}.")]
+    ParameterBindingError {
+        /// Free parameters without a value.
+        unbound: Vec<String>,
+        /// Parameters with a value that do not appear.
+        extra: Vec<String>,
+    },
+    /// Error when the parameter-shift rule can not be applied to an operation containing a parameter.
+    #[error("Parameter-shift rule can not be applied to parameter {name} in operation {hqslang}.")]
+    ParameterShiftNotApplicable {
+        /// Name of the parameter.
+        name: String,
+        /// hqslang name of the operation.
+        hqslang: &'static str,
+    },
+    /// Error when the parameter-shift rule is applied to an expectation value that is not linear in the measured quantities.
+    #[error("Parameter-shift rule can not be applied to the nonlinear expectation value {name}.")]
+    NonlinearExpectationValue {
+        /// Name of the expectation value.
+        name: String,
+    },
+    /// Error when serialized data requires a newer roqoqo version than the one deserializing it.
+    #[error("Data written with roqoqo {data_version} requires roqoqo {minimum_version} or newer, but roqoqo version is {library_version}.")]
+    IncompatibleVersion {
+        /// roqoqo version that serialized the data.
+        data_version: String,
+        /// Minimum roqoqo version required to deserialize the data.
+        minimum_version: String,
+        /// roqoqo version deserializing the data.
+        library_version: String,
+    },
+    /// Error when data can not be serialized or deserialized.
+    #[error("Serialization failed. {msg}")]
+    SerializationError {
+        /// Error message.
+        msg: String,
+    },
     /// Transparent propagation of CalculatorError.
     #[error(transparent)]
     CalculatorError(#[from] CalculatorError),
@@ -170,6 +320,19 @@ mod circuit;
 pub mod operations;
 pub mod prelude;
 pub use circuit::*;
+mod dag;
+mod parameters;
+pub use dag::*;
 pub mod backends;
+pub mod formats;
+pub mod histogram;
+#[cfg(feature = "json_schema")]
+pub mod json_schema;
 pub mod measurements;
+pub mod passes;
 pub mod registers;
+#[cfg(feature = "serialize")]
+pub mod serialization;
+pub mod statistics;
+pub mod unitary;
+pub mod validation;
@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
 /// Collected information for executing a basis rotation measurement.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct BasisRotation {
     /// Constant Circuit that is executed before each Circuit in circuits.
     pub constant_circuit: Option<Circuit>,
@@ -86,6 +87,23 @@ impl Measure for BasisRotation {
 }
 
 impl MeasureExpectationValues for BasisRotation {
+    /// Returns the names of the expectation values that are not linear combinations of Pauli products.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The names of the expectation values given by symbolic expressions.
+    fn nonlinear_expectation_values(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .input
+            .measured_exp_vals
+            .iter()
+            .filter(|(_, evaluation)| matches!(evaluation, PauliProductsToExpVal::Symbolic(_)))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
     // TODO add optional device later for use with flipped measurement
     #[allow(unused_variables)]
     /// Executes the basis rotation measurement.
@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
 /// Collected information for executing a cheated basis rotation measurement.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct CheatedBasisRotation {
     /// Constant Circuit that is executed before each Circuit in circuits.
     pub constant_circuit: Option<Circuit>,
@@ -85,6 +86,23 @@ impl Measure for CheatedBasisRotation {
 }
 
 impl MeasureExpectationValues for CheatedBasisRotation {
+    /// Returns the names of the expectation values that are not linear combinations of Pauli products.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The names of the expectation values given by symbolic expressions.
+    fn nonlinear_expectation_values(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .input
+            .measured_exp_vals
+            .iter()
+            .filter(|(_, evaluation)| matches!(evaluation, PauliProductsToExpVal::Symbolic(_)))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
     /// Executes the cheated basis rotation measurement
     ///
     /// # Arguments
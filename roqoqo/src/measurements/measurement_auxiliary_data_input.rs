@@ -24,6 +24,7 @@ pub type PauliProductMask = Vec<usize>;
 /// Defines how Pauli Products expectation values are post-processed into observable expectation value.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum PauliProductsToExpVal {
     /// Expectation value of observable is a linear combination of Pauli Product expectation values.
     ///
@@ -36,7 +37,13 @@ pub enum PauliProductsToExpVal {
     /// Symbolic expression is given by [qoqo_calculator::CalculatorFloat]
     /// The i-th PauliProduct us hardcoded as the variable `pauli_product_i`
     /// in the string expression of CalculatorFloat.
-    Symbolic(CalculatorFloat),
+    Symbolic(
+        #[cfg_attr(
+            feature = "json_schema",
+            schemars(with = "crate::json_schema::CalculatorFloat")
+        )]
+        CalculatorFloat,
+    ),
 }
 
 /// Provides Necessary Information to run a [crate::measurements::BasisRotation] measurement.
@@ -46,6 +53,7 @@ pub enum PauliProductsToExpVal {
 /// expectation values of pauli products.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct BasisRotationInput {
     /// Collection of PauliProductMasks for each readout register in Measurement.
     pub pauli_product_qubit_masks: HashMap<String, SingleReadoutPauliProductMasks>,
@@ -201,6 +209,7 @@ impl BasisRotationInput {
 /// Is used by the full measurement struct [crate::measurements::CheatedBasisRotation].
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct CheatedBasisRotationInput {
     /// Collection of names and construction methods of  expectation values.
     ///
@@ -320,8 +329,13 @@ impl CheatedBasisRotationInput {
 /// Is used by the full measurement struct [crate::measurements::Cheated].
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct CheatedInput {
     /// Map of expectation values and corresponding operator Matrices on the Hilbert Space.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "HashMap<String, (Vec<(usize, usize, (f64, f64))>, String)>")
+    )]
     pub measured_operators: HashMap<String, (OperatorSparseVec, String)>,
     /// Number of qubits that defines the dimension of the Hilbertspace.
     pub number_qubits: usize,
@@ -21,7 +21,7 @@
 //! # Note
 //! The functionality to **perform** the actual measurement is provided by the measurement operations [crate::operations].
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[doc(hidden)]
 mod measurement_auxiliary_data_input;
@@ -38,7 +38,11 @@ pub use cheated_measurement::*;
 #[doc(hidden)]
 mod classical_register_measurement;
 pub use classical_register_measurement::*;
+#[doc(hidden)]
+mod parameter_shift_gradient;
+pub use parameter_shift_gradient::*;
 
+use crate::parameters::check_parameter_binding;
 use crate::registers::BitOutputRegister;
 use crate::{
     registers::{ComplexOutputRegister, FloatOutputRegister},
@@ -123,6 +127,60 @@ pub trait Measure: PartialEq + Clone {
         &self,
         substituted_parameters: HashMap<String, f64>,
     ) -> Result<Self, RoqoqoError>;
+
+    /// Returns the names of all free symbolic parameters in the constant Circuit and the circuits.
+    ///
+    /// # Returns
+    ///
+    /// * `HashSet<String>` - The names of the symbolic parameters.
+    fn free_parameters(&self) -> HashSet<String> {
+        let mut parameters: HashSet<String> = match self.constant_circuit() {
+            Some(constant_circuit) => constant_circuit.free_parameters(),
+            None => HashSet::new(),
+        };
+        for circuit in self.circuits() {
+            parameters.extend(circuit.free_parameters());
+        }
+        parameters
+    }
+
+    /// Returns clone of Measurement with symbolic parameters replaced after checking that all of them are bound.
+    ///
+    /// Parameters with a default value given by an [crate::operations::InputSymbolic] definition
+    /// in the constant Circuit or in a circuit do not need to be bound.
+    /// The defaults of the constant Circuit apply to all circuits.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The values of the symbolic parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The Measurement with the parameters substituted.
+    /// * `Err(RoqoqoError::ParameterBindingError)` - Free parameters are not bound or unknown parameters are given.
+    /// * `Err(RoqoqoError)` - The substitution failed.
+    fn bind_parameters(&self, parameters: HashMap<String, f64>) -> Result<Self, RoqoqoError> {
+        let (constant_free, constant_defaults) = match self.constant_circuit() {
+            Some(constant_circuit) => (
+                constant_circuit.free_parameters(),
+                constant_circuit.default_parameters(),
+            ),
+            None => (HashSet::new(), HashMap::new()),
+        };
+        let mut circuits: Vec<(HashSet<String>, HashMap<String, f64>)> =
+            vec![(constant_free.clone(), constant_defaults.clone())];
+        for circuit in self.circuits() {
+            let mut free = constant_free.clone();
+            free.extend(circuit.free_parameters());
+            let mut defaults = constant_defaults.clone();
+            defaults.extend(circuit.default_parameters());
+            circuits.push((free, defaults));
+        }
+        check_parameter_binding(&circuits, &parameters)?;
+        let mut substituted_parameters = constant_defaults;
+        substituted_parameters.extend(parameters);
+        self.substitute_parameters(substituted_parameters)
+    }
 }
 
 /// Allows generic interfacing with roqoqo measurements that evaluate expectation values.
@@ -203,4 +261,16 @@ pub trait MeasureExpectationValues: PartialEq + Clone + Measure {
         float_registers: HashMap<String, FloatOutputRegister>,
         complex_registers: HashMap<String, ComplexOutputRegister>,
     ) -> Result<Option<HashMap<String, f64>>, RoqoqoError>;
+
+    /// Returns the names of the expectation values that are not linear in the measured quantities.
+    ///
+    /// Derivatives of these expectation values can not be obtained from the derivatives of the measured quantities
+    /// by the parameter-shift rule.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The names of the nonlinear expectation values, by default none.
+    fn nonlinear_expectation_values(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,569 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::dag::register_access;
+use crate::measurements::{Measure, MeasureExpectationValues};
+use crate::operations::*;
+use crate::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister};
+use crate::{Circuit, RoqoqoError};
+use qoqo_calculator::{Calculator, CalculatorFloat};
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::PI;
+
+/// Derivatives of expectation values with the names of the expectation value and the parameter as key.
+pub type ExpectationValueDerivatives = HashMap<(String, String), f64>;
+
+/// Gradient measurement using the parameter-shift rule.
+///
+/// Wraps a measurement of expectation values and measures the derivatives of all expectation values
+/// with respect to the symbolic parameters of the measurement circuits.
+/// For every occurrence of a parameter in the angle of a supported gate,
+/// copies of the circuit with the angle shifted are created and measured.
+/// The derivative is obtained by summing the shifted expectation values weighted with the coefficients
+/// of the shift rule and the derivative of the angle expression with respect to the parameter.
+///
+/// Gates whose generator has the eigenvalues ±1/2 ([RotateX], [RotateY], [RotateZ],
+/// [RotateAroundSphericalAxis], [ControlledPhaseShift], [VariableMSXX]) use the two-term shift rule
+/// with shifts of ±π/2.
+/// [XY] gates, whose generator has the eigenvalues 0 and ±1/2, use the generalized four-term shift rule.
+/// The angle of a gate containing a parameter must be that single parameter,
+/// gates with angles given by other expressions of parameters are rejected.
+/// Only expectation values that are linear combinations of the measured quantities can be differentiated.
+///
+/// The constant circuit of the wrapped measurement is prepended to each circuit.
+/// The readout registers of each circuit are renamed with the suffix `_parameter_shift_<index>`
+/// so that the registers of the unshifted and shifted circuits can be told apart when evaluating.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ParameterShiftGradient<T> {
+    /// The wrapped measurement with the symbolic parameters replaced.
+    measurement: T,
+    /// Always None, the constant circuit of the wrapped measurement is part of each circuit.
+    constant_circuit: Option<Circuit>,
+    /// The unshifted circuits of the wrapped measurement followed by the shifted circuits.
+    circuits: Vec<Circuit>,
+    /// The original names of the readout registers of each circuit.
+    circuit_registers: Vec<Vec<String>>,
+    /// The names of the parameters the derivatives are measured for.
+    parameters: Vec<String>,
+    /// The shifted circuits with their contributions to the derivatives.
+    shifts: Vec<ParameterShift>,
+}
+
+/// A shifted circuit of a [ParameterShiftGradient].
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+struct ParameterShift {
+    /// The index of the unshifted circuit of the wrapped measurement that is replaced.
+    replaced_circuit: usize,
+    /// The index of the shifted circuit.
+    circuit: usize,
+    /// The weight of the shifted expectation values in the derivative by each parameter.
+    weights: HashMap<String, f64>,
+}
+
+impl<T> ParameterShiftGradient<T>
+where
+    T: MeasureExpectationValues,
+{
+    /// Creates a new gradient measurement for the given parameter values.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurement` - The measurement of the expectation values with symbolic parameters.
+    /// * `parameters` - The values of the symbolic parameters. The derivatives are measured with respect to all of them.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The new gradient measurement.
+    /// * `Err(RoqoqoError::NonlinearExpectationValue)` - An expectation value of the measurement is not linear in the measured quantities.
+    /// * `Err(RoqoqoError::ParameterShiftNotApplicable)` - A parameter appears in an operation without a parameter-shift rule or in an angle that is not the single parameter.
+    /// * `Err(RoqoqoError::CalculatorError)` - A symbolic parameter could not be replaced.
+    pub fn new(measurement: T, parameters: HashMap<String, f64>) -> Result<Self, RoqoqoError> {
+        if let Some(name) = measurement
+            .nonlinear_expectation_values()
+            .into_iter()
+            .next()
+        {
+            return Err(RoqoqoError::NonlinearExpectationValue { name });
+        }
+        let mut parameter_names: Vec<String> = parameters.keys().cloned().collect();
+        parameter_names.sort();
+
+        let full_circuits: Vec<Circuit> = measurement
+            .circuits()
+            .map(|circuit| match measurement.constant_circuit() {
+                Some(constant_circuit) => constant_circuit.clone() + circuit,
+                None => circuit.clone(),
+            })
+            .collect();
+
+        let mut circuits: Vec<Circuit> = Vec::new();
+        let mut circuit_registers: Vec<Vec<String>> = Vec::new();
+        let mut substituted_circuits: Vec<Circuit> = Vec::new();
+        for (index, circuit) in full_circuits.iter().enumerate() {
+            let substituted = circuit.substitute_parameters(&mut calculator(&parameters))?;
+            circuits.push(rename_registers(&substituted, &register_suffix(index)));
+            circuit_registers.push(written_registers(&substituted));
+            substituted_circuits.push(substituted);
+        }
+
+        let mut shifts: Vec<ParameterShift> = Vec::new();
+        for (circuit_index, circuit) in full_circuits.iter().enumerate() {
+            for (operation_index, operation) in circuit.operations().iter().enumerate() {
+                let used_parameter = match operation
+                    .free_parameters()
+                    .into_iter()
+                    .filter(|symbol| parameters.contains_key(symbol))
+                    .min()
+                {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let (angle, frequency, number_frequencies) = match shift_rule_parameters(operation)
+                {
+                    Some(rule) => rule,
+                    None => {
+                        return Err(RoqoqoError::ParameterShiftNotApplicable {
+                            name: used_parameter,
+                            hqslang: operation.hqslang(),
+                        })
+                    }
+                };
+                match angle {
+                    CalculatorFloat::Str(expression) if expression.trim() == used_parameter => (),
+                    _ => {
+                        return Err(RoqoqoError::ParameterShiftNotApplicable {
+                            name: used_parameter,
+                            hqslang: operation.hqslang(),
+                        })
+                    }
+                }
+                let substituted_operation =
+                    &substituted_circuits[circuit_index].operations()[operation_index];
+                for (shift, coefficient) in shift_rule(frequency, number_frequencies) {
+                    let mut shifted_circuit = Circuit::new();
+                    for definition in substituted_circuits[circuit_index].definitions().iter() {
+                        shifted_circuit.add_operation(definition.clone());
+                    }
+                    for (index, op) in substituted_circuits[circuit_index]
+                        .operations()
+                        .iter()
+                        .enumerate()
+                    {
+                        if index == operation_index {
+                            shifted_circuit
+                                .add_operation(shifted_operation(substituted_operation, shift));
+                        } else {
+                            shifted_circuit.add_operation(op.clone());
+                        }
+                    }
+                    shifts.push(ParameterShift {
+                        replaced_circuit: circuit_index,
+                        circuit: circuits.len(),
+                        weights: vec![(used_parameter.clone(), coefficient)]
+                            .into_iter()
+                            .collect(),
+                    });
+                    circuits.push(rename_registers(
+                        &shifted_circuit,
+                        &register_suffix(circuits.len()),
+                    ));
+                    circuit_registers.push(circuit_registers[circuit_index].clone());
+                }
+            }
+        }
+
+        Ok(ParameterShiftGradient {
+            measurement: measurement.substitute_parameters(parameters)?,
+            constant_circuit: None,
+            circuits,
+            circuit_registers,
+            parameters: parameter_names,
+            shifts,
+        })
+    }
+
+    /// Returns the wrapped measurement with the symbolic parameters replaced.
+    ///
+    /// # Returns
+    ///
+    /// * `&T` - The wrapped measurement.
+    pub fn measurement(&self) -> &T {
+        &self.measurement
+    }
+
+    /// Returns the number of shifted circuits.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of circuits in addition to the circuits of the wrapped measurement.
+    pub fn number_shifted_circuits(&self) -> usize {
+        self.shifts.len()
+    }
+
+    /// Evaluates the derivatives of the expectation values.
+    ///
+    /// # Arguments
+    ///
+    /// * `bit_registers` - The classical bit registers as a HashMap with the register name as key.
+    /// * `float_registers` - The classical float registers as a HashMap with the register name as key.
+    /// * `complex_registers` - The classical complex registers as a HashMap with the register name as key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(ExpectationValueDerivatives))` - The derivatives with the names of the expectation value and the parameter as key.
+    /// * `Ok(None)` - The measurement did not fail but is incomplete. A new round of measurements is needed.
+    /// * `Err(RoqoqoError)` - The evaluation of the wrapped measurement failed.
+    pub fn evaluate(
+        &self,
+        bit_registers: HashMap<String, BitOutputRegister>,
+        float_registers: HashMap<String, FloatOutputRegister>,
+        complex_registers: HashMap<String, ComplexOutputRegister>,
+    ) -> Result<Option<ExpectationValueDerivatives>, RoqoqoError> {
+        let unshifted: Vec<usize> = (0..self.circuits.len() - self.shifts.len()).collect();
+        let expectation_values = match self.measurement.evaluate(
+            self.collect_registers(&unshifted, &bit_registers),
+            self.collect_registers(&unshifted, &float_registers),
+            self.collect_registers(&unshifted, &complex_registers),
+        )? {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+        let mut derivatives: ExpectationValueDerivatives = HashMap::new();
+        for name in expectation_values.keys() {
+            for parameter in self.parameters.iter() {
+                derivatives.insert((name.clone(), parameter.clone()), 0.0);
+            }
+        }
+        for shift in self.shifts.iter() {
+            let mut circuits = unshifted.clone();
+            circuits[shift.replaced_circuit] = shift.circuit;
+            let shifted_values = match self.measurement.evaluate(
+                self.collect_registers(&circuits, &bit_registers),
+                self.collect_registers(&circuits, &float_registers),
+                self.collect_registers(&circuits, &complex_registers),
+            )? {
+                Some(values) => values,
+                None => return Ok(None),
+            };
+            for (name, value) in shifted_values.iter() {
+                for (parameter, weight) in shift.weights.iter() {
+                    *derivatives
+                        .entry((name.clone(), parameter.clone()))
+                        .or_insert(0.0) += weight * value;
+                }
+            }
+        }
+        Ok(Some(derivatives))
+    }
+
+    /// Collects the registers of the selected circuits under their original names.
+    ///
+    /// Registers written by several circuits are appended in the order of the circuits,
+    /// the same way a backend combines the registers of the circuits of a measurement.
+    fn collect_registers<R: Clone>(
+        &self,
+        circuits: &[usize],
+        registers: &HashMap<String, Vec<R>>,
+    ) -> HashMap<String, Vec<R>> {
+        let mut collected: HashMap<String, Vec<R>> = HashMap::new();
+        for circuit in circuits.iter() {
+            let suffix = register_suffix(*circuit);
+            for name in self.circuit_registers[*circuit].iter() {
+                if let Some(register) = registers.get(&format!("{}{}", name, suffix)) {
+                    collected
+                        .entry(name.clone())
+                        .or_default()
+                        .extend(register.iter().cloned());
+                }
+            }
+        }
+        collected
+    }
+}
+
+impl<T> Measure for ParameterShiftGradient<T>
+where
+    T: MeasureExpectationValues,
+{
+    /// Returns iterator over circuits for measurement.
+    ///
+    /// # Returns
+    ///
+    /// * `Box<dyn Iterator<Item = &'a Circuit> + 'a>` - The unshifted and shifted quantum circuits.
+    fn circuits<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Circuit> + 'a> {
+        Box::new(self.circuits.iter())
+    }
+
+    /// Returns the constant Circuit that is executed before each Circuit in circuits.
+    ///
+    /// # Returns
+    ///
+    /// * `&None` - The constant circuit of the wrapped measurement is part of each circuit.
+    fn constant_circuit(&self) -> &Option<Circuit> {
+        &self.constant_circuit
+    }
+
+    /// Returns clone of Measurement with symbolic parameters replaced.
+    ///
+    /// The symbolic parameters are already replaced when the gradient measurement is created.
+    ///
+    /// # Arguments
+    ///
+    /// * `_substituted_parameters` - The HashMap containing the substitutions to use in the Circuit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - A clone of the gradient measurement.
+    fn substitute_parameters(
+        &self,
+        _substituted_parameters: HashMap<String, f64>,
+    ) -> Result<Self, RoqoqoError> {
+        Ok(self.clone())
+    }
+}
+
+/// Returns the suffix added to the names of the registers of a circuit.
+fn register_suffix(circuit: usize) -> String {
+    format!("_parameter_shift_{}", circuit)
+}
+
+/// Returns a calculator with the parameters set.
+fn calculator(parameters: &HashMap<String, f64>) -> Calculator {
+    let mut calculator = Calculator::new();
+    for (name, value) in parameters.iter() {
+        calculator.set_variable(name, *value);
+    }
+    calculator
+}
+
+/// Returns the shifts of the angle and the coefficients of the generalized parameter-shift rule.
+///
+/// The expectation value is a trigonometric polynomial in the angle
+/// with the frequencies `frequency`, `2 * frequency`, ..., `number_frequencies * frequency`.
+fn shift_rule(frequency: f64, number_frequencies: usize) -> Vec<(f64, f64)> {
+    let number_frequencies_f64 = number_frequencies as f64;
+    (1..=2 * number_frequencies)
+        .map(|mu| {
+            let x = (2.0 * mu as f64 - 1.0) * PI / (2.0 * number_frequencies_f64);
+            let sign = if mu % 2 == 1 { 1.0 } else { -1.0 };
+            let coefficient =
+                sign * frequency / (4.0 * number_frequencies_f64 * (x / 2.0).sin().powi(2));
+            (x / frequency, coefficient)
+        })
+        .collect()
+}
+
+/// Returns the angle and the spectrum of a gate supporting the parameter-shift rule.
+///
+/// # Returns
+///
+/// * `Some((&CalculatorFloat, f64, usize))` - The angle, the lowest frequency and the number of frequencies.
+/// * `None` - The operation does not support the parameter-shift rule.
+fn shift_rule_parameters(operation: &Operation) -> Option<(&CalculatorFloat, f64, usize)> {
+    match operation {
+        Operation::RotateX(op) => Some((op.theta(), 1.0, 1)),
+        Operation::RotateY(op) => Some((op.theta(), 1.0, 1)),
+        Operation::RotateZ(op) => Some((op.theta(), 1.0, 1)),
+        Operation::RotateAroundSphericalAxis(op)
+            if !op.spherical_theta().is_float() || !op.spherical_phi().is_float() =>
+        {
+            None
+        }
+        Operation::RotateAroundSphericalAxis(op) => Some((op.theta(), 1.0, 1)),
+        Operation::ControlledPhaseShift(op) => Some((op.theta(), 1.0, 1)),
+        Operation::VariableMSXX(op) => Some((op.theta(), 1.0, 1)),
+        Operation::XY(op) => Some((op.theta(), 0.5, 2)),
+        _ => None,
+    }
+}
+
+/// Returns a copy of a gate supporting the parameter-shift rule with the angle shifted.
+fn shifted_operation(operation: &Operation, shift: f64) -> Operation {
+    match operation {
+        Operation::RotateX(op) => RotateX::new(*op.qubit(), op.theta() + shift).into(),
+        Operation::RotateY(op) => RotateY::new(*op.qubit(), op.theta() + shift).into(),
+        Operation::RotateZ(op) => RotateZ::new(*op.qubit(), op.theta() + shift).into(),
+        Operation::RotateAroundSphericalAxis(op) => RotateAroundSphericalAxis::new(
+            *op.qubit(),
+            op.theta() + shift,
+            op.spherical_theta().clone(),
+            op.spherical_phi().clone(),
+        )
+        .into(),
+        Operation::ControlledPhaseShift(op) => {
+            ControlledPhaseShift::new(*op.control(), *op.target(), op.theta() + shift).into()
+        }
+        Operation::VariableMSXX(op) => {
+            VariableMSXX::new(*op.control(), *op.target(), op.theta() + shift).into()
+        }
+        Operation::XY(op) => XY::new(*op.control(), *op.target(), op.theta() + shift).into(),
+        _ => operation.clone(),
+    }
+}
+
+/// Returns the names of the registers defined or written in a circuit.
+fn written_registers(circuit: &Circuit) -> Vec<String> {
+    let mut registers: HashSet<String> = HashSet::new();
+    for definition in circuit.definitions().iter() {
+        match definition {
+            Operation::DefinitionBit(op) => registers.insert(op.name().clone()),
+            Operation::DefinitionFloat(op) => registers.insert(op.name().clone()),
+            Operation::DefinitionComplex(op) => registers.insert(op.name().clone()),
+            Operation::DefinitionUsize(op) => registers.insert(op.name().clone()),
+            _ => false,
+        };
+    }
+    for operation in circuit.operations().iter() {
+        registers.extend(register_access(operation).1);
+    }
+    let mut registers: Vec<String> = registers.into_iter().collect();
+    registers.sort();
+    registers
+}
+
+/// Returns a copy of a circuit with a suffix added to the names of all classical registers.
+fn rename_registers(circuit: &Circuit, suffix: &str) -> Circuit {
+    let rename = |name: &String| format!("{}{}", name, suffix);
+    let mut renamed = Circuit::new();
+    for operation in circuit.iter() {
+        let new_operation: Operation = match operation {
+            Operation::DefinitionBit(op) => {
+                DefinitionBit::new(rename(op.name()), *op.length(), *op.is_output()).into()
+            }
+            Operation::DefinitionFloat(op) => {
+                DefinitionFloat::new(rename(op.name()), *op.length(), *op.is_output()).into()
+            }
+            Operation::DefinitionComplex(op) => {
+                DefinitionComplex::new(rename(op.name()), *op.length(), *op.is_output()).into()
+            }
+            Operation::DefinitionUsize(op) => {
+                DefinitionUsize::new(rename(op.name()), *op.length(), *op.is_output()).into()
+            }
+            Operation::MeasureQubit(op) => {
+                MeasureQubit::new(*op.qubit(), rename(op.readout()), *op.readout_index()).into()
+            }
+            Operation::PragmaRepeatedMeasurement(op) => PragmaRepeatedMeasurement::new(
+                rename(op.readout()),
+                op.qubit_mapping().clone(),
+                *op.number_measurements(),
+            )
+            .into(),
+            Operation::PragmaGetStateVector(op) => PragmaGetStateVector::new(
+                rename(op.readout()),
+                op.circuit().as_ref().map(|c| rename_registers(c, suffix)),
+            )
+            .into(),
+            Operation::PragmaGetDensityMatrix(op) => PragmaGetDensityMatrix::new(
+                rename(op.readout()),
+                op.circuit().as_ref().map(|c| rename_registers(c, suffix)),
+            )
+            .into(),
+            Operation::PragmaGetOccupationProbability(op) => PragmaGetOccupationProbability::new(
+                rename(op.readout()),
+                op.circuit().as_ref().map(|c| rename_registers(c, suffix)),
+            )
+            .into(),
+            Operation::PragmaGetPauliProduct(op) => PragmaGetPauliProduct::new(
+                op.qubit_paulis().clone(),
+                rename(op.readout()),
+                rename_registers(op.circuit(), suffix),
+            )
+            .into(),
+            Operation::PragmaSetNumberOfMeasurements(op) => {
+                PragmaSetNumberOfMeasurements::new(*op.number_measurements(), rename(op.readout()))
+                    .into()
+            }
+            Operation::PragmaConditional(op) => PragmaConditional::new(
+                rename(op.condition_register()),
+                *op.condition_index(),
+                rename_registers(op.circuit(), suffix),
+            )
+            .into(),
+            Operation::PragmaConditionalPattern(op) => PragmaConditionalPattern::new(
+                rename(op.condition_register()),
+                op.condition().clone(),
+                rename_registers(op.circuit(), suffix),
+            )
+            .into(),
+            Operation::PragmaLoop(op) => PragmaLoop::new(
+                op.repetitions().clone(),
+                rename_registers(op.circuit(), suffix),
+            )
+            .into(),
+            Operation::PragmaRepeatUntilSuccess(op) => PragmaRepeatUntilSuccess::new(
+                rename(op.condition_register()),
+                *op.condition_index(),
+                *op.max_iterations(),
+                rename_registers(op.circuit(), suffix),
+            )
+            .into(),
+            Operation::ClassicalNot(op) => ClassicalNot::new(
+                rename(op.register()),
+                *op.index(),
+                rename(op.output_register()),
+                *op.output_index(),
+            )
+            .into(),
+            Operation::ClassicalCopy(op) => ClassicalCopy::new(
+                rename(op.register()),
+                *op.index(),
+                rename(op.output_register()),
+                *op.output_index(),
+            )
+            .into(),
+            Operation::ClassicalXor(op) => ClassicalXor::new(
+                rename(op.register()),
+                op.indices().clone(),
+                rename(op.output_register()),
+                *op.output_index(),
+            )
+            .into(),
+            Operation::ClassicalAnd(op) => ClassicalAnd::new(
+                rename(op.register()),
+                op.indices().clone(),
+                rename(op.output_register()),
+                *op.output_index(),
+            )
+            .into(),
+            Operation::ClassicalOr(op) => ClassicalOr::new(
+                rename(op.register()),
+                op.indices().clone(),
+                rename(op.output_register()),
+                *op.output_index(),
+            )
+            .into(),
+            Operation::ClassicalGreaterThan(op) => ClassicalGreaterThan::new(
+                rename(op.register()),
+                *op.index(),
+                op.threshold().clone(),
+                rename(op.output_register()),
+                *op.output_index(),
+            )
+            .into(),
+            Operation::ClassicalLessThan(op) => ClassicalLessThan::new(
+                rename(op.register()),
+                *op.index(),
+                op.threshold().clone(),
+                rename(op.output_register()),
+                *op.output_index(),
+            )
+            .into(),
+            _ => operation.clone(),
+        };
+        renamed.add_operation(new_operation);
+    }
+    renamed
+}
@@ -2,6 +2,7 @@ use crate::operations::*;
 #[doc = r" Enum of all Operations implementing [Operate]"]
 #[derive(Debug, Clone, PartialEq, InvolveQubits, Operate, Substitute)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum Operation {
     #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for SingleQubitGate"]
@@ -97,6 +98,15 @@ pub enum Operation {
     #[doc = "Variant for PragmaConditional"]
     PragmaConditional(PragmaConditional),
     #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for PragmaConditionalPattern"]
+    PragmaConditionalPattern(PragmaConditionalPattern),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for PragmaLoop"]
+    PragmaLoop(PragmaLoop),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for PragmaRepeatUntilSuccess"]
+    PragmaRepeatUntilSuccess(PragmaRepeatUntilSuccess),
+    #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for CNOT"]
     CNOT(CNOT),
     #[allow(clippy::upper_case_acronyms)]
@@ -160,6 +170,9 @@ pub enum Operation {
     #[doc = "Variant for PhaseShiftedControlledZ"]
     PhaseShiftedControlledZ(PhaseShiftedControlledZ),
     #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for TwoQubitGate"]
+    TwoQubitGate(Box<TwoQubitGate>),
+    #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for MeasureQubit"]
     MeasureQubit(MeasureQubit),
     #[allow(clippy::upper_case_acronyms)]
@@ -192,6 +205,48 @@ pub enum Operation {
     #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for InputSymbolic"]
     InputSymbolic(InputSymbolic),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for MultiQubitMS"]
+    MultiQubitMS(MultiQubitMS),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for MultiQubitGate"]
+    MultiQubitGate(MultiQubitGate),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for Toffoli"]
+    Toffoli(Toffoli),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ControlledControlledPauliZ"]
+    ControlledControlledPauliZ(ControlledControlledPauliZ),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ControlledControlledPhaseShift"]
+    ControlledControlledPhaseShift(ControlledControlledPhaseShift),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for MultiControlledGate"]
+    MultiControlledGate(MultiControlledGate),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for PauliProductRotation"]
+    PauliProductRotation(PauliProductRotation),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ClassicalNot"]
+    ClassicalNot(ClassicalNot),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ClassicalCopy"]
+    ClassicalCopy(ClassicalCopy),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ClassicalXor"]
+    ClassicalXor(ClassicalXor),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ClassicalAnd"]
+    ClassicalAnd(ClassicalAnd),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ClassicalOr"]
+    ClassicalOr(ClassicalOr),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ClassicalGreaterThan"]
+    ClassicalGreaterThan(ClassicalGreaterThan),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ClassicalLessThan"]
+    ClassicalLessThan(ClassicalLessThan),
 }
 #[doc = r" Enum of all Operations implementing [OperateSingleQubit]"]
 #[derive(
@@ -205,6 +260,7 @@ pub enum Operation {
     OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum SingleQubitOperation {
     #[doc = "Variant for SingleQubitGate"]
     SingleQubitGate(SingleQubitGate),
@@ -252,6 +308,7 @@ pub enum SingleQubitOperation {
     Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum TwoQubitOperation {
     #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for CNOT"]
@@ -316,6 +373,9 @@ pub enum TwoQubitOperation {
     #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for PhaseShiftedControlledZ"]
     PhaseShiftedControlledZ(PhaseShiftedControlledZ),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for TwoQubitGate"]
+    TwoQubitGate(Box<TwoQubitGate>),
 }
 #[doc = r" Enum of all Operations implementing [OperateMultiQubit]"]
 #[derive(
@@ -329,6 +389,7 @@ pub enum TwoQubitOperation {
     OperateMultiQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum MultiQubitOperation {
     #[doc = "Variant for PragmaOverrotation"]
     PragmaOverrotation(PragmaOverrotation),
@@ -340,12 +401,27 @@ pub enum MultiQubitOperation {
     PragmaStartDecompositionBlock(PragmaStartDecompositionBlock),
     #[doc = "Variant for PragmaStopDecompositionBlock"]
     PragmaStopDecompositionBlock(PragmaStopDecompositionBlock),
+    #[doc = "Variant for MultiQubitMS"]
+    MultiQubitMS(MultiQubitMS),
+    #[doc = "Variant for MultiQubitGate"]
+    MultiQubitGate(MultiQubitGate),
+    #[doc = "Variant for Toffoli"]
+    Toffoli(Toffoli),
+    #[doc = "Variant for ControlledControlledPauliZ"]
+    ControlledControlledPauliZ(ControlledControlledPauliZ),
+    #[doc = "Variant for ControlledControlledPhaseShift"]
+    ControlledControlledPhaseShift(ControlledControlledPhaseShift),
+    #[doc = "Variant for MultiControlledGate"]
+    MultiControlledGate(MultiControlledGate),
+    #[doc = "Variant for PauliProductRotation"]
+    PauliProductRotation(PauliProductRotation),
 }
 #[doc = r" Enum of all Operations implementing [OperatePragma]"]
 #[derive(
     Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum PragmaOperation {
     #[doc = "Variant for PragmaSetNumberOfMeasurements"]
     PragmaSetNumberOfMeasurements(PragmaSetNumberOfMeasurements),
@@ -383,6 +459,12 @@ pub enum PragmaOperation {
     PragmaGeneralNoise(PragmaGeneralNoise),
     #[doc = "Variant for PragmaConditional"]
     PragmaConditional(PragmaConditional),
+    #[doc = "Variant for PragmaConditionalPattern"]
+    PragmaConditionalPattern(PragmaConditionalPattern),
+    #[doc = "Variant for PragmaLoop"]
+    PragmaLoop(PragmaLoop),
+    #[doc = "Variant for PragmaRepeatUntilSuccess"]
+    PragmaRepeatUntilSuccess(PragmaRepeatUntilSuccess),
     #[doc = "Variant for PragmaGetStateVector"]
     PragmaGetStateVector(PragmaGetStateVector),
     #[doc = "Variant for PragmaGetDensityMatrix"]
@@ -407,6 +489,7 @@ pub enum PragmaOperation {
     OperatePragmaNoise,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum PragmaNoiseOperation {
     #[doc = "Variant for PragmaDamping"]
     PragmaDamping(PragmaDamping),
@@ -422,6 +505,7 @@ pub enum PragmaNoiseOperation {
     Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, OperateGate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum GateOperation {
     #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for SingleQubitGate"]
@@ -525,6 +609,30 @@ pub enum GateOperation {
     #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for PhaseShiftedControlledZ"]
     PhaseShiftedControlledZ(PhaseShiftedControlledZ),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for TwoQubitGate"]
+    TwoQubitGate(Box<TwoQubitGate>),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for MultiQubitMS"]
+    MultiQubitMS(MultiQubitMS),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for MultiQubitGate"]
+    MultiQubitGate(MultiQubitGate),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for Toffoli"]
+    Toffoli(Toffoli),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ControlledControlledPauliZ"]
+    ControlledControlledPauliZ(ControlledControlledPauliZ),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for ControlledControlledPhaseShift"]
+    ControlledControlledPhaseShift(ControlledControlledPhaseShift),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for MultiControlledGate"]
+    MultiControlledGate(MultiControlledGate),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for PauliProductRotation"]
+    PauliProductRotation(PauliProductRotation),
 }
 #[doc = r" Enum of all Operations implementing [Rotate]"]
 #[allow(clippy::upper_case_acronyms)]
@@ -540,6 +648,7 @@ pub enum GateOperation {
     Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum Rotation {
     #[doc = "Variant for RotateZ"]
     RotateZ(RotateZ),
@@ -559,12 +668,19 @@ pub enum Rotation {
     GivensRotation(GivensRotation),
     #[doc = "Variant for GivensRotationLittleEndian"]
     GivensRotationLittleEndian(GivensRotationLittleEndian),
+    #[doc = "Variant for MultiQubitMS"]
+    MultiQubitMS(MultiQubitMS),
+    #[doc = "Variant for ControlledControlledPhaseShift"]
+    ControlledControlledPhaseShift(ControlledControlledPhaseShift),
+    #[doc = "Variant for PauliProductRotation"]
+    PauliProductRotation(PauliProductRotation),
 }
 #[doc = r" Enum of all Operations implementing [Define]"]
 #[derive(
     Debug, Clone, PartialEq, InvolveQubits, Operate, OperateTryFromEnum, Substitute, Define,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum Definition {
     #[doc = "Variant for DefinitionFloat"]
     DefinitionFloat(DefinitionFloat),
@@ -590,6 +706,7 @@ pub enum Definition {
     OperateConstantGate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum ConstantGateOperation {}
 #[doc = r" Enum of all Operations implementing [OperateSingleQubitGate]"]
 #[derive(
@@ -605,6 +722,7 @@ pub enum ConstantGateOperation {}
     OperateSingleQubitGate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum SingleQubitGateOperation {
     #[doc = "Variant for SingleQubitGate"]
     SingleQubitGate(SingleQubitGate),
@@ -647,6 +765,7 @@ pub enum SingleQubitGateOperation {
     OperateTwoQubitGate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum TwoQubitGateOperation {
     #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for CNOT"]
@@ -711,6 +830,9 @@ pub enum TwoQubitGateOperation {
     #[allow(clippy::upper_case_acronyms)]
     #[doc = "Variant for PhaseShiftedControlledZ"]
     PhaseShiftedControlledZ(PhaseShiftedControlledZ),
+    #[allow(clippy::upper_case_acronyms)]
+    #[doc = "Variant for TwoQubitGate"]
+    TwoQubitGate(Box<TwoQubitGate>),
 }
 #[doc = r" Enum of all Operations implementing [OperateMultiQubitGate]"]
 #[derive(
@@ -726,4 +848,20 @@ pub enum TwoQubitGateOperation {
     OperateMultiQubitGate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-pub enum MultiQubitGateOperation {}
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum MultiQubitGateOperation {
+    #[doc = "Variant for MultiQubitMS"]
+    MultiQubitMS(MultiQubitMS),
+    #[doc = "Variant for MultiQubitGate"]
+    MultiQubitGate(MultiQubitGate),
+    #[doc = "Variant for Toffoli"]
+    Toffoli(Toffoli),
+    #[doc = "Variant for ControlledControlledPauliZ"]
+    ControlledControlledPauliZ(ControlledControlledPauliZ),
+    #[doc = "Variant for ControlledControlledPhaseShift"]
+    ControlledControlledPhaseShift(ControlledControlledPhaseShift),
+    #[doc = "Variant for MultiControlledGate"]
+    MultiControlledGate(MultiControlledGate),
+    #[doc = "Variant for PauliProductRotation"]
+    PauliProductRotation(PauliProductRotation),
+}
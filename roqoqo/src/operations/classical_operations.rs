@@ -0,0 +1,406 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Classical operations processing the values of the classical registers during the execution of a Circuit.
+//!
+//! Classical operations read bits from a [crate::registers::BitRegister] or floats from a
+//! [crate::registers::FloatRegister] defined with [crate::operations::DefinitionBit] or
+//! [crate::operations::DefinitionFloat] and write a single result bit into a [crate::registers::BitRegister].
+//! The result bit can then be used as the condition of a [crate::operations::PragmaConditional].
+//! Classical operations do not act on qubits.
+//!
+//! Every classical operation provides an `evaluate` function computing the result bit from the
+//! values of the input register, which backends can use to execute the operation.
+
+use crate::operations::{InvolveQubits, InvolvedQubits, Operate, RoqoqoError, Substitute};
+use qoqo_calculator::CalculatorFloat;
+
+/// Returns the bit at an index of a bit register.
+pub(crate) fn register_bit(register: &[bool], name: &str, index: usize) -> Result<bool, RoqoqoError> {
+    register
+        .get(index)
+        .copied()
+        .ok_or_else(|| RoqoqoError::ClassicalRegisterError {
+            name: name.to_string(),
+            msg: format!(
+                "Index {} is out of range of register with length {}",
+                index,
+                register.len()
+            ),
+        })
+}
+
+/// Returns the bits at the indices of a bit register.
+fn register_bits(register: &[bool], name: &str, indices: &[usize]) -> Result<Vec<bool>, RoqoqoError> {
+    indices
+        .iter()
+        .map(|index| register_bit(register, name, *index))
+        .collect()
+}
+
+/// Returns the float at an index of a float register.
+fn register_float(register: &[f64], name: &str, index: usize) -> Result<f64, RoqoqoError> {
+    register
+        .get(index)
+        .copied()
+        .ok_or_else(|| RoqoqoError::ClassicalRegisterError {
+            name: name.to_string(),
+            msg: format!(
+                "Index {} is out of range of register with length {}",
+                index,
+                register.len()
+            ),
+        })
+}
+
+/// The classical NOT operation.
+///
+/// Writes the negation of a bit in a [crate::registers::BitRegister] to the output bit.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::Substitute)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ClassicalNot {
+    /// The name of the [crate::registers::BitRegister] containing the input bit.
+    register: String,
+    /// The index of the input bit in the register.
+    index: usize,
+    /// The name of the [crate::registers::BitRegister] the result is written to.
+    output_register: String,
+    /// The index of the result in the output register.
+    output_index: usize,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ClassicalNot: &[&str; 3] = &["Operation", "ClassicalOperation", "ClassicalNot"];
+
+// Implementing the InvolveQubits trait for ClassicalNot.
+impl InvolveQubits for ClassicalNot {
+    /// Lists all involved Qubits (here, none).
+    fn involved_qubits(&self) -> InvolvedQubits {
+        InvolvedQubits::None
+    }
+}
+
+impl ClassicalNot {
+    /// Computes the result bit of the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The values of the input register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - The negated input bit.
+    /// * `Err(RoqoqoError::ClassicalRegisterError)` - The index is out of range of the register.
+    pub fn evaluate(&self, register: &[bool]) -> Result<bool, RoqoqoError> {
+        Ok(!register_bit(register, &self.register, self.index)?)
+    }
+}
+
+/// The classical copy operation.
+///
+/// Copies a bit in a [crate::registers::BitRegister] to the output bit.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::Substitute)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ClassicalCopy {
+    /// The name of the [crate::registers::BitRegister] containing the input bit.
+    register: String,
+    /// The index of the input bit in the register.
+    index: usize,
+    /// The name of the [crate::registers::BitRegister] the result is written to.
+    output_register: String,
+    /// The index of the result in the output register.
+    output_index: usize,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ClassicalCopy: &[&str; 3] = &["Operation", "ClassicalOperation", "ClassicalCopy"];
+
+// Implementing the InvolveQubits trait for ClassicalCopy.
+impl InvolveQubits for ClassicalCopy {
+    /// Lists all involved Qubits (here, none).
+    fn involved_qubits(&self) -> InvolvedQubits {
+        InvolvedQubits::None
+    }
+}
+
+impl ClassicalCopy {
+    /// Computes the result bit of the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The values of the input register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - The input bit.
+    /// * `Err(RoqoqoError::ClassicalRegisterError)` - The index is out of range of the register.
+    pub fn evaluate(&self, register: &[bool]) -> Result<bool, RoqoqoError> {
+        register_bit(register, &self.register, self.index)
+    }
+}
+
+/// The classical XOR operation.
+///
+/// Writes the parity of the bits at the given indices of a [crate::registers::BitRegister] to the output bit,
+/// true when an odd number of the bits is set. The parity of no bits is false.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::Substitute)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ClassicalXor {
+    /// The name of the [crate::registers::BitRegister] containing the input bits.
+    register: String,
+    /// The indices of the input bits in the register.
+    indices: Vec<usize>,
+    /// The name of the [crate::registers::BitRegister] the result is written to.
+    output_register: String,
+    /// The index of the result in the output register.
+    output_index: usize,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ClassicalXor: &[&str; 3] = &["Operation", "ClassicalOperation", "ClassicalXor"];
+
+// Implementing the InvolveQubits trait for ClassicalXor.
+impl InvolveQubits for ClassicalXor {
+    /// Lists all involved Qubits (here, none).
+    fn involved_qubits(&self) -> InvolvedQubits {
+        InvolvedQubits::None
+    }
+}
+
+impl ClassicalXor {
+    /// Computes the result bit of the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The values of the input register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - The parity of the input bits.
+    /// * `Err(RoqoqoError::ClassicalRegisterError)` - An index is out of range of the register.
+    pub fn evaluate(&self, register: &[bool]) -> Result<bool, RoqoqoError> {
+        Ok(register_bits(register, &self.register, &self.indices)?
+            .into_iter()
+            .fold(false, |parity, bit| parity ^ bit))
+    }
+}
+
+/// The classical AND operation.
+///
+/// Writes true to the output bit when all bits at the given indices of a [crate::registers::BitRegister] are set.
+/// The AND of no bits is true.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::Substitute)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ClassicalAnd {
+    /// The name of the [crate::registers::BitRegister] containing the input bits.
+    register: String,
+    /// The indices of the input bits in the register.
+    indices: Vec<usize>,
+    /// The name of the [crate::registers::BitRegister] the result is written to.
+    output_register: String,
+    /// The index of the result in the output register.
+    output_index: usize,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ClassicalAnd: &[&str; 3] = &["Operation", "ClassicalOperation", "ClassicalAnd"];
+
+// Implementing the InvolveQubits trait for ClassicalAnd.
+impl InvolveQubits for ClassicalAnd {
+    /// Lists all involved Qubits (here, none).
+    fn involved_qubits(&self) -> InvolvedQubits {
+        InvolvedQubits::None
+    }
+}
+
+impl ClassicalAnd {
+    /// Computes the result bit of the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The values of the input register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - True when all input bits are set.
+    /// * `Err(RoqoqoError::ClassicalRegisterError)` - An index is out of range of the register.
+    pub fn evaluate(&self, register: &[bool]) -> Result<bool, RoqoqoError> {
+        Ok(register_bits(register, &self.register, &self.indices)?
+            .into_iter()
+            .all(|bit| bit))
+    }
+}
+
+/// The classical OR operation.
+///
+/// Writes true to the output bit when any bit at the given indices of a [crate::registers::BitRegister] is set.
+/// The OR of no bits is false.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::Substitute)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ClassicalOr {
+    /// The name of the [crate::registers::BitRegister] containing the input bits.
+    register: String,
+    /// The indices of the input bits in the register.
+    indices: Vec<usize>,
+    /// The name of the [crate::registers::BitRegister] the result is written to.
+    output_register: String,
+    /// The index of the result in the output register.
+    output_index: usize,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ClassicalOr: &[&str; 3] = &["Operation", "ClassicalOperation", "ClassicalOr"];
+
+// Implementing the InvolveQubits trait for ClassicalOr.
+impl InvolveQubits for ClassicalOr {
+    /// Lists all involved Qubits (here, none).
+    fn involved_qubits(&self) -> InvolvedQubits {
+        InvolvedQubits::None
+    }
+}
+
+impl ClassicalOr {
+    /// Computes the result bit of the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The values of the input register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - True when any input bit is set.
+    /// * `Err(RoqoqoError::ClassicalRegisterError)` - An index is out of range of the register.
+    pub fn evaluate(&self, register: &[bool]) -> Result<bool, RoqoqoError> {
+        Ok(register_bits(register, &self.register, &self.indices)?
+            .into_iter()
+            .any(|bit| bit))
+    }
+}
+
+/// The classical greater-than comparison.
+///
+/// Writes true to the output bit when a float in a [crate::registers::FloatRegister] is strictly greater than the threshold.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::Substitute)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ClassicalGreaterThan {
+    /// The name of the [crate::registers::FloatRegister] containing the input float.
+    register: String,
+    /// The index of the input float in the register.
+    index: usize,
+    /// The threshold the input float is compared to.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    threshold: CalculatorFloat,
+    /// The name of the [crate::registers::BitRegister] the result is written to.
+    output_register: String,
+    /// The index of the result in the output register.
+    output_index: usize,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ClassicalGreaterThan: &[&str; 3] =
+    &["Operation", "ClassicalOperation", "ClassicalGreaterThan"];
+
+// Implementing the InvolveQubits trait for ClassicalGreaterThan.
+impl InvolveQubits for ClassicalGreaterThan {
+    /// Lists all involved Qubits (here, none).
+    fn involved_qubits(&self) -> InvolvedQubits {
+        InvolvedQubits::None
+    }
+}
+
+impl ClassicalGreaterThan {
+    /// Computes the result bit of the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The values of the input register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - True when the input float is greater than the threshold.
+    /// * `Err(RoqoqoError::ClassicalRegisterError)` - The index is out of range of the register.
+    /// * `Err(RoqoqoError::CalculatorError)` - The threshold is symbolic.
+    pub fn evaluate(&self, register: &[f64]) -> Result<bool, RoqoqoError> {
+        let value = register_float(register, &self.register, self.index)?;
+        Ok(value > *self.threshold.float()?)
+    }
+}
+
+/// The classical less-than comparison.
+///
+/// Writes true to the output bit when a float in a [crate::registers::FloatRegister] is strictly less than the threshold.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::Substitute)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ClassicalLessThan {
+    /// The name of the [crate::registers::FloatRegister] containing the input float.
+    register: String,
+    /// The index of the input float in the register.
+    index: usize,
+    /// The threshold the input float is compared to.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    threshold: CalculatorFloat,
+    /// The name of the [crate::registers::BitRegister] the result is written to.
+    output_register: String,
+    /// The index of the result in the output register.
+    output_index: usize,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ClassicalLessThan: &[&str; 3] =
+    &["Operation", "ClassicalOperation", "ClassicalLessThan"];
+
+// Implementing the InvolveQubits trait for ClassicalLessThan.
+impl InvolveQubits for ClassicalLessThan {
+    /// Lists all involved Qubits (here, none).
+    fn involved_qubits(&self) -> InvolvedQubits {
+        InvolvedQubits::None
+    }
+}
+
+impl ClassicalLessThan {
+    /// Computes the result bit of the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The values of the input register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - True when the input float is less than the threshold.
+    /// * `Err(RoqoqoError::ClassicalRegisterError)` - The index is out of range of the register.
+    /// * `Err(RoqoqoError::CalculatorError)` - The threshold is symbolic.
+    pub fn evaluate(&self, register: &[f64]) -> Result<bool, RoqoqoError> {
+        let value = register_float(register, &self.register, self.index)?;
+        Ok(value < *self.threshold.float()?)
+    }
+}
@@ -0,0 +1,475 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Numerical decompositions of unitary matrices into circuits of single-qubit rotations and CNOT gates.
+
+use crate::operations::{PragmaGlobalPhase, RotateY, RotateZ, CNOT};
+use crate::{Circuit, RoqoqoError};
+use nalgebra::{DMatrix, DVector};
+use ndarray::Array2;
+use num_complex::Complex64;
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+/// Maximal deviation of U U^dagger from the identity accepted for the matrix of a gate.
+pub(crate) const UNITARITY_TOLERANCE: f64 = 1e-10;
+
+/// Maximal deviation of an element of the matrix of a decomposition from the decomposed matrix.
+pub(crate) const DECOMPOSITION_TOLERANCE: f64 = 1e-8;
+
+/// Rotation angles and phases below this value are not added to decomposition circuits.
+const ANGLE_TOLERANCE: f64 = 1e-14;
+
+/// Checks that a matrix is a unitary acting on a number of qubits.
+///
+/// # Arguments
+///
+/// * `matrix` - The matrix of the gate.
+/// * `number_qubits` - The number of qubits the gate acts on.
+///
+/// # Returns
+///
+/// * `Ok(())` - The matrix is unitary and has dimension 2^number_qubits.
+/// * `Err(RoqoqoError::MismatchedUnitaryDimension)` - The matrix does not have dimension 2^number_qubits.
+/// * `Err(RoqoqoError::NonUnitaryMatrix)` - The matrix is not unitary within [UNITARITY_TOLERANCE].
+pub(crate) fn check_unitary(
+    matrix: &Array2<Complex64>,
+    number_qubits: usize,
+) -> Result<(), RoqoqoError> {
+    let (rows, columns) = matrix.dim();
+    let dimension = 1_usize.checked_shl(number_qubits as u32);
+    if dimension != Some(rows) || rows != columns {
+        return Err(RoqoqoError::MismatchedUnitaryDimension {
+            rows,
+            columns,
+            number_qubits,
+        });
+    }
+    let product = matrix.dot(&matrix.t().mapv(|value| value.conj()));
+    let deviation = product
+        .indexed_iter()
+        .map(|((row, column), value)| {
+            let identity = if row == column { 1.0 } else { 0.0 };
+            (value - identity).norm()
+        })
+        .fold(0.0, f64::max);
+    if deviation > UNITARITY_TOLERANCE {
+        return Err(RoqoqoError::NonUnitaryMatrix { deviation });
+    }
+    Ok(())
+}
+
+/// Decomposes a unitary matrix into single-qubit rotations and CNOT gates.
+///
+/// Uses the quantum Shannon decomposition: the matrix is split with a cosine-sine decomposition
+/// into a multiplexed RotateY on the most significant qubit between two block-diagonal matrices,
+/// which are in turn demultiplexed into a multiplexed RotateZ between unitaries on the remaining qubits.
+/// Multiplexed rotations are implemented with CNOT gates, single-qubit unitaries with RotateZ-RotateY-RotateZ rotations.
+/// The global phase is added as a [PragmaGlobalPhase], so that the circuit has exactly the unitary of the matrix.
+///
+/// # Arguments
+///
+/// * `qubits` - The qubits the matrix acts on, in descending order of significance.
+/// * `matrix` - The unitary matrix of dimension 2^n for n qubits.
+///
+/// # Returns
+///
+/// * `Circuit` - The decomposition.
+pub(crate) fn shannon_decomposition(qubits: &[usize], matrix: &Array2<Complex64>) -> Circuit {
+    let dimension = matrix.nrows();
+    let unitary = DMatrix::from_fn(dimension, dimension, |row, column| matrix[(row, column)]);
+    let mut circuit = Circuit::new();
+    let mut phase = 0.0;
+    decompose(qubits, &unitary, &mut circuit, &mut phase);
+    add_global_phase(&mut circuit, phase);
+    circuit
+}
+
+/// Adds a global phase, reduced to (-pi, pi], to a circuit unless it vanishes.
+pub(crate) fn add_global_phase(circuit: &mut Circuit, phase: f64) {
+    let phase = phase - 2.0 * PI * ((phase + PI) / (2.0 * PI)).floor();
+    let phase = if phase <= -PI {
+        phase + 2.0 * PI
+    } else {
+        phase
+    };
+    if phase.abs() > ANGLE_TOLERANCE {
+        circuit.add_operation(PragmaGlobalPhase::new(phase.into()));
+    }
+}
+
+/// Appends the decomposition of a unitary on the qubits to the circuit, accumulating the global phase.
+fn decompose(
+    qubits: &[usize],
+    unitary: &DMatrix<Complex64>,
+    circuit: &mut Circuit,
+    phase: &mut f64,
+) {
+    match qubits.len() {
+        0 => *phase += unitary[(0, 0)].arg(),
+        1 => *phase += zyz_rotations(qubits[0], unitary, circuit),
+        _ => {
+            let (left, right, angles) = cosine_sine_decomposition(unitary);
+            demultiplex(qubits, &right.0, &right.1, circuit, phase);
+            multiplexed_rotation(Axis::Y, qubits[0], &qubits[1..], &angles, circuit);
+            demultiplex(qubits, &left.0, &left.1, circuit, phase);
+        }
+    }
+}
+
+/// Appends RotateZ(delta) RotateY(gamma) RotateZ(beta) on the qubit and returns the remaining global phase.
+///
+/// The unitary is U = exp(i alpha) RotateZ(beta) RotateY(gamma) RotateZ(delta).
+pub(crate) fn zyz_rotations(
+    qubit: usize,
+    unitary: &DMatrix<Complex64>,
+    circuit: &mut Circuit,
+) -> f64 {
+    let (alpha, [delta, gamma, beta]) = zyz_angles(unitary);
+    add_rotation(circuit, Axis::Z, qubit, delta);
+    add_rotation(circuit, Axis::Y, qubit, gamma);
+    add_rotation(circuit, Axis::Z, qubit, beta);
+    alpha
+}
+
+/// Returns the phase alpha and the angles [delta, gamma, beta] of a single-qubit unitary
+/// U = exp(i alpha) RotateZ(beta) RotateY(gamma) RotateZ(delta).
+pub(crate) fn zyz_angles(unitary: &DMatrix<Complex64>) -> (f64, [f64; 3]) {
+    let determinant = unitary[(0, 0)] * unitary[(1, 1)] - unitary[(0, 1)] * unitary[(1, 0)];
+    let alpha = determinant.arg() / 2.0;
+    let rotation = Complex64::new(0.0, -alpha).exp();
+    let a = unitary[(0, 0)] * rotation;
+    let b = unitary[(1, 0)] * rotation;
+    let gamma = 2.0 * b.norm().atan2(a.norm());
+    let sum = -2.0 * a.arg();
+    let difference = 2.0 * b.arg();
+    let beta = (sum + difference) / 2.0;
+    let delta = (sum - difference) / 2.0;
+    (alpha, [delta, gamma, beta])
+}
+
+/// Rotation axis of a multiplexed rotation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    Y,
+    Z,
+}
+
+/// Adds a rotation on a qubit unless the angle vanishes.
+fn add_rotation(circuit: &mut Circuit, axis: Axis, qubit: usize, angle: f64) {
+    if angle.abs() <= ANGLE_TOLERANCE {
+        return;
+    }
+    match axis {
+        Axis::Y => circuit.add_operation(RotateY::new(qubit, angle.into())),
+        Axis::Z => circuit.add_operation(RotateZ::new(qubit, angle.into())),
+    }
+}
+
+/// Appends a rotation of the target qubit with an angle depending on the state of the control qubits.
+///
+/// The angle with index i is applied when the controls are in the basis state i, the first control being the most significant.
+fn multiplexed_rotation(
+    axis: Axis,
+    target: usize,
+    controls: &[usize],
+    angles: &[f64],
+    circuit: &mut Circuit,
+) {
+    if controls.is_empty() {
+        add_rotation(circuit, axis, target, angles[0]);
+        return;
+    }
+    let half = angles.len() / 2;
+    let sums: Vec<f64> = (0..half)
+        .map(|index| (angles[index] + angles[half + index]) / 2.0)
+        .collect();
+    let differences: Vec<f64> = (0..half)
+        .map(|index| (angles[index] - angles[half + index]) / 2.0)
+        .collect();
+    multiplexed_rotation(axis, target, &controls[1..], &sums, circuit);
+    // A CNOT on both sides flips the sign of the rotation when the first control is set
+    if differences
+        .iter()
+        .any(|angle| angle.abs() > ANGLE_TOLERANCE)
+    {
+        circuit.add_operation(CNOT::new(controls[0], target));
+        multiplexed_rotation(axis, target, &controls[1..], &differences, circuit);
+        circuit.add_operation(CNOT::new(controls[0], target));
+    }
+}
+
+/// Appends the block-diagonal unitary diag(first, second), where the first qubit selects the block.
+///
+/// The blocks are demultiplexed as first = V D W and second = V D^dagger W with a diagonal unitary D,
+/// so that the block-diagonal unitary is W on the remaining qubits, a multiplexed RotateZ on the first qubit
+/// and V on the remaining qubits.
+fn demultiplex(
+    qubits: &[usize],
+    first: &DMatrix<Complex64>,
+    second: &DMatrix<Complex64>,
+    circuit: &mut Circuit,
+    phase: &mut f64,
+) {
+    let (eigenvectors, triangular) = (first * second.adjoint()).schur().unpack();
+    let diagonal: Vec<Complex64> = (0..triangular.nrows())
+        .map(|index| triangular[(index, index)].sqrt())
+        .collect();
+    let w = DMatrix::from_diagonal(&DVector::from_vec(diagonal.clone()))
+        * eigenvectors.adjoint()
+        * second;
+    let angles: Vec<f64> = diagonal.iter().map(|value| -2.0 * value.arg()).collect();
+    decompose(&qubits[1..], &w, circuit, phase);
+    multiplexed_rotation(Axis::Z, qubits[0], &qubits[1..], &angles, circuit);
+    decompose(&qubits[1..], &eigenvectors, circuit, phase);
+}
+
+/// Block-diagonal factor of a cosine-sine decomposition, the block for the most significant qubit in state 0 first.
+type BlockDiagonal = (DMatrix<Complex64>, DMatrix<Complex64>);
+
+/// Computes the cosine-sine decomposition of a unitary of even dimension.
+///
+/// U = diag(L0, L1) [[C, -S], [S, C]] diag(R0, R1) with C = diag(cos(theta_i)) and S = diag(sin(theta_i)).
+///
+/// # Returns
+///
+/// * `(BlockDiagonal, BlockDiagonal, Vec<f64>)` - The left factor (L0, L1), the right factor (R0, R1)
+///   and the RotateY angles 2 theta_i of the middle factor.
+fn cosine_sine_decomposition(
+    unitary: &DMatrix<Complex64>,
+) -> (BlockDiagonal, BlockDiagonal, Vec<f64>) {
+    let half = unitary.nrows() / 2;
+    let top_left = unitary.slice((0, 0), (half, half)).clone_owned();
+    let top_right = unitary.slice((0, half), (half, half)).clone_owned();
+    let bottom_left = unitary.slice((half, 0), (half, half)).clone_owned();
+    let bottom_right = unitary.slice((half, half), (half, half)).clone_owned();
+
+    // top_left = L0 C R0
+    let svd = top_left.svd(true, true);
+    let cosines = svd.singular_values;
+    let l0 = svd.u.expect("Left singular vectors are computed");
+    let r0 = svd.v_t.expect("Right singular vectors are computed");
+
+    // bottom_left R0^dagger = L1 S, with L1 the unitary polar factor,
+    // which is well defined and numerically stable also for vanishing sines
+    let svd = (&bottom_left * r0.adjoint()).svd(true, true);
+    let right_vectors = svd.v_t.expect("Right singular vectors are computed");
+    let l1 = svd.u.expect("Left singular vectors are computed") * &right_vectors;
+    let positive = right_vectors.adjoint()
+        * DMatrix::from_diagonal(&svd.singular_values.map(|value| Complex64::new(value, 0.0)))
+        * &right_vectors;
+    let thetas: Vec<f64> = (0..half)
+        .map(|index| positive[(index, index)].re.atan2(cosines[index]))
+        .collect();
+
+    // R1 = C L1^dagger bottom_right - S L0^dagger top_right, avoiding divisions by small cosines or sines
+    let diagonal = |function: fn(f64) -> f64| {
+        DMatrix::from_diagonal(&DVector::from_iterator(
+            half,
+            thetas
+                .iter()
+                .map(|theta| Complex64::new(function(*theta), 0.0)),
+        ))
+    };
+    let r1 = diagonal(f64::cos) * l1.adjoint() * bottom_right
+        - diagonal(f64::sin) * l0.adjoint() * top_right;
+    let angles = thetas.iter().map(|theta| 2.0 * theta).collect();
+    ((l0, l1), (r0, r1), angles)
+}
+
+/// Parameters of the KAK decomposition of a numeric two-qubit unitary.
+///
+/// U = exp(i global_phase) (A_control ⊗ A_target) exp(i (k_x XX + k_y YY + k_z ZZ)) (B_control ⊗ B_target),
+/// where every single-qubit unitary is given by the angles [z1, y, z2] of RotateZ(z1), RotateY(y)
+/// and RotateZ(z2) applied in this order.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct KakParameters {
+    /// The global phase.
+    pub global_phase: f64,
+    /// The coefficients of the XX, YY and ZZ interactions.
+    pub k_vector: [f64; 3],
+    /// The rotation angles of the control qubit before the entangling operation.
+    pub control_before: [f64; 3],
+    /// The rotation angles of the target qubit before the entangling operation.
+    pub target_before: [f64; 3],
+    /// The rotation angles of the control qubit after the entangling operation.
+    pub control_after: [f64; 3],
+    /// The rotation angles of the target qubit after the entangling operation.
+    pub target_after: [f64; 3],
+}
+
+/// Computes the KAK decomposition of a two-qubit unitary with the control as most significant qubit.
+///
+/// In the magic basis local unitaries are real orthogonal matrices. The symmetric unitary U_m^T U_m
+/// is diagonalized by a real orthogonal matrix, which gives the local unitaries before the entangling
+/// operation and the eigenphases of the entangling operation.
+///
+/// # Arguments
+///
+/// * `matrix` - The unitary 4x4 matrix, unitarity has to be checked by the caller.
+///
+/// # Returns
+///
+/// * `Ok(KakParameters)` - The parameters of the decomposition.
+/// * `Err(RoqoqoError::DecompositionError)` - The symmetric unitary could not be diagonalized.
+pub(crate) fn kak_decomposition(matrix: &Array2<Complex64>) -> Result<KakParameters, RoqoqoError> {
+    let unitary = DMatrix::from_fn(4, 4, |row, column| matrix[(row, column)]);
+    let mut global_phase = unitary.determinant().arg() / 4.0;
+    let unitary = unitary * Complex64::new(0.0, -global_phase).exp();
+
+    let magic = magic_basis();
+    let magic_unitary = magic.adjoint() * unitary * &magic;
+    let symmetric = magic_unitary.transpose() * &magic_unitary;
+    let orthogonal = real_eigenvectors(&symmetric)?.map(|value| Complex64::new(value, 0.0));
+    let diagonal = orthogonal.transpose() * &symmetric * &orthogonal;
+    let mut thetas: Vec<f64> = (0..4)
+        .map(|index| diagonal[(index, index)].arg() / 2.0)
+        .collect();
+    let phases = |thetas: &[f64]| {
+        DMatrix::from_diagonal(&DVector::from_iterator(
+            4,
+            thetas.iter().map(|theta| Complex64::new(0.0, -theta).exp()),
+        ))
+    };
+    // magic_unitary = left diag(exp(i theta)) orthogonal^T with a real orthogonal left factor
+    let mut left = &magic_unitary * &orthogonal * phases(&thetas);
+    if left.determinant().re < 0.0 {
+        thetas[0] += PI;
+        left = &magic_unitary * &orthogonal * phases(&thetas);
+    }
+    let before = &magic * orthogonal.transpose() * magic.adjoint();
+    let after = &magic * left * magic.adjoint();
+
+    // The magic basis diagonalizes XX, YY and ZZ: theta_j = phase + sum_a k_a lambda_a,j
+    let paulis = [pauli_product(1), pauli_product(2), pauli_product(3)];
+    let eigenvalues: Vec<DMatrix<Complex64>> = paulis
+        .iter()
+        .map(|pauli| magic.adjoint() * pauli * &magic)
+        .collect();
+    let system = DMatrix::from_fn(4, 4, |row, column| match column {
+        0 => 1.0,
+        _ => eigenvalues[column - 1][(row, row)].re,
+    });
+    let solution = system
+        .lu()
+        .solve(&DVector::from_vec(thetas))
+        .expect("Eigenvalues of XX, YY and ZZ in the magic basis are linearly independent");
+    global_phase += solution[0];
+
+    let mut single_qubit = |unitary: DMatrix<Complex64>| {
+        let (phase, angles) = zyz_angles(&unitary);
+        global_phase += phase;
+        angles
+    };
+    let (control_before, target_before) = tensor_factors(&before);
+    let (control_after, target_after) = tensor_factors(&after);
+    let control_before = single_qubit(control_before);
+    let target_before = single_qubit(target_before);
+    let control_after = single_qubit(control_after);
+    let target_after = single_qubit(target_after);
+    Ok(KakParameters {
+        global_phase,
+        k_vector: [solution[1], solution[2], solution[3]],
+        control_before,
+        target_before,
+        control_after,
+        target_after,
+    })
+}
+
+/// Returns the matrix with the magic basis states as columns.
+fn magic_basis() -> DMatrix<Complex64> {
+    let zero = Complex64::new(0.0, 0.0);
+    let one = Complex64::new(FRAC_1_SQRT_2, 0.0);
+    let i = Complex64::new(0.0, FRAC_1_SQRT_2);
+    DMatrix::from_row_slice(
+        4,
+        4,
+        &[
+            one, zero, zero, i, //
+            zero, i, one, zero, //
+            zero, i, -one, zero, //
+            one, zero, zero, -i,
+        ],
+    )
+}
+
+/// Returns XX (1), YY (2) or ZZ (3) on two qubits.
+fn pauli_product(pauli: usize) -> DMatrix<Complex64> {
+    let zero = Complex64::new(0.0, 0.0);
+    let one = Complex64::new(1.0, 0.0);
+    let single = match pauli {
+        1 => DMatrix::from_row_slice(2, 2, &[zero, one, one, zero]),
+        2 => DMatrix::from_row_slice(2, 2, &[zero, -Complex64::i(), Complex64::i(), zero]),
+        _ => DMatrix::from_row_slice(2, 2, &[one, zero, zero, -one]),
+    };
+    single.kronecker(&single)
+}
+
+/// Returns a real orthogonal matrix with determinant one diagonalizing a symmetric unitary matrix.
+///
+/// Real and imaginary part of a symmetric unitary matrix are commuting real symmetric matrices,
+/// which are diagonalized together by the eigenvectors of a generic linear combination.
+///
+/// # Returns
+///
+/// * `Ok(DMatrix<f64>)` - The orthogonal matrix.
+/// * `Err(RoqoqoError::DecompositionError)` - None of the linear combinations diagonalizes the matrix.
+fn real_eigenvectors(symmetric: &DMatrix<Complex64>) -> Result<DMatrix<f64>, RoqoqoError> {
+    let real = symmetric.map(|value| value.re);
+    let imaginary = symmetric.map(|value| value.im);
+    let mut smallest_off_diagonal = f64::INFINITY;
+    for weight in [0.5772156649, 1.3247179572, 2.2360679775, 0.4142135624] {
+        let mut eigenvectors = (&real + &imaginary * weight).symmetric_eigen().eigenvectors;
+        let complex = eigenvectors.map(|value| Complex64::new(value, 0.0));
+        let diagonal = complex.transpose() * symmetric * &complex;
+        let off_diagonal = diagonal
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| index % 5 != 0)
+            .map(|(_, value)| value.norm())
+            .fold(0.0, f64::max);
+        if off_diagonal < UNITARITY_TOLERANCE {
+            if eigenvectors.determinant() < 0.0 {
+                eigenvectors.column_mut(0).neg_mut();
+            }
+            return Ok(eigenvectors);
+        }
+        smallest_off_diagonal = smallest_off_diagonal.min(off_diagonal);
+    }
+    Err(RoqoqoError::DecompositionError {
+        msg: format!(
+            "The KAK decomposition could not diagonalize the symmetric unitary, the smallest maximal off-diagonal element is {:e}.",
+            smallest_off_diagonal
+        ),
+    })
+}
+
+/// Splits a 4x4 tensor product A ⊗ B of single-qubit unitaries into A and B.
+fn tensor_factors(product: &DMatrix<Complex64>) -> (DMatrix<Complex64>, DMatrix<Complex64>) {
+    let block =
+        |row: usize, column: usize| product.slice((2 * row, 2 * column), (2, 2)).clone_owned();
+    let (largest_row, largest_column) = [(0, 0), (0, 1), (1, 0), (1, 1)]
+        .iter()
+        .cloned()
+        .max_by(|first, second| {
+            block(first.0, first.1)
+                .norm()
+                .total_cmp(&block(second.0, second.1).norm())
+        })
+        .expect("Four blocks are compared");
+    let largest = block(largest_row, largest_column);
+    let second = &largest / largest.determinant().sqrt();
+    let first = DMatrix::from_fn(2, 2, |row, column| {
+        (second.adjoint() * block(row, column)).trace() / 2.0
+    });
+    (first, second)
+}
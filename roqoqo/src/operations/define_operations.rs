@@ -44,6 +44,7 @@ use crate::operations::{Define, InvolveQubits, InvolvedQubits, Operate, RoqoqoEr
     roqoqo_derive::Define,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct DefinitionFloat {
     /// The name of the register that is defined.
     name: String,
@@ -75,6 +76,7 @@ impl InvolveQubits for DefinitionFloat {
     roqoqo_derive::Define,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct DefinitionComplex {
     /// The name of the register that is defined.
     name: String,
@@ -106,6 +108,7 @@ impl InvolveQubits for DefinitionComplex {
     roqoqo_derive::Define,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct DefinitionUsize {
     /// The name of the register that is defined.
     name: String,
@@ -137,6 +140,7 @@ impl InvolveQubits for DefinitionUsize {
     roqoqo_derive::Define,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct DefinitionBit {
     /// The name of the register that is defined.
     name: String,
@@ -168,6 +172,7 @@ impl InvolveQubits for DefinitionBit {
     roqoqo_derive::Define,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct InputSymbolic {
     /// The name of the register that is defined.
     name: String,
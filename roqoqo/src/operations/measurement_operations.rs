@@ -41,6 +41,7 @@ use crate::Circuit;
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct MeasureQubit {
     /// The measured qubit.
     qubit: usize,
@@ -57,6 +58,7 @@ const TAGS_MeasureQubit: &[&str; 3] = &["Operation", "Measurement", "MeasureQubi
 ///
 #[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaGetStateVector {
     /// The name of the classical readout register.
     readout: String,
@@ -91,6 +93,14 @@ impl Substitute for PragmaGetStateVector {
         };
         Ok(PragmaGetStateVector::new(self.readout.clone(), new_circuit))
     }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        self.circuit
+            .as_ref()
+            .map(|circuit| circuit.free_parameters())
+            .unwrap_or_default()
+    }
 }
 
 // Implements the InvolveQubits trait for PragmaGetStateVector.
@@ -105,6 +115,7 @@ impl InvolveQubits for PragmaGetStateVector {
 ///
 #[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaGetDensityMatrix {
     /// The name of the classical readout register.
     readout: String,
@@ -145,6 +156,14 @@ impl Substitute for PragmaGetDensityMatrix {
             new_circuit,
         ))
     }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        self.circuit
+            .as_ref()
+            .map(|circuit| circuit.free_parameters())
+            .unwrap_or_default()
+    }
 }
 
 // Implements the InvolveQubits trait for PragmaGetDensityMatrix.
@@ -162,6 +181,7 @@ impl InvolveQubits for PragmaGetDensityMatrix {
 ///
 #[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaGetOccupationProbability {
     /// The name of the classical readout register.
     readout: String,
@@ -202,6 +222,14 @@ impl Substitute for PragmaGetOccupationProbability {
             new_circuit,
         ))
     }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        self.circuit
+            .as_ref()
+            .map(|circuit| circuit.free_parameters())
+            .unwrap_or_default()
+    }
 }
 
 // Implements the InvolveQubits trait for PragmaGetOccupationProbability.
@@ -220,6 +248,7 @@ impl InvolveQubits for PragmaGetOccupationProbability {
 ///
 #[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaGetPauliProduct {
     /// The HashMap of the pauli matrix to apply to each qubit in the form {qubit: pauli}. Allowed values to be provided for 'pauli' are: `0` = identity, `1` = PauliX, `2` = PauliY, `3` = PauliZ.
     qubit_paulis: HashMap<usize, usize>,
@@ -241,13 +270,12 @@ const TAGS_PragmaGetPauliProduct: &[&str; 4] = &[
 impl Substitute for PragmaGetPauliProduct {
     /// Remaps qubits in operations in clone of the operation.
     fn remap_qubits(&self, mapping: &HashMap<usize, usize>) -> Result<Self, RoqoqoError> {
-        let mut mutable_mapping: HashMap<usize, usize> = self.qubit_paulis.clone();
-        for (old_qubit, new_qubit) in mapping {
-            if let Some(v) = mutable_mapping.remove(old_qubit) {
-                mutable_mapping.insert(*new_qubit, v);
-            }
-        }
-        let new_circuit = self.circuit.remap_qubits(mapping).unwrap();
+        let mutable_mapping: HashMap<usize, usize> = self
+            .qubit_paulis
+            .iter()
+            .map(|(qubit, pauli)| (*mapping.get(qubit).unwrap_or(qubit), *pauli))
+            .collect();
+        let new_circuit = self.circuit.remap_qubits(mapping)?;
         Ok(PragmaGetPauliProduct::new(
             mutable_mapping,
             self.readout.clone(),
@@ -257,13 +285,18 @@ impl Substitute for PragmaGetPauliProduct {
 
     /// Substitutes symbolic parameters in clone of the operation.
     fn substitute_parameters(&self, calculator: &mut Calculator) -> Result<Self, RoqoqoError> {
-        let new_circuit = self.circuit.substitute_parameters(calculator).unwrap();
+        let new_circuit = self.circuit.substitute_parameters(calculator)?;
         Ok(PragmaGetPauliProduct::new(
             self.qubit_paulis.clone(),
             self.readout.clone(),
             new_circuit,
         ))
     }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        self.circuit.free_parameters()
+    }
 }
 
 // Implements the InvolveQubits trait for PragmaGetPauliProduct.
@@ -287,6 +320,7 @@ impl InvolveQubits for PragmaGetPauliProduct {
 ///
 #[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaRepeatedMeasurement {
     /// The name of the classical readout register.
     readout: String,
@@ -308,14 +342,10 @@ const TAGS_PragmaRepeatedMeasurement: &[&str; 4] = &[
 impl Substitute for PragmaRepeatedMeasurement {
     /// Remaps qubits in operations in clone of the operation.
     fn remap_qubits(&self, mapping: &HashMap<usize, usize>) -> Result<Self, RoqoqoError> {
-        let new_mapping = (self.qubit_mapping.clone()).map(|hm| {
-            let mut mutable_mapping: HashMap<usize, usize> = hm;
-            for (old_qubit, new_qubit) in mapping {
-                if let Some(v) = mutable_mapping.remove(old_qubit) {
-                    mutable_mapping.insert(*new_qubit, v);
-                }
-            }
-            mutable_mapping
+        let new_mapping = self.qubit_mapping.as_ref().map(|hm| {
+            hm.iter()
+                .map(|(qubit, index)| (*mapping.get(qubit).unwrap_or(qubit), *index))
+                .collect::<HashMap<usize, usize>>()
         });
         Ok(PragmaRepeatedMeasurement::new(
             self.readout.clone(),
@@ -328,6 +358,11 @@ impl Substitute for PragmaRepeatedMeasurement {
     fn substitute_parameters(&self, _calculator: &mut Calculator) -> Result<Self, RoqoqoError> {
         Ok(self.clone())
     }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        HashSet::new()
+    }
 }
 
 // Implements the InvolveQubits trait for PragmaRepeatedMeasurement.
@@ -12,13 +12,14 @@
 
 //! Operations are the atomic instructions in any quantum program that can be represented by roqoqo.
 //!
-//! Operations can be of various kinds: Definitions, GateOperations, PRAGMAs or measurement Operations.
+//! Operations can be of various kinds: Definitions, GateOperations, PRAGMAs, measurement Operations or classical Operations.
 //! * Definition operations define the classical registers and variables in the Circuit.
 //! * GateOperations are single-, two- or multi-qubit gate operations that act on a set of qubits
 //! and can be executed on a quantum computing device.
 //! * PRAGMAs are operations that can be used when running a simulation of a quantum computing program.
 //! * Measurement Operations are operations that perform a measurement either on a quantum computing device (MeasuareQubit)
 //! or on a simulation of a quantum computing program (PRAGMA measurement operations).
+//! * Classical Operations process the values of classical registers, for example to compute the condition of a PragmaConditional.
 
 use crate::RoqoqoError;
 #[cfg(feature = "dynamic")]
@@ -28,6 +29,12 @@ use num_complex::Complex64;
 use qoqo_calculator::{Calculator, CalculatorComplex, CalculatorFloat};
 use roqoqo_derive::*;
 use std::collections::{HashMap, HashSet};
+/// Numerical decompositions of unitary matrices.
+mod decompositions;
+/// Collection of roqoqo classical operations.
+#[doc(hidden)]
+mod classical_operations;
+pub use classical_operations::*;
 /// Collection of roqoqo definition operations.
 #[doc(hidden)]
 mod define_operations;
@@ -61,6 +68,7 @@ pub use _auto_generated_operations::*;
 /// Represents qubits involved in a roqoqo Operation.
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum InvolvedQubits {
     /// Operation affects all qubits no matter how many there are.
     All,
@@ -183,6 +191,9 @@ pub trait InvolveQubits {
 /// qubit_mapping_test.insert(0, 2);
 /// let result = rotatez.remap_qubits(&qubit_mapping_test).unwrap();
 /// assert_eq!(result, RotateZ::new(2, CalculatorFloat::from(0.0)));
+/// // 3) The free_parameters function returns the symbols in the symbolic parameters of the Operation
+/// let rotatez = RotateZ::new(0, CalculatorFloat::from("2 * sub"));
+/// assert_eq!(rotatez.free_parameters(), vec!["sub".to_string()].into_iter().collect());
 /// ```
 ///
 pub trait Substitute
@@ -193,6 +204,8 @@ where
     fn substitute_parameters(&self, calculator: &mut Calculator) -> Result<Self, RoqoqoError>;
     /// Remaps the qubits in clone of the operation.
     fn remap_qubits(&self, mapping: &HashMap<usize, usize>) -> Result<Self, RoqoqoError>;
+    /// Returns the names of the symbols in the symbolic parameters of the operation and of the circuits it contains.
+    fn free_parameters(&self) -> HashSet<String>;
 }
 
 #[cfg(feature = "dynamic")]
@@ -208,6 +221,8 @@ pub trait SubstituteDyn {
         &self,
         mapping: &HashMap<usize, usize>,
     ) -> Result<Box<dyn Operate>, RoqoqoError>;
+    /// Return the symbols in the symbolic parameters of the operation.
+    fn free_parameters_dyn(&self) -> HashSet<String>;
 }
 
 #[cfg(feature = "dynamic")]
@@ -231,6 +246,10 @@ where
     ) -> Result<Box<dyn Operate>, RoqoqoError> {
         Ok(Box::new(Substitute::remap_qubits(self, mapping)?))
     }
+    /// Return the symbols in the symbolic parameters of the operation.
+    fn free_parameters_dyn(&self) -> HashSet<String> {
+        Substitute::free_parameters(self)
+    }
 }
 
 /// Trait for operations acting on exactly one qubit.
@@ -626,7 +645,10 @@ pub trait OperateMultiQubitGate:
 /// in a circuit.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-pub struct DynOperation(Box<dyn Operate>);
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct DynOperation(
+    #[cfg_attr(feature = "json_schema", schemars(with = "serde_json::Value"))] Box<dyn Operate>,
+);
 
 #[cfg(feature = "dynamic")]
 #[cfg_attr(feature = "dynamic", typetag::serde)]
@@ -656,6 +678,9 @@ impl Substitute for DynOperation {
     fn remap_qubits(&self, mapping: &HashMap<usize, usize>) -> Result<Self, RoqoqoError> {
         Ok(DynOperation(self.0.remap_qubits_dyn(mapping)?))
     }
+    fn free_parameters(&self) -> HashSet<String> {
+        self.0.free_parameters_dyn()
+    }
 }
 #[cfg(feature = "dynamic")]
 impl PartialEq for DynOperation {
@@ -11,16 +11,20 @@
 // limitations under the License.
 
 use crate::operations;
+use crate::operations::decompositions::{check_unitary, shannon_decomposition, zyz_angles};
 use crate::prelude::*;
 use crate::Circuit;
 use crate::RoqoqoError;
-use ndarray::Array2;
+use nalgebra::DMatrix;
+use ndarray::{array, s, Array2};
 use num_complex::Complex64;
 use qoqo_calculator::CalculatorFloat;
 #[cfg(feature = "overrotate")]
 use rand_distr::{Distribution, Normal};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
 
 /// The Molmer-Sorensen gate between multiple qubits.
 ///
@@ -36,10 +40,15 @@ use serde::{Deserialize, Serialize};
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct MultiQubitMS {
     /// The qubits involved in the multi qubit Molmer-Sorensen gate.
     qubits: Vec<usize>,
     /// The angle of the multi qubit Molmer-Sorensen gate.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
 }
 
@@ -86,3 +95,1063 @@ impl OperateMultiQubitGate for MultiQubitMS {
         circuit
     }
 }
+
+/// A general unitary gate acting on multiple qubits, defined by its unitary matrix.
+///
+/// The basis states of the matrix are ordered with the first qubit in `qubits` as the most significant qubit,
+/// so that for the qubits `[control, target]` the matrix of a CNOT gate is
+///
+/// $$
+/// U = \begin{pmatrix}
+/// 1 & 0 & 0 & 0 \\\\
+/// 0 & 1 & 0 & 0 \\\\
+/// 0 & 0 & 0 & 1 \\\\
+/// 0 & 0 & 1 & 0
+/// \end{pmatrix}
+/// $$
+///
+/// Use [MultiQubitGate::from_unitary] to check the matrix when creating the gate.
+/// The [OperateMultiQubitGate::circuit] decomposes the gate into single-qubit rotations and CNOT gates
+/// with the quantum Shannon decomposition, so that it can be run on backends without native support.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex64;
+/// use roqoqo::operations::{MultiQubitGate, OperateGate, OperateMultiQubitGate};
+///
+/// let zero = Complex64::new(0.0, 0.0);
+/// let one = Complex64::new(1.0, 0.0);
+/// let matrix = array![
+///     [one, zero, zero, zero],
+///     [zero, one, zero, zero],
+///     [zero, zero, zero, one],
+///     [zero, zero, one, zero],
+/// ];
+/// let gate = MultiQubitGate::from_unitary(vec![0, 1], matrix.clone()).unwrap();
+/// assert_eq!(gate.unitary_matrix().unwrap(), matrix);
+/// assert!(!gate.circuit().is_empty());
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    roqoqo_derive::InvolveQubits,
+    roqoqo_derive::Operate,
+    roqoqo_derive::Substitute,
+    roqoqo_derive::OperateMultiQubit,
+)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct MultiQubitGate {
+    /// The qubits the gate acts on, in descending order of significance.
+    qubits: Vec<usize>,
+    /// The unitary matrix of the gate.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::Array2<(f64, f64)>")
+    )]
+    matrix: Array2<Complex64>,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_MultiQubitGate: &[&str; 4] = &[
+    "Operation",
+    "GateOperation",
+    "MultiQubitGateOperation",
+    "MultiQubitGate",
+];
+
+impl MultiQubitGate {
+    /// Creates a new MultiQubitGate after checking its matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The qubits the gate acts on, in descending order of significance.
+    /// * `matrix` - The unitary matrix of dimension 2^n for n qubits.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The new gate.
+    /// * `Err(RoqoqoError::MismatchedUnitaryDimension)` - The dimension of the matrix does not match the number of qubits.
+    /// * `Err(RoqoqoError::NonUnitaryMatrix)` - The matrix is not unitary.
+    pub fn from_unitary(
+        qubits: Vec<usize>,
+        matrix: Array2<Complex64>,
+    ) -> Result<Self, RoqoqoError> {
+        check_unitary(&matrix, qubits.len())?;
+        Ok(Self::new(qubits, matrix))
+    }
+}
+
+impl OperateGate for MultiQubitGate {
+    /// Returns unitary matrix of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The unitary matrix representation of the gate.
+    /// * `Err(RoqoqoError)` - The matrix does not match the number of qubits or is not unitary.
+    fn unitary_matrix(&self) -> Result<Array2<Complex64>, RoqoqoError> {
+        check_unitary(&self.matrix, self.qubits.len())?;
+        Ok(self.matrix.clone())
+    }
+}
+
+impl OperateMultiQubitGate for MultiQubitGate {
+    /// Returns the quantum Shannon decomposition of the gate into single-qubit rotations and CNOT gates.
+    ///
+    /// The decomposition includes the global phase as a [operations::PragmaGlobalPhase].
+    /// It is only meaningful for a unitary matrix matching the number of qubits.
+    fn circuit(&self) -> Circuit {
+        shannon_decomposition(&self.qubits, &self.matrix)
+    }
+}
+
+/// The Toffoli gate, a Pauli X gate on the target qubit controlled by two control qubits.
+///
+/// The gate acts on the qubits `[control_0, control_1, target]`,
+/// with the first qubit as the most significant qubit of the matrix
+///
+/// $$
+/// U = \begin{pmatrix}
+/// 1 & 0 & 0 & 0 & 0 & 0 & 0 & 0 \\\\
+/// 0 & 1 & 0 & 0 & 0 & 0 & 0 & 0 \\\\
+/// 0 & 0 & 1 & 0 & 0 & 0 & 0 & 0 \\\\
+/// 0 & 0 & 0 & 1 & 0 & 0 & 0 & 0 \\\\
+/// 0 & 0 & 0 & 0 & 1 & 0 & 0 & 0 \\\\
+/// 0 & 0 & 0 & 0 & 0 & 1 & 0 & 0 \\\\
+/// 0 & 0 & 0 & 0 & 0 & 0 & 0 & 1 \\\\
+/// 0 & 0 & 0 & 0 & 0 & 0 & 1 & 0
+/// \end{pmatrix}
+/// $$
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::operations::{OperateMultiQubitGate, Toffoli};
+///
+/// let toffoli = Toffoli::new(vec![0, 1, 2]);
+/// assert_eq!(toffoli.circuit().len(), 16);
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    roqoqo_derive::InvolveQubits,
+    roqoqo_derive::Operate,
+    roqoqo_derive::Substitute,
+    roqoqo_derive::OperateMultiQubit,
+)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct Toffoli {
+    /// The qubits `[control_0, control_1, target]` of the gate.
+    qubits: Vec<usize>,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_Toffoli: &[&str; 4] = &[
+    "Operation",
+    "GateOperation",
+    "MultiQubitGateOperation",
+    "Toffoli",
+];
+
+impl OperateGate for Toffoli {
+    /// Returns unitary matrix of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The unitary matrix representation of the gate.
+    /// * `Err(RoqoqoError::InvalidQubits)` - The gate does not act on three qubits.
+    fn unitary_matrix(&self) -> Result<Array2<Complex64>, RoqoqoError> {
+        check_number_qubits(self.hqslang(), &self.qubits)?;
+        let zero = Complex64::new(0.0, 0.0);
+        let one = Complex64::new(1.0, 0.0);
+        Ok(controlled_matrix(2, array![[zero, one], [one, zero]]))
+    }
+}
+
+impl OperateMultiQubitGate for Toffoli {
+    /// Returns the decomposition of the gate into six CNOT gates and single-qubit gates.
+    ///
+    /// The decomposition does not need ancilla qubits
+    /// and includes the global phase as a [operations::PragmaGlobalPhase].
+    ///
+    /// # Panics
+    ///
+    /// Panics when the gate does not act on three qubits.
+    fn circuit(&self) -> Circuit {
+        let target = self.qubits[2];
+        let mut circuit = Circuit::new();
+        circuit += operations::Hadamard::new(target);
+        circuit += doubly_controlled_pauli_z(self.qubits[0], self.qubits[1], target);
+        circuit += operations::Hadamard::new(target);
+        circuit
+    }
+}
+
+/// The controlled-controlled Pauli Z gate, a Pauli Z gate on the target qubit controlled by two control qubits.
+///
+/// The gate acts on the qubits `[control_0, control_1, target]` and applies a phase of -1
+/// when all three qubits are in the state |1⟩.
+///
+/// $$
+/// U = \mathrm{diag}(1, 1, 1, 1, 1, 1, 1, -1)
+/// $$
+///
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    roqoqo_derive::InvolveQubits,
+    roqoqo_derive::Operate,
+    roqoqo_derive::Substitute,
+    roqoqo_derive::OperateMultiQubit,
+)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ControlledControlledPauliZ {
+    /// The qubits `[control_0, control_1, target]` of the gate.
+    qubits: Vec<usize>,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ControlledControlledPauliZ: &[&str; 4] = &[
+    "Operation",
+    "GateOperation",
+    "MultiQubitGateOperation",
+    "ControlledControlledPauliZ",
+];
+
+impl OperateGate for ControlledControlledPauliZ {
+    /// Returns unitary matrix of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The unitary matrix representation of the gate.
+    /// * `Err(RoqoqoError::InvalidQubits)` - The gate does not act on three qubits.
+    fn unitary_matrix(&self) -> Result<Array2<Complex64>, RoqoqoError> {
+        check_number_qubits(self.hqslang(), &self.qubits)?;
+        let zero = Complex64::new(0.0, 0.0);
+        let one = Complex64::new(1.0, 0.0);
+        Ok(controlled_matrix(2, array![[one, zero], [zero, -one]]))
+    }
+}
+
+impl OperateMultiQubitGate for ControlledControlledPauliZ {
+    /// Returns the decomposition of the gate into six CNOT gates and single-qubit gates.
+    ///
+    /// The decomposition does not need ancilla qubits
+    /// and includes the global phase as a [operations::PragmaGlobalPhase].
+    ///
+    /// # Panics
+    ///
+    /// Panics when the gate does not act on three qubits.
+    fn circuit(&self) -> Circuit {
+        doubly_controlled_pauli_z(self.qubits[0], self.qubits[1], self.qubits[2])
+    }
+}
+
+/// The controlled-controlled phase shift gate.
+///
+/// The gate acts on the qubits `[control_0, control_1, target]` and applies the phase $e^{i \theta}$
+/// when all three qubits are in the state |1⟩.
+///
+/// $$
+/// U = \mathrm{diag}(1, 1, 1, 1, 1, 1, 1, e^{i \theta})
+/// $$
+///
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    roqoqo_derive::InvolveQubits,
+    roqoqo_derive::Operate,
+    roqoqo_derive::Substitute,
+    roqoqo_derive::OperateMultiQubit,
+    roqoqo_derive::Rotate,
+)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct ControlledControlledPhaseShift {
+    /// The qubits `[control_0, control_1, target]` of the gate.
+    qubits: Vec<usize>,
+    /// The phase $\theta$ applied to the state |111⟩.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    theta: CalculatorFloat,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_ControlledControlledPhaseShift: &[&str; 5] = &[
+    "Operation",
+    "GateOperation",
+    "MultiQubitGateOperation",
+    "Rotation",
+    "ControlledControlledPhaseShift",
+];
+
+impl OperateGate for ControlledControlledPhaseShift {
+    /// Returns unitary matrix of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The unitary matrix representation of the gate.
+    /// * `Err(RoqoqoError::InvalidQubits)` - The gate does not act on three qubits.
+    /// * `Err(RoqoqoError::CalculatorError)` - The phase is symbolic.
+    fn unitary_matrix(&self) -> Result<Array2<Complex64>, RoqoqoError> {
+        check_number_qubits(self.hqslang(), &self.qubits)?;
+        let theta = *self.theta.float()?;
+        let zero = Complex64::new(0.0, 0.0);
+        let one = Complex64::new(1.0, 0.0);
+        Ok(controlled_matrix(
+            2,
+            array![[one, zero], [zero, Complex64::from_polar(1.0, theta)]],
+        ))
+    }
+}
+
+impl OperateMultiQubitGate for ControlledControlledPhaseShift {
+    /// Returns the decomposition of the gate into two CNOT gates and three controlled phase shifts.
+    ///
+    /// The decomposition does not need ancilla qubits.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the gate does not act on three qubits.
+    fn circuit(&self) -> Circuit {
+        let (control_0, control_1, target) = (self.qubits[0], self.qubits[1], self.qubits[2]);
+        let half = self.theta.clone() / 2.0;
+        let mut circuit = Circuit::new();
+        circuit += operations::ControlledPhaseShift::new(control_1, target, half.clone());
+        circuit += operations::CNOT::new(control_0, control_1);
+        circuit += operations::ControlledPhaseShift::new(control_1, target, -half.clone());
+        circuit += operations::CNOT::new(control_0, control_1);
+        circuit += operations::ControlledPhaseShift::new(control_0, target, half);
+        circuit
+    }
+}
+
+/// A general single-qubit unitary gate on a target qubit controlled by any number of control qubits.
+///
+/// The gate acts on the qubits `[control_0, ..., control_n, target]`
+/// and applies the single-qubit unitary
+///
+/// $$
+/// V = e^{i \varphi} R_z(z_2) R_y(y) R_z(z_1)
+/// $$
+///
+/// to the target qubit when all control qubits are in the state |1⟩.
+/// With the first qubit as the most significant qubit, the matrix of the gate is the identity
+/// with the lower right 2x2 block replaced by $V$.
+///
+/// The [OperateMultiQubitGate::circuit] decomposes the gate into single-qubit rotations and CNOT gates
+/// without ancilla qubits, using a number of gates growing exponentially with the number of controls.
+/// [MultiControlledGate::circuit_with_ancillas] uses ancilla qubits for a decomposition
+/// growing linearly with the number of controls.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::operations::{MultiControlledGate, OperateGate, OperateMultiQubitGate};
+///
+/// let gate = MultiControlledGate::pauli_x(vec![0, 1, 2, 3]);
+/// assert_eq!(gate.unitary_matrix().unwrap().dim(), (16, 16));
+/// let circuit = gate.circuit_with_ancillas(&[4]).unwrap();
+/// assert!(circuit.len() < gate.circuit().len());
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    roqoqo_derive::InvolveQubits,
+    roqoqo_derive::Operate,
+    roqoqo_derive::Substitute,
+    roqoqo_derive::OperateMultiQubit,
+)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct MultiControlledGate {
+    /// The control qubits followed by the target qubit.
+    qubits: Vec<usize>,
+    /// The phase $\varphi$ of the single-qubit unitary.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    phase: CalculatorFloat,
+    /// The angle $z_1$ of the first rotation around the z-axis.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    z1: CalculatorFloat,
+    /// The angle $y$ of the rotation around the y-axis.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    y: CalculatorFloat,
+    /// The angle $z_2$ of the second rotation around the z-axis.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    z2: CalculatorFloat,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_MultiControlledGate: &[&str; 4] = &[
+    "Operation",
+    "GateOperation",
+    "MultiQubitGateOperation",
+    "MultiControlledGate",
+];
+
+impl MultiControlledGate {
+    /// Creates a Pauli X gate controlled by all but the last qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The control qubits followed by the target qubit.
+    pub fn pauli_x(qubits: Vec<usize>) -> Self {
+        Self::new(
+            qubits,
+            FRAC_PI_2.into(),
+            FRAC_PI_2.into(),
+            PI.into(),
+            (-FRAC_PI_2).into(),
+        )
+    }
+
+    /// Creates a Pauli Z gate controlled by all but the last qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The control qubits followed by the target qubit.
+    pub fn pauli_z(qubits: Vec<usize>) -> Self {
+        Self::new(qubits, FRAC_PI_2.into(), PI.into(), 0.0.into(), 0.0.into())
+    }
+
+    /// Creates a phase shift on the state |1⟩ of the target qubit controlled by all but the last qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The control qubits followed by the target qubit.
+    /// * `theta` - The phase applied when all qubits are in the state |1⟩.
+    pub fn phase_shift(qubits: Vec<usize>, theta: CalculatorFloat) -> Self {
+        Self::new(qubits, theta.clone() / 2.0, theta, 0.0.into(), 0.0.into())
+    }
+
+    /// Creates a controlled gate from the unitary matrix applied to the target qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The control qubits followed by the target qubit.
+    /// * `matrix` - The unitary 2x2 matrix applied to the target qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The new gate.
+    /// * `Err(RoqoqoError::MismatchedUnitaryDimension)` - The matrix is not a 2x2 matrix.
+    /// * `Err(RoqoqoError::NonUnitaryMatrix)` - The matrix is not unitary.
+    pub fn from_unitary(
+        qubits: Vec<usize>,
+        matrix: Array2<Complex64>,
+    ) -> Result<Self, RoqoqoError> {
+        check_unitary(&matrix, 1)?;
+        let (phase, [z1, y, z2]) =
+            zyz_angles(&DMatrix::from_fn(2, 2, |row, column| matrix[(row, column)]));
+        Ok(Self::new(
+            qubits,
+            phase.into(),
+            z1.into(),
+            y.into(),
+            z2.into(),
+        ))
+    }
+
+    /// Returns the decomposition of the gate using ancilla qubits.
+    ///
+    /// The AND of all but the last control qubit is computed into a chain of ancilla qubits with Toffoli gates,
+    /// so that the gate only needs to be applied with two control qubits.
+    /// A gate with n > 2 control qubits needs n - 2 ancilla qubits, additional ancilla qubits are not used.
+    /// The ancilla qubits have to be in the state |0⟩ and are returned to the state |0⟩.
+    /// For at most two control qubits the decomposition is the same as [OperateMultiQubitGate::circuit].
+    ///
+    /// # Arguments
+    ///
+    /// * `ancillas` - The ancilla qubits available for the decomposition.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Circuit)` - The circuit implementing the gate.
+    /// * `Err(RoqoqoError::InvalidQubits)` - The gate has no target qubit, there are too few ancilla qubits or they are not distinct from the qubits of the gate.
+    pub fn circuit_with_ancillas(&self, ancillas: &[usize]) -> Result<Circuit, RoqoqoError> {
+        let (target, controls) =
+            self.qubits
+                .split_last()
+                .ok_or_else(|| RoqoqoError::InvalidQubits {
+                    hqslang: self.hqslang(),
+                    msg: "The gate needs a target qubit.".to_string(),
+                })?;
+        if controls.len() <= 2 {
+            return Ok(self.circuit());
+        }
+        let number_ancillas = controls.len() - 2;
+        if ancillas.len() < number_ancillas {
+            return Err(RoqoqoError::InvalidQubits {
+                hqslang: self.hqslang(),
+                msg: format!(
+                    "The decomposition with {} control qubits needs {} ancilla qubits, got {}.",
+                    controls.len(),
+                    number_ancillas,
+                    ancillas.len()
+                ),
+            });
+        }
+        let ancillas = &ancillas[..number_ancillas];
+        let distinct: HashSet<&usize> = self.qubits.iter().chain(ancillas.iter()).collect();
+        if distinct.len() != self.qubits.len() + number_ancillas {
+            return Err(RoqoqoError::InvalidQubits {
+                hqslang: self.hqslang(),
+                msg: format!(
+                    "The ancilla qubits {:?} are not distinct from the qubits {:?} of the gate.",
+                    ancillas, self.qubits
+                ),
+            });
+        }
+        let mut computation = vec![Toffoli::new(vec![controls[0], controls[1], ancillas[0]])];
+        for (index, control) in controls[2..controls.len() - 1].iter().enumerate() {
+            computation.push(Toffoli::new(vec![
+                *control,
+                ancillas[index],
+                ancillas[index + 1],
+            ]));
+        }
+        let mut circuit = Circuit::new();
+        for toffoli in computation.iter() {
+            circuit += toffoli.circuit();
+        }
+        circuit += multi_controlled_rotations(
+            &[ancillas[number_ancillas - 1], controls[controls.len() - 1]],
+            *target,
+            &self.phase,
+            [&self.z1, &self.y, &self.z2],
+        );
+        for toffoli in computation.iter().rev() {
+            circuit += toffoli.circuit();
+        }
+        Ok(circuit)
+    }
+}
+
+impl OperateGate for MultiControlledGate {
+    /// Returns unitary matrix of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The unitary matrix representation of the gate.
+    /// * `Err(RoqoqoError::InvalidQubits)` - The gate has no target qubit.
+    /// * `Err(RoqoqoError::CalculatorError)` - A parameter is symbolic.
+    fn unitary_matrix(&self) -> Result<Array2<Complex64>, RoqoqoError> {
+        if self.qubits.is_empty() {
+            return Err(RoqoqoError::InvalidQubits {
+                hqslang: self.hqslang(),
+                msg: "The gate needs a target qubit.".to_string(),
+            });
+        }
+        let phase = self.phase.float()?;
+        let z1 = self.z1.float()?;
+        let (cos, sin) = ((self.y.float()? / 2.0).cos(), (self.y.float()? / 2.0).sin());
+        let z2 = self.z2.float()?;
+        let unitary = array![
+            [
+                Complex64::from_polar(cos, phase - (z1 + z2) / 2.0),
+                Complex64::from_polar(-sin, phase + (z1 - z2) / 2.0)
+            ],
+            [
+                Complex64::from_polar(sin, phase - (z1 - z2) / 2.0),
+                Complex64::from_polar(cos, phase + (z1 + z2) / 2.0)
+            ]
+        ];
+        Ok(controlled_matrix(self.qubits.len() - 1, unitary))
+    }
+}
+
+impl OperateMultiQubitGate for MultiControlledGate {
+    /// Returns the decomposition of the gate into single-qubit rotations, CNOT gates and controlled phase shifts.
+    ///
+    /// The decomposition does not need ancilla qubits and includes the global phase as a [operations::PragmaGlobalPhase].
+    /// The number of gates grows exponentially with the number of control qubits,
+    /// see [MultiControlledGate::circuit_with_ancillas] for a decomposition with ancilla qubits.
+    fn circuit(&self) -> Circuit {
+        match self.qubits.split_last() {
+            Some((target, controls)) => multi_controlled_rotations(
+                controls,
+                *target,
+                &self.phase,
+                [&self.z1, &self.y, &self.z2],
+            ),
+            None => Circuit::new(),
+        }
+    }
+}
+
+/// The rotation exp(-i θ/2 P) under a product P of Pauli operators on multiple qubits.
+///
+/// The Pauli operator on each qubit uses the encoding of [operations::PragmaGetPauliProduct]:
+/// `0` = identity, `1` = PauliX, `2` = PauliY, `3` = PauliZ.
+/// The basis states of the matrix are ordered with the first qubit in `qubits` as the most significant qubit.
+///
+/// $$
+/// U = \cos\left(\frac{\theta}{2}\right) 1 - i \sin\left(\frac{\theta}{2}\right) P
+/// $$
+///
+/// The [OperateMultiQubitGate::circuit] decomposes the rotation into basis changes, a CNOT ladder and a RotateZ,
+/// [PauliProductRotation::circuit_with_layout] can also use a tree of CNOT gates with logarithmic depth.
+///
+/// # Example
+///
+/// ```
+/// use qoqo_calculator::CalculatorFloat;
+/// use roqoqo::operations::{
+///     OperateMultiQubit, OperateMultiQubitGate, PauliProductLayout, PauliProductRotation,
+/// };
+/// use std::collections::HashMap;
+///
+/// let mut qubit_paulis: HashMap<usize, usize> = HashMap::new();
+/// qubit_paulis.insert(0, 1);
+/// qubit_paulis.insert(2, 3);
+/// let rotation = PauliProductRotation::from_qubit_paulis(&qubit_paulis, CalculatorFloat::from("theta"));
+/// assert_eq!(rotation.qubits(), &vec![0, 2]);
+/// assert_eq!(rotation.paulis(), &vec![1, 3]);
+/// assert_eq!(rotation.circuit(), rotation.circuit_with_layout(PauliProductLayout::Ladder));
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    roqoqo_derive::InvolveQubits,
+    roqoqo_derive::Operate,
+    roqoqo_derive::Substitute,
+    roqoqo_derive::OperateMultiQubit,
+    roqoqo_derive::Rotate,
+)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct PauliProductRotation {
+    /// The qubits of the Pauli product, in descending order of significance.
+    qubits: Vec<usize>,
+    /// The Pauli operators on the qubits in the same order: `0` = identity, `1` = PauliX, `2` = PauliY, `3` = PauliZ.
+    paulis: Vec<usize>,
+    /// The angle $\theta$ of the rotation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    theta: CalculatorFloat,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_PauliProductRotation: &[&str; 5] = &[
+    "Operation",
+    "GateOperation",
+    "MultiQubitGateOperation",
+    "Rotation",
+    "PauliProductRotation",
+];
+
+/// The layout of the CNOT gates computing the parity of the qubits in the decomposition of a [PauliProductRotation].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauliProductLayout {
+    /// A chain of CNOT gates between neighbouring qubits of the Pauli product, with linear depth.
+    Ladder,
+    /// A binary tree of CNOT gates, with logarithmic depth.
+    Tree,
+}
+
+impl PauliProductRotation {
+    /// Creates a new PauliProductRotation from a {qubit: pauli} map.
+    ///
+    /// The qubits are sorted in ascending order, so the lowest qubit is the most significant qubit of the matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_paulis` - The Pauli operators on the qubits in the form {qubit: pauli}.
+    /// * `theta` - The angle of the rotation.
+    pub fn from_qubit_paulis(qubit_paulis: &HashMap<usize, usize>, theta: CalculatorFloat) -> Self {
+        let mut qubits: Vec<usize> = qubit_paulis.keys().cloned().collect();
+        qubits.sort_unstable();
+        let paulis: Vec<usize> = qubits.iter().map(|qubit| qubit_paulis[qubit]).collect();
+        Self::new(qubits, paulis, theta)
+    }
+
+    /// Returns the Pauli operators on the qubits in the form {qubit: pauli}.
+    pub fn qubit_paulis(&self) -> HashMap<usize, usize> {
+        self.qubits
+            .iter()
+            .cloned()
+            .zip(self.paulis.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the decomposition of the rotation with a given layout of the CNOT gates.
+    ///
+    /// Every qubit with a PauliX or PauliY operator is rotated to the Z basis,
+    /// the parity of the qubits with non-identity Pauli operators is computed into one qubit with CNOT gates,
+    /// where a RotateZ with the angle of the rotation is applied, and the CNOT gates and basis changes are undone.
+    /// A product of identities only adds the global phase as a [operations::PragmaGlobalPhase].
+    ///
+    /// # Arguments
+    ///
+    /// * `layout` - The layout of the CNOT gates computing the parity.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the numbers of qubits and Pauli operators differ or a Pauli operator is not in 0..=3.
+    pub fn circuit_with_layout(&self, layout: PauliProductLayout) -> Circuit {
+        if let Err(error) = self.check_paulis() {
+            panic!("{}", error);
+        }
+        let mut basis_change = Circuit::new();
+        let mut basis_change_back = Circuit::new();
+        let mut parity_qubits: Vec<usize> = Vec::new();
+        for (qubit, pauli) in self.qubits.iter().zip(self.paulis.iter()) {
+            match pauli {
+                1 => {
+                    basis_change += operations::Hadamard::new(*qubit);
+                    basis_change_back += operations::Hadamard::new(*qubit);
+                }
+                2 => {
+                    basis_change += operations::RotateX::new(*qubit, FRAC_PI_2.into());
+                    basis_change_back += operations::RotateX::new(*qubit, (-FRAC_PI_2).into());
+                }
+                _ => (),
+            }
+            if *pauli != 0 {
+                parity_qubits.push(*qubit);
+            }
+        }
+        let mut circuit = Circuit::new();
+        let (target, parity) = match parity_qubits.last() {
+            Some(target) => match layout {
+                PauliProductLayout::Ladder => (*target, cnot_ladder(&parity_qubits)),
+                PauliProductLayout::Tree => cnot_tree(&parity_qubits),
+            },
+            None => {
+                circuit += operations::PragmaGlobalPhase::new(-self.theta.clone() / 2.0);
+                return circuit;
+            }
+        };
+        circuit += basis_change;
+        for cnot in parity.iter() {
+            circuit += cnot.clone();
+        }
+        circuit += operations::RotateZ::new(target, self.theta.clone());
+        for cnot in parity.iter().rev() {
+            circuit += cnot.clone();
+        }
+        circuit += basis_change_back;
+        circuit
+    }
+
+    /// Checks that the Pauli operators match the qubits and are valid.
+    fn check_paulis(&self) -> Result<(), RoqoqoError> {
+        if self.qubits.len() != self.paulis.len() {
+            return Err(RoqoqoError::InvalidQubits {
+                hqslang: self.hqslang(),
+                msg: format!(
+                    "The {} qubits do not match the {} Pauli operators.",
+                    self.qubits.len(),
+                    self.paulis.len()
+                ),
+            });
+        }
+        if let Some(pauli) = self.paulis.iter().find(|pauli| **pauli > 3) {
+            return Err(RoqoqoError::InvalidQubits {
+                hqslang: self.hqslang(),
+                msg: format!(
+                    "Pauli operator {} is not one of 0 (identity), 1 (PauliX), 2 (PauliY) or 3 (PauliZ).",
+                    pauli
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl OperateGate for PauliProductRotation {
+    /// Returns unitary matrix of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The unitary matrix representation of the gate.
+    /// * `Err(RoqoqoError::InvalidQubits)` - The Pauli operators do not match the qubits or are not valid.
+    /// * `Err(RoqoqoError::CalculatorError)` - The angle is symbolic.
+    fn unitary_matrix(&self) -> Result<Array2<Complex64>, RoqoqoError> {
+        self.check_paulis()?;
+        let theta = *self.theta.float()?;
+        let cos = Complex64::new((theta / 2.0).cos(), 0.0);
+        let sin = Complex64::new(0.0, -(theta / 2.0).sin());
+        let number_qubits = self.qubits.len();
+        let dimension = 2_usize.pow(number_qubits as u32);
+        // The Pauli product maps each basis state to a single basis state with a phase
+        let mut flip: usize = 0;
+        for (index, pauli) in self.paulis.iter().enumerate() {
+            if *pauli == 1 || *pauli == 2 {
+                flip |= 1 << (number_qubits - 1 - index);
+            }
+        }
+        let mut matrix: Array2<Complex64> = Array2::zeros((dimension, dimension));
+        for column in 0..dimension {
+            let mut phase = Complex64::new(1.0, 0.0);
+            for (index, pauli) in self.paulis.iter().enumerate() {
+                let bit_set = column & (1 << (number_qubits - 1 - index)) != 0;
+                phase *= match (pauli, bit_set) {
+                    (2, false) => Complex64::new(0.0, 1.0),
+                    (2, true) => Complex64::new(0.0, -1.0),
+                    (3, true) => Complex64::new(-1.0, 0.0),
+                    _ => Complex64::new(1.0, 0.0),
+                };
+            }
+            matrix[(column, column)] += cos;
+            matrix[(column ^ flip, column)] += sin * phase;
+        }
+        Ok(matrix)
+    }
+}
+
+impl OperateMultiQubitGate for PauliProductRotation {
+    /// Returns the decomposition of the rotation into basis changes, a CNOT ladder and a RotateZ.
+    ///
+    /// See [PauliProductRotation::circuit_with_layout] for the decomposition with a CNOT tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the numbers of qubits and Pauli operators differ or a Pauli operator is not in 0..=3.
+    fn circuit(&self) -> Circuit {
+        self.circuit_with_layout(PauliProductLayout::Ladder)
+    }
+}
+
+/// Returns the CNOT gates computing the parity of the qubits into the last qubit with a chain of CNOT gates.
+fn cnot_ladder(qubits: &[usize]) -> Vec<operations::CNOT> {
+    qubits
+        .windows(2)
+        .map(|pair| operations::CNOT::new(pair[0], pair[1]))
+        .collect()
+}
+
+/// Returns the CNOT gates computing the parity of the qubits with a binary tree and the qubit holding the parity.
+fn cnot_tree(qubits: &[usize]) -> (usize, Vec<operations::CNOT>) {
+    let mut cnots: Vec<operations::CNOT> = Vec::new();
+    let mut active: Vec<usize> = qubits.to_vec();
+    while active.len() > 1 {
+        let mut next: Vec<usize> = Vec::new();
+        for pair in active.chunks(2) {
+            if let [control, target] = pair {
+                cnots.push(operations::CNOT::new(*control, *target));
+            }
+            next.push(pair[pair.len() - 1]);
+        }
+        active = next;
+    }
+    (active[0], cnots)
+}
+
+/// Checks that a doubly controlled gate acts on three qubits.
+fn check_number_qubits(hqslang: &'static str, qubits: &[usize]) -> Result<(), RoqoqoError> {
+    if qubits.len() != 3 {
+        return Err(RoqoqoError::InvalidQubits {
+            hqslang,
+            msg: format!(
+                "The gate acts on two control qubits and one target qubit, got qubits {:?}.",
+                qubits
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the matrix of a single-qubit unitary controlled by a number of control qubits.
+///
+/// The control qubits are the most significant qubits, so the matrix is the identity
+/// with the lower right 2x2 block replaced by the unitary.
+fn controlled_matrix(number_controls: usize, unitary: Array2<Complex64>) -> Array2<Complex64> {
+    let dimension = 2_usize.pow(number_controls as u32 + 1);
+    let mut matrix: Array2<Complex64> = Array2::eye(dimension);
+    matrix
+        .slice_mut(s![dimension - 2.., dimension - 2..])
+        .assign(&unitary);
+    matrix
+}
+
+/// Returns true when the angle is the float zero.
+fn is_zero(angle: &CalculatorFloat) -> bool {
+    matches!(angle, CalculatorFloat::Float(x) if *x == 0.0)
+}
+
+/// Adds a rotation to the circuit unless its angle is the float zero.
+fn add_rotation<T>(circuit: &mut Circuit, angle: &CalculatorFloat, rotation: T)
+where
+    T: Into<operations::Operation>,
+{
+    if !is_zero(angle) {
+        *circuit += rotation;
+    }
+}
+
+/// Returns the decomposition of the doubly controlled Pauli Z gate into six CNOT gates and T gates.
+fn doubly_controlled_pauli_z(control_0: usize, control_1: usize, target: usize) -> Circuit {
+    let tdg = |qubit: usize| operations::RotateZ::new(qubit, (-FRAC_PI_4).into());
+    let mut circuit = Circuit::new();
+    circuit += operations::CNOT::new(control_1, target);
+    circuit += tdg(target);
+    circuit += operations::CNOT::new(control_0, target);
+    circuit += operations::TGate::new(target);
+    circuit += operations::CNOT::new(control_1, target);
+    circuit += tdg(target);
+    circuit += operations::CNOT::new(control_0, target);
+    circuit += operations::TGate::new(control_1);
+    circuit += operations::TGate::new(target);
+    circuit += operations::CNOT::new(control_0, control_1);
+    circuit += operations::TGate::new(control_0);
+    circuit += tdg(control_1);
+    circuit += operations::CNOT::new(control_0, control_1);
+    // Each RotateZ(-pi/4) differs from the adjoint of the T gate by the phase exp(i pi/8).
+    circuit += operations::PragmaGlobalPhase::new((-3.0 * PI / 8.0).into());
+    circuit
+}
+
+/// Returns the ancilla-free decomposition of a multi-controlled Pauli X gate.
+///
+/// For more than two control qubits the gate is built from gates with fewer control qubits
+/// following Lemma 7.5 of Barenco et al., Phys. Rev. A 52, 3457 (1995), with the square root of Pauli X.
+fn multi_controlled_pauli_x(controls: &[usize], target: usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    match controls {
+        [] => circuit += operations::PauliX::new(target),
+        [control] => circuit += operations::CNOT::new(*control, target),
+        [control_0, control_1] => {
+            circuit += Toffoli::new(vec![*control_0, *control_1, target]).circuit()
+        }
+        [others @ .., last] => {
+            let sqrt_x: [CalculatorFloat; 4] = [
+                FRAC_PI_4.into(),
+                FRAC_PI_2.into(),
+                FRAC_PI_2.into(),
+                (-FRAC_PI_2).into(),
+            ];
+            let sqrt_x_dagger: [CalculatorFloat; 4] = [
+                (-FRAC_PI_4).into(),
+                FRAC_PI_2.into(),
+                (-FRAC_PI_2).into(),
+                (-FRAC_PI_2).into(),
+            ];
+            let rotations = |controls: &[usize], parameters: &[CalculatorFloat; 4]| {
+                multi_controlled_rotations(
+                    controls,
+                    target,
+                    &parameters[0],
+                    [&parameters[1], &parameters[2], &parameters[3]],
+                )
+            };
+            circuit += rotations(&[*last], &sqrt_x);
+            circuit += multi_controlled_pauli_x(others, *last);
+            circuit += rotations(&[*last], &sqrt_x_dagger);
+            circuit += multi_controlled_pauli_x(others, *last);
+            circuit += rotations(others, &sqrt_x);
+        }
+    }
+    circuit
+}
+
+/// Returns the ancilla-free decomposition of exp(i phase) RotateZ(z2) RotateY(y) RotateZ(z1)
+/// on the target qubit controlled by all control qubits.
+///
+/// The rotation is written as A X B X C with ABC = 1 following Lemma 7.9 of Barenco et al.,
+/// Phys. Rev. A 52, 3457 (1995), and the phase is applied as a phase shift controlled by the remaining controls.
+fn multi_controlled_rotations(
+    controls: &[usize],
+    target: usize,
+    phase: &CalculatorFloat,
+    angles: [&CalculatorFloat; 3],
+) -> Circuit {
+    let [z1, y, z2] = angles;
+    let mut circuit = Circuit::new();
+    let (last, others) = match controls.split_last() {
+        Some(split) => split,
+        None => {
+            add_rotation(
+                &mut circuit,
+                z1,
+                operations::RotateZ::new(target, z1.clone()),
+            );
+            add_rotation(&mut circuit, y, operations::RotateY::new(target, y.clone()));
+            add_rotation(
+                &mut circuit,
+                z2,
+                operations::RotateZ::new(target, z2.clone()),
+            );
+            add_rotation(
+                &mut circuit,
+                phase,
+                operations::PragmaGlobalPhase::new(phase.clone()),
+            );
+            return circuit;
+        }
+    };
+    let angle_c = (z1.clone() - z2) / 2.0;
+    let angle_b_z = -(z1.clone() + z2) / 2.0;
+    let angle_b_y = -y.clone() / 2.0;
+    let angle_a_y = y.clone() / 2.0;
+    add_rotation(
+        &mut circuit,
+        &angle_c,
+        operations::RotateZ::new(target, angle_c.clone()),
+    );
+    circuit += multi_controlled_pauli_x(controls, target);
+    add_rotation(
+        &mut circuit,
+        &angle_b_z,
+        operations::RotateZ::new(target, angle_b_z.clone()),
+    );
+    add_rotation(
+        &mut circuit,
+        &angle_b_y,
+        operations::RotateY::new(target, angle_b_y.clone()),
+    );
+    circuit += multi_controlled_pauli_x(controls, target);
+    add_rotation(
+        &mut circuit,
+        &angle_a_y,
+        operations::RotateY::new(target, angle_a_y.clone()),
+    );
+    add_rotation(
+        &mut circuit,
+        z2,
+        operations::RotateZ::new(target, z2.clone()),
+    );
+    if !is_zero(phase) {
+        match others {
+            [control] => {
+                circuit += operations::ControlledPhaseShift::new(*control, *last, phase.clone())
+            }
+            _ => {
+                let zero = CalculatorFloat::from(0.0);
+                circuit += multi_controlled_rotations(
+                    others,
+                    *last,
+                    &(phase.clone() / 2.0),
+                    [phase, &zero, &zero],
+                )
+            }
+        }
+    }
+    circuit
+}
@@ -18,13 +18,15 @@ use num_complex::Complex64;
 use qoqo_calculator::{Calculator, CalculatorFloat};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 
+use crate::operations::classical_operations::register_bit;
 use crate::operations::{
     InvolveQubits, InvolvedQubits, Operate, OperateMultiQubit, OperatePragma, OperatePragmaNoise,
     OperateSingleQubit, RoqoqoError, Substitute,
 };
+use crate::parameters::expression_symbols;
 use crate::Circuit;
 
 /// This PRAGMA Operation sets the number of measurements of the circuit.
@@ -41,6 +43,7 @@ use crate::Circuit;
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaSetNumberOfMeasurements {
     /// The number of measurements.
     number_measurements: usize,
@@ -97,8 +100,13 @@ impl InvolveQubits for PragmaSetNumberOfMeasurements {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaSetStateVector {
     /// The statevector that is initialized.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::Array1<(f64, f64)>")
+    )]
     statevector: Array1<Complex64>,
 }
 
@@ -143,8 +151,13 @@ impl InvolveQubits for PragmaSetStateVector {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaSetDensityMatrix {
     /// The density matrix that is initialized.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::Array2<(f64, f64)>")
+    )]
     density_matrix: Array2<Complex64>,
 }
 
@@ -173,6 +186,7 @@ impl InvolveQubits for PragmaSetDensityMatrix {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaRepeatGate {
     /// The number of times the following gate is repeated.
     repetition_coefficient: usize,
@@ -209,6 +223,7 @@ impl InvolveQubits for PragmaRepeatGate {
     roqoqo_derive::OperateMultiQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 // #[cfg_attr(feature = "overrotate")]
 pub struct PragmaOverrotation {
     /// The unique hqslang name of the gate to overrotate.
@@ -240,8 +255,13 @@ const TAGS_PragmaOverrotation: &[&str; 4] = &[
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaBoostNoise {
     /// The coefficient by which the noise is boosted, i.e. the number by which the gate time is multiplied.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     noise_coefficient: CalculatorFloat,
 }
 
@@ -269,10 +289,15 @@ impl InvolveQubits for PragmaBoostNoise {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaStopParallelBlock {
     /// The qubits involved in parallel execution block.
     qubits: Vec<usize>,
     /// The time for the execution of the block in seconds.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     execution_time: CalculatorFloat,
 }
 
@@ -298,8 +323,13 @@ const TAGS_PragmaStopParallelBlock: &[&str; 4] = &[
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaGlobalPhase {
     /// The picked up global phase.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     phase: CalculatorFloat,
 }
 
@@ -330,10 +360,15 @@ impl InvolveQubits for PragmaGlobalPhase {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaSleep {
     /// The qubits involved in the sleep block.
     qubits: Vec<usize>,
     /// Time for the execution of the operation in seconds.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     sleep_time: CalculatorFloat,
 }
 
@@ -358,6 +393,7 @@ const TAGS_PragmaSleep: &[&str; 4] = &[
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaActiveReset {
     /// The qubit to be reset.
     qubit: usize,
@@ -383,6 +419,7 @@ const TAGS_PragmaActiveReset: &[&str; 4] = &[
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaStartDecompositionBlock {
     /// The qubits involved in the decomposition block.
     qubits: Vec<usize>,
@@ -436,6 +473,11 @@ impl Substitute for PragmaStartDecompositionBlock {
     fn substitute_parameters(&self, _calculator: &mut Calculator) -> Result<Self, RoqoqoError> {
         Ok(self.clone())
     }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        HashSet::new()
+    }
 }
 
 /// This PRAGMA Operation signals the STOP of a decomposition block.
@@ -451,6 +493,7 @@ impl Substitute for PragmaStartDecompositionBlock {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaStopDecompositionBlock {
     /// The qubits involved in the decomposition block.
     qubits: Vec<usize>,
@@ -479,12 +522,21 @@ const TAGS_PragmaStopDecompositionBlock: &[&str; 4] = &[
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaDamping {
     /// The qubit on which to apply the damping.
     qubit: usize,
     /// The time (in seconds) the gate takes to be applied to the qubit on the (simulated) hardware
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     gate_time: CalculatorFloat,
     /// The error rate of the damping (in 1/second).
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     rate: CalculatorFloat,
 }
 
@@ -546,12 +598,21 @@ impl OperatePragmaNoise for PragmaDamping {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaDepolarising {
     /// The qubit on which to apply the depolarising.
     qubit: usize,
     /// The time (in seconds) the gate takes to be applied to the qubit on the (simulated) hardware
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     gate_time: CalculatorFloat,
     /// The error rate of the depolarisation (in 1/second).
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     rate: CalculatorFloat,
 }
 
@@ -615,12 +676,21 @@ impl OperatePragmaNoise for PragmaDepolarising {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaDephasing {
     /// The qubit on which to apply the dephasing.
     qubit: usize,
     /// The time (in seconds) the gate takes to be applied to the qubit on the (simulated) hardware
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     gate_time: CalculatorFloat,
     /// The error rate of the dephasing (in 1/second).
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     rate: CalculatorFloat,
 }
 
@@ -681,14 +751,27 @@ impl OperatePragmaNoise for PragmaDephasing {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaRandomNoise {
     /// The qubit the PRAGMA Operation is applied to.
     qubit: usize,
     /// The time (in seconds) the gate takes to be applied to the qubit on the (simulated) hardware
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     gate_time: CalculatorFloat,
     /// The error rate of the depolarisation (in 1/second).
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     depolarising_rate: CalculatorFloat,
     /// The error rate of the dephasing (in 1/second).
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     dephasing_rate: CalculatorFloat,
 }
 
@@ -798,14 +881,27 @@ impl OperatePragmaNoise for PragmaRandomNoise {
     roqoqo_derive::OperatePragma,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaGeneralNoise {
     /// The qubit the PRAGMA Operation is applied to.
     qubit: usize,
     /// The time (in seconds) the gate takes to be applied to the qubit on the (simulated) hardware
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     gate_time: CalculatorFloat,
     /// The error rate of the noise (in 1/second).
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     rate: CalculatorFloat,
     /// The operators representing the general noise (a 3x3 matrix).
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::Array2<(f64, f64)>")
+    )]
     operators: Array2<Complex64>,
 }
 
@@ -823,6 +919,7 @@ const TAGS_PragmaGeneralNoise: &[&str; 4] = &[
 ///
 #[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PragmaConditional {
     /// The name of the [crate::registers::BitRegister] containting the condition bool value.
     condition_register: String,
@@ -852,7 +949,7 @@ impl InvolveQubits for PragmaConditional {
 impl Substitute for PragmaConditional {
     /// Remaps qubits in clone of the operation.
     fn remap_qubits(&self, mapping: &HashMap<usize, usize>) -> Result<Self, RoqoqoError> {
-        let new_circuit = self.circuit.remap_qubits(mapping).unwrap();
+        let new_circuit = self.circuit.remap_qubits(mapping)?;
         Ok(PragmaConditional::new(
             self.condition_register.clone(),
             self.condition_index,
@@ -862,11 +959,267 @@ impl Substitute for PragmaConditional {
 
     /// Substitutes symbolic parameters in clone of the operation.
     fn substitute_parameters(&self, calculator: &mut Calculator) -> Result<Self, RoqoqoError> {
-        let new_circuit = self.circuit.substitute_parameters(calculator).unwrap();
+        let new_circuit = self.circuit.substitute_parameters(calculator)?;
         Ok(PragmaConditional::new(
             self.condition_register.clone(),
             self.condition_index,
             new_circuit,
         ))
     }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        self.circuit.free_parameters()
+    }
+}
+
+/// The conditional PRAGMA operation with a condition on several bits.
+///
+/// This PRAGMA executes a circuit when each bit of a [crate::registers::BitRegister] listed in the condition
+/// has the value it is paired with, for example when a syndrome matches the syndrome of a correctable error.
+/// Conditions on other combinations of bits or on floats can be computed into a single bit
+/// with classical operations such as [crate::operations::ClassicalXor].
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct PragmaConditionalPattern {
+    /// The name of the [crate::registers::BitRegister] containting the condition bits.
+    condition_register: String,
+    /// The index of each condition bit in the [crate::registers::BitRegister] paired with the value it must have for the circuit to be executed.
+    condition: Vec<(usize, bool)>,
+    /// The circuit executed if the condition is met.
+    circuit: Circuit,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_PragmaConditionalPattern: &[&str; 3] =
+    &["Operation", "PragmaOperation", "PragmaConditionalPattern"];
+
+// Implementing the InvolveQubits trait for PragmaConditionalPattern.
+impl InvolveQubits for PragmaConditionalPattern {
+    /// Lists all involved qubits.
+    fn involved_qubits(&self) -> InvolvedQubits {
+        self.circuit.involved_qubits()
+    }
+}
+
+/// Substitute trait allowing to replace symbolic parameters and to perform qubit mappings.
+impl Substitute for PragmaConditionalPattern {
+    /// Remaps qubits in clone of the operation.
+    fn remap_qubits(&self, mapping: &HashMap<usize, usize>) -> Result<Self, RoqoqoError> {
+        let new_circuit = self.circuit.remap_qubits(mapping)?;
+        Ok(PragmaConditionalPattern::new(
+            self.condition_register.clone(),
+            self.condition.clone(),
+            new_circuit,
+        ))
+    }
+
+    /// Substitutes symbolic parameters in clone of the operation.
+    fn substitute_parameters(&self, calculator: &mut Calculator) -> Result<Self, RoqoqoError> {
+        let new_circuit = self.circuit.substitute_parameters(calculator)?;
+        Ok(PragmaConditionalPattern::new(
+            self.condition_register.clone(),
+            self.condition.clone(),
+            new_circuit,
+        ))
+    }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        self.circuit.free_parameters()
+    }
+}
+
+impl PragmaConditionalPattern {
+    /// Returns whether the condition is met by the values of the condition register.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The values of the condition register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - True when all condition bits have the values they are paired with.
+    /// * `Err(RoqoqoError::ClassicalRegisterError)` - A condition index is out of range of the register.
+    pub fn condition_is_met(&self, register: &[bool]) -> Result<bool, RoqoqoError> {
+        for (index, value) in self.condition.iter() {
+            if register_bit(register, &self.condition_register, *index)? != *value {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// The loop PRAGMA operation.
+///
+/// This PRAGMA executes a circuit a fixed number of times.
+/// The number of repetitions can be symbolic but must be a non-negative integer once all parameters are substituted.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct PragmaLoop {
+    /// The number of times the circuit is executed.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    repetitions: CalculatorFloat,
+    /// The circuit executed in each repetition.
+    circuit: Circuit,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_PragmaLoop: &[&str; 3] = &["Operation", "PragmaOperation", "PragmaLoop"];
+
+// Implementing the InvolveQubits trait for PragmaLoop.
+impl InvolveQubits for PragmaLoop {
+    /// Lists all involved qubits.
+    fn involved_qubits(&self) -> InvolvedQubits {
+        self.circuit.involved_qubits()
+    }
+}
+
+/// Substitute trait allowing to replace symbolic parameters and to perform qubit mappings.
+impl Substitute for PragmaLoop {
+    /// Remaps qubits in clone of the operation.
+    fn remap_qubits(&self, mapping: &HashMap<usize, usize>) -> Result<Self, RoqoqoError> {
+        let new_circuit = self.circuit.remap_qubits(mapping)?;
+        Ok(PragmaLoop::new(self.repetitions.clone(), new_circuit))
+    }
+
+    /// Substitutes symbolic parameters in clone of the operation.
+    fn substitute_parameters(&self, calculator: &mut Calculator) -> Result<Self, RoqoqoError> {
+        let new_repetitions = calculator.parse_get(self.repetitions.clone())?;
+        let new_circuit = self.circuit.substitute_parameters(calculator)?;
+        Ok(PragmaLoop::new(new_repetitions.into(), new_circuit))
+    }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        let mut parameters = self.circuit.free_parameters();
+        parameters.extend(expression_symbols(&self.repetitions));
+        parameters
+    }
+}
+
+impl PragmaLoop {
+    /// Returns the number of repetitions of the loop.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of times the circuit is executed.
+    /// * `Err(RoqoqoError::CalculatorError)` - The number of repetitions is symbolic.
+    /// * `Err(RoqoqoError::InvalidRepetitions)` - The number of repetitions is not a non-negative integer.
+    pub fn number_repetitions(&self) -> Result<usize, RoqoqoError> {
+        let repetitions = *self.repetitions.float()?;
+        if !repetitions.is_finite() || repetitions < 0.0 || repetitions.fract() != 0.0 {
+            return Err(RoqoqoError::InvalidRepetitions { repetitions });
+        }
+        Ok(repetitions as usize)
+    }
+
+    /// Unrolls the loop into a circuit containing the circuit of the loop once for each repetition.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Circuit)` - The unrolled circuit.
+    /// * `Err(RoqoqoError)` - The number of repetitions is symbolic or not a non-negative integer.
+    pub fn unroll(&self) -> Result<Circuit, RoqoqoError> {
+        let mut unrolled = Circuit::new();
+        for _ in 0..self.number_repetitions()? {
+            unrolled += self.circuit.clone();
+        }
+        Ok(unrolled)
+    }
+}
+
+/// The repeat-until-success PRAGMA operation.
+///
+/// This PRAGMA executes a circuit and executes it again as long as the bit at the condition index of a
+/// [crate::registers::BitRegister] is true afterwards, for example when an ancilla measurement flags a failed
+/// state preparation. The circuit is executed at most max_iterations times.
+///
+#[derive(Debug, Clone, PartialEq, roqoqo_derive::Operate, roqoqo_derive::OperatePragma)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct PragmaRepeatUntilSuccess {
+    /// The name of the [crate::registers::BitRegister] containting the condition bool value.
+    condition_register: String,
+    /// The index in the [crate::registers::BitRegister] containting the condition bool value.
+    condition_index: usize,
+    /// The maximal number of times the circuit is executed.
+    max_iterations: usize,
+    /// The circuit executed until the condition bit is false.
+    circuit: Circuit,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_PragmaRepeatUntilSuccess: &[&str; 3] =
+    &["Operation", "PragmaOperation", "PragmaRepeatUntilSuccess"];
+
+// Implementing the InvolveQubits trait for PragmaRepeatUntilSuccess.
+impl InvolveQubits for PragmaRepeatUntilSuccess {
+    /// Lists all involved qubits.
+    fn involved_qubits(&self) -> InvolvedQubits {
+        self.circuit.involved_qubits()
+    }
+}
+
+/// Substitute trait allowing to replace symbolic parameters and to perform qubit mappings.
+impl Substitute for PragmaRepeatUntilSuccess {
+    /// Remaps qubits in clone of the operation.
+    fn remap_qubits(&self, mapping: &HashMap<usize, usize>) -> Result<Self, RoqoqoError> {
+        let new_circuit = self.circuit.remap_qubits(mapping)?;
+        Ok(PragmaRepeatUntilSuccess::new(
+            self.condition_register.clone(),
+            self.condition_index,
+            self.max_iterations,
+            new_circuit,
+        ))
+    }
+
+    /// Substitutes symbolic parameters in clone of the operation.
+    fn substitute_parameters(&self, calculator: &mut Calculator) -> Result<Self, RoqoqoError> {
+        let new_circuit = self.circuit.substitute_parameters(calculator)?;
+        Ok(PragmaRepeatUntilSuccess::new(
+            self.condition_register.clone(),
+            self.condition_index,
+            self.max_iterations,
+            new_circuit,
+        ))
+    }
+
+    /// Returns the symbols in the symbolic parameters of the operation.
+    fn free_parameters(&self) -> HashSet<String> {
+        self.circuit.free_parameters()
+    }
+}
+
+impl PragmaRepeatUntilSuccess {
+    /// Unrolls the loop into max_iterations copies of the circuit nested in [PragmaConditional] operations.
+    ///
+    /// Each copy after the first is only executed when the condition bit is true after the previous copy.
+    ///
+    /// # Returns
+    ///
+    /// * `Circuit` - The unrolled circuit.
+    pub fn unroll(&self) -> Circuit {
+        let mut unrolled = Circuit::new();
+        for iteration in 0..self.max_iterations {
+            let mut outer = self.circuit.clone();
+            if iteration > 0 {
+                outer += PragmaConditional::new(
+                    self.condition_register.clone(),
+                    self.condition_index,
+                    unrolled,
+                );
+            }
+            unrolled = outer;
+        }
+        unrolled
+    }
 }
@@ -46,18 +46,39 @@ use rand_distr::{Distribution, Normal};
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct SingleQubitGate {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
     /// The real part $ \alpha_r $ of the on-diagonal elements of the single-qubit unitary.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     alpha_r: CalculatorFloat,
     /// The imaginary part $ \alpha_i $ of the on-diagonal elements of the single-qubit unitary.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     alpha_i: CalculatorFloat,
     /// The real part $ \beta_r $ of the off-diagonal elements of the single-qubit unitary.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     beta_r: CalculatorFloat,
     /// The imaginary part $ \beta_i $ of the off-diagonal elements of the single-qubit unitary.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     beta_i: CalculatorFloat,
     /// The global phase $ \phi $ of the single-qubit unitary.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     global_phase: CalculatorFloat,
 }
 
@@ -182,10 +203,15 @@ impl OperateSingleQubitGate for SingleQubitGate {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct RotateZ {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
     /// The angle $\theta$ of the rotation, in the interval from 0 to $2^* 2 \pi$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
 }
 
@@ -288,10 +314,15 @@ impl OperateSingleQubitGate for RotateZ {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct RotateX {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
     /// The angle $\theta$ of the rotation, in the interval from 0 to $2^* 2 \pi$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
 }
 #[allow(non_upper_case_globals)]
@@ -389,10 +420,15 @@ impl OperateSingleQubitGate for RotateX {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct RotateY {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
     /// The angle $\theta$ of the rotation, in the interval from 0 to $2^* 2 \pi$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
 }
 #[allow(non_upper_case_globals)]
@@ -485,6 +521,7 @@ impl OperateSingleQubitGate for RotateY {
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PauliX {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
@@ -577,6 +614,7 @@ impl OperateSingleQubitGate for PauliX {
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PauliY {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
@@ -669,6 +707,7 @@ impl OperateSingleQubitGate for PauliY {
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PauliZ {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
@@ -761,6 +800,7 @@ impl OperateSingleQubitGate for PauliZ {
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct SqrtPauliX {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
@@ -856,6 +896,7 @@ impl OperateSingleQubitGate for SqrtPauliX {
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct InvSqrtPauliX {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
@@ -951,6 +992,7 @@ impl OperateSingleQubitGate for InvSqrtPauliX {
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct Hadamard {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
@@ -1044,6 +1086,7 @@ impl OperateSingleQubitGate for Hadamard {
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct SGate {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
@@ -1136,6 +1179,7 @@ impl OperateSingleQubitGate for SGate {
     roqoqo_derive::OperateSingleQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct TGate {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
@@ -1241,14 +1285,27 @@ impl OperateSingleQubitGate for TGate {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct RotateAroundSphericalAxis {
     /// The qubit the unitary gate is applied to.
     qubit: usize,
     /// The angle $\theta$ of the rotation, in the interval from 0 to $2^* 2 \pi$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
     /// The rotation axis, unit-vector spherical coordinates $\theta_{sph}$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     spherical_theta: CalculatorFloat,
     /// The rotation axis, unit-vector spherical coordinates $\phi_{sph}$  gives the angle in the x-y plane.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     spherical_phi: CalculatorFloat,
 }
 
@@ -10,6 +10,9 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::operations::decompositions::{
+    check_unitary, kak_decomposition, DECOMPOSITION_TOLERANCE,
+};
 use crate::operations::single_qubit_gate_operations::*;
 use crate::prelude::*;
 use crate::Circuit;
@@ -66,6 +69,7 @@ pub struct KakDecomposition {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct CNOT {
     /// The index of the most significant qubit in the unitary representation. Here, the qubit that controls the application of NOT on the target qubit.
     control: usize,
@@ -170,6 +174,7 @@ impl OperateTwoQubitGate for CNOT {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct SWAP {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
@@ -266,6 +271,7 @@ impl OperateTwoQubitGate for SWAP {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct ISwap {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
@@ -362,6 +368,7 @@ impl OperateTwoQubitGate for ISwap {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct FSwap {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
@@ -462,6 +469,7 @@ impl OperateTwoQubitGate for FSwap {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct SqrtISwap {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
@@ -559,6 +567,7 @@ impl OperateTwoQubitGate for SqrtISwap {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct InvSqrtISwap {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
@@ -657,12 +666,17 @@ impl OperateTwoQubitGate for InvSqrtISwap {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct XY {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The rotation angle $\theta$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
 }
 
@@ -759,12 +773,17 @@ impl OperateTwoQubitGate for XY {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct ControlledPhaseShift {
     /// The index of the most significant qubit in the unitary representation. Here, the qubit that controls the application of the phase-shift on the target qubit.
     control: usize,
     /// The index of the least significant qubit in the unitary representation. Here, the qubit phase-shift is applied to.
     target: usize,
     /// The rotation angle $\theta$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
 }
 
@@ -865,6 +884,7 @@ impl OperateTwoQubitGate for ControlledPhaseShift {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct ControlledPauliY {
     /// The index of the most significant qubit in the unitary representation. Here, the qubit that controls the application of PauliY gate on the target qubit.
     control: usize,
@@ -969,6 +989,7 @@ impl OperateTwoQubitGate for ControlledPauliY {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct ControlledPauliZ {
     /// The index of the most significant qubit in the unitary representation. Here, the qubit that controls the application of PauliZ gate on the target qubit.
     control: usize,
@@ -1071,6 +1092,7 @@ impl OperateTwoQubitGate for ControlledPauliZ {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct MolmerSorensenXX {
     /// The index of the most significant qubit in the unitary representation. The gate is symmetric under the exchange of qubits.
     control: usize,
@@ -1169,12 +1191,17 @@ impl OperateTwoQubitGate for MolmerSorensenXX {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct VariableMSXX {
     /// The index of the most significant qubit in the unitary representation. The gate is symmetric under the exchange of qubits.
     control: usize,
     /// The index of the least significant qubit in the unitary representation. The gate is symmetric under the exchange of qubits.
     target: usize,
     /// The rotation angle $\theta$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
 }
 
@@ -1275,14 +1302,23 @@ impl OperateTwoQubitGate for VariableMSXX {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct GivensRotation {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The rotation angle $\theta$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
     /// The phase $\phi$ of the rotation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     phi: CalculatorFloat,
 }
 
@@ -1392,14 +1428,23 @@ impl OperateTwoQubitGate for GivensRotation {
     roqoqo_derive::Rotate,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct GivensRotationLittleEndian {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The rotation angle $\theta$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     theta: CalculatorFloat,
     /// The phase $\phi$ of the rotation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     phi: CalculatorFloat,
 }
 
@@ -1504,16 +1549,29 @@ impl OperateTwoQubitGate for GivensRotationLittleEndian {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct Qsim {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The prefactor of the XX interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     x: CalculatorFloat,
     /// The prefactor of the YY interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     y: CalculatorFloat,
     /// The prefactor of the ZZ interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     z: CalculatorFloat,
 }
 
@@ -1624,16 +1682,29 @@ impl OperateTwoQubitGate for Qsim {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct Fsim {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The hopping strength.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     t: CalculatorFloat,
     /// The interaction strength.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     u: CalculatorFloat,
     /// The Bogoliubov interaction strength $\Delta$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     delta: CalculatorFloat,
 }
 
@@ -1733,16 +1804,29 @@ impl OperateTwoQubitGate for Fsim {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct SpinInteraction {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The prefactor of the XX interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     x: CalculatorFloat,
     /// The prefactor of the YY interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     y: CalculatorFloat,
     /// The prefactor of the ZZ interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     z: CalculatorFloat,
 }
 
@@ -1856,14 +1940,23 @@ impl OperateTwoQubitGate for SpinInteraction {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct Bogoliubov {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The real part of the complex Bogoliubov interaction strength $Re(\Delta)$
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     delta_real: CalculatorFloat,
     /// The imaginary part of the complex Bogoliubov interaction strength $Im(\Delta)$
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     delta_imag: CalculatorFloat,
 }
 
@@ -1967,12 +2060,17 @@ impl OperateTwoQubitGate for Bogoliubov {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PMInteraction {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The strength of the rotation $\theta$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     t: CalculatorFloat,
 }
 
@@ -2063,14 +2161,23 @@ impl OperateTwoQubitGate for PMInteraction {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct ComplexPMInteraction {
     /// The index of the most significant qubit in the unitary representation.
     control: usize,
     /// The index of the least significant qubit in the unitary representation.
     target: usize,
     /// The real part of the strength of the rotation $Re(\theta)$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     t_real: CalculatorFloat,
     /// The imaginary part of the strength of the rotation $Im(\theta)$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     t_imag: CalculatorFloat,
 }
 
@@ -2177,12 +2284,17 @@ impl OperateTwoQubitGate for ComplexPMInteraction {
     roqoqo_derive::OperateTwoQubit,
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PhaseShiftedControlledZ {
     /// The index of the most significant qubit in the unitary representation. Here, the qubit that controls the application of the phase-shift on the target qubit.
     control: usize,
     /// The index of the least significant qubit in the unitary representation. Here, the qubit phase-shift is applied to.
     target: usize,
     /// The single qubit phase $\phi$.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
     phi: CalculatorFloat,
 }
 
@@ -2267,3 +2379,343 @@ impl OperateTwoQubitGate for PhaseShiftedControlledZ {
         }
     }
 }
+
+/// A general two-qubit gate given by the parameters of its KAK decomposition.
+///
+/// $$
+/// U = e^{i \phi} (A_c \otimes A_t) \exp\left(i (k_x XX + k_y YY + k_z ZZ)\right) (B_c \otimes B_t)
+/// $$
+///
+/// with the global phase $\phi$ given by `phase` and the k_vector given by `kx`, `ky` and `kz`.
+/// Each single-qubit unitary is given by three rotation angles as RotateZ(z1), followed by RotateY(y),
+/// followed by RotateZ(z2), i.e. $B_c = R_z(z_2) R_y(y) R_z(z_1)$ for `control_before_z1`, `control_before_y`
+/// and `control_before_z2`.
+/// The gate can be constructed from a numeric unitary matrix with [TwoQubitGate::from_unitary].
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex64;
+/// use roqoqo::operations::{OperateGate, TwoQubitGate};
+///
+/// let zero = Complex64::new(0.0, 0.0);
+/// let one = Complex64::new(1.0, 0.0);
+/// let cnot = array![
+///     [one, zero, zero, zero],
+///     [zero, one, zero, zero],
+///     [zero, zero, zero, one],
+///     [zero, zero, one, zero],
+/// ];
+/// let gate = TwoQubitGate::from_unitary(0, 1, cnot.clone()).unwrap();
+/// let matrix = gate.unitary_matrix().unwrap();
+/// assert!(matrix.iter().zip(cnot.iter()).all(|(a, b)| (a - b).norm() < 1e-10));
+/// ```
+///
+/// TwoQubitGate is stored as `Box<TwoQubitGate>` in the operation enums.
+///
+#[allow(clippy::upper_case_acronyms)]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    roqoqo_derive::InvolveQubits,
+    roqoqo_derive::Operate,
+    roqoqo_derive::Substitute,
+    roqoqo_derive::OperateTwoQubit,
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+#[boxed_variant]
+pub struct TwoQubitGate {
+    /// The index of the most significant qubit in the unitary representation.
+    control: usize,
+    /// The index of the least significant qubit in the unitary representation.
+    target: usize,
+    /// The global phase of the gate.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    phase: CalculatorFloat,
+    /// The prefactor of the XX interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    kx: CalculatorFloat,
+    /// The prefactor of the YY interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    ky: CalculatorFloat,
+    /// The prefactor of the ZZ interaction.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    kz: CalculatorFloat,
+    /// The first RotateZ angle on the control qubit before the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    control_before_z1: CalculatorFloat,
+    /// The RotateY angle on the control qubit before the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    control_before_y: CalculatorFloat,
+    /// The second RotateZ angle on the control qubit before the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    control_before_z2: CalculatorFloat,
+    /// The first RotateZ angle on the target qubit before the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    target_before_z1: CalculatorFloat,
+    /// The RotateY angle on the target qubit before the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    target_before_y: CalculatorFloat,
+    /// The second RotateZ angle on the target qubit before the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    target_before_z2: CalculatorFloat,
+    /// The first RotateZ angle on the control qubit after the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    control_after_z1: CalculatorFloat,
+    /// The RotateY angle on the control qubit after the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    control_after_y: CalculatorFloat,
+    /// The second RotateZ angle on the control qubit after the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    control_after_z2: CalculatorFloat,
+    /// The first RotateZ angle on the target qubit after the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    target_after_z1: CalculatorFloat,
+    /// The RotateY angle on the target qubit after the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    target_after_y: CalculatorFloat,
+    /// The second RotateZ angle on the target qubit after the entangling operation.
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(with = "crate::json_schema::CalculatorFloat")
+    )]
+    target_after_z2: CalculatorFloat,
+}
+
+#[allow(non_upper_case_globals)]
+const TAGS_TwoQubitGate: &[&str; 4] = &[
+    "Operation",
+    "GateOperation",
+    "TwoQubitGateOperation",
+    "TwoQubitGate",
+];
+
+impl TwoQubitGate {
+    /// Creates a TwoQubitGate from a numeric unitary matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The index of the most significant qubit in the unitary representation.
+    /// * `target` - The index of the least significant qubit in the unitary representation.
+    /// * `matrix` - The unitary 4x4 matrix of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TwoQubitGate)` - The gate with the parameters of the KAK decomposition of the matrix.
+    /// * `Err(RoqoqoError::MismatchedUnitaryDimension)` - The matrix is not a 4x4 matrix.
+    /// * `Err(RoqoqoError::NonUnitaryMatrix)` - The matrix is not unitary.
+    /// * `Err(RoqoqoError::DecompositionError)` - The numerical KAK decomposition failed or does not reproduce the matrix.
+    pub fn from_unitary(
+        control: usize,
+        target: usize,
+        matrix: Array2<Complex64>,
+    ) -> Result<Self, RoqoqoError> {
+        check_unitary(&matrix, 2)?;
+        let kak = kak_decomposition(&matrix)?;
+        let [kx, ky, kz] = kak.k_vector;
+        let [control_before_z1, control_before_y, control_before_z2] = kak.control_before;
+        let [target_before_z1, target_before_y, target_before_z2] = kak.target_before;
+        let [control_after_z1, control_after_y, control_after_z2] = kak.control_after;
+        let [target_after_z1, target_after_y, target_after_z2] = kak.target_after;
+        let gate = Self::new(
+            control,
+            target,
+            kak.global_phase.into(),
+            kx.into(),
+            ky.into(),
+            kz.into(),
+            control_before_z1.into(),
+            control_before_y.into(),
+            control_before_z2.into(),
+            target_before_z1.into(),
+            target_before_y.into(),
+            target_before_z2.into(),
+            control_after_z1.into(),
+            control_after_y.into(),
+            control_after_z2.into(),
+            target_after_z1.into(),
+            target_after_y.into(),
+            target_after_z2.into(),
+        );
+        let deviation = gate
+            .unitary_matrix()?
+            .iter()
+            .zip(matrix.iter())
+            .map(|(value, expected)| (value - expected).norm())
+            .fold(0.0, f64::max);
+        if deviation > DECOMPOSITION_TOLERANCE {
+            return Err(RoqoqoError::DecompositionError {
+                msg: format!(
+                    "The matrix of the TwoQubitGate deviates by {:e} from the decomposed matrix.",
+                    deviation
+                ),
+            });
+        }
+        Ok(gate)
+    }
+
+    /// Returns the k_vector of the entangling operation exp(i (k_x XX + k_y YY + k_z ZZ)).
+    pub fn k_vector(&self) -> [CalculatorFloat; 3] {
+        [self.kx.clone(), self.ky.clone(), self.kz.clone()]
+    }
+
+    /// Returns the rotation angles of control and target before the entangling operation.
+    fn angles_before(&self) -> [[&CalculatorFloat; 3]; 2] {
+        [
+            [
+                &self.control_before_z1,
+                &self.control_before_y,
+                &self.control_before_z2,
+            ],
+            [
+                &self.target_before_z1,
+                &self.target_before_y,
+                &self.target_before_z2,
+            ],
+        ]
+    }
+
+    /// Returns the rotation angles of control and target after the entangling operation.
+    fn angles_after(&self) -> [[&CalculatorFloat; 3]; 2] {
+        [
+            [
+                &self.control_after_z1,
+                &self.control_after_y,
+                &self.control_after_z2,
+            ],
+            [
+                &self.target_after_z1,
+                &self.target_after_y,
+                &self.target_after_z2,
+            ],
+        ]
+    }
+
+    /// Returns the circuit of RotateZ, RotateY and RotateZ rotations on control and target.
+    fn rotations(&self, angles: [[&CalculatorFloat; 3]; 2]) -> Circuit {
+        let mut circuit = Circuit::new();
+        for (qubit, [z1, y, z2]) in [self.control, self.target].iter().zip(angles) {
+            circuit += RotateZ::new(*qubit, z1.clone());
+            circuit += RotateY::new(*qubit, y.clone());
+            circuit += RotateZ::new(*qubit, z2.clone());
+        }
+        circuit
+    }
+}
+
+/// Returns the tensor product of the rotations on control and target with the control as most significant qubit.
+fn rotations_matrix(angles: [[&CalculatorFloat; 3]; 2]) -> Result<Array2<Complex64>, RoqoqoError> {
+    let mut matrices: Vec<Array2<Complex64>> = Vec::with_capacity(2);
+    for [z1, y, z2] in angles {
+        matrices.push(
+            RotateZ::new(0, z2.clone())
+                .unitary_matrix()?
+                .dot(&RotateY::new(0, y.clone()).unitary_matrix()?)
+                .dot(&RotateZ::new(0, z1.clone()).unitary_matrix()?),
+        );
+    }
+    Ok(Array2::from_shape_fn((4, 4), |(row, column)| {
+        matrices[0][(row / 2, column / 2)] * matrices[1][(row % 2, column % 2)]
+    }))
+}
+
+/// Trait for all Operations acting with a unitary gate on a set of qubits.
+impl OperateGate for TwoQubitGate {
+    /// Returns unitary matrix of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The unitary matrix representation of the gate.
+    /// * `Err(RoqoqoError)` - The conversion of parameters to f64 failed.
+    fn unitary_matrix(&self) -> Result<Array2<Complex64>, RoqoqoError> {
+        let phase: f64 = f64::try_from(self.phase.clone())?;
+        let kx: f64 = f64::try_from(self.kx.clone())?;
+        let ky: f64 = f64::try_from(self.ky.clone())?;
+        let kz: f64 = f64::try_from(self.kz.clone())?;
+
+        // exp(i (kx XX + ky YY + kz ZZ)) in the computational basis
+        let plus = Complex64::new(0.0, kz).exp();
+        let minus = Complex64::new(0.0, -kz).exp();
+        let cm = Complex64::new((kx - ky).cos(), 0.0) * plus;
+        let sm = Complex64::new(0.0, (kx - ky).sin()) * plus;
+        let cp = Complex64::new((kx + ky).cos(), 0.0) * minus;
+        let sp = Complex64::new(0.0, (kx + ky).sin()) * minus;
+        let zero = Complex64::new(0.0, 0.0);
+        let entangling = array![
+            [cm, zero, zero, sm],
+            [zero, cp, sp, zero],
+            [zero, sp, cp, zero],
+            [sm, zero, zero, cm],
+        ];
+
+        let before = rotations_matrix(self.angles_before())?;
+        let after = rotations_matrix(self.angles_after())?;
+        let matrix = after.dot(&entangling).dot(&before);
+        Ok(matrix.mapv(|value| value * Complex64::new(0.0, phase).exp()))
+    }
+}
+
+/// Trait for all gate operations acting on exactly two qubits.
+impl OperateTwoQubitGate for TwoQubitGate {
+    /// Returns [KakDecomposition] of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * struct `KakDecomposition { global_phase, k_vector, circuit_before, circuit_after }`
+    fn kak_decomposition(&self) -> KakDecomposition {
+        KakDecomposition {
+            global_phase: self.phase.clone(),
+            k_vector: self.k_vector(),
+            circuit_before: Some(self.rotations(self.angles_before())),
+            circuit_after: Some(self.rotations(self.angles_after())),
+        }
+    }
+}
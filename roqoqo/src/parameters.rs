@@ -0,0 +1,133 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RoqoqoError;
+use qoqo_calculator::CalculatorFloat;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Returns the names of the symbols in a symbolic expression.
+///
+/// The expression is split into tokens the same way the calculator lexes it.
+/// Names followed by an opening bracket are functions and names followed by `=` are assigned in the expression,
+/// all other names that are not assigned before they are used are symbols.
+/// The expression is not evaluated, so symbols are found even if the expression cannot be evaluated for some values.
+pub(crate) fn expression_symbols(expression: &CalculatorFloat) -> BTreeSet<String> {
+    let mut symbols: BTreeSet<String> = BTreeSet::new();
+    if let CalculatorFloat::Str(expression) = expression {
+        let chars: Vec<char> = expression.chars().collect();
+        let mut assigned: HashSet<String> = HashSet::new();
+        let mut index = 0;
+        while index < chars.len() {
+            let c = chars[index];
+            if c == '#' {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+            } else if c.is_alphabetic() {
+                let start = index;
+                while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_')
+                {
+                    index += 1;
+                }
+                let name: String = chars[start..index].iter().collect();
+                match next_token_start(&chars, index) {
+                    Some('(') => (),
+                    Some('=') => {
+                        assigned.insert(name);
+                    }
+                    _ => {
+                        if !assigned.contains(&name) {
+                            symbols.insert(name);
+                        }
+                    }
+                }
+            } else if c.is_ascii_digit() || c == '.' {
+                while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.')
+                {
+                    index += 1;
+                }
+                if index < chars.len() && (chars[index] == 'e' || chars[index] == 'E') {
+                    index += 1;
+                    if index < chars.len() && (chars[index] == '+' || chars[index] == '-') {
+                        index += 1;
+                    }
+                    while index < chars.len() && chars[index].is_ascii_digit() {
+                        index += 1;
+                    }
+                }
+            } else {
+                index += 1;
+            }
+        }
+    }
+    symbols
+}
+
+/// Returns the first character of the next token, skipping whitespace and comments.
+fn next_token_start(chars: &[char], mut index: usize) -> Option<char> {
+    while index < chars.len() {
+        match chars[index] {
+            '#' => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+            }
+            c if c.is_whitespace() => index += 1,
+            c => return Some(c),
+        }
+    }
+    None
+}
+
+/// Checks that a set of parameter values binds exactly the free parameters of one or more circuits.
+///
+/// # Arguments
+///
+/// * `circuits` - The free parameters and the default values of the parameters of each circuit. Parameters with default values can but do not need to be bound.
+/// * `parameters` - The parameter values that are bound.
+///
+/// # Returns
+///
+/// * `Ok(())` - All free parameters are bound and no unknown parameters are given.
+/// * `Err(RoqoqoError::ParameterBindingError)` - Free parameters are not bound or unknown parameters are given.
+pub(crate) fn check_parameter_binding(
+    circuits: &[(HashSet<String>, HashMap<String, f64>)],
+    parameters: &HashMap<String, f64>,
+) -> Result<(), RoqoqoError> {
+    let mut unbound: BTreeSet<String> = BTreeSet::new();
+    let mut known: HashSet<&String> = HashSet::new();
+    for (free_parameters, default_parameters) in circuits.iter() {
+        unbound.extend(
+            free_parameters
+                .iter()
+                .filter(|name| {
+                    !parameters.contains_key(*name) && !default_parameters.contains_key(*name)
+                })
+                .cloned(),
+        );
+        known.extend(free_parameters.iter());
+        known.extend(default_parameters.keys());
+    }
+    let extra: BTreeSet<String> = parameters
+        .keys()
+        .filter(|name| !known.contains(name))
+        .cloned()
+        .collect();
+    if unbound.is_empty() && extra.is_empty() {
+        Ok(())
+    } else {
+        Err(RoqoqoError::ParameterBindingError {
+            unbound: unbound.into_iter().collect(),
+            extra: extra.into_iter().collect(),
+        })
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::dag::register_access;
+use crate::operations::{InvolveQubits, InvolvedQubits, Operation};
+use crate::Circuit;
+use std::collections::HashSet;
+
+/// Removes all operations outside the backward light cone of the measured qubits and readout registers.
+///
+/// The circuit is traversed backwards starting from the operations writing to the readout registers
+/// (for example [crate::operations::MeasureQubit] or [crate::operations::PragmaRepeatedMeasurement]).
+/// An operation is kept when it writes to a readout register
+/// or acts on a qubit or reads a register that a kept operation later in the circuit depends on.
+/// All other operations cannot influence the measurement results and are removed.
+///
+/// For a [crate::operations::PragmaRepeatedMeasurement] with a qubit mapping
+/// only the qubits in the mapping are considered measured.
+/// A kept operation acting on all qubits ([InvolvedQubits::All]) puts all qubits into the light cone,
+/// so that every operation before it acting on qubits is kept.
+/// Operations acting on no qubits and definitions are always kept.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to reduce.
+/// * `readout_registers` - The names of the readout registers that are needed. When `None`, all registers are needed.
+///
+/// # Returns
+///
+/// * `Circuit` - The circuit containing only the operations in the backward light cone.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::operations::{CNOT, Hadamard, MeasureQubit, PauliX};
+/// use roqoqo::passes::backward_light_cone;
+///
+/// let mut circuit = Circuit::new();
+/// circuit += Hadamard::new(0);
+/// circuit += PauliX::new(2);
+/// circuit += CNOT::new(0, 1);
+/// circuit += MeasureQubit::new(1, "ro".to_string(), 0);
+///
+/// let mut reduced = Circuit::new();
+/// reduced += Hadamard::new(0);
+/// reduced += CNOT::new(0, 1);
+/// reduced += MeasureQubit::new(1, "ro".to_string(), 0);
+/// assert_eq!(backward_light_cone(&circuit, None), reduced);
+/// ```
+pub fn backward_light_cone(circuit: &Circuit, readout_registers: Option<&[String]>) -> Circuit {
+    let mut relevant_qubits: HashSet<usize> = HashSet::new();
+    let mut all_qubits_relevant = false;
+    let mut relevant_registers: HashSet<String> = HashSet::new();
+    if let Some(registers) = readout_registers {
+        relevant_registers.extend(registers.iter().cloned());
+    }
+
+    let mut kept: Vec<&Operation> = Vec::new();
+    for operation in circuit.operations().iter().rev() {
+        let (reads, writes) = register_access(operation);
+        let qubits = light_cone_qubits(operation);
+        let writes_relevant_register = writes
+            .iter()
+            .any(|register| readout_registers.is_none() || relevant_registers.contains(register));
+        let keep = writes_relevant_register
+            || match &qubits {
+                InvolvedQubits::None => true,
+                InvolvedQubits::All => all_qubits_relevant || !relevant_qubits.is_empty(),
+                InvolvedQubits::Set(set) => {
+                    all_qubits_relevant || set.iter().any(|qubit| relevant_qubits.contains(qubit))
+                }
+            };
+        if !keep {
+            continue;
+        }
+        match qubits {
+            InvolvedQubits::None => (),
+            InvolvedQubits::All => all_qubits_relevant = true,
+            InvolvedQubits::Set(set) => relevant_qubits.extend(set),
+        }
+        relevant_registers.extend(reads);
+        kept.push(operation);
+    }
+
+    let mut reduced = Circuit::new();
+    for definition in circuit.definitions().iter() {
+        reduced.add_operation(definition.clone());
+    }
+    for operation in kept.into_iter().rev() {
+        reduced.add_operation(operation.clone());
+    }
+    reduced
+}
+
+/// Returns the qubits whose state can influence the result of an operation.
+///
+/// Only differs from [InvolveQubits::involved_qubits] for a PragmaRepeatedMeasurement
+/// with a qubit mapping, where only the mapped qubits are measured.
+fn light_cone_qubits(operation: &Operation) -> InvolvedQubits {
+    match operation {
+        Operation::PragmaRepeatedMeasurement(op) => match op.qubit_mapping() {
+            Some(mapping) => InvolvedQubits::Set(mapping.keys().copied().collect()),
+            None => InvolvedQubits::All,
+        },
+        _ => operation.involved_qubits(),
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Passes transforming quantum circuits.
+//!
+//! A pass takes a [crate::Circuit] and returns a new, transformed Circuit
+//! without changing the results of the measurements performed by the circuit.
+
+#[doc(hidden)]
+mod light_cone;
+pub use light_cone::*;
+#[doc(hidden)]
+mod qubit_compaction;
+pub use qubit_compaction::*;
+#[doc(hidden)]
+mod rewrite;
+pub use rewrite::*;
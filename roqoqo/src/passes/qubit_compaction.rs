@@ -0,0 +1,198 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::operations::{
+    InvolveQubits, InvolvedQubits, Operation, PragmaSetDensityMatrix, PragmaSetStateVector,
+};
+use crate::{Circuit, RoqoqoError};
+use ndarray::{Array1, Array2};
+use num_complex::Complex64;
+use std::collections::{BTreeSet, HashMap};
+
+/// Amplitudes and populations below this threshold are treated as zero when removing qubits from a state.
+const ZERO_TOLERANCE: f64 = 1e-12;
+
+/// Returns the sorted qubits a circuit uses.
+///
+/// These are all qubits in the [InvolvedQubits::Set] of an operation
+/// and the qubits in the qubit mapping of a [crate::operations::PragmaRepeatedMeasurement].
+/// Operations acting on all qubits do not add any qubits.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit.
+///
+/// # Returns
+///
+/// * `Vec<usize>` - The sorted indices of the used qubits.
+pub fn used_qubits(circuit: &Circuit) -> Vec<usize> {
+    let mut qubits: BTreeSet<usize> = BTreeSet::new();
+    for operation in circuit.operations().iter() {
+        match operation {
+            Operation::PragmaRepeatedMeasurement(op) => {
+                if let Some(mapping) = op.qubit_mapping() {
+                    qubits.extend(mapping.keys());
+                }
+            }
+            _ => {
+                if let InvolvedQubits::Set(set) = operation.involved_qubits() {
+                    qubits.extend(set);
+                }
+            }
+        }
+    }
+    qubits.into_iter().collect()
+}
+
+/// Relabels the qubits of a circuit to 0..n-1, where n is the number of used qubits.
+///
+/// The used qubits (see [used_qubits]) are mapped to new indices in ascending order and the mapping
+/// is applied with [Circuit::remap_qubits], which also updates the qubit mappings of
+/// [crate::operations::PragmaRepeatedMeasurement] operations.
+/// The states set by [PragmaSetStateVector] and [PragmaSetDensityMatrix] are reduced to the used qubits.
+/// This is only possible when all qubits that are not used are in the |0> state.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to compact.
+///
+/// # Returns
+///
+/// * `Ok((Circuit, HashMap<usize, usize>))` - The compacted circuit and the inverse mapping from new to original qubit indices.
+/// * `Err(RoqoqoError::QubitMappingError)` - A set state has population on a qubit that is not used.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::operations::{CNOT, PauliX};
+/// use roqoqo::passes::compact_qubits;
+///
+/// let mut circuit = Circuit::new();
+/// circuit += PauliX::new(7);
+/// circuit += CNOT::new(42, 0);
+///
+/// let (compacted, inverse_mapping) = compact_qubits(&circuit).unwrap();
+/// let mut expected = Circuit::new();
+/// expected += PauliX::new(1);
+/// expected += CNOT::new(2, 0);
+/// assert_eq!(compacted, expected);
+/// assert_eq!(inverse_mapping.get(&2), Some(&42));
+/// ```
+pub fn compact_qubits(circuit: &Circuit) -> Result<(Circuit, HashMap<usize, usize>), RoqoqoError> {
+    let qubits = used_qubits(circuit);
+    let mapping: HashMap<usize, usize> = qubits
+        .iter()
+        .enumerate()
+        .map(|(new, old)| (*old, new))
+        .collect();
+    let inverse_mapping: HashMap<usize, usize> = qubits.iter().copied().enumerate().collect();
+
+    let remapped = circuit.remap_qubits(&mapping)?;
+    let mut compacted = Circuit::new();
+    for definition in remapped.definitions().iter() {
+        compacted.add_operation(definition.clone());
+    }
+    for operation in remapped.operations().iter() {
+        match operation {
+            Operation::PragmaSetStateVector(op) => compacted.add_operation(
+                PragmaSetStateVector::new(compact_statevector(op.statevector(), &qubits)?),
+            ),
+            Operation::PragmaSetDensityMatrix(op) => compacted.add_operation(
+                PragmaSetDensityMatrix::new(compact_density_matrix(op.density_matrix(), &qubits)?),
+            ),
+            _ => compacted.add_operation(operation.clone()),
+        }
+    }
+    Ok((compacted, inverse_mapping))
+}
+
+/// Returns the basis state index in the original register for a basis state index in the compacted register.
+///
+/// Returns None when a bit is set for a qubit that is not part of the original register.
+fn original_index(index: usize, qubits: &[usize], number_qubits: usize) -> Option<usize> {
+    let mut original = 0;
+    for (new, old) in qubits.iter().enumerate() {
+        if index >> new & 1 == 1 {
+            if *old >= number_qubits {
+                return None;
+            }
+            original |= 1 << old;
+        }
+    }
+    Some(original)
+}
+
+/// Checks that no population is lost when removing the qubits that are not used.
+fn check_unused_population(
+    population: impl Iterator<Item = f64>,
+    qubits: &[usize],
+    number_qubits: usize,
+) -> Result<(), RoqoqoError> {
+    let used_mask: usize = qubits
+        .iter()
+        .filter(|qubit| **qubit < number_qubits)
+        .fold(0, |mask, qubit| mask | 1 << qubit);
+    for (index, value) in population.enumerate() {
+        let unused = index & !used_mask;
+        if unused != 0 && value > ZERO_TOLERANCE {
+            return Err(RoqoqoError::QubitMappingError {
+                qubit: unused.trailing_zeros() as usize,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Reduces a statevector to the used qubits.
+fn compact_statevector(
+    statevector: &Array1<Complex64>,
+    qubits: &[usize],
+) -> Result<Array1<Complex64>, RoqoqoError> {
+    let number_qubits = statevector.len().trailing_zeros() as usize;
+    check_unused_population(
+        statevector.iter().map(|x| x.norm_sqr()),
+        qubits,
+        number_qubits,
+    )?;
+    Ok(Array1::from_shape_fn(1 << qubits.len(), |index| {
+        original_index(index, qubits, number_qubits)
+            .map(|original| statevector[original])
+            .unwrap_or_else(|| Complex64::new(0.0, 0.0))
+    }))
+}
+
+/// Reduces a density matrix to the used qubits.
+fn compact_density_matrix(
+    density_matrix: &Array2<Complex64>,
+    qubits: &[usize],
+) -> Result<Array2<Complex64>, RoqoqoError> {
+    let number_qubits = density_matrix.nrows().trailing_zeros() as usize;
+    check_unused_population(
+        density_matrix.diag().iter().map(|x| x.norm()),
+        qubits,
+        number_qubits,
+    )?;
+    let dimension = 1 << qubits.len();
+    Ok(Array2::from_shape_fn(
+        (dimension, dimension),
+        |(row, column)| match (
+            original_index(row, qubits, number_qubits),
+            original_index(column, qubits, number_qubits),
+        ) {
+            (Some(original_row), Some(original_column)) => {
+                density_matrix[(original_row, original_column)]
+            }
+            _ => Complex64::new(0.0, 0.0),
+        },
+    ))
+}
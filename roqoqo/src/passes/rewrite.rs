@@ -0,0 +1,865 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::dag::register_access;
+use crate::operations::*;
+use crate::parameters::expression_symbols;
+use crate::{Circuit, RoqoqoError};
+use qoqo_calculator::{Calculator, CalculatorFloat};
+use std::collections::{HashMap, HashSet};
+
+/// Maximal absolute deviation of angles that are treated as equal when matching patterns.
+const ANGLE_TOLERANCE: f64 = 1e-10;
+
+/// A match of the pattern of a [RewriteRule] in a circuit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewriteMatch {
+    /// The indices of the matched operations in the operations of the circuit.
+    pub indices: Vec<usize>,
+    /// The mapping from the wildcard qubits of the pattern to the qubits in the circuit.
+    pub qubits: HashMap<usize, usize>,
+    /// The values of the symbolic angles of the pattern.
+    pub parameters: HashMap<String, CalculatorFloat>,
+}
+
+/// A declarative rule replacing a pattern of operations in a circuit.
+///
+/// The qubits in the pattern are wildcards that match any qubits in the circuit,
+/// different wildcard qubits always match different qubits.
+/// An angle in the pattern is either a float that has to match exactly
+/// or a symbol that matches any angle.
+/// A symbol appearing several times in the pattern has to match the same angle each time.
+///
+/// The operations of the pattern do not need to be adjacent in the circuit.
+/// Operations between them are skipped when the later operations of the pattern
+/// can be commuted past them, because they act on disjoint qubits and classical registers.
+///
+/// The replacement uses the wildcard qubits of the pattern and expressions of the symbols of the pattern,
+/// which are replaced with the matched qubits and angles.
+/// The matched operations are removed and the replacement is inserted at the position of the first matched operation.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::operations::{PauliX, RotateZ};
+/// use roqoqo::passes::{apply_rewrite_rules, RewriteRule};
+///
+/// let mut pattern = Circuit::new();
+/// pattern += RotateZ::new(0, "a".into());
+/// pattern += RotateZ::new(0, "b".into());
+/// let mut replacement = Circuit::new();
+/// replacement += RotateZ::new(0, "a + b".into());
+/// let rule = RewriteRule::new("merge_rotate_z".to_string(), pattern, replacement).unwrap();
+///
+/// let mut circuit = Circuit::new();
+/// circuit += RotateZ::new(3, 0.1.into());
+/// circuit += PauliX::new(1);
+/// circuit += RotateZ::new(3, 0.2.into());
+/// let (rewritten, number_rewrites) = apply_rewrite_rules(&circuit, &[rule], 10).unwrap();
+/// assert_eq!(number_rewrites, 1);
+/// assert_eq!(rewritten.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RewriteRule {
+    /// The name of the rule.
+    name: String,
+    /// The pattern of operations that is replaced.
+    pattern: Circuit,
+    /// The operations replacing the pattern.
+    replacement: Circuit,
+    /// Additional condition a match has to fulfill for the rule to be applied.
+    guard: Option<fn(&RewriteMatch) -> bool>,
+}
+
+impl RewriteRule {
+    /// Creates a new rewrite rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the rule.
+    /// * `pattern` - The pattern of operations with wildcard qubits and symbolic angles.
+    /// * `replacement` - The replacement of the pattern using the qubits and symbols of the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The new rewrite rule.
+    /// * `Err(RoqoqoError::RewriteRuleError)` - The pattern or the replacement is not valid.
+    pub fn new(name: String, pattern: Circuit, replacement: Circuit) -> Result<Self, RoqoqoError> {
+        if pattern.operations().is_empty() {
+            return Err(RoqoqoError::RewriteRuleError {
+                msg: format!("Pattern of rule {} contains no operations", name),
+            });
+        }
+        let mut pattern_qubits: HashSet<usize> = HashSet::new();
+        let mut pattern_symbols: HashSet<String> = HashSet::new();
+        for operation in pattern.operations().iter() {
+            match operation.involved_qubits() {
+                InvolvedQubits::All => {
+                    return Err(RoqoqoError::RewriteRuleError {
+                        msg: format!(
+                            "Operation {} in pattern of rule {} acts on all qubits",
+                            operation.hqslang(),
+                            name
+                        ),
+                    })
+                }
+                InvolvedQubits::Set(qubits) => pattern_qubits.extend(qubits),
+                InvolvedQubits::None => (),
+            }
+            let symbols = operation.free_parameters();
+            if !symbols.is_empty() {
+                let angles = angles(operation).ok_or_else(|| RoqoqoError::RewriteRuleError {
+                    msg: format!(
+                        "Symbolic parameters of operation {} in pattern of rule {} are not supported",
+                        operation.hqslang(),
+                        name
+                    ),
+                })?;
+                for angle in angles {
+                    if let CalculatorFloat::Str(symbol) = angle {
+                        if !is_symbol(symbol) {
+                            return Err(RoqoqoError::RewriteRuleError {
+                                msg: format!(
+                                    "Angle {} in pattern of rule {} is not a single symbol",
+                                    symbol, name
+                                ),
+                            });
+                        }
+                    }
+                }
+                pattern_symbols.extend(symbols);
+            }
+        }
+        for operation in replacement.operations().iter() {
+            match operation.involved_qubits() {
+                InvolvedQubits::All => {
+                    return Err(RoqoqoError::RewriteRuleError {
+                        msg: format!(
+                            "Operation {} in replacement of rule {} acts on all qubits",
+                            operation.hqslang(),
+                            name
+                        ),
+                    })
+                }
+                InvolvedQubits::Set(qubits) => {
+                    if let Some(qubit) = qubits.iter().find(|q| !pattern_qubits.contains(q)) {
+                        return Err(RoqoqoError::RewriteRuleError {
+                            msg: format!(
+                                "Qubit {} in replacement of rule {} does not appear in pattern",
+                                qubit, name
+                            ),
+                        });
+                    }
+                }
+                InvolvedQubits::None => (),
+            }
+            let symbols = operation.free_parameters();
+            if !symbols.is_empty() {
+                if angles(operation).is_none() {
+                    return Err(RoqoqoError::RewriteRuleError {
+                        msg: format!(
+                            "Symbolic parameters of operation {} in replacement of rule {} are not supported",
+                            operation.hqslang(),
+                            name
+                        ),
+                    });
+                }
+                if let Some(symbol) = symbols
+                    .iter()
+                    .filter(|s| !pattern_symbols.contains(*s))
+                    .min()
+                {
+                    return Err(RoqoqoError::RewriteRuleError {
+                        msg: format!(
+                            "Symbol {} in replacement of rule {} does not appear in pattern",
+                            symbol, name
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(RewriteRule {
+            name,
+            pattern,
+            replacement,
+            guard: None,
+        })
+    }
+
+    /// Returns a clone of the rule that is only applied to matches fulfilling the guard.
+    ///
+    /// # Arguments
+    ///
+    /// * `guard` - The condition a match has to fulfill.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The rule with the guard.
+    pub fn with_guard(mut self, guard: fn(&RewriteMatch) -> bool) -> Self {
+        self.guard = Some(guard);
+        self
+    }
+
+    /// Returns the name of the rule.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the pattern of the rule.
+    pub fn pattern(&self) -> &Circuit {
+        &self.pattern
+    }
+
+    /// Returns the replacement of the rule.
+    pub fn replacement(&self) -> &Circuit {
+        &self.replacement
+    }
+
+    /// Finds the first match of the pattern in a circuit.
+    ///
+    /// Matches are ordered by the indices of the matched operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to search.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(RewriteMatch)` - The first match fulfilling the guard.
+    /// * `None` - The pattern does not match.
+    pub fn find_match(&self, circuit: &Circuit) -> Option<RewriteMatch> {
+        let operations = circuit.operations();
+        let empty = RewriteMatch {
+            indices: Vec::new(),
+            qubits: HashMap::new(),
+            parameters: HashMap::new(),
+        };
+        (0..operations.len()).find_map(|start| {
+            match_operation(&self.pattern.operations()[0], &operations[start], &empty)
+                .into_iter()
+                .find_map(|mut partial| {
+                    partial.indices.push(start);
+                    self.extend_match(operations, partial, &SkippedOperations::default())
+                })
+        })
+    }
+
+    /// Replaces a match of the pattern in a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit containing the match.
+    /// * `rewrite_match` - The match found by [RewriteRule::find_match].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Circuit)` - The circuit with the matched operations replaced.
+    /// * `Err(RoqoqoError)` - The replacement could not be constructed.
+    pub fn apply(
+        &self,
+        circuit: &Circuit,
+        rewrite_match: &RewriteMatch,
+    ) -> Result<Circuit, RoqoqoError> {
+        let first_index = rewrite_match.indices[0];
+        let mut rewritten = Circuit::new();
+        for definition in circuit.definitions().iter() {
+            rewritten.add_operation(definition.clone());
+        }
+        for (index, operation) in circuit.operations().iter().enumerate() {
+            if index == first_index {
+                for replacement_operation in self.replacement.operations().iter() {
+                    rewritten.add_operation(instantiate(replacement_operation, rewrite_match)?);
+                }
+            }
+            if !rewrite_match.indices.contains(&index) {
+                rewritten.add_operation(operation.clone());
+            }
+        }
+        Ok(rewritten)
+    }
+
+    /// Extends a partial match with the remaining operations of the pattern.
+    fn extend_match(
+        &self,
+        operations: &[Operation],
+        partial: RewriteMatch,
+        skipped: &SkippedOperations,
+    ) -> Option<RewriteMatch> {
+        let position = partial.indices.len();
+        if position == self.pattern.operations().len() {
+            return match self.guard {
+                Some(guard) if !guard(&partial) => None,
+                _ => Some(partial),
+            };
+        }
+        let pattern_operation = &self.pattern.operations()[position];
+        let mut skipped = skipped.clone();
+        let last_index = *partial.indices.last().unwrap_or(&0);
+        for (index, operation) in operations.iter().enumerate().skip(last_index + 1) {
+            if skipped.commutes_with(operation) {
+                for mut extended in match_operation(pattern_operation, operation, &partial) {
+                    extended.indices.push(index);
+                    if let Some(complete) = self.extend_match(operations, extended, &skipped) {
+                        return Some(complete);
+                    }
+                }
+            }
+            if !skipped.add(operation) {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+/// The qubits and registers of the operations skipped between matched operations.
+#[derive(Debug, Clone, Default)]
+struct SkippedOperations {
+    /// The qubits the skipped operations act on.
+    qubits: HashSet<usize>,
+    /// The registers the skipped operations read.
+    reads: HashSet<String>,
+    /// The registers the skipped operations write.
+    writes: HashSet<String>,
+}
+
+impl SkippedOperations {
+    /// Returns true if an operation can be commuted to before the skipped operations.
+    fn commutes_with(&self, operation: &Operation) -> bool {
+        let disjoint_qubits = match operation.involved_qubits() {
+            InvolvedQubits::All => self.qubits.is_empty(),
+            InvolvedQubits::None => true,
+            InvolvedQubits::Set(qubits) => self.qubits.is_disjoint(&qubits),
+        };
+        let (reads, writes) = register_access(operation);
+        disjoint_qubits
+            && reads.iter().all(|name| !self.writes.contains(name))
+            && writes
+                .iter()
+                .all(|name| !self.writes.contains(name) && !self.reads.contains(name))
+    }
+
+    /// Adds an operation to the skipped operations.
+    ///
+    /// Returns false when no later operation can be commuted past the skipped operations.
+    fn add(&mut self, operation: &Operation) -> bool {
+        let (reads, writes) = register_access(operation);
+        self.reads.extend(reads);
+        self.writes.extend(writes);
+        match operation.involved_qubits() {
+            InvolvedQubits::All => false,
+            InvolvedQubits::None => true,
+            InvolvedQubits::Set(qubits) => {
+                self.qubits.extend(qubits);
+                true
+            }
+        }
+    }
+}
+
+/// Returns all extensions of a partial match matching a pattern operation to an operation.
+fn match_operation(
+    pattern_operation: &Operation,
+    operation: &Operation,
+    partial: &RewriteMatch,
+) -> Vec<RewriteMatch> {
+    if pattern_operation.hqslang() != operation.hqslang() {
+        return Vec::new();
+    }
+    let mut pattern_qubits: Vec<usize> = match pattern_operation.involved_qubits() {
+        InvolvedQubits::Set(qubits) => qubits.into_iter().collect(),
+        _ => Vec::new(),
+    };
+    pattern_qubits.sort_unstable();
+    let qubits: Vec<usize> = match operation.involved_qubits() {
+        InvolvedQubits::Set(qubits) => qubits.into_iter().collect(),
+        InvolvedQubits::None => Vec::new(),
+        InvolvedQubits::All => return Vec::new(),
+    };
+    if pattern_qubits.len() != qubits.len() {
+        return Vec::new();
+    }
+    let parameters = match match_angles(pattern_operation, operation, &partial.parameters) {
+        Some(parameters) => parameters,
+        None => return Vec::new(),
+    };
+    qubit_assignments(&pattern_qubits, &qubits, &partial.qubits)
+        .into_iter()
+        .filter(|assignment| {
+            let mapping: HashMap<usize, usize> = pattern_qubits
+                .iter()
+                .map(|qubit| (*qubit, assignment[qubit]))
+                .collect();
+            let remapped = match pattern_operation.remap_qubits(&mapping) {
+                Ok(remapped) => remapped,
+                Err(_) => return false,
+            };
+            match angles(operation) {
+                Some(operation_angles) => {
+                    let operation_angles: Vec<CalculatorFloat> =
+                        operation_angles.into_iter().cloned().collect();
+                    with_angles(&remapped, &operation_angles) == *operation
+                }
+                None => remapped == *operation,
+            }
+        })
+        .map(|assignment| RewriteMatch {
+            indices: partial.indices.clone(),
+            qubits: assignment,
+            parameters: parameters.clone(),
+        })
+        .collect()
+}
+
+/// Returns all injective extensions of a qubit assignment mapping the pattern qubits to the qubits.
+fn qubit_assignments(
+    pattern_qubits: &[usize],
+    qubits: &[usize],
+    assignment: &HashMap<usize, usize>,
+) -> Vec<HashMap<usize, usize>> {
+    let (pattern_qubit, remaining) = match pattern_qubits.split_first() {
+        Some(split) => split,
+        None => return vec![assignment.clone()],
+    };
+    let candidates: Vec<usize> = match assignment.get(pattern_qubit) {
+        Some(qubit) if qubits.contains(qubit) => vec![*qubit],
+        Some(_) => Vec::new(),
+        None => qubits
+            .iter()
+            .filter(|qubit| !assignment.values().any(|assigned| assigned == *qubit))
+            .cloned()
+            .collect(),
+    };
+    let mut assignments: Vec<HashMap<usize, usize>> = Vec::new();
+    for candidate in candidates {
+        let mut extended = assignment.clone();
+        extended.insert(*pattern_qubit, candidate);
+        assignments.extend(qubit_assignments(remaining, qubits, &extended));
+    }
+    assignments
+}
+
+/// Matches the angles of a pattern operation to the angles of an operation.
+///
+/// Returns the extended bindings of the symbols or None when the angles do not match.
+fn match_angles(
+    pattern_operation: &Operation,
+    operation: &Operation,
+    parameters: &HashMap<String, CalculatorFloat>,
+) -> Option<HashMap<String, CalculatorFloat>> {
+    let mut parameters = parameters.clone();
+    let (pattern_angles, operation_angles) = match (angles(pattern_operation), angles(operation)) {
+        (Some(pattern_angles), Some(operation_angles)) => (pattern_angles, operation_angles),
+        _ => return Some(parameters),
+    };
+    for (pattern_angle, angle) in pattern_angles.into_iter().zip(operation_angles) {
+        let expected = match pattern_angle {
+            CalculatorFloat::Float(_) => pattern_angle,
+            CalculatorFloat::Str(symbol) => match parameters.get(symbol) {
+                Some(bound) => bound,
+                None => {
+                    parameters.insert(symbol.clone(), angle.clone());
+                    continue;
+                }
+            },
+        };
+        if !angles_equal(expected, angle) {
+            return None;
+        }
+    }
+    Some(parameters)
+}
+
+/// Returns true if two angles are equal floats within the tolerance or identical expressions.
+fn angles_equal(first: &CalculatorFloat, second: &CalculatorFloat) -> bool {
+    match (first, second) {
+        (CalculatorFloat::Float(x), CalculatorFloat::Float(y)) => (x - y).abs() < ANGLE_TOLERANCE,
+        (CalculatorFloat::Str(x), CalculatorFloat::Str(y)) => x.trim() == y.trim(),
+        _ => false,
+    }
+}
+
+/// Returns true if an expression consists of a single symbol.
+fn is_symbol(expression: &str) -> bool {
+    let expression = expression.trim();
+    let mut characters = expression.chars();
+    match characters.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            characters.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+/// Creates a replacement operation for a match.
+fn instantiate(
+    operation: &Operation,
+    rewrite_match: &RewriteMatch,
+) -> Result<Operation, RoqoqoError> {
+    let remapped = match operation.involved_qubits() {
+        InvolvedQubits::Set(_) => operation.remap_qubits(&rewrite_match.qubits)?,
+        _ => operation.clone(),
+    };
+    match angles(&remapped) {
+        Some(replacement_angles) => {
+            let mut new_angles: Vec<CalculatorFloat> = Vec::new();
+            for angle in replacement_angles {
+                new_angles.push(substitute_symbols(angle, &rewrite_match.parameters)?);
+            }
+            Ok(with_angles(&remapped, &new_angles))
+        }
+        None => Ok(remapped),
+    }
+}
+
+/// Replaces the symbols in an angle expression with the matched angles.
+///
+/// The result is a float when all matched angles are floats and a symbolic expression otherwise.
+fn substitute_symbols(
+    angle: &CalculatorFloat,
+    parameters: &HashMap<String, CalculatorFloat>,
+) -> Result<CalculatorFloat, RoqoqoError> {
+    let expression = match angle {
+        CalculatorFloat::Float(_) => return Ok(angle.clone()),
+        CalculatorFloat::Str(expression) => expression,
+    };
+    let symbols = expression_symbols(angle);
+    if symbols
+        .iter()
+        .all(|symbol| matches!(parameters.get(symbol), Some(CalculatorFloat::Float(_))))
+    {
+        let mut calculator = Calculator::new();
+        for symbol in symbols.iter() {
+            if let Some(CalculatorFloat::Float(value)) = parameters.get(symbol) {
+                calculator.set_variable(symbol, *value);
+            }
+        }
+        return Ok(CalculatorFloat::Float(calculator.parse_str(expression)?));
+    }
+    let mut substituted = String::new();
+    let mut identifier = String::new();
+    for character in expression.chars().chain(std::iter::once(' ')) {
+        if character.is_ascii_alphanumeric() || character == '_' {
+            identifier.push(character);
+            continue;
+        }
+        match parameters.get(&identifier) {
+            Some(value) => substituted.push_str(&format!("({})", value)),
+            None => substituted.push_str(&identifier),
+        }
+        identifier.clear();
+        substituted.push(character);
+    }
+    Ok(CalculatorFloat::Str(substituted.trim_end().to_string()))
+}
+
+/// Returns the angles of operations whose angles can be matched and replaced.
+fn angles(operation: &Operation) -> Option<Vec<&CalculatorFloat>> {
+    match operation {
+        Operation::RotateX(op) => Some(vec![op.theta()]),
+        Operation::RotateY(op) => Some(vec![op.theta()]),
+        Operation::RotateZ(op) => Some(vec![op.theta()]),
+        Operation::RotateAroundSphericalAxis(op) => {
+            Some(vec![op.theta(), op.spherical_theta(), op.spherical_phi()])
+        }
+        Operation::ControlledPhaseShift(op) => Some(vec![op.theta()]),
+        Operation::VariableMSXX(op) => Some(vec![op.theta()]),
+        Operation::XY(op) => Some(vec![op.theta()]),
+        Operation::GivensRotation(op) => Some(vec![op.theta(), op.phi()]),
+        Operation::GivensRotationLittleEndian(op) => Some(vec![op.theta(), op.phi()]),
+        Operation::PMInteraction(op) => Some(vec![op.t()]),
+        Operation::PragmaGlobalPhase(op) => Some(vec![op.phase()]),
+        _ => None,
+    }
+}
+
+/// Returns a copy of an operation with the angles returned by [angles] replaced.
+fn with_angles(operation: &Operation, angles: &[CalculatorFloat]) -> Operation {
+    match operation {
+        Operation::RotateX(op) => RotateX::new(*op.qubit(), angles[0].clone()).into(),
+        Operation::RotateY(op) => RotateY::new(*op.qubit(), angles[0].clone()).into(),
+        Operation::RotateZ(op) => RotateZ::new(*op.qubit(), angles[0].clone()).into(),
+        Operation::RotateAroundSphericalAxis(op) => RotateAroundSphericalAxis::new(
+            *op.qubit(),
+            angles[0].clone(),
+            angles[1].clone(),
+            angles[2].clone(),
+        )
+        .into(),
+        Operation::ControlledPhaseShift(op) => {
+            ControlledPhaseShift::new(*op.control(), *op.target(), angles[0].clone()).into()
+        }
+        Operation::VariableMSXX(op) => {
+            VariableMSXX::new(*op.control(), *op.target(), angles[0].clone()).into()
+        }
+        Operation::XY(op) => XY::new(*op.control(), *op.target(), angles[0].clone()).into(),
+        Operation::GivensRotation(op) => GivensRotation::new(
+            *op.control(),
+            *op.target(),
+            angles[0].clone(),
+            angles[1].clone(),
+        )
+        .into(),
+        Operation::GivensRotationLittleEndian(op) => GivensRotationLittleEndian::new(
+            *op.control(),
+            *op.target(),
+            angles[0].clone(),
+            angles[1].clone(),
+        )
+        .into(),
+        Operation::PMInteraction(op) => {
+            PMInteraction::new(*op.control(), *op.target(), angles[0].clone()).into()
+        }
+        Operation::PragmaGlobalPhase(_) => PragmaGlobalPhase::new(angles[0].clone()).into(),
+        _ => operation.clone(),
+    }
+}
+
+/// Applies rewrite rules to a circuit until no rule matches or the budget is used up.
+///
+/// In each step the rules are tried in the given order and the first match of the first matching rule is replaced.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to rewrite.
+/// * `rules` - The rewrite rules.
+/// * `max_rewrites` - The maximal number of replacements.
+///
+/// # Returns
+///
+/// * `Ok((Circuit, usize))` - The rewritten circuit and the number of replacements.
+/// * `Err(RoqoqoError)` - A replacement could not be constructed.
+pub fn apply_rewrite_rules(
+    circuit: &Circuit,
+    rules: &[RewriteRule],
+    max_rewrites: usize,
+) -> Result<(Circuit, usize), RoqoqoError> {
+    let mut rewritten = circuit.clone();
+    let mut number_rewrites: usize = 0;
+    while number_rewrites < max_rewrites {
+        let next = rules
+            .iter()
+            .find_map(|rule| rule.find_match(&rewritten).map(|m| (rule, m)));
+        match next {
+            Some((rule, rewrite_match)) => {
+                rewritten = rule.apply(&rewritten, &rewrite_match)?;
+                number_rewrites += 1;
+            }
+            None => break,
+        }
+    }
+    Ok((rewritten, number_rewrites))
+}
+
+/// Creates a built-in rule.
+fn builtin_rule(name: &str, pattern: Vec<Operation>, replacement: Vec<Operation>) -> RewriteRule {
+    RewriteRule::new(
+        name.to_string(),
+        pattern.into_iter().collect(),
+        replacement.into_iter().collect(),
+    )
+    .expect("Unexpectedly invalid built-in rewrite rule")
+}
+
+/// Returns rules removing pairs of self-inverse or mutually inverse gates.
+///
+/// # Returns
+///
+/// * `Vec<RewriteRule>` - The cancellation rules.
+pub fn cancellation_rules() -> Vec<RewriteRule> {
+    vec![
+        builtin_rule(
+            "cancel_pauli_x",
+            vec![PauliX::new(0).into(), PauliX::new(0).into()],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_pauli_y",
+            vec![PauliY::new(0).into(), PauliY::new(0).into()],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_pauli_z",
+            vec![PauliZ::new(0).into(), PauliZ::new(0).into()],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_hadamard",
+            vec![Hadamard::new(0).into(), Hadamard::new(0).into()],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_sqrt_pauli_x",
+            vec![SqrtPauliX::new(0).into(), InvSqrtPauliX::new(0).into()],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_inv_sqrt_pauli_x",
+            vec![InvSqrtPauliX::new(0).into(), SqrtPauliX::new(0).into()],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_cnot",
+            vec![CNOT::new(0, 1).into(), CNOT::new(0, 1).into()],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_controlled_pauli_z",
+            vec![
+                ControlledPauliZ::new(0, 1).into(),
+                ControlledPauliZ::new(0, 1).into(),
+            ],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_controlled_pauli_z_reversed",
+            vec![
+                ControlledPauliZ::new(0, 1).into(),
+                ControlledPauliZ::new(1, 0).into(),
+            ],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_swap",
+            vec![SWAP::new(0, 1).into(), SWAP::new(0, 1).into()],
+            vec![],
+        ),
+        builtin_rule(
+            "cancel_swap_reversed",
+            vec![SWAP::new(0, 1).into(), SWAP::new(1, 0).into()],
+            vec![],
+        ),
+    ]
+}
+
+/// Returns true if all matched angles are zero.
+fn zero_angles(rewrite_match: &RewriteMatch) -> bool {
+    rewrite_match.parameters.values().all(|angle| match angle {
+        CalculatorFloat::Float(x) => x.abs() < ANGLE_TOLERANCE,
+        CalculatorFloat::Str(_) => false,
+    })
+}
+
+/// Returns rules merging consecutive rotations and removing rotations by zero.
+///
+/// # Returns
+///
+/// * `Vec<RewriteRule>` - The rotation merging rules.
+pub fn rotation_merge_rules() -> Vec<RewriteRule> {
+    vec![
+        builtin_rule(
+            "merge_rotate_x",
+            vec![
+                RotateX::new(0, "a".into()).into(),
+                RotateX::new(0, "b".into()).into(),
+            ],
+            vec![RotateX::new(0, "a + b".into()).into()],
+        ),
+        builtin_rule(
+            "merge_rotate_y",
+            vec![
+                RotateY::new(0, "a".into()).into(),
+                RotateY::new(0, "b".into()).into(),
+            ],
+            vec![RotateY::new(0, "a + b".into()).into()],
+        ),
+        builtin_rule(
+            "merge_rotate_z",
+            vec![
+                RotateZ::new(0, "a".into()).into(),
+                RotateZ::new(0, "b".into()).into(),
+            ],
+            vec![RotateZ::new(0, "a + b".into()).into()],
+        ),
+        builtin_rule(
+            "merge_controlled_phase_shift",
+            vec![
+                ControlledPhaseShift::new(0, 1, "a".into()).into(),
+                ControlledPhaseShift::new(0, 1, "b".into()).into(),
+            ],
+            vec![ControlledPhaseShift::new(0, 1, "a + b".into()).into()],
+        ),
+        builtin_rule(
+            "merge_global_phase",
+            vec![
+                PragmaGlobalPhase::new("a".into()).into(),
+                PragmaGlobalPhase::new("b".into()).into(),
+            ],
+            vec![PragmaGlobalPhase::new("a + b".into()).into()],
+        ),
+        builtin_rule(
+            "remove_zero_rotate_x",
+            vec![RotateX::new(0, "a".into()).into()],
+            vec![],
+        )
+        .with_guard(zero_angles),
+        builtin_rule(
+            "remove_zero_rotate_y",
+            vec![RotateY::new(0, "a".into()).into()],
+            vec![],
+        )
+        .with_guard(zero_angles),
+        builtin_rule(
+            "remove_zero_rotate_z",
+            vec![RotateZ::new(0, "a".into()).into()],
+            vec![],
+        )
+        .with_guard(zero_angles),
+        builtin_rule(
+            "remove_zero_controlled_phase_shift",
+            vec![ControlledPhaseShift::new(0, 1, "a".into()).into()],
+            vec![],
+        )
+        .with_guard(zero_angles),
+    ]
+}
+
+/// Returns rules simplifying products of Clifford and T gates.
+///
+/// The replacements are equal to the patterns up to a global phase.
+///
+/// # Returns
+///
+/// * `Vec<RewriteRule>` - The Clifford simplification rules.
+pub fn clifford_rules() -> Vec<RewriteRule> {
+    vec![
+        builtin_rule(
+            "s_squared",
+            vec![SGate::new(0).into(), SGate::new(0).into()],
+            vec![PauliZ::new(0).into()],
+        ),
+        builtin_rule(
+            "t_squared",
+            vec![TGate::new(0).into(), TGate::new(0).into()],
+            vec![SGate::new(0).into()],
+        ),
+        builtin_rule(
+            "hadamard_conjugate_pauli_x",
+            vec![
+                Hadamard::new(0).into(),
+                PauliX::new(0).into(),
+                Hadamard::new(0).into(),
+            ],
+            vec![PauliZ::new(0).into()],
+        ),
+        builtin_rule(
+            "hadamard_conjugate_pauli_z",
+            vec![
+                Hadamard::new(0).into(),
+                PauliZ::new(0).into(),
+                Hadamard::new(0).into(),
+            ],
+            vec![PauliX::new(0).into()],
+        ),
+    ]
+}
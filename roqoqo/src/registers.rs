@@ -15,7 +15,10 @@
 //! Registers are used to store classical information during the execution of a
 //! roqoqo circuit and to provide a unified output interface for the different backends.
 
+use crate::operations::{Define, Operation};
+use crate::{Circuit, RoqoqoError};
 use num_complex::Complex64;
+use std::collections::HashMap;
 
 // This could be optimized by using bit-vec or bitvec traits
 // but needs to be tested first.
@@ -46,3 +49,377 @@ pub type FloatOutputRegister = Vec<FloatRegister>;
 /// Vector over single complex registers that are each the output of a single run
 /// of a quantum program.
 pub type ComplexOutputRegister = Vec<ComplexRegister>;
+
+/// The output registers of one or several runs of a circuit, as returned by backends.
+///
+/// Each register is stored by name with the register values of every run (shot) of the circuit.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::registers::OutputRegisters;
+///
+/// let mut registers = OutputRegisters::new();
+/// registers.set_bit_register("ro".to_string(), vec![vec![true, false]]);
+///
+/// let mut other = OutputRegisters::new();
+/// other.set_bit_register("ro".to_string(), vec![vec![false, false]]);
+/// registers.append(other);
+///
+/// assert_eq!(registers.number_shots("ro"), Some(2));
+/// assert_eq!(
+///     registers.bit_register("ro"),
+///     Some(&vec![vec![true, false], vec![false, false]])
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutputRegisters {
+    /// The bit output registers by name.
+    bit_registers: HashMap<String, BitOutputRegister>,
+    /// The float output registers by name.
+    float_registers: HashMap<String, FloatOutputRegister>,
+    /// The complex output registers by name.
+    complex_registers: HashMap<String, ComplexOutputRegister>,
+}
+
+impl OutputRegisters {
+    /// Creates new empty OutputRegisters.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The new OutputRegisters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the bit output register with a name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&BitOutputRegister)` - The register.
+    /// * `None` - There is no bit register with the name.
+    pub fn bit_register(&self, name: &str) -> Option<&BitOutputRegister> {
+        self.bit_registers.get(name)
+    }
+
+    /// Returns the float output register with a name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&FloatOutputRegister)` - The register.
+    /// * `None` - There is no float register with the name.
+    pub fn float_register(&self, name: &str) -> Option<&FloatOutputRegister> {
+        self.float_registers.get(name)
+    }
+
+    /// Returns the complex output register with a name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&ComplexOutputRegister)` - The register.
+    /// * `None` - There is no complex register with the name.
+    pub fn complex_register(&self, name: &str) -> Option<&ComplexOutputRegister> {
+        self.complex_registers.get(name)
+    }
+
+    /// Returns all bit output registers.
+    ///
+    /// # Returns
+    ///
+    /// * `&HashMap<String, BitOutputRegister>` - The registers by name.
+    pub fn bit_registers(&self) -> &HashMap<String, BitOutputRegister> {
+        &self.bit_registers
+    }
+
+    /// Returns all float output registers.
+    ///
+    /// # Returns
+    ///
+    /// * `&HashMap<String, FloatOutputRegister>` - The registers by name.
+    pub fn float_registers(&self) -> &HashMap<String, FloatOutputRegister> {
+        &self.float_registers
+    }
+
+    /// Returns all complex output registers.
+    ///
+    /// # Returns
+    ///
+    /// * `&HashMap<String, ComplexOutputRegister>` - The registers by name.
+    pub fn complex_registers(&self) -> &HashMap<String, ComplexOutputRegister> {
+        &self.complex_registers
+    }
+
+    /// Sets a bit output register, replacing a register with the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register.
+    /// * `register` - The values of the register in every run.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<BitOutputRegister>` - The replaced register.
+    pub fn set_bit_register(
+        &mut self,
+        name: String,
+        register: BitOutputRegister,
+    ) -> Option<BitOutputRegister> {
+        self.bit_registers.insert(name, register)
+    }
+
+    /// Sets a float output register, replacing a register with the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register.
+    /// * `register` - The values of the register in every run.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<FloatOutputRegister>` - The replaced register.
+    pub fn set_float_register(
+        &mut self,
+        name: String,
+        register: FloatOutputRegister,
+    ) -> Option<FloatOutputRegister> {
+        self.float_registers.insert(name, register)
+    }
+
+    /// Sets a complex output register, replacing a register with the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register.
+    /// * `register` - The values of the register in every run.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<ComplexOutputRegister>` - The replaced register.
+    pub fn set_complex_register(
+        &mut self,
+        name: String,
+        register: ComplexOutputRegister,
+    ) -> Option<ComplexOutputRegister> {
+        self.complex_registers.insert(name, register)
+    }
+
+    /// Appends the runs of other output registers.
+    ///
+    /// The runs of registers with the same name and type are appended to the runs of this register,
+    /// registers that are not present yet are added.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The output registers of further runs.
+    pub fn append(&mut self, other: OutputRegisters) {
+        append_registers(&mut self.bit_registers, other.bit_registers);
+        append_registers(&mut self.float_registers, other.float_registers);
+        append_registers(&mut self.complex_registers, other.complex_registers);
+    }
+
+    /// Returns the number of runs (shots) stored in a register.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The number of runs in the register.
+    /// * `None` - There is no register with the name.
+    pub fn number_shots(&self, name: &str) -> Option<usize> {
+        self.bit_registers
+            .get(name)
+            .map(|register| register.len())
+            .or_else(|| {
+                self.float_registers
+                    .get(name)
+                    .map(|register| register.len())
+            })
+            .or_else(|| {
+                self.complex_registers
+                    .get(name)
+                    .map(|register| register.len())
+            })
+    }
+
+    /// Returns the number of runs (shots) of all registers.
+    ///
+    /// # Returns
+    ///
+    /// * `HashMap<String, usize>` - The number of runs by register name.
+    pub fn shot_counts(&self) -> HashMap<String, usize> {
+        self.bit_registers
+            .iter()
+            .map(|(name, register)| (name.clone(), register.len()))
+            .chain(
+                self.float_registers
+                    .iter()
+                    .map(|(name, register)| (name.clone(), register.len())),
+            )
+            .chain(
+                self.complex_registers
+                    .iter()
+                    .map(|(name, register)| (name.clone(), register.len())),
+            )
+            .collect()
+    }
+
+    /// Returns true if there are no registers.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if there are no registers.
+    pub fn is_empty(&self) -> bool {
+        self.bit_registers.is_empty()
+            && self.float_registers.is_empty()
+            && self.complex_registers.is_empty()
+    }
+
+    /// Validates the output registers against the register definitions of a circuit.
+    ///
+    /// Every register has to be defined in the circuit with the same type,
+    /// every run of a register has to have the defined length
+    /// and every register defined as an output has to be present.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit that produced the output registers.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The output registers match the definitions.
+    /// * `Err(RoqoqoError::MissingRegister)` - A register defined as an output is missing.
+    /// * `Err(RoqoqoError::OutputRegisterMismatch)` - A register is not defined or has runs of a different length.
+    pub fn validate(&self, circuit: &Circuit) -> Result<(), RoqoqoError> {
+        let mut definitions: HashMap<&str, (&'static str, usize, bool)> = HashMap::new();
+        for operation in circuit.definitions() {
+            let (name, definition) = match operation {
+                Operation::DefinitionBit(def) => {
+                    (def.name(), ("bit", *def.length(), *def.is_output()))
+                }
+                Operation::DefinitionFloat(def) => {
+                    (def.name(), ("float", *def.length(), *def.is_output()))
+                }
+                Operation::DefinitionComplex(def) => {
+                    (def.name(), ("complex", *def.length(), *def.is_output()))
+                }
+                _ => continue,
+            };
+            definitions.insert(name.as_str(), definition);
+        }
+        validate_registers(&self.bit_registers, "bit", &definitions)?;
+        validate_registers(&self.float_registers, "float", &definitions)?;
+        validate_registers(&self.complex_registers, "complex", &definitions)?;
+        for (name, (_, _, is_output)) in definitions.iter() {
+            if *is_output && self.number_shots(name).is_none() {
+                return Err(RoqoqoError::MissingRegister {
+                    name: name.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Appends the runs of registers to the registers with the same name.
+fn append_registers<T>(registers: &mut HashMap<String, Vec<T>>, other: HashMap<String, Vec<T>>) {
+    for (name, mut runs) in other.into_iter() {
+        registers.entry(name).or_default().append(&mut runs);
+    }
+}
+
+/// Validates registers of one type against the definitions of a circuit.
+fn validate_registers<T>(
+    registers: &HashMap<String, Vec<Vec<T>>>,
+    register_type: &str,
+    definitions: &HashMap<&str, (&'static str, usize, bool)>,
+) -> Result<(), RoqoqoError> {
+    for (name, runs) in registers.iter() {
+        let (defined_type, length) = match definitions.get(name.as_str()) {
+            Some((defined_type, length, _)) => (*defined_type, *length),
+            None => {
+                return Err(RoqoqoError::OutputRegisterMismatch {
+                    name: name.clone(),
+                    msg: "The register is not defined in the circuit".to_string(),
+                })
+            }
+        };
+        if defined_type != register_type {
+            return Err(RoqoqoError::OutputRegisterMismatch {
+                name: name.clone(),
+                msg: format!(
+                    "The register is a {} register but is defined as a {} register",
+                    register_type, defined_type
+                ),
+            });
+        }
+        if let Some((run, values)) = runs
+            .iter()
+            .enumerate()
+            .find(|(_, values)| values.len() != length)
+        {
+            return Err(RoqoqoError::OutputRegisterMismatch {
+                name: name.clone(),
+                msg: format!(
+                    "Run {} has {} values but the register is defined with length {}",
+                    run,
+                    values.len(),
+                    length
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+impl
+    From<(
+        HashMap<String, BitOutputRegister>,
+        HashMap<String, FloatOutputRegister>,
+        HashMap<String, ComplexOutputRegister>,
+    )> for OutputRegisters
+{
+    fn from(
+        registers: (
+            HashMap<String, BitOutputRegister>,
+            HashMap<String, FloatOutputRegister>,
+            HashMap<String, ComplexOutputRegister>,
+        ),
+    ) -> Self {
+        OutputRegisters {
+            bit_registers: registers.0,
+            float_registers: registers.1,
+            complex_registers: registers.2,
+        }
+    }
+}
+
+impl From<OutputRegisters>
+    for (
+        HashMap<String, BitOutputRegister>,
+        HashMap<String, FloatOutputRegister>,
+        HashMap<String, ComplexOutputRegister>,
+    )
+{
+    fn from(registers: OutputRegisters) -> Self {
+        (
+            registers.bit_registers,
+            registers.float_registers,
+            registers.complex_registers,
+        )
+    }
+}
@@ -0,0 +1,532 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Versioned serialization of circuits and measurements.
+//!
+//! Data serialized with [to_versioned_json] is wrapped in a [SerializationEnvelope] recording the
+//! roqoqo version that wrote it and the minimum roqoqo version able to read it,
+//! which is determined by the operations actually used ([SupportedVersion]).
+//! [from_versioned_json] rejects data requiring a newer roqoqo version with
+//! [RoqoqoError::IncompatibleVersion] and applies the [Migration]s of a [MigrationRegistry]
+//! to data written by older roqoqo versions before deserializing it.
+
+use crate::measurements::{
+    BasisRotation, Cheated, CheatedBasisRotation, ClassicalRegister, Measure,
+    MeasureExpectationValues, ParameterShiftGradient,
+};
+use crate::operations::{Operate, Operation};
+use crate::{Circuit, RoqoqoError, ROQOQO_VERSION};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A version of roqoqo as (major, minor, patch).
+pub type Version = (u32, u32, u32);
+
+/// The version all operations and measurements without a later version in [INTRODUCED_IN] require.
+const FIRST_VERSION: Version = (0, 1, 0);
+
+/// The roqoqo versions introducing operations and measurements after the first version.
+///
+/// The versions are taken from the release sections of the changelog.
+/// Additions that are not released yet are marked with the current version of the crate,
+/// so that data written by this version can be read again.
+const INTRODUCED_IN: &[(&str, Version)] = &[
+    ("PhaseShiftedControlledZ", (0, 5, 0)),
+    ("ParameterShiftGradient", (0, 5, 0)),
+    ("MultiQubitGate", (0, 5, 0)),
+    ("Toffoli", (0, 5, 0)),
+    ("ControlledControlledPauliZ", (0, 5, 0)),
+    ("ControlledControlledPhaseShift", (0, 5, 0)),
+    ("MultiControlledGate", (0, 5, 0)),
+    ("PauliProductRotation", (0, 5, 0)),
+    ("ClassicalNot", (0, 5, 0)),
+    ("ClassicalCopy", (0, 5, 0)),
+    ("ClassicalXor", (0, 5, 0)),
+    ("ClassicalAnd", (0, 5, 0)),
+    ("ClassicalOr", (0, 5, 0)),
+    ("ClassicalGreaterThan", (0, 5, 0)),
+    ("ClassicalLessThan", (0, 5, 0)),
+    ("PragmaConditionalPattern", (0, 5, 0)),
+    ("PragmaLoop", (0, 5, 0)),
+    ("PragmaRepeatUntilSuccess", (0, 5, 0)),
+    ("TwoQubitGate", (0, 5, 0)),
+];
+
+/// Returns the version introducing an operation or measurement.
+fn introduced_in(name: &str) -> Version {
+    INTRODUCED_IN
+        .iter()
+        .find(|(introduced, _)| *introduced == name)
+        .map(|(_, version)| *version)
+        .unwrap_or(FIRST_VERSION)
+}
+
+/// Returns the version as a `major.minor.patch` string.
+fn version_to_string(version: Version) -> String {
+    format!("{}.{}.{}", version.0, version.1, version.2)
+}
+
+/// Parses a `major.minor.patch` version, ignoring pre-release and build suffixes.
+///
+/// # Arguments
+///
+/// * `version` - The version string.
+///
+/// # Returns
+///
+/// * `Ok(Version)` - The parsed version.
+/// * `Err(RoqoqoError::SerializationError)` - The version is not of the form `major.minor.patch`.
+pub fn parse_version(version: &str) -> Result<Version, RoqoqoError> {
+    let core = version.split(['-', '+']).next().unwrap_or("");
+    let numbers: Vec<Option<u32>> = core.split('.').map(|n| n.parse().ok()).collect();
+    match numbers.as_slice() {
+        [Some(major), Some(minor), Some(patch)] => Ok((*major, *minor, *patch)),
+        _ => Err(RoqoqoError::SerializationError {
+            msg: format!("Invalid roqoqo version '{}'", version),
+        }),
+    }
+}
+
+/// Trait returning the minimum roqoqo version able to deserialize serialized data.
+pub trait SupportedVersion {
+    /// Returns the minimum roqoqo version able to deserialize the data.
+    ///
+    /// # Returns
+    ///
+    /// * `Version` - The minimum roqoqo version as (major, minor, patch).
+    fn minimum_supported_roqoqo_version(&self) -> Version {
+        FIRST_VERSION
+    }
+}
+
+impl SupportedVersion for Operation {
+    fn minimum_supported_roqoqo_version(&self) -> Version {
+        let version = introduced_in(self.hqslang());
+        match self {
+            Operation::PragmaConditional(op) => {
+                version.max(op.circuit().minimum_supported_roqoqo_version())
+            }
+            Operation::PragmaConditionalPattern(op) => {
+                version.max(op.circuit().minimum_supported_roqoqo_version())
+            }
+            Operation::PragmaLoop(op) => {
+                version.max(op.circuit().minimum_supported_roqoqo_version())
+            }
+            Operation::PragmaRepeatUntilSuccess(op) => {
+                version.max(op.circuit().minimum_supported_roqoqo_version())
+            }
+            Operation::PragmaGetPauliProduct(op) => {
+                version.max(op.circuit().minimum_supported_roqoqo_version())
+            }
+            Operation::PragmaGetOccupationProbability(op) => match op.circuit() {
+                Some(circuit) => version.max(circuit.minimum_supported_roqoqo_version()),
+                None => version,
+            },
+            Operation::PragmaGetStateVector(op) => match op.circuit() {
+                Some(circuit) => version.max(circuit.minimum_supported_roqoqo_version()),
+                None => version,
+            },
+            Operation::PragmaGetDensityMatrix(op) => match op.circuit() {
+                Some(circuit) => version.max(circuit.minimum_supported_roqoqo_version()),
+                None => version,
+            },
+            _ => version,
+        }
+    }
+}
+
+impl SupportedVersion for Circuit {
+    fn minimum_supported_roqoqo_version(&self) -> Version {
+        self.iter()
+            .map(|operation| operation.minimum_supported_roqoqo_version())
+            .fold(FIRST_VERSION, Version::max)
+    }
+}
+
+/// Returns the minimum roqoqo version of a measurement introduced in a version.
+fn measurement_version<M: Measure>(measurement: &M, introduced: Version) -> Version {
+    measurement
+        .constant_circuit()
+        .iter()
+        .chain(measurement.circuits())
+        .map(|circuit| circuit.minimum_supported_roqoqo_version())
+        .fold(introduced, Version::max)
+}
+
+impl SupportedVersion for BasisRotation {
+    fn minimum_supported_roqoqo_version(&self) -> Version {
+        measurement_version(self, introduced_in("BasisRotation"))
+    }
+}
+
+impl SupportedVersion for CheatedBasisRotation {
+    fn minimum_supported_roqoqo_version(&self) -> Version {
+        measurement_version(self, introduced_in("CheatedBasisRotation"))
+    }
+}
+
+impl SupportedVersion for Cheated {
+    fn minimum_supported_roqoqo_version(&self) -> Version {
+        measurement_version(self, introduced_in("Cheated"))
+    }
+}
+
+impl SupportedVersion for ClassicalRegister {
+    fn minimum_supported_roqoqo_version(&self) -> Version {
+        measurement_version(self, introduced_in("ClassicalRegister"))
+    }
+}
+
+impl<T> SupportedVersion for ParameterShiftGradient<T>
+where
+    T: MeasureExpectationValues,
+{
+    fn minimum_supported_roqoqo_version(&self) -> Version {
+        measurement_version(self, introduced_in("ParameterShiftGradient"))
+    }
+}
+
+/// Serialized data together with the roqoqo versions needed to read it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializationEnvelope<T> {
+    /// The roqoqo version that serialized the data.
+    pub roqoqo_version: String,
+    /// The minimum roqoqo version able to deserialize the data.
+    pub minimum_roqoqo_version: String,
+    /// The serialized data.
+    pub data: T,
+}
+
+/// Serializes data to JSON wrapped in a [SerializationEnvelope].
+///
+/// # Arguments
+///
+/// * `data` - The circuit, operation or measurement to serialize.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON of the envelope.
+/// * `Err(RoqoqoError::SerializationError)` - The data could not be serialized.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::operations::PauliX;
+/// use roqoqo::serialization::{from_versioned_json, to_versioned_json};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += PauliX::new(0);
+///
+/// let json = to_versioned_json(&circuit).unwrap();
+/// let deserialized: Circuit = from_versioned_json(&json).unwrap();
+/// assert_eq!(deserialized, circuit);
+/// ```
+pub fn to_versioned_json<T>(data: &T) -> Result<String, RoqoqoError>
+where
+    T: Serialize + SupportedVersion,
+{
+    let envelope = SerializationEnvelope {
+        roqoqo_version: ROQOQO_VERSION.to_string(),
+        minimum_roqoqo_version: version_to_string(data.minimum_supported_roqoqo_version()),
+        data,
+    };
+    serde_json::to_string(&envelope).map_err(|err| RoqoqoError::SerializationError {
+        msg: err.to_string(),
+    })
+}
+
+/// Deserializes data from the JSON of a [SerializationEnvelope] without migrations.
+///
+/// See [MigrationRegistry::from_versioned_json].
+///
+/// # Arguments
+///
+/// * `json` - The JSON of the envelope.
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data.
+/// * `Err(RoqoqoError::IncompatibleVersion)` - The data requires a newer roqoqo version.
+/// * `Err(RoqoqoError::SerializationError)` - The data could not be deserialized.
+pub fn from_versioned_json<T>(json: &str) -> Result<T, RoqoqoError>
+where
+    T: DeserializeOwned,
+{
+    MigrationRegistry::new().from_versioned_json(json)
+}
+
+/// A change of the serialized data between roqoqo versions.
+#[derive(Debug, Clone)]
+pub enum Migration {
+    /// An operation or measurement was renamed.
+    RenameOperation {
+        /// The version introducing the new name.
+        version: Version,
+        /// The name before the version.
+        old_name: String,
+        /// The name since the version.
+        new_name: String,
+    },
+    /// A field of an operation or measurement was renamed.
+    RenameField {
+        /// The version introducing the new name.
+        version: Version,
+        /// The name of the operation or measurement since the version.
+        operation: String,
+        /// The name of the field before the version.
+        old_name: String,
+        /// The name of the field since the version.
+        new_name: String,
+    },
+    /// Any other change, migrated by a function changing the serialized data in place.
+    Custom {
+        /// The version introducing the change.
+        version: Version,
+        /// The description of the change used in error messages.
+        description: String,
+        /// The function migrating the serialized data.
+        migrate: fn(&mut Value) -> Result<(), RoqoqoError>,
+    },
+}
+
+impl Migration {
+    /// Returns the version introducing the change.
+    ///
+    /// # Returns
+    ///
+    /// * `Version` - The version as (major, minor, patch).
+    pub fn version(&self) -> Version {
+        match self {
+            Migration::RenameOperation { version, .. } => *version,
+            Migration::RenameField { version, .. } => *version,
+            Migration::Custom { version, .. } => *version,
+        }
+    }
+
+    /// Applies the migration to serialized data.
+    ///
+    /// Renames are applied to all serialized operations, including the operations in the circuits of measurements
+    /// and in the circuits contained in operations. Other data, like user-defined names, is not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The serialized data.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The data was migrated.
+    /// * `Err(RoqoqoError::SerializationError)` - The custom migration failed.
+    pub fn apply(&self, value: &mut Value) -> Result<(), RoqoqoError> {
+        match self {
+            Migration::RenameOperation {
+                old_name, new_name, ..
+            } => {
+                visit_operations(value, &mut |map| rename_key(map, old_name, new_name));
+                Ok(())
+            }
+            Migration::RenameField {
+                operation,
+                old_name,
+                new_name,
+                ..
+            } => {
+                visit_operations(value, &mut |map| {
+                    if let Some(Value::Object(fields)) = map.get_mut(operation) {
+                        rename_key(fields, old_name, new_name);
+                    }
+                });
+                Ok(())
+            }
+            Migration::Custom {
+                description,
+                migrate,
+                ..
+            } => migrate(value).map_err(|err| RoqoqoError::SerializationError {
+                msg: format!("Migration '{}' failed: {}", description, err),
+            }),
+        }
+    }
+}
+
+/// Calls a function for every serialized operation in the value.
+///
+/// The serialized operations are the value itself if it is a single operation
+/// and the definitions and operations of all circuits nested in the value.
+fn visit_operations(value: &mut Value, f: &mut dyn FnMut(&mut Map<String, Value>)) {
+    if let Value::Object(map) = value {
+        if is_operation(map) {
+            f(map);
+        }
+    }
+    visit_circuits(value, f);
+}
+
+/// Calls a function for the definitions and operations of every serialized circuit nested in the value.
+fn visit_circuits(value: &mut Value, f: &mut dyn FnMut(&mut Map<String, Value>)) {
+    match value {
+        Value::Object(map) => {
+            let is_circuit = matches!(map.get("operations"), Some(Value::Array(_)));
+            for (key, child) in map.iter_mut() {
+                if is_circuit && (key == "definitions" || key == "operations") {
+                    if let Value::Array(operations) = child {
+                        for operation in operations.iter_mut() {
+                            match operation {
+                                Value::Object(operation) if is_operation(operation) => f(operation),
+                                _ => (),
+                            }
+                        }
+                    }
+                }
+                visit_circuits(child, f);
+            }
+        }
+        Value::Array(items) => {
+            for child in items.iter_mut() {
+                visit_circuits(child, f);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Returns true if an object is a serialized operation, the name of the operation with its fields.
+fn is_operation(map: &Map<String, Value>) -> bool {
+    map.len() == 1 && map.values().all(|fields| fields.is_object())
+}
+
+/// Renames a key of a map if it is present.
+fn rename_key(map: &mut Map<String, Value>, old_name: &str, new_name: &str) {
+    if let Some(entry) = map.remove(old_name) {
+        map.insert(new_name.to_string(), entry);
+    }
+}
+
+/// The migrations applied to data written by older roqoqo versions.
+#[derive(Debug, Clone)]
+pub struct MigrationRegistry {
+    /// The registered migrations.
+    migrations: Vec<Migration>,
+}
+
+impl Default for MigrationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MigrationRegistry {
+    /// Creates a new empty MigrationRegistry.
+    ///
+    /// The serialized data of roqoqo has not changed since versioned serialization was introduced,
+    /// so there are no built-in migrations yet.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The new registry.
+    pub fn new() -> Self {
+        MigrationRegistry {
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Registers a migration.
+    ///
+    /// # Arguments
+    ///
+    /// * `migration` - The migration applied to data written before its version.
+    pub fn register(&mut self, migration: Migration) {
+        self.migrations.push(migration);
+    }
+
+    /// Returns the registered migrations.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Migration]` - The migrations in the order of registration.
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Deserializes data from the JSON of a [SerializationEnvelope].
+    ///
+    /// Data requiring a newer roqoqo version than the current one is rejected.
+    /// All migrations with a version newer than the version that wrote the data are applied
+    /// in the order of their versions before deserializing.
+    /// JSON without an envelope, written before versioned serialization, is migrated with all migrations.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON of the envelope.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` - The deserialized data.
+    /// * `Err(RoqoqoError::IncompatibleVersion)` - The data requires a newer roqoqo version.
+    /// * `Err(RoqoqoError::SerializationError)` - The data could not be deserialized.
+    pub fn from_versioned_json<T>(&self, json: &str) -> Result<T, RoqoqoError>
+    where
+        T: DeserializeOwned,
+    {
+        let value: Value =
+            serde_json::from_str(json).map_err(|err| RoqoqoError::SerializationError {
+                msg: err.to_string(),
+            })?;
+        let (data_version, mut data) = match value {
+            Value::Object(mut map)
+                if map.contains_key("roqoqo_version")
+                    && map.contains_key("minimum_roqoqo_version")
+                    && map.contains_key("data") =>
+            {
+                let data_version = version_field(&map, "roqoqo_version")?;
+                let minimum_version = version_field(&map, "minimum_roqoqo_version")?;
+                let library_version = parse_version(ROQOQO_VERSION)?;
+                if minimum_version > library_version {
+                    return Err(RoqoqoError::IncompatibleVersion {
+                        data_version: version_to_string(data_version),
+                        minimum_version: version_to_string(minimum_version),
+                        library_version: ROQOQO_VERSION.to_string(),
+                    });
+                }
+                (data_version, map.remove("data").unwrap_or(Value::Null))
+            }
+            value => ((0, 0, 0), value),
+        };
+        let mut migrations: Vec<&Migration> = self
+            .migrations
+            .iter()
+            .filter(|migration| migration.version() > data_version)
+            .collect();
+        migrations.sort_by_key(|migration| migration.version());
+        for migration in migrations {
+            migration.apply(&mut data)?;
+        }
+        serde_json::from_value(data).map_err(|err| RoqoqoError::SerializationError {
+            msg: format!(
+                "Data written with roqoqo {} could not be deserialized: {}",
+                version_to_string(data_version),
+                err
+            ),
+        })
+    }
+}
+
+/// Returns a version field of an envelope.
+fn version_field(map: &Map<String, Value>, field: &str) -> Result<Version, RoqoqoError> {
+    match map.get(field) {
+        Some(Value::String(version)) => parse_version(version),
+        _ => Err(RoqoqoError::SerializationError {
+            msg: format!("Field {} of the envelope is not a version string", field),
+        }),
+    }
+}
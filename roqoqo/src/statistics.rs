@@ -0,0 +1,223 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resource statistics of quantum circuits.
+//!
+//! [CircuitStatistics] collects depth, gate counts, critical path and the qubit and register footprint
+//! of a [Circuit] in one serializable report.
+
+use crate::operations::{Define, InvolveQubits, InvolvedQubits, Operate, Operation, Substitute};
+use crate::{Circuit, CircuitDag};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The hqslang names of the gates that are always Clifford gates.
+const CLIFFORD_GATES: &[&str] = &[
+    "PauliX",
+    "PauliY",
+    "PauliZ",
+    "SqrtPauliX",
+    "InvSqrtPauliX",
+    "Hadamard",
+    "SGate",
+    "CNOT",
+    "SWAP",
+    "ISwap",
+    "FSwap",
+    "ControlledPauliY",
+    "ControlledPauliZ",
+    "MolmerSorensenXX",
+];
+
+/// Resource statistics of a quantum circuit.
+///
+/// The depth of a circuit is the number of gate operations on its critical path,
+/// the longest path through the [CircuitDag] of the gate operations of the circuit.
+/// Pragmas, measurements and definitions do not contribute to the depth.
+/// Maps are ordered by key so that the serialized summary is reproducible and can be compared between runs.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::operations::{CNOT, Hadamard, TGate};
+/// use roqoqo::statistics::CircuitStatistics;
+///
+/// let mut circuit = Circuit::new();
+/// circuit += Hadamard::new(0);
+/// circuit += TGate::new(1);
+/// circuit += CNOT::new(0, 1);
+///
+/// let statistics = CircuitStatistics::from(&circuit);
+/// assert_eq!(statistics.depth, 2);
+/// assert_eq!(statistics.two_qubit_depth, 1);
+/// assert_eq!(statistics.t_count, 1);
+/// assert_eq!(statistics.clifford_count, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct CircuitStatistics {
+    /// Number of operations in the circuit, not including definitions.
+    pub number_operations: usize,
+    /// Number of gate operations in the circuit.
+    pub number_gates: usize,
+    /// Depth of the circuit counting only gate operations.
+    pub depth: usize,
+    /// Depth of the circuit counting only gates acting on two or more qubits.
+    pub two_qubit_depth: usize,
+    /// Number of operations (including definitions) for each hqslang name.
+    pub operation_counts: BTreeMap<String, usize>,
+    /// Number of gate operations acting on each qubit.
+    pub gate_counts_per_qubit: BTreeMap<usize, usize>,
+    /// Indices (in [Circuit::operations]) of the gate operations on the critical path, in order of execution.
+    pub critical_path: Vec<usize>,
+    /// Number of T gates.
+    pub t_count: usize,
+    /// Number of Clifford gates.
+    pub clifford_count: usize,
+    /// Number of gates that are not known to be Clifford gates.
+    pub non_clifford_count: usize,
+    /// Number of measurement operations.
+    pub number_measurements: usize,
+    /// Sorted names of the symbolic parameters appearing in the operations of the circuit.
+    pub symbolic_parameters: Vec<String>,
+    /// Sorted indices of the qubits the operations explicitly act on.
+    pub qubits: Vec<usize>,
+    /// True if the circuit contains an operation acting on all qubits.
+    pub involves_all_qubits: bool,
+    /// Lengths of the bit registers defined in the circuit.
+    pub bit_registers: BTreeMap<String, usize>,
+    /// Lengths of the float registers defined in the circuit.
+    pub float_registers: BTreeMap<String, usize>,
+    /// Lengths of the complex registers defined in the circuit.
+    pub complex_registers: BTreeMap<String, usize>,
+    /// Lengths of the usize registers defined in the circuit.
+    pub usize_registers: BTreeMap<String, usize>,
+}
+
+impl From<&Circuit> for CircuitStatistics {
+    /// Collects the statistics of a circuit.
+    fn from(circuit: &Circuit) -> Self {
+        let mut gates = CircuitDag::new();
+        let mut gate_indices: Vec<usize> = Vec::new();
+        let mut multi_qubit_gates = CircuitDag::new();
+        let mut operation_counts: BTreeMap<String, usize> = BTreeMap::new();
+        let mut gate_counts_per_qubit: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut qubits: BTreeSet<usize> = BTreeSet::new();
+        let mut involves_all_qubits = false;
+        let mut number_gates = 0;
+        let mut t_count = 0;
+        let mut clifford_count = 0;
+        let mut number_measurements = 0;
+
+        for operation in circuit.iter() {
+            *operation_counts
+                .entry(operation.hqslang().to_string())
+                .or_insert(0) += 1;
+        }
+        for (index, operation) in circuit.operations().iter().enumerate() {
+            let tags = operation.tags();
+            let involved = operation.involved_qubits();
+            match &involved {
+                InvolvedQubits::All => involves_all_qubits = true,
+                InvolvedQubits::None => (),
+                InvolvedQubits::Set(set) => qubits.extend(set.iter()),
+            }
+            if tags.contains(&"Measurement") {
+                number_measurements += 1;
+            }
+            if !tags.contains(&"GateOperation") {
+                continue;
+            }
+            number_gates += 1;
+            gates.add_operation(operation.clone());
+            gate_indices.push(index);
+            if let InvolvedQubits::Set(set) = involved {
+                for qubit in set.iter() {
+                    *gate_counts_per_qubit.entry(*qubit).or_insert(0) += 1;
+                }
+                if set.len() > 1 {
+                    multi_qubit_gates.add_operation(operation.clone());
+                }
+            }
+            match operation.hqslang() {
+                "TGate" => t_count += 1,
+                name if CLIFFORD_GATES.contains(&name) => clifford_count += 1,
+                _ => (),
+            }
+        }
+
+        let mut bit_registers: BTreeMap<String, usize> = BTreeMap::new();
+        let mut float_registers: BTreeMap<String, usize> = BTreeMap::new();
+        let mut complex_registers: BTreeMap<String, usize> = BTreeMap::new();
+        let mut usize_registers: BTreeMap<String, usize> = BTreeMap::new();
+        for definition in circuit.definitions().iter() {
+            match definition {
+                Operation::DefinitionBit(x) => {
+                    bit_registers.insert(x.name().clone(), *x.length());
+                }
+                Operation::DefinitionFloat(x) => {
+                    float_registers.insert(x.name().clone(), *x.length());
+                }
+                Operation::DefinitionComplex(x) => {
+                    complex_registers.insert(x.name().clone(), *x.length());
+                }
+                Operation::DefinitionUsize(x) => {
+                    usize_registers.insert(x.name().clone(), *x.length());
+                }
+                _ => (),
+            }
+        }
+
+        let critical_path: Vec<usize> = gates
+            .longest_path()
+            .into_iter()
+            .map(|node| gate_indices[node])
+            .collect();
+
+        CircuitStatistics {
+            number_operations: circuit.operations().len(),
+            number_gates,
+            depth: critical_path.len(),
+            two_qubit_depth: multi_qubit_gates.longest_path().len(),
+            operation_counts,
+            gate_counts_per_qubit,
+            critical_path,
+            t_count,
+            clifford_count,
+            non_clifford_count: number_gates - clifford_count,
+            number_measurements,
+            symbolic_parameters: symbolic_parameters(circuit.operations()),
+            qubits: qubits.into_iter().collect(),
+            involves_all_qubits,
+            bit_registers,
+            float_registers,
+            complex_registers,
+            usize_registers,
+        }
+    }
+}
+
+impl From<Circuit> for CircuitStatistics {
+    /// Collects the statistics of a circuit.
+    fn from(circuit: Circuit) -> Self {
+        CircuitStatistics::from(&circuit)
+    }
+}
+
+/// Returns the sorted names of the symbolic parameters appearing in operations.
+fn symbolic_parameters(operations: &[Operation]) -> Vec<String> {
+    let mut names: BTreeSet<String> = BTreeSet::new();
+    for operation in operations.iter().filter(|op| op.is_parametrized()) {
+        names.extend(operation.free_parameters());
+    }
+    names.into_iter().collect()
+}
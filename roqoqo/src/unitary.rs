@@ -0,0 +1,352 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unitary matrix representation of complete roqoqo circuits.
+//!
+//! [crate::operations::OperateGate::unitary_matrix] only returns the matrix of a single gate
+//! on the qubits the gate acts on.
+//! This module combines the gates of a [crate::Circuit] into the unitary matrix of the full circuit
+//! and allows to check if two circuits implement the same unitary up to a global phase
+//! and an optional permutation of the output qubits.
+//!
+//! # Qubit ordering
+//!
+//! The unitary of a circuit is given in the computational basis of an ordered list of qubits.
+//! The k-th qubit in the list corresponds to the k-th bit (counting from the least significant bit)
+//! of the index of a basis state, i.e. the first qubit in the list is the least significant qubit.
+//! By default the list contains all qubits involved in the circuit in ascending order.
+
+use crate::operations::{
+    InvolveQubits, InvolvedQubits, MultiQubitGateOperation, Operate, OperateGate,
+    OperateMultiQubit, OperateSingleQubit, OperateTwoQubit, Operation, SingleQubitGateOperation,
+    TwoQubitGateOperation,
+};
+use crate::{Circuit, RoqoqoError};
+use ndarray::Array2;
+use num_complex::Complex64;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+/// Returns the unitary matrix of a circuit acting on all qubits involved in the circuit.
+///
+/// The qubits are ordered ascending and the lowest qubit is the least significant qubit
+/// in the index of the basis states.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit containing only gate operations (definitions and [crate::operations::PragmaGlobalPhase] are allowed).
+///
+/// # Returns
+///
+/// * `Ok((Vec<usize>, Array2<Complex64>))` - The ordered qubits the unitary acts on and the unitary matrix.
+/// * `Err([RoqoqoError::OperationNotUnitary])` - The circuit contains an operation without a unitary representation.
+/// * `Err([RoqoqoError::CalculatorError])` - A parameter of an operation in the circuit is symbolic.
+///
+/// # Example
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::operations::{CNOT, Hadamard};
+/// use roqoqo::unitary::circuit_unitary;
+///
+/// let mut circuit = Circuit::new();
+/// circuit += Hadamard::new(0);
+/// circuit += CNOT::new(0, 1);
+/// let (qubits, unitary) = circuit_unitary(&circuit).unwrap();
+/// assert_eq!(qubits, vec![0, 1]);
+/// assert_eq!(unitary.shape(), &[4, 4]);
+/// ```
+pub fn circuit_unitary(circuit: &Circuit) -> Result<(Vec<usize>, Array2<Complex64>), RoqoqoError> {
+    let qubits = ordered_involved_qubits(circuit)?;
+    let unitary = circuit_unitary_on_qubits(circuit, &qubits)?;
+    Ok((qubits, unitary))
+}
+
+/// Returns the unitary matrix of a circuit acting on a given ordered list of qubits.
+///
+/// The k-th qubit in `qubits` corresponds to the k-th bit of the index of the basis states.
+/// Qubits in `qubits` that are not involved in the circuit are acted on with the identity.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit containing only gate operations (definitions and [crate::operations::PragmaGlobalPhase] are allowed).
+/// * `qubits` - The ordered list of qubits the unitary matrix is constructed for.
+///
+/// # Returns
+///
+/// * `Ok(Array2<Complex64>)` - The unitary matrix of the circuit.
+/// * `Err([RoqoqoError::OperationNotUnitary])` - The circuit contains an operation without a unitary representation.
+/// * `Err([RoqoqoError::QubitMappingError])` - The circuit acts on a qubit that is not in `qubits`.
+/// * `Err([RoqoqoError::CalculatorError])` - A parameter of an operation in the circuit is symbolic.
+pub fn circuit_unitary_on_qubits(
+    circuit: &Circuit,
+    qubits: &[usize],
+) -> Result<Array2<Complex64>, RoqoqoError> {
+    let positions: HashMap<usize, usize> = qubits
+        .iter()
+        .enumerate()
+        .map(|(position, qubit)| (*qubit, position))
+        .collect();
+    let dimension = 2_usize.pow(qubits.len() as u32);
+    let mut unitary: Array2<Complex64> = Array2::eye(dimension);
+    for operation in circuit.operations() {
+        if let Operation::PragmaGlobalPhase(pragma) = operation {
+            let phase = Complex64::new(0.0, *pragma.phase().float()?).exp();
+            unitary.mapv_inplace(|value| value * phase);
+            continue;
+        }
+        let (gate_qubits, gate_matrix) = gate_qubits_and_matrix(operation)?;
+        let mut gate_positions: Vec<usize> = Vec::with_capacity(gate_qubits.len());
+        for qubit in gate_qubits.iter() {
+            gate_positions.push(
+                *positions
+                    .get(qubit)
+                    .ok_or(RoqoqoError::QubitMappingError { qubit: *qubit })?,
+            );
+        }
+        unitary = apply_gate(&unitary, &gate_matrix, &gate_positions);
+    }
+    Ok(unitary)
+}
+
+/// Checks if two circuits implement the same unitary up to a global phase.
+///
+/// The circuits are compared on the union of their involved qubits.
+/// Optionally the outputs of the circuits can be compared up to a permutation of the qubits:
+/// The `second` circuit is equivalent to the `first` circuit when
+/// U_second = exp(i φ) P U_first, where P moves the state of qubit `q` to qubit `output_permutation[q]`.
+/// Qubits that are not keys in `output_permutation` are not moved.
+///
+/// # Arguments
+///
+/// * `first` - The first circuit to compare.
+/// * `second` - The second circuit to compare.
+/// * `tolerance` - The maximal absolute deviation allowed for each matrix element.
+/// * `output_permutation` - The optional permutation of the output qubits of the first circuit.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - True when the circuits are equivalent, false otherwise.
+/// * `Err([RoqoqoError::OperationNotUnitary])` - A circuit contains an operation without a unitary representation.
+/// * `Err([RoqoqoError::QubitMappingError])` - The output permutation is not a permutation of the involved qubits.
+/// * `Err([RoqoqoError::CalculatorError])` - A parameter of an operation in the circuits is symbolic.
+///
+/// # Example
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::operations::{CNOT, ControlledPauliZ, Hadamard, RotateZ, SWAP};
+/// use roqoqo::unitary::circuits_equivalent;
+/// use std::collections::HashMap;
+///
+/// let mut cnot = Circuit::new();
+/// cnot += CNOT::new(0, 1);
+/// let mut decomposed = Circuit::new();
+/// decomposed += Hadamard::new(1);
+/// decomposed += ControlledPauliZ::new(0, 1);
+/// decomposed += Hadamard::new(1);
+/// assert!(circuits_equivalent(&cnot, &decomposed, 1e-10, None).unwrap());
+///
+/// let mut swapped = cnot.clone();
+/// swapped += SWAP::new(0, 1);
+/// let mut permutation: HashMap<usize, usize> = HashMap::new();
+/// permutation.insert(0, 1);
+/// permutation.insert(1, 0);
+/// assert!(circuits_equivalent(&cnot, &swapped, 1e-10, Some(&permutation)).unwrap());
+/// ```
+pub fn circuits_equivalent(
+    first: &Circuit,
+    second: &Circuit,
+    tolerance: f64,
+    output_permutation: Option<&HashMap<usize, usize>>,
+) -> Result<bool, RoqoqoError> {
+    let mut qubit_set: HashSet<usize> = ordered_involved_qubits(first)?.into_iter().collect();
+    qubit_set.extend(ordered_involved_qubits(second)?);
+    if let Some(permutation) = output_permutation {
+        qubit_set.extend(permutation.keys().cloned());
+        qubit_set.extend(permutation.values().cloned());
+    }
+    let mut qubits: Vec<usize> = qubit_set.into_iter().collect();
+    qubits.sort_unstable();
+
+    let mut first_unitary = circuit_unitary_on_qubits(first, &qubits)?;
+    let second_unitary = circuit_unitary_on_qubits(second, &qubits)?;
+    if let Some(permutation) = output_permutation {
+        first_unitary = permute_output_qubits(&first_unitary, &qubits, permutation)?;
+    }
+    Ok(equal_up_to_global_phase(
+        &first_unitary,
+        &second_unitary,
+        tolerance,
+    ))
+}
+
+/// Checks if two matrices are equal up to a global phase.
+///
+/// # Arguments
+///
+/// * `first` - The first matrix.
+/// * `second` - The second matrix.
+/// * `tolerance` - The maximal absolute deviation allowed for each matrix element.
+///
+/// # Returns
+///
+/// * `bool` - True when `second` = exp(i φ) `first` for some phase φ.
+pub fn equal_up_to_global_phase(
+    first: &Array2<Complex64>,
+    second: &Array2<Complex64>,
+    tolerance: f64,
+) -> bool {
+    if first.shape() != second.shape() {
+        return false;
+    }
+    // The global phase is determined from the largest element of the first matrix
+    // to keep numerical errors small
+    let reference = first
+        .indexed_iter()
+        .max_by(|(_, a), (_, b)| {
+            a.norm()
+                .partial_cmp(&b.norm())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(index, _)| index);
+    let phase = match reference {
+        Some(index) if first[index].norm() > tolerance => {
+            let ratio = second[index] / first[index];
+            if ratio.norm() == 0.0 {
+                return false;
+            }
+            ratio / ratio.norm()
+        }
+        _ => Complex64::new(1.0, 0.0),
+    };
+    first
+        .iter()
+        .zip(second.iter())
+        .all(|(a, b)| (a * phase - b).norm() <= tolerance)
+}
+
+// Returns the qubits involved in a circuit in ascending order
+fn ordered_involved_qubits(circuit: &Circuit) -> Result<Vec<usize>, RoqoqoError> {
+    let mut qubits: Vec<usize> = match circuit.involved_qubits() {
+        InvolvedQubits::None => Vec::new(),
+        InvolvedQubits::Set(set) => set.into_iter().collect(),
+        InvolvedQubits::All => {
+            // InvolvedQubits::All is only returned by operations that are not unitary gates
+            let hqslang = circuit
+                .operations()
+                .iter()
+                .find(|op| op.involved_qubits() == InvolvedQubits::All)
+                .map_or("Circuit", |op| op.hqslang());
+            return Err(RoqoqoError::OperationNotUnitary { hqslang });
+        }
+    };
+    qubits.sort_unstable();
+    Ok(qubits)
+}
+
+// Returns the qubits of a gate operation ordered by descending significance and the unitary matrix of the gate
+fn gate_qubits_and_matrix(
+    operation: &Operation,
+) -> Result<(Vec<usize>, Array2<Complex64>), RoqoqoError> {
+    if let Ok(gate) = SingleQubitGateOperation::try_from(operation) {
+        Ok((vec![*gate.qubit()], gate.unitary_matrix()?))
+    } else if let Ok(gate) = TwoQubitGateOperation::try_from(operation) {
+        Ok((
+            vec![*gate.control(), *gate.target()],
+            gate.unitary_matrix()?,
+        ))
+    } else if let Ok(gate) = MultiQubitGateOperation::try_from(operation) {
+        Ok((gate.qubits().clone(), gate.unitary_matrix()?))
+    } else {
+        Err(RoqoqoError::OperationNotUnitary {
+            hqslang: operation.hqslang(),
+        })
+    }
+}
+
+// Applies a gate acting on the bit positions in `positions` (most significant first) to the unitary from the left
+fn apply_gate(
+    unitary: &Array2<Complex64>,
+    gate_matrix: &Array2<Complex64>,
+    positions: &[usize],
+) -> Array2<Complex64> {
+    let dimension = unitary.nrows();
+    let number_gate_qubits = positions.len();
+    let gate_dimension = 2_usize.pow(number_gate_qubits as u32);
+    // Mask of all bits the gate acts on
+    let gate_mask: usize = positions.iter().map(|p| 1 << p).sum();
+    // Precomputed offsets of the local basis states of the gate in the full basis
+    let local_offsets: Vec<usize> = (0..gate_dimension)
+        .map(|local| {
+            positions
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| local & (1 << (number_gate_qubits - 1 - k)) != 0)
+                .map(|(_, p)| 1 << p)
+                .sum()
+        })
+        .collect();
+    let mut new_unitary: Array2<Complex64> = Array2::zeros((dimension, dimension));
+    for base in (0..dimension).filter(|index| index & gate_mask == 0) {
+        for (row_local, row_offset) in local_offsets.iter().enumerate() {
+            let row = base + row_offset;
+            for (column_local, column_offset) in local_offsets.iter().enumerate() {
+                let factor = gate_matrix[(row_local, column_local)];
+                if factor == Complex64::new(0.0, 0.0) {
+                    continue;
+                }
+                let source = base + column_offset;
+                for column in 0..dimension {
+                    new_unitary[(row, column)] += factor * unitary[(source, column)];
+                }
+            }
+        }
+    }
+    new_unitary
+}
+
+// Moves the output state of qubit q to qubit permutation[q]
+fn permute_output_qubits(
+    unitary: &Array2<Complex64>,
+    qubits: &[usize],
+    permutation: &HashMap<usize, usize>,
+) -> Result<Array2<Complex64>, RoqoqoError> {
+    let positions: HashMap<usize, usize> = qubits
+        .iter()
+        .enumerate()
+        .map(|(position, qubit)| (*qubit, position))
+        .collect();
+    let mut target_positions: Vec<usize> = Vec::with_capacity(qubits.len());
+    // Qubit already moved to each target position, used to detect permutations that are not bijective
+    let mut occupied: HashMap<usize, usize> = HashMap::new();
+    for qubit in qubits.iter() {
+        let target = permutation.get(qubit).unwrap_or(qubit);
+        let position = *positions
+            .get(target)
+            .ok_or(RoqoqoError::QubitMappingError { qubit: *target })?;
+        if let Some(first) = occupied.insert(position, *qubit) {
+            return Err(RoqoqoError::QubitMappingError { qubit: first });
+        }
+        target_positions.push(position);
+    }
+    let dimension = unitary.nrows();
+    let mut permuted: Array2<Complex64> = Array2::zeros((dimension, dimension));
+    for row in 0..dimension {
+        let new_row: usize = target_positions
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| row & (1 << position) != 0)
+            .map(|(_, target)| 1 << target)
+            .sum();
+        permuted.row_mut(new_row).assign(&unitary.row(row));
+    }
+    Ok(permuted)
+}
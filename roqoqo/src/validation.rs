@@ -0,0 +1,542 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Static validation of quantum circuits.
+//!
+//! [validate_circuit] checks a [Circuit] for mistakes that otherwise only surface when the circuit
+//! is run on a backend, such as measurements into undefined registers, and for suspicious but valid
+//! constructs, such as unused definitions.
+
+use crate::dag::register_access;
+use crate::operations::{Define, InvolveQubits, InvolvedQubits, Operate, Operation, Substitute};
+use crate::Circuit;
+use qoqo_calculator::CalculatorFloat;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Severity of a [Diagnostic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// The circuit is valid but probably does not do what was intended.
+    Warning,
+    /// The circuit will fail or give wrong results on a backend.
+    Error,
+}
+
+/// Kind of problem found by [validate_circuit].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticKind {
+    /// An operation uses a register that is not defined in the circuit.
+    UndefinedRegister,
+    /// An operation uses a register of the wrong type.
+    WrongRegisterType,
+    /// An operation uses an index past the length of a register.
+    RegisterIndexOutOfRange,
+    /// A register or symbolic input is defined more than once.
+    DuplicateDefinition,
+    /// A register or symbolic input is defined but never used.
+    UnusedDefinition,
+    /// A [crate::operations::PragmaConditional] reads a register that is not written before it.
+    UnwrittenConditionRegister,
+    /// An operation acts on qubits after a [crate::operations::PragmaRepeatedMeasurement].
+    OperationAfterRepeatedMeasurement,
+    /// A state vector or density matrix does not have the dimension of a qubit register large enough for the circuit.
+    StateDimensionMismatch,
+    /// A [crate::operations::PragmaLoop] has a constant number of repetitions that is not a non-negative integer.
+    InvalidRepetitions,
+}
+
+/// A problem found in a circuit by [validate_circuit].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    /// Index of the operation in the circuit, counting definitions first as in [Circuit::get].
+    /// Problems inside the circuit of a [crate::operations::PragmaConditional] or a loop are reported at the conditional or loop.
+    pub index: usize,
+    /// Severity of the problem.
+    pub severity: Severity,
+    /// Kind of the problem.
+    pub kind: DiagnosticKind,
+    /// Explanation of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(
+            f,
+            "{} at operation {}: {}",
+            severity, self.index, self.message
+        )
+    }
+}
+
+/// Type of a classical register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RegisterType {
+    Bit,
+    Float,
+    Complex,
+    Usize,
+}
+
+impl fmt::Display for RegisterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RegisterType::Bit => "bit",
+            RegisterType::Float => "float",
+            RegisterType::Complex => "complex",
+            RegisterType::Usize => "usize",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Validates a circuit without running it.
+///
+/// The following problems are reported as errors:
+///
+/// * Measurements and readout pragmas writing to undefined registers, to registers of the wrong type
+///   or to indices past the length of the register.
+/// * Conditions on undefined registers or on indices past the length of the register.
+/// * [crate::operations::PragmaLoop] operations with a constant number of repetitions that is not a non-negative integer.
+/// * Classical operations reading or writing undefined registers, registers of the wrong type
+///   or indices past the length of the register.
+/// * Registers or symbolic inputs defined more than once.
+/// * Operations acting on qubits after a [crate::operations::PragmaRepeatedMeasurement].
+/// * [crate::operations::PragmaSetStateVector] and [crate::operations::PragmaSetDensityMatrix]
+///   with a dimension that is not a power of two or too small for the qubits of the circuit.
+///
+/// Conditions on registers that are not written before the [crate::operations::PragmaConditional]
+/// or [crate::operations::PragmaConditionalPattern], exit conditions of a [crate::operations::PragmaRepeatUntilSuccess]
+/// that are written neither before nor inside the loop and definitions that are never used are reported as warnings.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to validate.
+///
+/// # Returns
+///
+/// * `Vec<Diagnostic>` - The problems found, ordered by operation index. Empty if the circuit is valid.
+///
+/// # Example
+///
+/// ```
+/// use roqoqo::Circuit;
+/// use roqoqo::operations::{DefinitionBit, MeasureQubit};
+/// use roqoqo::validation::{validate_circuit, DiagnosticKind, Severity};
+///
+/// let mut circuit = Circuit::new();
+/// circuit += DefinitionBit::new("ro".to_string(), 1, true);
+/// circuit += MeasureQubit::new(0, "ro".to_string(), 1);
+///
+/// let diagnostics = validate_circuit(&circuit);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].index, 1);
+/// assert_eq!(diagnostics[0].severity, Severity::Error);
+/// assert_eq!(diagnostics[0].kind, DiagnosticKind::RegisterIndexOutOfRange);
+/// ```
+pub fn validate_circuit(circuit: &Circuit) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        registers: HashMap::new(),
+        diagnostics: Vec::new(),
+    };
+    let mut symbols: HashMap<String, usize> = HashMap::new();
+    for (index, definition) in circuit.definitions().iter().enumerate() {
+        let (name, register) = match definition {
+            Operation::DefinitionBit(def) => (def.name(), Some((RegisterType::Bit, *def.length()))),
+            Operation::DefinitionFloat(def) => {
+                (def.name(), Some((RegisterType::Float, *def.length())))
+            }
+            Operation::DefinitionComplex(def) => {
+                (def.name(), Some((RegisterType::Complex, *def.length())))
+            }
+            Operation::DefinitionUsize(def) => {
+                (def.name(), Some((RegisterType::Usize, *def.length())))
+            }
+            Operation::InputSymbolic(def) => (def.name(), None),
+            _ => continue,
+        };
+        let duplicate = match register {
+            Some((register_type, length)) => validator
+                .registers
+                .insert(name.clone(), (register_type, length, index))
+                .is_some(),
+            None => symbols.insert(name.clone(), index).is_some(),
+        };
+        if duplicate {
+            validator.push(
+                index,
+                Severity::Error,
+                DiagnosticKind::DuplicateDefinition,
+                format!("{} is defined more than once", name),
+            );
+        }
+    }
+
+    let offset = circuit.definitions().len();
+    let number_qubits = number_qubits(circuit);
+    let mut written: HashSet<String> = HashSet::new();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut repeated_measurement: Option<usize> = None;
+    for (position, operation) in circuit.operations().iter().enumerate() {
+        let index = offset + position;
+        if let Some(measurement_index) = repeated_measurement {
+            if operation.involved_qubits() != InvolvedQubits::None {
+                validator.push(
+                    index,
+                    Severity::Error,
+                    DiagnosticKind::OperationAfterRepeatedMeasurement,
+                    format!(
+                        "{} acts on qubits after the PragmaRepeatedMeasurement at operation {}",
+                        operation.hqslang(),
+                        measurement_index
+                    ),
+                );
+            }
+        }
+        validator.check_operation(operation, index, number_qubits, &written);
+        if let Operation::PragmaRepeatedMeasurement(_) = operation {
+            repeated_measurement.get_or_insert(index);
+        }
+        let (reads, writes) = register_access(operation);
+        used.extend(reads);
+        used.extend(writes.iter().cloned());
+        written.extend(writes);
+        used.extend(operation.free_parameters());
+    }
+
+    for (index, definition) in circuit.definitions().iter().enumerate() {
+        let name = match definition {
+            Operation::DefinitionBit(def) => def.name(),
+            Operation::DefinitionFloat(def) => def.name(),
+            Operation::DefinitionComplex(def) => def.name(),
+            Operation::DefinitionUsize(def) => def.name(),
+            Operation::InputSymbolic(def) => def.name(),
+            _ => continue,
+        };
+        if !used.contains(name) {
+            validator.push(
+                index,
+                Severity::Warning,
+                DiagnosticKind::UnusedDefinition,
+                format!("{} is defined but never used", name),
+            );
+        }
+    }
+    validator
+        .diagnostics
+        .sort_by_key(|diagnostic| diagnostic.index);
+    validator.diagnostics
+}
+
+/// Collects the diagnostics of a circuit.
+struct Validator {
+    /// Type, length and definition index of the registers by name.
+    registers: HashMap<String, (RegisterType, usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator {
+    fn push(&mut self, index: usize, severity: Severity, kind: DiagnosticKind, message: String) {
+        self.diagnostics.push(Diagnostic {
+            index,
+            severity,
+            kind,
+            message,
+        });
+    }
+
+    /// Checks a single operation, recursing into the circuits of conditional operations.
+    fn check_operation(
+        &mut self,
+        operation: &Operation,
+        index: usize,
+        number_qubits: usize,
+        written: &HashSet<String>,
+    ) {
+        match operation {
+            Operation::MeasureQubit(op) => self.check_register(
+                index,
+                op.readout(),
+                RegisterType::Bit,
+                &[*op.readout_index()],
+            ),
+            Operation::PragmaRepeatedMeasurement(op) => {
+                let indices: Vec<usize> = match op.qubit_mapping() {
+                    Some(mapping) => mapping.values().copied().collect(),
+                    None => number_qubits.checked_sub(1).into_iter().collect(),
+                };
+                self.check_register(index, op.readout(), RegisterType::Bit, &indices)
+            }
+            Operation::PragmaSetNumberOfMeasurements(op) => {
+                self.check_register(index, op.readout(), RegisterType::Bit, &[])
+            }
+            Operation::PragmaGetStateVector(op) => {
+                self.check_register(index, op.readout(), RegisterType::Complex, &[])
+            }
+            Operation::PragmaGetDensityMatrix(op) => {
+                self.check_register(index, op.readout(), RegisterType::Complex, &[])
+            }
+            Operation::PragmaGetOccupationProbability(op) => {
+                self.check_register(index, op.readout(), RegisterType::Float, &[])
+            }
+            Operation::PragmaGetPauliProduct(op) => {
+                self.check_register(index, op.readout(), RegisterType::Float, &[])
+            }
+            Operation::PragmaSetStateVector(op) => {
+                self.check_dimension(index, "state vector", op.statevector().len(), number_qubits)
+            }
+            Operation::PragmaSetDensityMatrix(op) => {
+                let (rows, columns) = op.density_matrix().dim();
+                if rows != columns {
+                    self.push(
+                        index,
+                        Severity::Error,
+                        DiagnosticKind::StateDimensionMismatch,
+                        format!("Density matrix is not square but {}x{}", rows, columns),
+                    );
+                } else {
+                    self.check_dimension(index, "density matrix", rows, number_qubits)
+                }
+            }
+            Operation::PragmaConditional(op) => {
+                let register = op.condition_register();
+                self.check_register(index, register, RegisterType::Bit, &[*op.condition_index()]);
+                if self.registers.contains_key(register) && !written.contains(register) {
+                    self.push(
+                        index,
+                        Severity::Warning,
+                        DiagnosticKind::UnwrittenConditionRegister,
+                        format!(
+                            "Condition register {} is not written before the PragmaConditional",
+                            register
+                        ),
+                    );
+                }
+                for inner in op.circuit().iter() {
+                    self.check_operation(inner, index, number_qubits, written);
+                }
+            }
+            Operation::PragmaConditionalPattern(op) => {
+                let register = op.condition_register();
+                let indices: Vec<usize> = op.condition().iter().map(|(index, _)| *index).collect();
+                self.check_register(index, register, RegisterType::Bit, &indices);
+                if self.registers.contains_key(register) && !written.contains(register) {
+                    self.push(
+                        index,
+                        Severity::Warning,
+                        DiagnosticKind::UnwrittenConditionRegister,
+                        format!(
+                            "Condition register {} is not written before the PragmaConditionalPattern",
+                            register
+                        ),
+                    );
+                }
+                for inner in op.circuit().iter() {
+                    self.check_operation(inner, index, number_qubits, written);
+                }
+            }
+            Operation::PragmaLoop(op) => {
+                if let CalculatorFloat::Float(repetitions) = op.repetitions() {
+                    if op.number_repetitions().is_err() {
+                        self.push(
+                            index,
+                            Severity::Error,
+                            DiagnosticKind::InvalidRepetitions,
+                            format!(
+                                "The number of repetitions {} is not a non-negative integer",
+                                repetitions
+                            ),
+                        );
+                    }
+                }
+                for inner in op.circuit().iter() {
+                    self.check_operation(inner, index, number_qubits, written);
+                }
+            }
+            Operation::PragmaRepeatUntilSuccess(op) => {
+                let register = op.condition_register();
+                self.check_register(index, register, RegisterType::Bit, &[*op.condition_index()]);
+                let written_inside = op
+                    .circuit()
+                    .iter()
+                    .any(|inner| register_access(inner).1.contains(register));
+                if self.registers.contains_key(register)
+                    && !written.contains(register)
+                    && !written_inside
+                {
+                    self.push(
+                        index,
+                        Severity::Warning,
+                        DiagnosticKind::UnwrittenConditionRegister,
+                        format!(
+                            "Condition register {} is not written before or inside the PragmaRepeatUntilSuccess",
+                            register
+                        ),
+                    );
+                }
+                for inner in op.circuit().iter() {
+                    self.check_operation(inner, index, number_qubits, written);
+                }
+            }
+            Operation::ClassicalNot(op) => {
+                self.check_register(index, op.register(), RegisterType::Bit, &[*op.index()]);
+                self.check_register(
+                    index,
+                    op.output_register(),
+                    RegisterType::Bit,
+                    &[*op.output_index()],
+                );
+            }
+            Operation::ClassicalCopy(op) => {
+                self.check_register(index, op.register(), RegisterType::Bit, &[*op.index()]);
+                self.check_register(
+                    index,
+                    op.output_register(),
+                    RegisterType::Bit,
+                    &[*op.output_index()],
+                );
+            }
+            Operation::ClassicalXor(op) => {
+                self.check_register(index, op.register(), RegisterType::Bit, op.indices());
+                self.check_register(
+                    index,
+                    op.output_register(),
+                    RegisterType::Bit,
+                    &[*op.output_index()],
+                );
+            }
+            Operation::ClassicalAnd(op) => {
+                self.check_register(index, op.register(), RegisterType::Bit, op.indices());
+                self.check_register(
+                    index,
+                    op.output_register(),
+                    RegisterType::Bit,
+                    &[*op.output_index()],
+                );
+            }
+            Operation::ClassicalOr(op) => {
+                self.check_register(index, op.register(), RegisterType::Bit, op.indices());
+                self.check_register(
+                    index,
+                    op.output_register(),
+                    RegisterType::Bit,
+                    &[*op.output_index()],
+                );
+            }
+            Operation::ClassicalGreaterThan(op) => {
+                self.check_register(index, op.register(), RegisterType::Float, &[*op.index()]);
+                self.check_register(
+                    index,
+                    op.output_register(),
+                    RegisterType::Bit,
+                    &[*op.output_index()],
+                );
+            }
+            Operation::ClassicalLessThan(op) => {
+                self.check_register(index, op.register(), RegisterType::Float, &[*op.index()]);
+                self.check_register(
+                    index,
+                    op.output_register(),
+                    RegisterType::Bit,
+                    &[*op.output_index()],
+                );
+            }
+            _ => (),
+        }
+    }
+
+    /// Checks that a register is defined with the expected type and is long enough for the indices.
+    fn check_register(
+        &mut self,
+        index: usize,
+        name: &str,
+        expected: RegisterType,
+        indices: &[usize],
+    ) {
+        let (register_type, length) = match self.registers.get(name) {
+            Some((register_type, length, _)) => (*register_type, *length),
+            None => {
+                return self.push(
+                    index,
+                    Severity::Error,
+                    DiagnosticKind::UndefinedRegister,
+                    format!("Register {} is not defined", name),
+                )
+            }
+        };
+        if register_type != expected {
+            return self.push(
+                index,
+                Severity::Error,
+                DiagnosticKind::WrongRegisterType,
+                format!(
+                    "Register {} is a {} register but a {} register is required",
+                    name, register_type, expected
+                ),
+            );
+        }
+        if let Some(register_index) = indices.iter().max().filter(|max| **max >= length) {
+            self.push(
+                index,
+                Severity::Error,
+                DiagnosticKind::RegisterIndexOutOfRange,
+                format!(
+                    "Index {} is out of range of register {} with length {}",
+                    register_index, name, length
+                ),
+            );
+        }
+    }
+
+    /// Checks that the dimension of a state is a power of two large enough for the qubits of the circuit.
+    fn check_dimension(
+        &mut self,
+        index: usize,
+        state: &str,
+        dimension: usize,
+        number_qubits: usize,
+    ) {
+        let minimum_dimension = 1_usize.checked_shl(number_qubits as u32).unwrap_or(0);
+        if !dimension.is_power_of_two() || dimension < minimum_dimension {
+            self.push(
+                index,
+                Severity::Error,
+                DiagnosticKind::StateDimensionMismatch,
+                format!(
+                    "The {} has dimension {} but the circuit acts on {} qubits requiring a power of two of at least {}",
+                    state, dimension, number_qubits, minimum_dimension
+                ),
+            );
+        }
+    }
+}
+
+/// Returns the number of qubits the circuit explicitly acts on, one more than the largest qubit index.
+fn number_qubits(circuit: &Circuit) -> usize {
+    circuit
+        .operations()
+        .iter()
+        .filter_map(|operation| match operation.involved_qubits() {
+            InvolvedQubits::Set(qubits) => qubits.iter().max().map(|qubit| qubit + 1),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
@@ -11,7 +11,7 @@
 // limitations under the License.
 use qoqo_calculator::{Calculator, CalculatorFloat};
 use roqoqo::operations::*;
-use roqoqo::{AsVec, Circuit};
+use roqoqo::{AsVec, Circuit, RoqoqoError};
 use std::collections::{HashMap, HashSet};
 #[cfg(feature = "overrotate")]
 use std::convert::TryInto;
@@ -182,6 +182,110 @@ fn substitute_params_input_symbolic() {
     assert_eq!(result, circuit)
 }
 
+/// Test that InputSymbolic values are overridden by values set in the calculator
+#[test]
+fn substitute_params_input_symbolic_default() {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(InputSymbolic::new("test".to_string(), 0.5));
+    circuit.add_operation(RotateX::new(0, CalculatorFloat::from(1.5)));
+
+    let mut circuit_test = Circuit::new();
+    circuit_test.add_operation(InputSymbolic::new("test".to_string(), 0.5));
+    circuit_test.add_operation(RotateX::new(0, CalculatorFloat::from("test")));
+
+    let mut substitution_dict: Calculator = Calculator::new();
+    substitution_dict.set_variable("test", 1.5);
+    let result = circuit_test
+        .substitute_parameters(&mut substitution_dict)
+        .unwrap();
+    assert_eq!(result, circuit)
+}
+
+/// Test free_parameters and default_parameters functions
+#[test]
+fn free_and_default_parameters() {
+    let mut inner = Circuit::new();
+    inner += RotateY::new(0, "gamma".into());
+    let mut circuit = Circuit::new();
+    circuit += InputSymbolic::new("delta".to_string(), 0.5);
+    circuit += RotateX::new(0, "2 * alpha + sin(beta)".into());
+    circuit += RotateZ::new(0, "delta".into());
+    circuit += RotateZ::new(1, 0.2.into());
+    circuit += PragmaConditional::new("ro".to_string(), 0, inner);
+
+    let free: HashSet<String> = ["alpha", "beta", "gamma", "delta"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+    assert_eq!(circuit.free_parameters(), free);
+    let mut defaults: HashMap<String, f64> = HashMap::new();
+    defaults.insert("delta".to_string(), 0.5);
+    assert_eq!(circuit.default_parameters(), defaults);
+    assert!(Circuit::new().free_parameters().is_empty());
+}
+
+/// Test that free_parameters does not depend on the values the expressions can be evaluated for
+#[test]
+fn free_parameters_singular_expressions() {
+    let mut inner = Circuit::new();
+    inner += RotateY::new(0, "2e-3 * c + d # e".into());
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "1/(a-1) + b".into());
+    circuit += RotateZ::new(0, "sin(a) + sqrt(b * b)".into());
+    circuit += PragmaConditional::new("Str(\"f\")".to_string(), 0, inner);
+
+    let free: HashSet<String> = ["a", "b", "c", "d"].iter().map(|x| x.to_string()).collect();
+    assert_eq!(circuit.free_parameters(), free);
+
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("a".to_string(), 2.0);
+    parameters.insert("b".to_string(), -2.0);
+    parameters.insert("c".to_string(), 1000.0);
+    parameters.insert("d".to_string(), 0.5);
+    let bound = circuit.bind_parameters(&parameters).unwrap();
+    assert!(bound.free_parameters().is_empty());
+    assert_eq!(bound[0], RotateX::new(0, (-1.0).into()).into());
+    assert_eq!(
+        bound[1],
+        RotateZ::new(0, (2.0_f64.sin() + 2.0).into()).into()
+    );
+}
+
+/// Test bind_parameters function
+#[test]
+fn bind_parameters() {
+    let mut circuit = Circuit::new();
+    circuit += InputSymbolic::new("delta".to_string(), 0.5);
+    circuit += RotateX::new(0, "alpha".into());
+    circuit += RotateZ::new(0, "delta".into());
+
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("alpha".to_string(), 1.0);
+    let mut expected = Circuit::new();
+    expected += InputSymbolic::new("delta".to_string(), 0.5);
+    expected += RotateX::new(0, 1.0.into());
+    expected += RotateZ::new(0, 0.5.into());
+    assert_eq!(circuit.bind_parameters(&parameters).unwrap(), expected);
+
+    parameters.insert("delta".to_string(), 2.0);
+    let mut expected = Circuit::new();
+    expected += InputSymbolic::new("delta".to_string(), 0.5);
+    expected += RotateX::new(0, 1.0.into());
+    expected += RotateZ::new(0, 2.0.into());
+    assert_eq!(circuit.bind_parameters(&parameters).unwrap(), expected);
+
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("beta".to_string(), 1.0);
+    parameters.insert("gamma".to_string(), 1.0);
+    assert_eq!(
+        circuit.bind_parameters(&parameters),
+        Err(RoqoqoError::ParameterBindingError {
+            unbound: vec!["alpha".to_string()],
+            extra: vec!["beta".to_string(), "gamma".to_string()],
+        })
+    );
+}
+
 /// Test remap_qubits function
 #[test]
 fn remap_qbits() {
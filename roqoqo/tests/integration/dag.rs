@@ -0,0 +1,164 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations::*;
+use roqoqo::{Circuit, CircuitDag};
+
+fn example_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += Hadamard::new(0); // 0
+    circuit += PauliX::new(1); // 1
+    circuit += CNOT::new(0, 1); // 2
+    circuit += RotateZ::new(2, 0.1.into()); // 3
+    circuit += CNOT::new(1, 2); // 4
+    circuit += PauliZ::new(0); // 5
+    circuit
+}
+
+/// Test conversion from and to Circuit
+#[test]
+fn circuit_round_trip() {
+    let circuit = example_circuit();
+    let dag = CircuitDag::from(&circuit);
+    assert_eq!(dag.len(), 6);
+    assert!(!dag.is_empty());
+    assert_eq!(dag.definitions(), circuit.definitions());
+    assert_eq!(dag.operations(), circuit.operations());
+    assert_eq!(dag.get(2), Some(&Operation::from(CNOT::new(0, 1))));
+    assert_eq!(dag.get(6), None);
+    assert_eq!(dag.to_circuit(), circuit);
+    assert_eq!(Circuit::from(CircuitDag::from(circuit.clone())), circuit);
+
+    let empty = CircuitDag::default();
+    assert!(empty.is_empty());
+    assert_eq!(empty.len(), 0);
+
+    let mut definitions_only = Circuit::new();
+    definitions_only += DefinitionBit::new("ro".to_string(), 1, true);
+    let definitions_only = CircuitDag::from(&definitions_only);
+    assert_eq!(definitions_only.len(), 0);
+    assert!(definitions_only.is_empty());
+    assert_eq!(empty, CircuitDag::new());
+    assert!(empty.front_layer().is_empty());
+    assert!(empty.layers().is_empty());
+    assert!(empty.longest_path().is_empty());
+}
+
+/// Test edges created by shared qubits
+#[test]
+fn qubit_edges() {
+    let dag = CircuitDag::from(example_circuit());
+    assert!(dag.predecessors(0).is_empty());
+    assert_eq!(dag.predecessors(2), &[0, 1]);
+    assert_eq!(dag.predecessors(4), &[2, 3]);
+    assert_eq!(dag.predecessors(5), &[2]);
+    assert_eq!(dag.successors(0), &[2]);
+    assert_eq!(dag.successors(2), &[4, 5]);
+    assert!(dag.successors(4).is_empty());
+}
+
+/// Test front layer, back layer and topological layers
+#[test]
+fn layers() {
+    let dag = CircuitDag::from(example_circuit());
+    assert_eq!(dag.front_layer(), vec![0, 1, 3]);
+    assert_eq!(dag.back_layer(), vec![4, 5]);
+    assert_eq!(dag.layers(), vec![vec![0, 1, 3], vec![2], vec![4, 5]]);
+}
+
+/// Test longest path
+#[test]
+fn longest_path() {
+    let dag = CircuitDag::from(example_circuit());
+    assert_eq!(dag.longest_path(), vec![0, 2, 4]);
+
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0);
+    circuit += PauliX::new(1);
+    circuit += PauliX::new(1);
+    let dag = CircuitDag::from(circuit);
+    assert_eq!(dag.longest_path(), vec![1, 2]);
+}
+
+/// Test edges created by classical register access
+#[test]
+fn register_edges() {
+    let mut conditional_circuit = Circuit::new();
+    conditional_circuit += PauliX::new(1);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0); // 0
+    circuit += PragmaConditional::new("ro".to_string(), 0, conditional_circuit.clone()); // 1
+    circuit += PragmaConditional::new("ro".to_string(), 0, Circuit::new()); // 2
+    circuit += MeasureQubit::new(2, "ro".to_string(), 0); // 3
+    circuit += MeasureQubit::new(3, "other".to_string(), 0); // 4
+    let dag = CircuitDag::from(circuit);
+
+    assert_eq!(dag.predecessors(1), &[0]);
+    assert_eq!(dag.predecessors(2), &[0]);
+    // A write after reads depends on the previous write and all reads
+    assert_eq!(dag.predecessors(3), &[0, 1, 2]);
+    assert!(dag.predecessors(4).is_empty());
+    assert_eq!(dag.front_layer(), vec![0, 4]);
+}
+
+/// Test edges for operations acting on all qubits
+#[test]
+fn all_qubit_edges() {
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0); // 0
+    circuit += PauliX::new(1); // 1
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 10); // 2
+    circuit += PauliX::new(5); // 3
+    circuit += PragmaGlobalPhase::new(0.1.into()); // 4
+    let dag = CircuitDag::from(circuit);
+
+    assert_eq!(dag.predecessors(2), &[0, 1]);
+    assert_eq!(dag.predecessors(3), &[2]);
+    assert!(dag.predecessors(4).is_empty());
+    assert_eq!(dag.layers(), vec![vec![0, 1, 4], vec![2], vec![3]]);
+}
+
+/// Test adding operations to a CircuitDag
+#[test]
+fn add_operation() {
+    let mut dag = CircuitDag::new();
+    assert_eq!(
+        dag.add_operation(DefinitionFloat::new("ro".to_string(), 1, true)),
+        None
+    );
+    assert_eq!(dag.add_operation(PauliX::new(0)), Some(0));
+    assert_eq!(dag.add_operation(PauliY::new(0)), Some(1));
+    assert_eq!(dag.definitions().len(), 1);
+    assert_eq!(dag.predecessors(1), &[0]);
+}
+
+/// Test sub-DAG extraction
+#[test]
+fn sub_dag() {
+    let circuit = example_circuit();
+    let dag = CircuitDag::from(&circuit);
+    let sub_dag = dag.sub_dag(&[5, 0, 3, 4, 17]);
+    assert_eq!(sub_dag.definitions(), circuit.definitions());
+    assert_eq!(
+        sub_dag.operations(),
+        &vec![
+            Operation::from(Hadamard::new(0)),
+            Operation::from(RotateZ::new(2, 0.1.into())),
+            Operation::from(CNOT::new(1, 2)),
+            Operation::from(PauliZ::new(0)),
+        ]
+    );
+    assert_eq!(sub_dag.predecessors(2), &[1]);
+    assert_eq!(sub_dag.predecessors(3), &[0]);
+}
@@ -0,0 +1,178 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the text diagrams of circuits
+
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::formats::{circuit_to_text_diagram, DiagramCharset};
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use std::collections::HashMap;
+use test_case::test_case;
+
+/// Returns a circuit with gates, measurements and a conditional operation
+fn example_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += DefinitionBit::new("flag".to_string(), 1, false);
+    circuit += RotateX::new(0, CalculatorFloat::FRAC_PI_2);
+    circuit += RotateZ::new(1, "2 * theta + phi_1".into());
+    circuit += RotateY::new(2, 0.123456.into());
+    circuit += CNOT::new(2, 0);
+    circuit += ControlledPhaseShift::new(1, 2, (-3.0 * std::f64::consts::FRAC_PI_4).into());
+    circuit += MeasureQubit::new(0, "flag".to_string(), 0);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(1);
+    conditional += SWAP::new(1, 2);
+    circuit += PragmaConditional::new("flag".to_string(), 0, conditional);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 100);
+    circuit
+}
+
+/// Test the ASCII diagram of a Bell state preparation
+#[test]
+fn ascii_bell_state() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += Hadamard::new(0);
+    circuit += CNOT::new(0, 1);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    assert_eq!(
+        circuit_to_text_diagram(&circuit, DiagramCharset::Ascii, None),
+        "q0: -H--@--M-----
+q1: ----X--#--M--
+ro: =======0==1==
+"
+    );
+}
+
+/// Test the ASCII diagram of a loop and a repeat-until-success loop
+#[test]
+fn ascii_loops() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    let mut body = Circuit::new();
+    body += PauliX::new(0);
+    circuit += PragmaLoop::new(3.0.into(), body);
+    let mut attempt = Circuit::new();
+    attempt += Hadamard::new(1);
+    attempt += MeasureQubit::new(1, "ro".to_string(), 0);
+    circuit += PragmaRepeatUntilSuccess::new("ro".to_string(), 0, 4, attempt);
+    assert_eq!(
+        circuit_to_text_diagram(&circuit, DiagramCharset::Ascii, None),
+        "q0: -loop(3):X---
+q1: -rus(4):H M--
+ro: =====0=======
+"
+    );
+}
+
+/// Test the Unicode diagram with connectors, measurements and a conditional operation
+#[test]
+fn unicode_diagram() {
+    assert_eq!(
+        circuit_to_text_diagram(&example_circuit(), DiagramCharset::Unicode, None),
+        "  q0: ────Rx(π/2)─────⊕────────────M───────────M────
+  q1: ─Rz(2*θ+phi_1)──┼─────●──────╫──if:X ×───M────
+  q2: ───Ry(0.123)────●──P(-3π/4)──╫───if:×────M────
+  ro: ═════════════════════════════╬════╬═════×100══
+flag: ═════════════════════════════0════0═══════════
+"
+    );
+}
+
+/// Test the folding of the diagram at the maximal width
+#[test]
+fn folded_diagram() {
+    let diagram = circuit_to_text_diagram(&example_circuit(), DiagramCharset::Ascii, Some(40));
+    assert_eq!(
+        diagram,
+        "  q0: -----Rx(pi/2)------X-------------
+  q1: -Rz(2*theta+p...)--+------@------
+  q2: ----Ry(0.123)------@--P(-3pi/4)--
+  ro: =================================
+flag: =================================
+
+  q0: -M-----------M----
+  q1: -#--if:X x---M----
+  q2: -#---if:x----M----
+  ro: =#====#=====x100==
+flag: =0====0===========
+"
+    );
+    for line in diagram.lines() {
+        assert!(line.chars().count() <= 40);
+    }
+
+    let mut circuit = Circuit::new();
+    for _ in 0..30 {
+        circuit += Hadamard::new(0);
+    }
+    let diagram = circuit_to_text_diagram(&circuit, DiagramCharset::Unicode, Some(20));
+    assert_eq!(diagram.lines().filter(|line| line.is_empty()).count(), 5);
+    for line in diagram.lines() {
+        assert!(line.chars().count() <= 20);
+    }
+}
+
+/// Test the compact formatting of parameters
+#[test_case(CalculatorFloat::PI, "Rx(π)"; "pi")]
+#[test_case(CalculatorFloat::from(-std::f64::consts::FRAC_PI_2), "Rx(-π/2)"; "negative fraction")]
+#[test_case(CalculatorFloat::from(4.0 * std::f64::consts::PI / 3.0), "Rx(4π/3)"; "multiple")]
+#[test_case(CalculatorFloat::from(0.0), "Rx(0)"; "zero")]
+#[test_case(CalculatorFloat::from(0.5), "Rx(0.5)"; "decimal")]
+#[test_case(CalculatorFloat::from(1e-5), "Rx(1.0e-5)"; "small")]
+#[test_case(CalculatorFloat::from("alpha * beta"), "Rx(α*β)"; "greek letters")]
+#[test_case(CalculatorFloat::from("very_long_parameter"), "Rx(very_long_p…)"; "truncated")]
+fn parameter_formatting(theta: CalculatorFloat, label: &str) {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, theta);
+    let diagram = circuit_to_text_diagram(&circuit, DiagramCharset::Unicode, None);
+    assert_eq!(diagram, format!("q0: ─{}──\n", label));
+}
+
+/// Test pragmas drawn on several qubits and classical registers
+#[test]
+fn pragma_operations() {
+    let mut circuit = Circuit::new();
+    circuit += PragmaSleep::new(vec![2, 0], 1.0.into());
+    circuit += PragmaGlobalPhase::new(CalculatorFloat::FRAC_PI_4);
+    let mut paulis = HashMap::new();
+    paulis.insert(1, 3);
+    paulis.insert(0, 1);
+    circuit += PragmaGetPauliProduct::new(paulis, "expectation".to_string(), Circuit::new());
+    circuit += PragmaActiveReset::new(1);
+    assert_eq!(
+        circuit_to_text_diagram(&circuit, DiagramCharset::Ascii, None),
+        "         q0: -Sleep(1)--GlobalPhase(pi/4)--<X>-------
+         q1: ----+------GlobalPhase(pi/4)--<Z>--|0>--
+         q2: -Sleep(1)--GlobalPhase(pi/4)---#--------
+expectation: ===============================v========
+"
+    );
+}
+
+/// Test the diagrams of empty circuits
+#[test]
+fn empty_circuits() {
+    assert_eq!(
+        circuit_to_text_diagram(&Circuit::new(), DiagramCharset::Unicode, Some(10)),
+        ""
+    );
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("values".to_string(), 1, true);
+    assert_eq!(
+        circuit_to_text_diagram(&circuit, DiagramCharset::Ascii, None),
+        "values: =\n"
+    );
+}
@@ -0,0 +1,152 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the quantikz LaTeX and SVG drawings of circuits
+
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::formats::{circuit_to_quantikz, circuit_to_svg, DrawingOptions, GateLabels};
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use test_case::test_case;
+
+/// Returns a circuit with a measurement, a conditional operation and a noise pragma
+fn example_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, CalculatorFloat::FRAC_PI_2);
+    circuit += CNOT::new(1, 0);
+    circuit += PragmaDamping::new(1, 0.01.into(), 2.0.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 1);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(1);
+    circuit += PragmaConditional::new("ro".to_string(), 1, conditional);
+    circuit
+}
+
+/// Test the quantikz drawing with the default options
+#[test]
+fn quantikz_default() {
+    let latex = circuit_to_quantikz(&example_circuit(), &DrawingOptions::default());
+    assert_eq!(
+        latex,
+        "\\begin{quantikz}\n\
+         \\lstick{q_{0}} & \\gate{R_x(\\pi/2)} & \\targ{} \\vqw{1} & \\meter{} \\vcw{2} & \\qw & \\qw \\\\\n\
+         \\lstick{q_{1}} & \\qw & \\control{} & \\qw & \\gate{X} \\vcw{1} & \\qw \\\\\n\
+         \\lstick{\\mathrm{ro}} & \\cw & \\cw & \\push{1} \\cw & \\push{1} \\cw & \\cw\n\
+         \\end{quantikz}\n"
+    );
+}
+
+/// Test the quantikz drawing with hqslang labels, noise and without parameters
+#[test]
+fn quantikz_options() {
+    let options = DrawingOptions {
+        gate_labels: GateLabels::Hqslang,
+        show_parameters: false,
+        show_noise: true,
+    };
+    let latex = circuit_to_quantikz(&example_circuit(), &options);
+    assert!(latex.contains("\\gate{\\mathrm{RotateX}}"));
+    assert!(latex.contains("\\gate[style={dashed}]{\\mathrm{PragmaDamping}}"));
+    assert!(!latex.contains("\\pi"));
+
+    let options = DrawingOptions {
+        show_noise: true,
+        ..DrawingOptions::default()
+    };
+    let latex = circuit_to_quantikz(&example_circuit(), &options);
+    assert!(latex.contains("\\gate[style={dashed}]{\\mathrm{Damp}(0.01, 2)}"));
+}
+
+/// Test the formatting of parameters in quantikz labels
+#[test_case(CalculatorFloat::PI, "R_z(\\pi)"; "pi")]
+#[test_case((-3.0 * std::f64::consts::FRAC_PI_4).into(), "R_z(-3\\pi/4)"; "negative fraction")]
+#[test_case(0.25.into(), "R_z(0.25)"; "decimal")]
+#[test_case("2 * theta".into(), "R_z(2\\cdot \\theta)"; "greek")]
+#[test_case("angle_1 + x".into(), "R_z(\\mathrm{angle\\_1}+x)"; "names")]
+fn quantikz_parameters(theta: CalculatorFloat, label: &str) {
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, theta);
+    let latex = circuit_to_quantikz(&circuit, &DrawingOptions::default());
+    assert!(
+        latex.contains(&format!("\\gate{{{}}}", label)),
+        "{} does not contain {}",
+        latex,
+        label
+    );
+}
+
+/// Test multi-qubit boxes and decomposition blocks in quantikz
+#[test]
+fn quantikz_decomposition_block() {
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(2);
+    circuit += PragmaStartDecompositionBlock::new(vec![0, 1], std::collections::HashMap::new());
+    circuit += ISwap::new(0, 1);
+    circuit += Hadamard::new(1);
+    circuit += PragmaStopDecompositionBlock::new(vec![0, 1]);
+    circuit += ISwap::new(0, 2);
+    let latex = circuit_to_quantikz(&circuit, &DrawingOptions::default());
+    assert_eq!(
+        latex,
+        "\\begin{quantikz}\n\
+         \\lstick{q_{0}} & \\gate[wires=2]{i\\mathrm{SWAP}} \\gategroup[wires=2,steps=2,style={dashed,rounded corners}]{} & \\qw & \\gate{i\\mathrm{SWAP}} \\vqw{2} & \\qw \\\\\n\
+         \\lstick{q_{1}} & \\qw & \\gate{H} & \\qw & \\qw \\\\\n\
+         \\lstick{q_{2}} & \\gate{X} & \\qw & \\gate{i\\mathrm{SWAP}} & \\qw\n\
+         \\end{quantikz}\n"
+    );
+}
+
+/// Test the SVG drawing
+#[test]
+fn svg_drawing() {
+    let svg = circuit_to_svg(&example_circuit(), &DrawingOptions::default());
+    assert!(svg.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg "));
+    assert!(svg.ends_with("</svg>\n"));
+    assert!(svg.contains(">Rx(π/2)</text>"));
+    assert!(svg.contains(">X</text>"));
+    assert!(svg.contains(">ro</text>"));
+    assert!(!svg.contains("Damp"));
+    assert!(!svg.contains("stroke-dasharray"));
+
+    let options = DrawingOptions {
+        gate_labels: GateLabels::Hqslang,
+        show_parameters: false,
+        show_noise: true,
+    };
+    let svg = circuit_to_svg(&example_circuit(), &options);
+    assert!(svg.contains(">RotateX</text>"));
+    assert!(svg.contains(">PragmaDamping</text>"));
+    assert!(svg.contains("stroke-dasharray"));
+}
+
+/// Test that the SVG drawing escapes register names and draws decomposition blocks
+#[test]
+fn svg_escaping_and_blocks() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("a<b&c".to_string(), 1, true);
+    circuit += PragmaStartDecompositionBlock::new(vec![0], std::collections::HashMap::new());
+    circuit += MeasureQubit::new(0, "a<b&c".to_string(), 0);
+    circuit += PragmaStopDecompositionBlock::new(vec![0]);
+    let svg = circuit_to_svg(&circuit, &DrawingOptions::default());
+    assert!(svg.contains(">a&lt;b&amp;c</text>"));
+    assert!(svg.contains("stroke=\"gray\" stroke-dasharray"));
+}
+
+/// Test drawing empty circuits
+#[test]
+fn empty_circuits() {
+    let latex = circuit_to_quantikz(&Circuit::new(), &DrawingOptions::default());
+    assert_eq!(latex, "\\begin{quantikz}\n\\end{quantikz}\n");
+    let svg = circuit_to_svg(&Circuit::new(), &DrawingOptions::default());
+    assert!(svg.ends_with("</svg>\n"));
+}
@@ -0,0 +1,263 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the hqslang text format
+
+use ndarray::{array, Array1, Array2};
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::formats::{circuit_to_hqslang, hqslang_to_circuit, operation_to_hqslang};
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+use std::collections::HashMap;
+use test_case::test_case;
+
+/// Returns a small circuit used in the operations with nested circuits
+fn nested_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("inner".to_string(), 1, false);
+    circuit += Hadamard::new(0);
+    circuit += RotateZ::new(1, "2 * theta".into());
+    circuit
+}
+
+/// Returns a qubit mapping used in the operations with mappings
+fn mapping() -> HashMap<usize, usize> {
+    let mut mapping = HashMap::new();
+    mapping.insert(2, 0);
+    mapping.insert(0, 1);
+    mapping.insert(1, 2);
+    mapping
+}
+
+/// Test that every operation is parsed back into the same operation
+#[test_case(Operation::from(SingleQubitGate::new(0, 0.6.into(), 0.0.into(), 0.0.into(), 0.8.into(), "phi".into())); "SingleQubitGate")]
+#[test_case(Operation::from(RotateZ::new(0, 0.3.into())); "RotateZ")]
+#[test_case(Operation::from(RotateX::new(1, "theta".into())); "RotateX")]
+#[test_case(Operation::from(RotateY::new(2, (-1e-12).into())); "RotateY")]
+#[test_case(Operation::from(PauliX::new(0)); "PauliX")]
+#[test_case(Operation::from(PauliY::new(0)); "PauliY")]
+#[test_case(Operation::from(PauliZ::new(0)); "PauliZ")]
+#[test_case(Operation::from(SqrtPauliX::new(0)); "SqrtPauliX")]
+#[test_case(Operation::from(InvSqrtPauliX::new(0)); "InvSqrtPauliX")]
+#[test_case(Operation::from(Hadamard::new(0)); "Hadamard")]
+#[test_case(Operation::from(SGate::new(0)); "SGate")]
+#[test_case(Operation::from(TGate::new(0)); "TGate")]
+#[test_case(Operation::from(RotateAroundSphericalAxis::new(0, 0.5.into(), 0.3.into(), 1.2.into())); "RotateAroundSphericalAxis")]
+#[test_case(Operation::from(PragmaSetNumberOfMeasurements::new(100, "ro".to_string())); "PragmaSetNumberOfMeasurements")]
+#[test_case(Operation::from(PragmaSetStateVector::new(array![Complex64::new(0.6, 0.0), Complex64::new(0.0, -0.8)])); "PragmaSetStateVector")]
+#[test_case(Operation::from(PragmaSetDensityMatrix::new(array![[Complex64::new(0.5, 0.0), Complex64::new(0.0, 0.5)], [Complex64::new(0.0, -0.5), Complex64::new(0.5, 0.0)]])); "PragmaSetDensityMatrix")]
+#[test_case(Operation::from(PragmaRepeatGate::new(3)); "PragmaRepeatGate")]
+#[test_case(Operation::from(PragmaOverrotation::new("RotateX".to_string(), vec![0, 1], 0.03, 0.001)); "PragmaOverrotation")]
+#[test_case(Operation::from(PragmaBoostNoise::new(1.5.into())); "PragmaBoostNoise")]
+#[test_case(Operation::from(PragmaStopParallelBlock::new(vec![], 0.0.into())); "PragmaStopParallelBlock")]
+#[test_case(Operation::from(PragmaGlobalPhase::new(CalculatorFloat::PI)); "PragmaGlobalPhase")]
+#[test_case(Operation::from(PragmaSleep::new(vec![0, 3], "time".into())); "PragmaSleep")]
+#[test_case(Operation::from(PragmaActiveReset::new(4)); "PragmaActiveReset")]
+#[test_case(Operation::from(PragmaStartDecompositionBlock::new(vec![0, 1, 2], mapping())); "PragmaStartDecompositionBlock")]
+#[test_case(Operation::from(PragmaStopDecompositionBlock::new(vec![0, 1, 2])); "PragmaStopDecompositionBlock")]
+#[test_case(Operation::from(PragmaDamping::new(0, 0.005.into(), 0.02.into())); "PragmaDamping")]
+#[test_case(Operation::from(PragmaDepolarising::new(0, 0.005.into(), 0.02.into())); "PragmaDepolarising")]
+#[test_case(Operation::from(PragmaDephasing::new(0, 0.005.into(), 0.02.into())); "PragmaDephasing")]
+#[test_case(Operation::from(PragmaRandomNoise::new(0, 0.005.into(), 0.02.into(), 0.01.into())); "PragmaRandomNoise")]
+#[test_case(Operation::from(PragmaGeneralNoise::new(0, 0.005.into(), 0.02.into(), Array2::from_diag(&array![Complex64::new(1.0, 0.0), Complex64::new(1.0, 0.0), Complex64::new(1.0, 0.0)]))); "PragmaGeneralNoise")]
+#[test_case(Operation::from(PragmaConditional::new("flags".to_string(), 1, nested_circuit())); "PragmaConditional")]
+#[test_case(Operation::from(PragmaConditionalPattern::new("flags".to_string(), vec![(0, true), (2, false)], nested_circuit())); "PragmaConditionalPattern")]
+#[test_case(Operation::from(PragmaLoop::new("repetitions".into(), nested_circuit())); "PragmaLoop")]
+#[test_case(Operation::from(PragmaRepeatUntilSuccess::new("flags".to_string(), 1, 5, nested_circuit())); "PragmaRepeatUntilSuccess")]
+#[test_case(Operation::from(CNOT::new(1, 0)); "CNOT")]
+#[test_case(Operation::from(SWAP::new(1, 0)); "SWAP")]
+#[test_case(Operation::from(ISwap::new(1, 0)); "ISwap")]
+#[test_case(Operation::from(FSwap::new(1, 0)); "FSwap")]
+#[test_case(Operation::from(SqrtISwap::new(1, 0)); "SqrtISwap")]
+#[test_case(Operation::from(InvSqrtISwap::new(1, 0)); "InvSqrtISwap")]
+#[test_case(Operation::from(XY::new(1, 0, 0.7.into())); "XY")]
+#[test_case(Operation::from(ControlledPhaseShift::new(1, 0, 0.7.into())); "ControlledPhaseShift")]
+#[test_case(Operation::from(ControlledPauliY::new(1, 0)); "ControlledPauliY")]
+#[test_case(Operation::from(ControlledPauliZ::new(1, 0)); "ControlledPauliZ")]
+#[test_case(Operation::from(MolmerSorensenXX::new(1, 0)); "MolmerSorensenXX")]
+#[test_case(Operation::from(VariableMSXX::new(1, 0, 0.7.into())); "VariableMSXX")]
+#[test_case(Operation::from(GivensRotation::new(1, 0, 0.7.into(), "phi".into())); "GivensRotation")]
+#[test_case(Operation::from(GivensRotationLittleEndian::new(1, 0, 0.7.into(), 0.2.into())); "GivensRotationLittleEndian")]
+#[test_case(Operation::from(Qsim::new(1, 0, 1.0.into(), 1.0.into(), (-1.0).into())); "Qsim")]
+#[test_case(Operation::from(Fsim::new(1, 0, 1.0.into(), 2.0.into(), (-1.0).into())); "Fsim")]
+#[test_case(Operation::from(SpinInteraction::new(1, 0, 1.0.into(), 2.0.into(), (-1.0).into())); "SpinInteraction")]
+#[test_case(Operation::from(Bogoliubov::new(1, 0, 1.0.into(), (-1.0).into())); "Bogoliubov")]
+#[test_case(Operation::from(PMInteraction::new(1, 0, 0.7.into())); "PMInteraction")]
+#[test_case(Operation::from(ComplexPMInteraction::new(1, 0, 1.0.into(), (-1.0).into())); "ComplexPMInteraction")]
+#[test_case(Operation::from(PhaseShiftedControlledZ::new(1, 0, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ")]
+#[test_case(Operation::from(TwoQubitGate::from_unitary(1, 0, CNOT::new(0, 1).unitary_matrix().unwrap()).unwrap()); "TwoQubitGate")]
+#[test_case(Operation::from(MultiQubitMS::new(vec![0, 2, 1], "theta".into())); "MultiQubitMS")]
+#[test_case(Operation::from(MultiQubitGate::new(vec![1, 0], array![[Complex64::new(0.0, 1.0), Complex64::new(0.0, 0.0)], [Complex64::new(0.0, 0.0), Complex64::new(-0.6, 0.8)]])); "MultiQubitGate")]
+#[test_case(Operation::from(Toffoli::new(vec![0, 1, 2])); "Toffoli")]
+#[test_case(Operation::from(ControlledControlledPauliZ::new(vec![2, 0, 1])); "ControlledControlledPauliZ")]
+#[test_case(Operation::from(ControlledControlledPhaseShift::new(vec![0, 1, 2], CalculatorFloat::from("theta"))); "ControlledControlledPhaseShift")]
+#[test_case(Operation::from(MultiControlledGate::new(vec![0, 1, 2, 3], CalculatorFloat::from(0.1), CalculatorFloat::from("z1"), CalculatorFloat::from(-0.3), CalculatorFloat::from(0.4))); "MultiControlledGate")]
+#[test_case(Operation::from(PauliProductRotation::new(vec![2, 0, 1], vec![1, 2, 3], CalculatorFloat::from("theta"))); "PauliProductRotation")]
+#[test_case(Operation::from(ClassicalNot::new("ro".to_string(), 1, "flags".to_string(), 0)); "ClassicalNot")]
+#[test_case(Operation::from(ClassicalCopy::new("ro".to_string(), 1, "flags".to_string(), 0)); "ClassicalCopy")]
+#[test_case(Operation::from(ClassicalXor::new("ro".to_string(), vec![0, 1, 3], "flags".to_string(), 2)); "ClassicalXor")]
+#[test_case(Operation::from(ClassicalAnd::new("ro".to_string(), vec![0, 1], "flags".to_string(), 2)); "ClassicalAnd")]
+#[test_case(Operation::from(ClassicalOr::new("ro".to_string(), vec![], "flags".to_string(), 2)); "ClassicalOr")]
+#[test_case(Operation::from(ClassicalGreaterThan::new("x".to_string(), 1, CalculatorFloat::from(0.5), "flags".to_string(), 0)); "ClassicalGreaterThan")]
+#[test_case(Operation::from(ClassicalLessThan::new("x".to_string(), 1, CalculatorFloat::from("threshold"), "flags".to_string(), 0)); "ClassicalLessThan")]
+#[test_case(Operation::from(MeasureQubit::new(0, "ro".to_string(), 1)); "MeasureQubit")]
+#[test_case(Operation::from(PragmaGetStateVector::new("ro".to_string(), None)); "PragmaGetStateVector")]
+#[test_case(Operation::from(PragmaGetDensityMatrix::new("ro".to_string(), Some(nested_circuit()))); "PragmaGetDensityMatrix")]
+#[test_case(Operation::from(PragmaGetOccupationProbability::new("ro".to_string(), Some(Circuit::new()))); "PragmaGetOccupationProbability")]
+#[test_case(Operation::from(PragmaGetPauliProduct::new(mapping(), "ro".to_string(), nested_circuit())); "PragmaGetPauliProduct")]
+#[test_case(Operation::from(PragmaRepeatedMeasurement::new("ro".to_string(), Some(mapping()), 100)); "PragmaRepeatedMeasurement")]
+#[test_case(Operation::from(DefinitionFloat::new("values".to_string(), 2, true)); "DefinitionFloat")]
+#[test_case(Operation::from(DefinitionComplex::new("complex values".to_string(), 2, false)); "DefinitionComplex")]
+#[test_case(Operation::from(DefinitionUsize::new("None".to_string(), 2, true)); "DefinitionUsize")]
+#[test_case(Operation::from(DefinitionBit::new("ro".to_string(), 2, true)); "DefinitionBit")]
+#[test_case(Operation::from(InputSymbolic::new("theta".to_string(), 1.0 / 3.0)); "InputSymbolic")]
+fn round_trip(operation: Operation) {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(operation.clone());
+    let text = circuit_to_hqslang(&circuit);
+    assert!(text.starts_with(&format!("{} ", operation.hqslang())));
+    assert_eq!(hqslang_to_circuit(&text).unwrap(), circuit);
+
+    let mut conditional = Circuit::new();
+    conditional.add_operation(PragmaConditional::new(
+        "flag".to_string(),
+        0,
+        circuit.clone(),
+    ));
+    assert_eq!(
+        hqslang_to_circuit(&circuit_to_hqslang(&conditional)).unwrap(),
+        conditional
+    );
+}
+
+/// Test the written text of operations with the different kinds of arguments
+#[test]
+fn write_operations() {
+    assert_eq!(
+        operation_to_hqslang(&Operation::from(RotateX::new(0, "2 * \"x\"".into()))),
+        "RotateX 0 \"2 * \\\"x\\\"\""
+    );
+    assert_eq!(
+        operation_to_hqslang(&Operation::from(PragmaRepeatedMeasurement::new(
+            "ro".to_string(),
+            Some(mapping()),
+            10
+        ))),
+        "PragmaRepeatedMeasurement ro {0: 1, 1: 2, 2: 0} 10"
+    );
+    assert_eq!(
+        operation_to_hqslang(&Operation::from(PragmaGetStateVector::new(
+            "ro".to_string(),
+            None
+        ))),
+        "PragmaGetStateVector ro None"
+    );
+    assert_eq!(
+        operation_to_hqslang(&Operation::from(PragmaSetStateVector::new(array![
+            Complex64::new(1.0, 0.0),
+            Complex64::new(0.0, -0.5)
+        ]))),
+        "PragmaSetStateVector [(1.0, 0.0), (0.0, -0.5)]"
+    );
+    assert_eq!(
+        operation_to_hqslang(&Operation::from(DefinitionFloat::new(
+            "my values".to_string(),
+            1,
+            false
+        ))),
+        "DefinitionFloat \"my values\" 1 false"
+    );
+
+    let mut inner = Circuit::new();
+    inner += PauliX::new(0);
+    let mut outer = Circuit::new();
+    outer += PragmaConditional::new("a".to_string(), 0, inner);
+    outer += PragmaGetOccupationProbability::new("ro".to_string(), Some(Circuit::new()));
+    let mut circuit = Circuit::new();
+    circuit += PragmaConditional::new("b".to_string(), 1, outer);
+    assert_eq!(
+        circuit_to_hqslang(&circuit),
+        "PragmaConditional b 1 {
+    PragmaConditional a 0 {
+        PauliX 0
+    }
+    PragmaGetOccupationProbability ro {
+    }
+}
+"
+    );
+}
+
+/// Test parsing of hand-written text with comments, empty lines and multi-line matrices
+#[test]
+fn parse_hand_written() {
+    let source = "# Preparation
+DefinitionBit ro 2 true
+
+Hadamard 0   # comment after an operation
+RotateZ 1 -0.5e-1
+RotateX 1 \"theta + 1\"
+PragmaSetDensityMatrix [
+    [(1.0, 0), (0, 0)],
+    [(0, 0), (0, 0)],
+]
+PragmaConditional ro 0 {}
+PragmaRepeatedMeasurement ro None 10";
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("ro".to_string(), 2, true);
+    expected += Hadamard::new(0);
+    expected += RotateZ::new(1, (-0.05).into());
+    expected += RotateX::new(1, "theta + 1".into());
+    expected += PragmaSetDensityMatrix::new(array![
+        [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)],
+        [Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0)]
+    ]);
+    expected += PragmaConditional::new("ro".to_string(), 0, Circuit::new());
+    expected += PragmaRepeatedMeasurement::new("ro".to_string(), None, 10);
+    assert_eq!(hqslang_to_circuit(source).unwrap(), expected);
+
+    let vector: Array1<Complex64> = Array1::zeros(0);
+    let mut expected = Circuit::new();
+    expected += PragmaSetStateVector::new(vector);
+    assert_eq!(
+        hqslang_to_circuit("PragmaSetStateVector []").unwrap(),
+        expected
+    );
+}
+
+/// Test the position and message of parse errors
+#[test_case("Hadamard 0\nFoo 1\n", 2, 1, "Unknown operation 'Foo'"; "unknown operation")]
+#[test_case("CNOT 0\n", 1, 7, "Expected non-negative integer for argument `target` of CNOT but found end of line"; "missing argument")]
+#[test_case("RotateX 0 theta\n", 1, 11, "Expected number or quoted symbolic expression for argument `theta` of RotateX but found 'theta'"; "unquoted expression")]
+#[test_case("PauliX -1\n", 1, 8, "Expected non-negative integer for argument `qubit` of PauliX but found '-1'"; "negative qubit")]
+#[test_case("PauliX 0 1\n", 1, 10, "Expected end of line for the arguments of PauliX but found '1'"; "too many arguments")]
+#[test_case("DefinitionBit ro 1 yes\n", 1, 20, "Expected 'true' or 'false' for argument `is_output` of DefinitionBit but found 'yes'"; "invalid bool")]
+#[test_case("PragmaSleep [0 1] 1.0\n", 1, 16, "Expected ',' or ']' for argument `qubits` of PragmaSleep but found '1'"; "missing comma")]
+#[test_case("PragmaStartDecompositionBlock [0] {0: 1, 0: 2}\n", 1, 42, "Duplicate key 0 in mapping"; "duplicate key")]
+#[test_case("PragmaSetDensityMatrix [[(1, 0)], []]\n", 1, 24, "All rows of a matrix must have the same length"; "ragged matrix")]
+#[test_case("PragmaConditional ro 0 {\n    PauliX 0\n", 2, 13, "Missing '}' closing the circuit opened at line 1, column 24"; "unclosed circuit")]
+#[test_case("PauliX 0\n}\n", 2, 1, "Expected the hqslang name of an operation but found '}'"; "unopened circuit")]
+#[test_case("PragmaConditional ro 0 {\n    PauliX\n}\n", 2, 11, "Expected non-negative integer for argument `qubit` of PauliX but found end of line"; "nested error")]
+#[test_case("PragmaConditionalPattern ro [0] {\n}\n", 1, 30, "Expected pair '(index, value)' for argument `condition` of PragmaConditionalPattern but found '0'"; "unpaired condition")]
+#[test_case("MeasureQubit 0 \"ro 0\n", 1, 16, "Missing closing '\"' of string"; "unclosed string")]
+#[test_case("PauliX 0;\n", 1, 9, "Unexpected character ';'"; "invalid character")]
+fn parse_errors(source: &str, line: usize, column: usize, msg: &str) {
+    assert_eq!(
+        hqslang_to_circuit(source),
+        Err(RoqoqoError::HqslangParseError {
+            line,
+            column,
+            msg: msg.to_string()
+        })
+    );
+}
@@ -0,0 +1,281 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the OpenQASM 2.0 export
+
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::formats::{circuit_to_qasm, qelib1_decomposition, QasmStrictness};
+use roqoqo::operations::*;
+use roqoqo::unitary::circuits_equivalent;
+use roqoqo::{Circuit, RoqoqoError};
+use test_case::test_case;
+
+/// Test the export of a circuit using only qelib1 gates
+#[test]
+fn export_qelib1_circuit() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += Hadamard::new(0);
+    circuit += CNOT::new(0, 1);
+    circuit += RotateZ::new(1, 0.5.into());
+    circuit += ControlledPhaseShift::new(1, 0, (-0.25).into());
+    circuit += Toffoli::new(vec![2, 0, 1]);
+    circuit += PragmaActiveReset::new(2);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+
+    let expected = "OPENQASM 2.0;
+include \"qelib1.inc\";
+qreg q[3];
+creg ro[2];
+h q[0];
+cx q[0],q[1];
+rz(0.5) q[1];
+cu1(-0.25) q[1],q[0];
+ccx q[2],q[0],q[1];
+reset q[2];
+measure q[0] -> ro[0];
+measure q[1] -> ro[1];
+";
+    assert_eq!(
+        circuit_to_qasm(&circuit, QasmStrictness::Error).unwrap(),
+        expected
+    );
+}
+
+/// Test custom gate definitions for gates without a qelib1 counterpart
+#[test]
+fn export_custom_gates() {
+    let mut circuit = Circuit::new();
+    circuit += SqrtPauliX::new(0);
+    circuit += SWAP::new(2, 1);
+    circuit += SqrtPauliX::new(1);
+    circuit += Fsim::new(0, 1, 0.1.into(), 0.2.into(), 0.3.into());
+    circuit += Fsim::new(1, 2, 0.1.into(), 0.2.into(), 0.3.into());
+    circuit += Fsim::new(0, 1, 0.4.into(), 0.2.into(), 0.3.into());
+    let qasm = circuit_to_qasm(&circuit, QasmStrictness::Error).unwrap();
+
+    assert_eq!(qasm.matches("gate sqrtpaulix a {").count(), 1);
+    assert_eq!(qasm.matches("gate swap_gate a,b {").count(), 1);
+    assert_eq!(qasm.matches("gate fsim a,b {").count(), 1);
+    assert_eq!(qasm.matches("gate fsim_1 a,b {").count(), 1);
+    assert!(qasm.contains("\nsqrtpaulix q[0];\n"));
+    assert!(qasm.contains("\nswap_gate q[2],q[1];\n"));
+    assert!(qasm.contains("\nsqrtpaulix q[1];\n"));
+    assert!(qasm.contains("\nfsim q[0],q[1];\n"));
+    assert!(qasm.contains("\nfsim q[1],q[2];\n"));
+    assert!(qasm.contains("\nfsim_1 q[0],q[1];\n"));
+    assert!(qasm.find("gate fsim_1").unwrap() < qasm.find("qreg q[3];").unwrap());
+}
+
+/// Test the export of PragmaRepeatedMeasurement and PragmaConditional
+#[test]
+fn export_measurement_and_conditional() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += DefinitionBit::new("flag".to_string(), 1, false);
+    circuit += PauliX::new(1);
+    circuit += MeasureQubit::new(1, "flag".to_string(), 0);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(0);
+    conditional += SqrtPauliX::new(1);
+    circuit += PragmaConditional::new("flag".to_string(), 0, conditional);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 100);
+    let qasm = circuit_to_qasm(&circuit, QasmStrictness::Error).unwrap();
+
+    assert!(qasm.contains("creg flag[1];\n"));
+    assert!(qasm.contains("\nif(flag==1) x q[0];\nif(flag==1) sqrtpaulix q[1];\n"));
+    assert!(qasm.ends_with("measure q[0] -> ro[0];\nmeasure q[1] -> ro[1];\n"));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    let mut mapping = std::collections::HashMap::new();
+    mapping.insert(1, 2);
+    mapping.insert(0, 1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), Some(mapping), 100);
+    let qasm = circuit_to_qasm(&circuit, QasmStrictness::Error).unwrap();
+    assert!(qasm.ends_with("measure q[0] -> ro[1];\nmeasure q[1] -> ro[2];\n"));
+}
+
+/// Test the export of PragmaConditionalPattern as a comparison of the whole condition register
+#[test]
+fn export_conditional_pattern() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("syndrome".to_string(), 3, false);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(0);
+    circuit += PragmaConditionalPattern::new(
+        "syndrome".to_string(),
+        vec![(2, true), (0, true), (1, false)],
+        conditional.clone(),
+    );
+    let qasm = circuit_to_qasm(&circuit, QasmStrictness::Error).unwrap();
+    assert!(qasm.ends_with("creg syndrome[3];\nif(syndrome==5) x q[0];\n"));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("syndrome".to_string(), 3, false);
+    circuit += PragmaConditionalPattern::new(
+        "syndrome".to_string(),
+        vec![(0, true), (1, false)],
+        conditional,
+    );
+    assert!(matches!(
+        circuit_to_qasm(&circuit, QasmStrictness::Error),
+        Err(RoqoqoError::QasmExportError { .. })
+    ));
+}
+
+/// Test that PragmaLoop is unrolled and PragmaRepeatUntilSuccess cannot be represented
+#[test]
+fn export_loops() {
+    let mut body = Circuit::new();
+    body += Hadamard::new(0);
+    let mut circuit = Circuit::new();
+    circuit += PragmaLoop::new(2.0.into(), body.clone());
+    let qasm = circuit_to_qasm(&circuit, QasmStrictness::Error).unwrap();
+    assert!(qasm.ends_with("qreg q[1];\nh q[0];\nh q[0];\n"));
+
+    let mut circuit = Circuit::new();
+    circuit += PragmaLoop::new("repetitions".into(), body.clone());
+    assert!(matches!(
+        circuit_to_qasm(&circuit, QasmStrictness::Drop),
+        Err(RoqoqoError::QasmExportError { .. })
+    ));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PragmaRepeatUntilSuccess::new("ro".to_string(), 0, 3, body);
+    assert!(matches!(
+        circuit_to_qasm(&circuit, QasmStrictness::Error),
+        Err(RoqoqoError::QasmExportError { .. })
+    ));
+}
+
+/// Test the handling of operations that cannot be represented
+#[test]
+fn export_strictness() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("values".to_string(), 1, true);
+    circuit += DefinitionBit::new("flags".to_string(), 2, false);
+    circuit += PauliX::new(0);
+    circuit += PragmaSleep::new(vec![0], 1.0.into());
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(0);
+    circuit += PragmaConditional::new("flags".to_string(), 1, conditional);
+
+    assert!(matches!(
+        circuit_to_qasm(&circuit, QasmStrictness::Error),
+        Err(RoqoqoError::QasmExportError { .. })
+    ));
+
+    let dropped = circuit_to_qasm(&circuit, QasmStrictness::Drop).unwrap();
+    assert_eq!(
+        dropped,
+        "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[1];\ncreg flags[2];\nx q[0];\n"
+    );
+
+    let commented = circuit_to_qasm(&circuit, QasmStrictness::Comment).unwrap();
+    let lines: Vec<&str> = commented.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[3], "// DefinitionFloat values 1 true");
+    assert_eq!(lines[6], "// PragmaSleep [0] 1.0");
+    assert_eq!(lines[7], "// PragmaConditional flags 1 { PauliX 0 }");
+}
+
+/// Test that symbolic parameters are rejected
+#[test]
+fn export_symbolic_parameters() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    assert!(matches!(
+        circuit_to_qasm(&circuit, QasmStrictness::Comment),
+        Err(RoqoqoError::QasmExportError { .. })
+    ));
+    let mut circuit = Circuit::new();
+    circuit += XY::new(0, 1, "theta".into());
+    assert!(matches!(
+        circuit_to_qasm(&circuit, QasmStrictness::Drop),
+        Err(RoqoqoError::QasmExportError { .. })
+    ));
+}
+
+/// Test that the qelib1 decomposition of every gate is equivalent to the gate
+#[test_case(Operation::from(SingleQubitGate::new(0, 0.6.into(), 0.0.into(), 0.0.into(), 0.8.into(), 0.3.into())); "SingleQubitGate")]
+#[test_case(Operation::from(RotateZ::new(0, 0.3.into())); "RotateZ")]
+#[test_case(Operation::from(RotateX::new(0, 0.3.into())); "RotateX")]
+#[test_case(Operation::from(RotateY::new(0, 0.3.into())); "RotateY")]
+#[test_case(Operation::from(PauliX::new(0)); "PauliX")]
+#[test_case(Operation::from(PauliY::new(0)); "PauliY")]
+#[test_case(Operation::from(PauliZ::new(0)); "PauliZ")]
+#[test_case(Operation::from(SqrtPauliX::new(0)); "SqrtPauliX")]
+#[test_case(Operation::from(InvSqrtPauliX::new(0)); "InvSqrtPauliX")]
+#[test_case(Operation::from(Hadamard::new(0)); "Hadamard")]
+#[test_case(Operation::from(SGate::new(0)); "SGate")]
+#[test_case(Operation::from(TGate::new(0)); "TGate")]
+#[test_case(Operation::from(RotateAroundSphericalAxis::new(0, 0.5.into(), 0.3.into(), 1.2.into())); "RotateAroundSphericalAxis")]
+#[test_case(Operation::from(CNOT::new(1, 0)); "CNOT")]
+#[test_case(Operation::from(SWAP::new(1, 0)); "SWAP")]
+#[test_case(Operation::from(ISwap::new(1, 0)); "ISwap")]
+#[test_case(Operation::from(FSwap::new(1, 0)); "FSwap")]
+#[test_case(Operation::from(SqrtISwap::new(1, 0)); "SqrtISwap")]
+#[test_case(Operation::from(InvSqrtISwap::new(1, 0)); "InvSqrtISwap")]
+#[test_case(Operation::from(XY::new(1, 0, 0.7.into())); "XY")]
+#[test_case(Operation::from(ControlledPhaseShift::new(1, 0, 0.7.into())); "ControlledPhaseShift")]
+#[test_case(Operation::from(ControlledPauliY::new(1, 0)); "ControlledPauliY")]
+#[test_case(Operation::from(ControlledPauliZ::new(1, 0)); "ControlledPauliZ")]
+#[test_case(Operation::from(MolmerSorensenXX::new(1, 0)); "MolmerSorensenXX")]
+#[test_case(Operation::from(VariableMSXX::new(1, 0, 0.7.into())); "VariableMSXX")]
+#[test_case(Operation::from(GivensRotation::new(1, 0, 0.7.into(), 0.2.into())); "GivensRotation")]
+#[test_case(Operation::from(GivensRotationLittleEndian::new(1, 0, 0.7.into(), 0.2.into())); "GivensRotationLittleEndian")]
+#[test_case(Operation::from(Qsim::new(1, 0, 1.0.into(), 1.0.into(), (-1.0).into())); "Qsim")]
+#[test_case(Operation::from(Fsim::new(1, 0, 1.0.into(), 2.0.into(), (-1.0).into())); "Fsim")]
+#[test_case(Operation::from(SpinInteraction::new(1, 0, 1.0.into(), 2.0.into(), (-1.0).into())); "SpinInteraction")]
+#[test_case(Operation::from(Bogoliubov::new(1, 0, 1.0.into(), (-1.0).into())); "Bogoliubov")]
+#[test_case(Operation::from(PMInteraction::new(1, 0, 0.7.into())); "PMInteraction")]
+#[test_case(Operation::from(ComplexPMInteraction::new(1, 0, 1.0.into(), (-1.0).into())); "ComplexPMInteraction")]
+#[test_case(Operation::from(PhaseShiftedControlledZ::new(1, 0, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ")]
+fn decomposition_equivalent(operation: Operation) {
+    let decomposition = qelib1_decomposition(&operation).unwrap();
+    for gate in decomposition.iter() {
+        assert!(matches!(
+            gate,
+            Operation::RotateX(_)
+                | Operation::RotateY(_)
+                | Operation::RotateZ(_)
+                | Operation::PauliX(_)
+                | Operation::PauliY(_)
+                | Operation::PauliZ(_)
+                | Operation::Hadamard(_)
+                | Operation::SGate(_)
+                | Operation::TGate(_)
+                | Operation::CNOT(_)
+                | Operation::ControlledPauliY(_)
+                | Operation::ControlledPauliZ(_)
+                | Operation::ControlledPhaseShift(_)
+        ));
+    }
+    let mut circuit = Circuit::new();
+    circuit.add_operation(operation);
+    assert!(circuits_equivalent(&circuit, &decomposition, 1e-10, None).unwrap());
+}
+
+/// Test that the decomposition of non-gate operations fails
+#[test]
+fn decomposition_not_unitary() {
+    let operation = Operation::from(MeasureQubit::new(0, "ro".to_string(), 0));
+    assert_eq!(
+        qelib1_decomposition(&operation),
+        Err(RoqoqoError::OperationNotUnitary {
+            hqslang: "MeasureQubit"
+        })
+    );
+}
@@ -0,0 +1,330 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the OpenQASM import
+
+use ndarray::Array2;
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::formats::{circuit_to_qasm, qasm_to_circuit, QasmStrictness};
+use roqoqo::operations::*;
+use roqoqo::unitary::{circuit_unitary_on_qubits, circuits_equivalent, equal_up_to_global_phase};
+use roqoqo::{Circuit, RoqoqoError};
+use std::f64::consts::PI;
+use test_case::test_case;
+
+/// Returns the unitary of a gate controlled by qubit 0 and acting on qubit 1
+fn controlled(unitary: Array2<Complex64>) -> Array2<Complex64> {
+    let mut matrix = Array2::<Complex64>::zeros((4, 4));
+    matrix[(0, 0)] = Complex64::new(1.0, 0.0);
+    matrix[(2, 2)] = Complex64::new(1.0, 0.0);
+    for i in 0..2 {
+        for j in 0..2 {
+            matrix[(1 + 2 * i, 1 + 2 * j)] = unitary[(i, j)];
+        }
+    }
+    matrix
+}
+
+/// Returns the unitary of a permutation of basis states
+fn permutation(dimension: usize, swapped: (usize, usize)) -> Array2<Complex64> {
+    let mut matrix = Array2::<Complex64>::eye(dimension);
+    matrix[(swapped.0, swapped.0)] = Complex64::new(0.0, 0.0);
+    matrix[(swapped.1, swapped.1)] = Complex64::new(0.0, 0.0);
+    matrix[(swapped.0, swapped.1)] = Complex64::new(1.0, 0.0);
+    matrix[(swapped.1, swapped.0)] = Complex64::new(1.0, 0.0);
+    matrix
+}
+
+/// Returns the unitary of the imported circuit of a single gate statement
+fn gate_unitary(statement: &str, number_qubits: usize) -> Array2<Complex64> {
+    let source = format!(
+        "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[{}];\n{}\n",
+        number_qubits, statement
+    );
+    let circuit = qasm_to_circuit(&source).unwrap();
+    let qubits: Vec<usize> = (0..number_qubits).collect();
+    circuit_unitary_on_qubits(&circuit, &qubits).unwrap()
+}
+
+/// Test the import of an OpenQASM 2.0 program
+#[test]
+fn import_qasm2() {
+    let source = "OPENQASM 2.0;
+include \"qelib1.inc\";
+// Two quantum registers
+qreg a[2];
+qreg b[1];
+creg c[3];
+creg flag[1];
+/* Preparation */
+h a;
+cx a[0], b[0];
+rz(pi/4) a[1];
+u3(0.1, -0.2, 2*pi) b[0];
+cu1(-pi) a[1],b[0];
+barrier a, b;
+measure a -> c[0:1] ;
+";
+    assert!(matches!(
+        qasm_to_circuit(source),
+        Err(RoqoqoError::QasmImportError {
+            line: 15,
+            column: 17,
+            ..
+        })
+    ));
+
+    let source = source.replace("c[0:1] ", "flag");
+    assert!(matches!(
+        qasm_to_circuit(&source),
+        Err(RoqoqoError::QasmImportError {
+            line: 15,
+            column: 1,
+            ..
+        })
+    ));
+
+    let source = source.replace(
+        "measure a -> flag;",
+        "measure b -> flag;
+if(flag==1) x a[0];
+measure a[0] -> c[2];
+reset a;
+",
+    );
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("c".to_string(), 3, true);
+    expected += DefinitionBit::new("flag".to_string(), 1, true);
+    expected += Hadamard::new(0);
+    expected += Hadamard::new(1);
+    expected += CNOT::new(0, 2);
+    expected += RotateZ::new(1, CalculatorFloat::FRAC_PI_4);
+    expected += RotateZ::new(2, (2.0 * PI).into());
+    expected += RotateY::new(2, 0.1.into());
+    expected += RotateZ::new(2, (-0.2).into());
+    expected += ControlledPhaseShift::new(1, 2, (-PI).into());
+    expected += PragmaStopParallelBlock::new(vec![0, 1, 2], 0.0.into());
+    expected += MeasureQubit::new(2, "flag".to_string(), 0);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(0);
+    expected += PragmaConditional::new("flag".to_string(), 0, conditional);
+    expected += MeasureQubit::new(0, "c".to_string(), 2);
+    expected += PragmaActiveReset::new(0);
+    expected += PragmaActiveReset::new(1);
+    assert_eq!(qasm_to_circuit(&source).unwrap(), expected);
+}
+
+/// Test the import of the supported subset of OpenQASM 3.0
+#[test]
+fn import_qasm3() {
+    let source = "OPENQASM 3;
+include \"stdgates.inc\";
+input float[64] theta;
+input angle phi;
+qubit[2] q;
+qubit r;
+bit[2] c;
+bit d;
+rx(theta / 2) q[0];
+p(sin(phi) + 1.5) r;
+gphase(π);
+c[1] = measure q[0];
+d = measure r;
+if (c[1]) {
+    x q[1];
+    cx q[1], r;
+}
+if (d == 1) reset q;
+";
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("c".to_string(), 2, true);
+    expected += DefinitionBit::new("d".to_string(), 1, true);
+    expected += RotateX::new(0, "theta / 2".into());
+    expected += RotateZ::new(2, "sin ( phi ) + 1.5".into());
+    expected += PragmaGlobalPhase::new(CalculatorFloat::PI);
+    expected += MeasureQubit::new(0, "c".to_string(), 1);
+    expected += MeasureQubit::new(2, "d".to_string(), 0);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(1);
+    conditional += CNOT::new(1, 2);
+    expected += PragmaConditional::new("c".to_string(), 1, conditional);
+    let mut conditional = Circuit::new();
+    conditional += PragmaActiveReset::new(0);
+    conditional += PragmaActiveReset::new(1);
+    expected += PragmaConditional::new("d".to_string(), 0, conditional);
+    assert_eq!(qasm_to_circuit(source).unwrap(), expected);
+}
+
+/// Test inlining of user gate definitions
+#[test]
+fn import_gate_definitions() {
+    let source = "OPENQASM 2.0;
+include \"qelib1.inc\";
+gate rot(alpha, beta) a { rx(alpha) a; rz(2 * beta) a; }
+gate pair(gamma) a, b { rot(gamma, -gamma) b; cx a, b; barrier a, b; }
+qreg q[3];
+pair(0.5) q[2], q[0];
+pair(0.25) q[0], q[1];
+";
+    let mut expected = Circuit::new();
+    expected += RotateX::new(0, 0.5.into());
+    expected += RotateZ::new(0, (-1.0).into());
+    expected += CNOT::new(2, 0);
+    expected += RotateX::new(1, 0.25.into());
+    expected += RotateZ::new(1, (-0.5).into());
+    expected += CNOT::new(0, 1);
+    assert_eq!(qasm_to_circuit(source).unwrap(), expected);
+}
+
+/// Test that the composite gates of qelib1.inc are imported with the correct unitary
+#[test]
+fn import_composite_gates() {
+    let theta = 0.7;
+    let u3 = |theta: f64, phi: f64, lambda: f64| {
+        ndarray::array![
+            [
+                Complex64::new((theta / 2.0).cos(), 0.0),
+                -Complex64::from_polar(1.0, lambda) * (theta / 2.0).sin()
+            ],
+            [
+                Complex64::from_polar(1.0, phi) * (theta / 2.0).sin(),
+                Complex64::from_polar(1.0, phi + lambda) * (theta / 2.0).cos()
+            ]
+        ]
+    };
+    let cases: Vec<(&str, usize, Array2<Complex64>)> = vec![
+        ("u2(0.3, 0.5) q[0];", 1, u3(PI / 2.0, 0.3, 0.5)),
+        ("u(0.7, 0.3, 0.5) q[0];", 1, u3(0.7, 0.3, 0.5)),
+        (
+            "sdg q[0];",
+            1,
+            SGate::new(0)
+                .unitary_matrix()
+                .unwrap()
+                .t()
+                .mapv(|x| x.conj()),
+        ),
+        (
+            "tdg q[0];",
+            1,
+            TGate::new(0)
+                .unitary_matrix()
+                .unwrap()
+                .t()
+                .mapv(|x| x.conj()),
+        ),
+        (
+            "ch q[0], q[1];",
+            2,
+            controlled(Hadamard::new(0).unitary_matrix().unwrap()),
+        ),
+        (
+            "crx(0.7) q[0], q[1];",
+            2,
+            controlled(RotateX::new(0, theta.into()).unitary_matrix().unwrap()),
+        ),
+        (
+            "cry(0.7) q[0], q[1];",
+            2,
+            controlled(RotateY::new(0, theta.into()).unitary_matrix().unwrap()),
+        ),
+        (
+            "crz(0.7) q[0], q[1];",
+            2,
+            controlled(RotateZ::new(0, theta.into()).unitary_matrix().unwrap()),
+        ),
+        (
+            "cu3(0.7, 0.3, 0.5) q[0], q[1];",
+            2,
+            controlled(u3(0.7, 0.3, 0.5)),
+        ),
+        (
+            "rxx(0.7) q[0], q[1];",
+            2,
+            VariableMSXX::new(0, 1, theta.into())
+                .unitary_matrix()
+                .unwrap(),
+        ),
+        ("ccx q[0], q[1], q[2];", 3, permutation(8, (3, 7))),
+        ("cswap q[0], q[1], q[2];", 3, permutation(8, (3, 5))),
+    ];
+    for (statement, number_qubits, expected) in cases {
+        assert!(
+            equal_up_to_global_phase(&gate_unitary(statement, number_qubits), &expected, 1e-10),
+            "{}",
+            statement
+        );
+    }
+
+    let mut rzz = Circuit::new();
+    rzz += Hadamard::new(0);
+    rzz += Hadamard::new(1);
+    rzz += VariableMSXX::new(0, 1, theta.into());
+    rzz += Hadamard::new(0);
+    rzz += Hadamard::new(1);
+    let imported = qasm_to_circuit("qreg q[2]; rzz(0.7) q[0], q[1];").unwrap();
+    assert!(circuits_equivalent(&rzz, &imported, 1e-10, None).unwrap());
+
+    let mut ryy = Circuit::new();
+    ryy += RotateZ::new(0, (-PI / 2.0).into());
+    ryy += RotateZ::new(1, (-PI / 2.0).into());
+    ryy += VariableMSXX::new(0, 1, theta.into());
+    ryy += SGate::new(0);
+    ryy += SGate::new(1);
+    let imported = qasm_to_circuit("qreg q[2]; ryy(0.7) q[0], q[1];").unwrap();
+    assert!(circuits_equivalent(&ryy, &imported, 1e-10, None).unwrap());
+}
+
+/// Test that exported circuits are imported as equivalent circuits
+#[test]
+fn export_import_roundtrip() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += SqrtPauliX::new(1);
+    circuit += ISwap::new(1, 2);
+    circuit += Fsim::new(2, 0, 0.1.into(), 0.2.into(), 0.3.into());
+    circuit += RotateAroundSphericalAxis::new(1, 0.5.into(), 0.3.into(), 1.2.into());
+    circuit += Bogoliubov::new(0, 1, 1.0.into(), (-1.0).into());
+    circuit += ControlledPhaseShift::new(0, 2, 0.4.into());
+    let qasm = circuit_to_qasm(&circuit, QasmStrictness::Error).unwrap();
+    let imported = qasm_to_circuit(&qasm).unwrap();
+    assert!(circuits_equivalent(&circuit, &imported, 1e-10, None).unwrap());
+}
+
+/// Test the position of errors in invalid programs
+#[test_case("qreg q[2];\nh q[2];", 2, 5; "index out of range")]
+#[test_case("qreg q[2];\nfoo q[0];", 2, 1; "unknown gate")]
+#[test_case("qreg q[2];\n  cx q[0];", 2, 3; "wrong number of qubits")]
+#[test_case("qreg q[2];\ncx q[0], q[0];", 2, 1; "repeated qubit")]
+#[test_case("qreg q[2];\nrx(alpha) q[0];", 2, 4; "unknown parameter")]
+#[test_case("qreg q[2];\nh r[0];", 2, 3; "unknown register")]
+#[test_case("qreg q[2];\nh q[0]\nh q[1];", 3, 1; "missing semicolon")]
+#[test_case("OPENQASM 4.0;", 1, 10; "unsupported version")]
+#[test_case("include \"other.inc\";", 1, 9; "unsupported include")]
+#[test_case("qreg q[1];\ncreg c[2];\nif(c==1) x q[0];", 3, 4; "multi-bit condition")]
+#[test_case("qreg q[1];\nopaque g a;", 2, 1; "opaque gate")]
+#[test_case("qreg q[1];\n/* unterminated", 2, 1; "unterminated comment")]
+#[test_case("qreg q[1];\nh q[0]; $", 2, 9; "unexpected character")]
+#[test_case("qreg q[1];\nrx(1.0 q[0];", 2, 12; "missing parenthesis")]
+#[test_case("qreg q[2];\nqreg q[1];", 2, 1; "duplicate register")]
+#[test_case("qreg q[2];\nqreg r[2];\ncx q, r[0], q[1];\ncx q, r;", 3, 1; "repeated broadcast qubit")]
+fn import_errors(source: &str, line: usize, column: usize) {
+    match qasm_to_circuit(source) {
+        Err(RoqoqoError::QasmImportError {
+            line: error_line,
+            column: error_column,
+            msg,
+        }) => assert_eq!((error_line, error_column), (line, column), "{}", msg),
+        result => panic!("Unexpected result {:?}", result),
+    }
+}
@@ -0,0 +1,400 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the Quil export and import
+
+use ndarray::{array, Array2};
+use num_complex::Complex64;
+use roqoqo::formats::{circuit_to_quil, quil_to_circuit};
+use roqoqo::operations::*;
+use roqoqo::unitary::{circuit_unitary_on_qubits, circuits_equivalent, equal_up_to_global_phase};
+use roqoqo::{Circuit, RoqoqoError};
+use std::convert::TryFrom;
+use test_case::test_case;
+
+/// Returns a circuit using all operations that have a direct counterpart in Quil
+fn shared_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += DefinitionFloat::new("angles".to_string(), 3, true);
+    circuit += DefinitionUsize::new("counts".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PauliY::new(1);
+    circuit += PauliZ::new(2);
+    circuit += Hadamard::new(0);
+    circuit += SGate::new(1);
+    circuit += TGate::new(2);
+    circuit += RotateX::new(0, 0.1.into());
+    circuit += RotateY::new(1, (-0.2).into());
+    circuit += RotateZ::new(2, 3.5.into());
+    circuit += CNOT::new(2, 0);
+    circuit += ControlledPauliZ::new(0, 1);
+    circuit += SWAP::new(1, 2);
+    circuit += ISwap::new(0, 2);
+    circuit += ControlledPhaseShift::new(1, 0, 0.25.into());
+    circuit += XY::new(0, 1, 1.5.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 1);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(1);
+    conditional += PragmaActiveReset::new(2);
+    circuit += PragmaConditional::new("ro".to_string(), 1, conditional);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 0);
+    circuit
+}
+
+/// Test the exported text of a circuit
+#[test]
+fn export_quil() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += RotateX::new(0, 0.5.into());
+    circuit += PauliX::new(0);
+    circuit += Toffoli::new(vec![2, 0, 1]);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let mut conditional = Circuit::new();
+    conditional += CNOT::new(0, 1);
+    conditional += PragmaActiveReset::new(0);
+    circuit += PragmaConditional::new("ro".to_string(), 0, conditional);
+    circuit += SqrtPauliX::new(1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 10);
+
+    let expected = "DEFGATE SQRTPAULIX:
+    0.7071067811865476+0i, 0-0.7071067811865475i
+    0-0.7071067811865475i, 0.7071067811865476+0i
+
+DECLARE ro BIT[1]
+RX(0.5) 0
+X 0
+CCNOT 2 0 1
+MEASURE 0 ro[0]
+JUMP-UNLESS @conditional_0 ro[0]
+CNOT 0 1
+RESET 0
+LABEL @conditional_0
+SQRTPAULIX 1
+MEASURE 0 ro[0]
+";
+    assert_eq!(circuit_to_quil(&circuit).unwrap(), expected);
+}
+
+/// Test the export of classical operations and of PragmaConditionalPattern
+#[test]
+fn export_classical_quil() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += DefinitionBit::new("syndrome".to_string(), 2, false);
+    circuit += DefinitionFloat::new("energy".to_string(), 1, false);
+    circuit += ClassicalXor::new("ro".to_string(), vec![0, 1], "syndrome".to_string(), 0);
+    circuit += ClassicalXor::new("ro".to_string(), vec![1, 2], "ro".to_string(), 2);
+    circuit += ClassicalAnd::new("ro".to_string(), vec![], "syndrome".to_string(), 1);
+    circuit += ClassicalOr::new("ro".to_string(), vec![0, 2], "syndrome".to_string(), 1);
+    circuit += ClassicalNot::new("ro".to_string(), 0, "ro".to_string(), 0);
+    circuit += ClassicalNot::new("ro".to_string(), 0, "syndrome".to_string(), 1);
+    circuit += ClassicalCopy::new("ro".to_string(), 1, "syndrome".to_string(), 0);
+    circuit += ClassicalGreaterThan::new("energy".to_string(), 0, 1.0.into(), "ro".to_string(), 0);
+    circuit += ClassicalLessThan::new("energy".to_string(), 0, (-0.5).into(), "ro".to_string(), 1);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(1);
+    circuit += PragmaConditionalPattern::new(
+        "syndrome".to_string(),
+        vec![(0, true), (1, false)],
+        conditional,
+    );
+
+    let expected = "DECLARE ro BIT[3]
+DECLARE syndrome BIT[2]
+DECLARE energy REAL[1]
+MOVE syndrome[0] ro[0]
+XOR syndrome[0] ro[1]
+XOR ro[2] ro[1]
+MOVE syndrome[1] 1
+MOVE syndrome[1] ro[0]
+IOR syndrome[1] ro[2]
+NOT ro[0]
+MOVE syndrome[1] ro[0]
+NOT syndrome[1]
+MOVE syndrome[0] ro[1]
+GT ro[0] energy[0] 1.0
+LT ro[1] energy[0] -0.5
+JUMP-UNLESS @conditional_0 syndrome[0]
+JUMP-WHEN @conditional_0 syndrome[1]
+X 1
+LABEL @conditional_0
+";
+    assert_eq!(circuit_to_quil(&circuit).unwrap(), expected);
+
+    let mut circuit = Circuit::new();
+    circuit += ClassicalGreaterThan::new(
+        "energy".to_string(),
+        0,
+        "threshold".into(),
+        "ro".to_string(),
+        0,
+    );
+    assert!(matches!(
+        circuit_to_quil(&circuit),
+        Err(RoqoqoError::QuilExportError { .. })
+    ));
+}
+
+/// Test the export of PragmaLoop and PragmaRepeatUntilSuccess by unrolling
+#[test]
+fn export_loops_quil() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    let mut body = Circuit::new();
+    body += PauliX::new(0);
+    circuit += PragmaLoop::new(2.0.into(), body);
+    let mut attempt = Circuit::new();
+    attempt += Hadamard::new(1);
+    attempt += MeasureQubit::new(1, "ro".to_string(), 0);
+    circuit += PragmaRepeatUntilSuccess::new("ro".to_string(), 0, 2, attempt.clone());
+
+    let expected = "DECLARE ro BIT[1]
+X 0
+X 0
+H 1
+MEASURE 1 ro[0]
+JUMP-UNLESS @conditional_0 ro[0]
+H 1
+MEASURE 1 ro[0]
+LABEL @conditional_0
+";
+    assert_eq!(circuit_to_quil(&circuit).unwrap(), expected);
+
+    let mut circuit = Circuit::new();
+    circuit += PragmaLoop::new("repetitions".into(), attempt);
+    assert!(matches!(
+        circuit_to_quil(&circuit),
+        Err(RoqoqoError::QuilExportError { .. })
+    ));
+}
+
+/// Test that circuits using the shared gate set survive the round trip unchanged
+#[test]
+fn roundtrip_shared_gates() {
+    let circuit = shared_circuit();
+    let quil = circuit_to_quil(&circuit).unwrap();
+    assert_eq!(quil_to_circuit(&quil).unwrap(), circuit);
+    assert_eq!(
+        circuit_to_quil(&quil_to_circuit(&quil).unwrap()).unwrap(),
+        quil
+    );
+}
+
+/// Test the round trip of single-qubit gates through DEFGATE
+#[test_case(Operation::from(SqrtPauliX::new(0)); "SqrtPauliX")]
+#[test_case(Operation::from(InvSqrtPauliX::new(0)); "InvSqrtPauliX")]
+#[test_case(Operation::from(RotateAroundSphericalAxis::new(0, 0.5.into(), 0.3.into(), 1.2.into())); "RotateAroundSphericalAxis")]
+#[test_case(Operation::from(SingleQubitGate::new(0, 0.6.into(), 0.0.into(), 0.0.into(), 0.8.into(), 0.3.into())); "SingleQubitGate")]
+fn roundtrip_defgate(operation: Operation) {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(operation.clone());
+    circuit.add_operation(
+        operation
+            .remap_qubits(&[(0, 1)].iter().cloned().collect())
+            .unwrap(),
+    );
+    let quil = circuit_to_quil(&circuit).unwrap();
+    assert_eq!(quil.matches("DEFGATE").count(), 1);
+    let imported = quil_to_circuit(&quil).unwrap();
+    assert_eq!(imported.len(), 2);
+    for (original, imported) in circuit.iter().zip(imported.iter()) {
+        assert!(matches!(imported, Operation::SingleQubitGate(_)));
+        let original_unitary = GateOperation::try_from(original.clone())
+            .unwrap()
+            .unitary_matrix()
+            .unwrap();
+        let imported_unitary = GateOperation::try_from(imported.clone())
+            .unwrap()
+            .unitary_matrix()
+            .unwrap();
+        for (a, b) in original_unitary.iter().zip(imported_unitary.iter()) {
+            assert!((a - b).norm() < 1e-12);
+        }
+    }
+}
+
+/// Test that standard Quil gates without a roqoqo counterpart are imported with the correct unitary
+#[test]
+fn import_standard_gates() {
+    let phase = Complex64::from_polar(1.0, 0.7);
+    let one = Complex64::new(1.0, 0.0);
+    let zero = Complex64::new(0.0, 0.0);
+    let diagonal = |entries: [Complex64; 4]| Array2::from_diag(&ndarray::arr1(&entries));
+    let permutation = |dimension: usize, swapped: (usize, usize)| {
+        let mut matrix = Array2::<Complex64>::eye(dimension);
+        matrix[(swapped.0, swapped.0)] = zero;
+        matrix[(swapped.1, swapped.1)] = zero;
+        matrix[(swapped.0, swapped.1)] = one;
+        matrix[(swapped.1, swapped.0)] = one;
+        matrix
+    };
+    // Quil matrices use the first qubit as the most significant qubit
+    let cases: Vec<(&str, Vec<usize>, Array2<Complex64>)> = vec![
+        ("PHASE(0.7) 0", vec![0], array![[one, zero], [zero, phase]]),
+        (
+            "CPHASE00(0.7) 0 1",
+            vec![1, 0],
+            diagonal([phase, one, one, one]),
+        ),
+        (
+            "CPHASE01(0.7) 0 1",
+            vec![1, 0],
+            diagonal([one, phase, one, one]),
+        ),
+        (
+            "CPHASE10(0.7) 0 1",
+            vec![1, 0],
+            diagonal([one, one, phase, one]),
+        ),
+        (
+            "PSWAP(0.7) 0 1",
+            vec![1, 0],
+            array![
+                [one, zero, zero, zero],
+                [zero, zero, phase, zero],
+                [zero, phase, zero, zero],
+                [zero, zero, zero, one]
+            ],
+        ),
+        ("CCNOT 0 1 2", vec![2, 1, 0], permutation(8, (6, 7))),
+        ("CSWAP 0 1 2", vec![2, 1, 0], permutation(8, (5, 6))),
+    ];
+    for (instruction, qubits, expected) in cases {
+        let circuit = quil_to_circuit(instruction).unwrap();
+        let unitary = circuit_unitary_on_qubits(&circuit, &qubits).unwrap();
+        assert!(
+            equal_up_to_global_phase(&unitary, &expected, 1e-10),
+            "{}",
+            instruction
+        );
+    }
+}
+
+/// Test the import of DEFGATE definitions, expressions, comments and ignored instructions
+#[test]
+fn import_defgate_and_expressions() {
+    let source = "# Custom gate
+PRAGMA INITIAL_REWIRING \"PARTIAL\"
+DEFGATE HALF AS MATRIX:
+    1/sqrt(2), -i/sqrt(2)
+    -1.0i/sqrt(2), cos(pi/4)
+DEFGATE PHASED:
+    cis(0.3), 0
+    0, exp(-0.3i)
+
+HALF 3  # applied to qubit 3
+NOP
+PHASED 0
+RX(pi/2 - 0.5*2) 1
+";
+    let circuit = quil_to_circuit(source).unwrap();
+    assert_eq!(circuit.len(), 3);
+    let mut expected = Circuit::new();
+    expected += RotateX::new(3, std::f64::consts::FRAC_PI_2.into());
+    expected += RotateZ::new(0, (-0.6).into());
+    expected += RotateX::new(1, (std::f64::consts::FRAC_PI_2 - 1.0).into());
+    assert!(circuits_equivalent(&circuit, &expected, 1e-10, None).unwrap());
+    assert_eq!(
+        circuit[2],
+        Operation::from(RotateX::new(1, (std::f64::consts::FRAC_PI_2 - 1.0).into()))
+    );
+}
+
+/// Test errors when exporting operations that cannot be represented
+#[test]
+fn export_errors() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    assert!(matches!(
+        circuit_to_quil(&circuit),
+        Err(RoqoqoError::QuilExportError { .. })
+    ));
+    let mut circuit = Circuit::new();
+    circuit += SqrtPauliX::new(0);
+    circuit += Fsim::new(0, 1, "theta".into(), 0.1.into(), 0.2.into());
+    assert!(matches!(
+        circuit_to_quil(&circuit),
+        Err(RoqoqoError::QuilExportError { .. })
+    ));
+    let mut circuit = Circuit::new();
+    circuit += DefinitionComplex::new("ro".to_string(), 1, true);
+    assert!(matches!(
+        circuit_to_quil(&circuit),
+        Err(RoqoqoError::QuilExportError { .. })
+    ));
+    let mut circuit = Circuit::new();
+    circuit += PragmaSleep::new(vec![0], 1.0.into());
+    assert!(matches!(
+        circuit_to_quil(&circuit),
+        Err(RoqoqoError::QuilExportError { .. })
+    ));
+}
+
+/// Test that two-qubit gates without counterpart are exported as DEFGATE with the unitary matrix
+#[test]
+fn export_two_qubit_defgate() {
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliY::new(1, 0);
+    let quil = circuit_to_quil(&circuit).unwrap();
+    assert_eq!(
+        quil,
+        "DEFGATE CONTROLLEDPAULIY:
+    1+0i, 0+0i, 0+0i, 0+0i
+    0+0i, 1+0i, 0+0i, 0+0i
+    0+0i, 0+0i, 0+0i, 0-1i
+    0+0i, 0+0i, 0+1i, 0+0i
+
+CONTROLLEDPAULIY 1 0
+"
+    );
+    assert!(matches!(
+        quil_to_circuit(&quil),
+        Err(RoqoqoError::QuilImportError {
+            line: 1,
+            column: 1,
+            ..
+        })
+    ));
+}
+
+/// Test the position of errors in invalid programs
+#[test_case("H 0\nFOO 1", 2, 1; "unknown gate")]
+#[test_case("H 0\n  CNOT 1", 2, 3; "wrong number of qubits")]
+#[test_case("CNOT 1 1", 1, 1; "repeated qubit")]
+#[test_case("RX(theta) 0", 1, 4; "memory reference")]
+#[test_case("RX(i) 0", 1, 4; "complex parameter")]
+#[test_case("DECLARE ro BIT\nMEASURE 0 ro[1]", 2, 11; "index out of range")]
+#[test_case("DECLARE ro REAL\nMEASURE 0 ro", 2, 11; "not a bit register")]
+#[test_case("MEASURE 0 ro", 1, 11; "unknown register")]
+#[test_case("MEASURE 0", 1, 10; "discarded measurement")]
+#[test_case("RESET", 1, 6; "reset all")]
+#[test_case("DECLARE ro OCTET", 1, 12; "unsupported type")]
+#[test_case("DECLARE ro BIT\nJUMP-UNLESS @end ro\nX 0", 2, 13; "missing label")]
+#[test_case("LABEL @start\nX 0", 1, 1; "unsupported label")]
+#[test_case("X 0\nJUMP @start", 2, 1; "unsupported jump")]
+#[test_case("DAGGER X 0", 1, 1; "gate modifier")]
+#[test_case("DEFGATE G(%a):\n    1, 0\n    0, 1", 1, 10; "parametric defgate")]
+#[test_case("DEFGATE G:\n    1, 1\n    0, 1", 1, 1; "not unitary")]
+#[test_case("DEFGATE X:\n    1, 0\n    0, 1", 1, 9; "redefined standard gate")]
+#[test_case("H 0 ; X 1", 1, 5; "unexpected character")]
+fn import_errors(source: &str, line: usize, column: usize) {
+    match quil_to_circuit(source) {
+        Err(RoqoqoError::QuilImportError {
+            line: error_line,
+            column: error_column,
+            msg,
+        }) => assert_eq!((error_line, error_column), (line, column), "{}", msg),
+        result => panic!("Unexpected result {:?}", result),
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for bit-string histograms
+
+use roqoqo::histogram::*;
+use roqoqo::RoqoqoError;
+use std::collections::{BTreeMap, HashMap};
+use test_case::test_case;
+
+/// Returns a register with three bits and eight runs
+fn example_register() -> Vec<Vec<bool>> {
+    vec![
+        vec![true, false, false],
+        vec![true, false, false],
+        vec![true, false, false],
+        vec![false, false, true],
+        vec![false, false, true],
+        vec![true, true, true],
+        vec![false, false, false],
+        vec![false, false, false],
+    ]
+}
+
+/// Test counts and probabilities in both bit orders
+#[test]
+fn counts_and_probabilities() {
+    let histogram = BitStringHistogram::from_register(&example_register()).unwrap();
+    assert_eq!(histogram.number_bits(), 3);
+    assert_eq!(histogram.number_shots(), 8);
+    assert_eq!(histogram.count(&[true, false, false]), 3);
+    assert_eq!(histogram.count(&[false, true, false]), 0);
+    assert_eq!(histogram.counts().len(), 4);
+
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    counts.insert("100".to_string(), 3);
+    counts.insert("001".to_string(), 2);
+    counts.insert("111".to_string(), 1);
+    counts.insert("000".to_string(), 2);
+    assert_eq!(histogram.bit_string_counts(BitOrder::FirstBitLeft), counts);
+    assert_eq!(histogram.bit_string_counts(BitOrder::default()), counts);
+
+    let right = histogram.bit_string_counts(BitOrder::FirstBitRight);
+    assert_eq!(right["001"], 3);
+    assert_eq!(right["100"], 2);
+
+    let probabilities = histogram.probabilities(BitOrder::FirstBitRight);
+    assert_eq!(probabilities["001"], 0.375);
+    assert_eq!(probabilities["111"], 0.125);
+    assert_eq!(probabilities.values().sum::<f64>(), 1.0);
+}
+
+/// Test creating histograms from counts of bit strings
+#[test]
+fn from_counts() {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    counts.insert("001".to_string(), 3);
+    counts.insert("100".to_string(), 2);
+    counts.insert("111".to_string(), 1);
+    counts.insert("000".to_string(), 2);
+    let histogram = BitStringHistogram::from_counts(&counts, BitOrder::FirstBitRight).unwrap();
+    assert_eq!(
+        histogram,
+        BitStringHistogram::from_register(&example_register()).unwrap()
+    );
+
+    counts.insert("0a1".to_string(), 1);
+    assert_eq!(
+        BitStringHistogram::from_counts(&counts, BitOrder::FirstBitRight),
+        Err(RoqoqoError::BitStringHistogramError {
+            msg: "Bit string 0a1 contains characters other than 0 and 1".to_string()
+        })
+    );
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    counts.insert("01".to_string(), 1);
+    counts.insert("011".to_string(), 1);
+    assert!(BitStringHistogram::from_counts(&counts, BitOrder::FirstBitLeft).is_err());
+}
+
+/// Test that runs of different length are rejected
+#[test]
+fn inconsistent_register() {
+    let register = vec![vec![true, false], vec![true]];
+    assert_eq!(
+        BitStringHistogram::from_register(&register),
+        Err(RoqoqoError::BitStringHistogramError {
+            msg: "Bit string has 1 bits but the histogram has 2 bits".to_string()
+        })
+    );
+    let empty = BitStringHistogram::from_register(&[]).unwrap();
+    assert_eq!(empty.number_shots(), 0);
+    assert!(empty.parity_expectation_value(&[]).is_err());
+}
+
+/// Test marginalising over subsets of bits
+#[test]
+fn marginal() {
+    let histogram = BitStringHistogram::from_register(&example_register()).unwrap();
+    let marginal = histogram.marginal(&[2, 0]).unwrap();
+    assert_eq!(marginal.number_bits(), 2);
+    assert_eq!(marginal.number_shots(), 8);
+    assert_eq!(marginal.count(&[false, true]), 3);
+    assert_eq!(marginal.count(&[true, false]), 2);
+    assert_eq!(marginal.count(&[true, true]), 1);
+    assert_eq!(marginal.count(&[false, false]), 2);
+
+    let single = histogram.marginal(&[1]).unwrap();
+    assert_eq!(single.count(&[true]), 1);
+    assert_eq!(single.count(&[false]), 7);
+
+    assert_eq!(
+        histogram.marginal(&[0, 3]),
+        Err(RoqoqoError::BitStringHistogramError {
+            msg: "Bit index 3 exceeds the number of bits 3".to_string()
+        })
+    );
+}
+
+/// Test the expectation values of products of Pauli Z operators
+#[test_case(&[], 1.0; "identity")]
+#[test_case(&[0], 0.0; "first")]
+#[test_case(&[1], 0.75; "second")]
+#[test_case(&[0, 2], -0.25; "first and last")]
+#[test_case(&[0, 1, 2], -0.5; "all")]
+fn parity_expectation_value(mask: &[usize], expected: f64) {
+    let histogram = BitStringHistogram::from_register(&example_register()).unwrap();
+    assert_eq!(histogram.parity_expectation_value(mask).unwrap(), expected);
+}
+
+/// Test bootstrapped error bars of expectation values
+#[test]
+fn bootstrap() {
+    let histogram = BitStringHistogram::from_register(&example_register()).unwrap();
+    let (value, error) = histogram
+        .bootstrap_parity_expectation_value(&[0, 2], 200, 42)
+        .unwrap();
+    assert_eq!(value, -0.25);
+    // The standard error of the mean of eight values of +-1
+    let expected_error = ((1.0 - 0.25_f64.powi(2)) / 8.0).sqrt();
+    assert!((error - expected_error).abs() < 0.1, "{}", error);
+    assert_eq!(
+        histogram
+            .bootstrap_parity_expectation_value(&[0, 2], 200, 42)
+            .unwrap(),
+        (value, error)
+    );
+
+    let (_, error) = histogram
+        .bootstrap_parity_expectation_value(&[], 10, 1)
+        .unwrap();
+    assert_eq!(error, 0.0);
+    assert_eq!(
+        histogram
+            .bootstrap_parity_expectation_value(&[1], 0, 1)
+            .unwrap(),
+        (0.75, 0.0)
+    );
+    assert!(histogram
+        .bootstrap_parity_expectation_value(&[4], 10, 1)
+        .is_err());
+}
+
+/// Test converting histograms back to registers
+#[test]
+fn to_register_and_sample() {
+    let histogram = BitStringHistogram::from_register(&example_register()).unwrap();
+    let register = histogram.to_register();
+    assert_eq!(register.len(), 8);
+    assert_eq!(register[0], vec![false, false, false]);
+    assert_eq!(
+        BitStringHistogram::from_register(&register).unwrap(),
+        histogram
+    );
+
+    let sampled = histogram.sample(1000, 7);
+    assert_eq!(sampled.len(), 1000);
+    assert_eq!(sampled, histogram.sample(1000, 7));
+    let sampled_histogram = BitStringHistogram::from_register(&sampled).unwrap();
+    assert_eq!(sampled_histogram.count(&[false, true, false]), 0);
+    let frequency = sampled_histogram.count(&[true, false, false]) as f64 / 1000.0;
+    assert!((frequency - 0.375).abs() < 0.05, "{}", frequency);
+
+    assert!(BitStringHistogram::new(2).sample(10, 0).is_empty());
+}
@@ -0,0 +1,561 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the JSON Schemas of the serialized types
+
+use ndarray::{array, Array2};
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::json_schema::{json_schema, json_schema_string, JsonSchema};
+use roqoqo::measurements::*;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Returns a small circuit used in the operations with nested circuits
+fn nested_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("inner".to_string(), 1, false);
+    circuit += Hadamard::new(0);
+    circuit += RotateZ::new(1, "2 * theta".into());
+    circuit
+}
+
+/// Returns a qubit mapping used in the operations with mappings
+fn mapping() -> HashMap<usize, usize> {
+    let mut mapping = HashMap::new();
+    mapping.insert(2, 0);
+    mapping.insert(0, 1);
+    mapping.insert(1, 2);
+    mapping
+}
+
+/// Returns the errors of validating a JSON value against a schema of the definitions of a root schema.
+///
+/// Supports the subset of JSON Schema generated for roqoqo types.
+fn validate(value: &Value, schema: &Value, definitions: &Value, path: &str) -> Vec<String> {
+    let schema = match schema {
+        Value::Bool(true) => return Vec::new(),
+        Value::Bool(false) => return vec![format!("{}: no value allowed", path)],
+        Value::Object(schema) => schema,
+        _ => return vec![format!("{}: invalid schema", path)],
+    };
+    let mut errors = Vec::new();
+    if let Some(Value::String(reference)) = schema.get("$ref") {
+        let name = reference.trim_start_matches("#/definitions/");
+        return validate(value, &definitions[name], definitions, path);
+    }
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::String(single) => vec![single.as_str()],
+            Value::Array(many) => many.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        let matches = types.iter().any(|t| match *t {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "integer" => value.is_u64() || value.is_i64(),
+            "number" => value.is_number(),
+            "string" => value.is_string(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => false,
+        });
+        if !matches {
+            return vec![format!("{}: {} is not of type {:?}", path, value, types)];
+        }
+    }
+    if let (Some(minimum), Some(number)) = (schema.get("minimum"), value.as_f64()) {
+        if number < minimum.as_f64().unwrap_or(f64::MIN) {
+            errors.push(format!("{}: {} is below the minimum", path, number));
+        }
+    }
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            errors.push(format!("{}: {} is not one of {:?}", path, value, options));
+        }
+    }
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        for sub_schema in all {
+            errors.extend(validate(value, sub_schema, definitions, path));
+        }
+    }
+    for keyword in ["anyOf", "oneOf"].iter() {
+        if let Some(Value::Array(options)) = schema.get(*keyword) {
+            let valid = options
+                .iter()
+                .filter(|option| validate(value, option, definitions, path).is_empty())
+                .count();
+            if valid == 0 || (*keyword == "oneOf" && valid > 1) {
+                errors.push(format!(
+                    "{}: {} matches {} of {}",
+                    path, value, valid, keyword
+                ));
+            }
+        }
+    }
+    if let Value::Object(map) = value {
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(|r| r.as_str()) {
+                if !map.contains_key(name) {
+                    errors.push(format!("{}: missing property {}", path, name));
+                }
+            }
+        }
+        for (name, entry) in map {
+            let entry_path = format!("{}/{}", path, name);
+            match properties.and_then(|p| p.get(name)) {
+                Some(property) => {
+                    errors.extend(validate(entry, property, definitions, &entry_path))
+                }
+                None => {
+                    if let Some(additional) = schema.get("additionalProperties") {
+                        errors.extend(validate(entry, additional, definitions, &entry_path));
+                    }
+                }
+            }
+        }
+    }
+    if let Value::Array(items) = value {
+        if let Some(minimum) = schema.get("minItems").and_then(|m| m.as_u64()) {
+            if (items.len() as u64) < minimum {
+                errors.push(format!("{}: fewer than {} items", path, minimum));
+            }
+        }
+        if let Some(maximum) = schema.get("maxItems").and_then(|m| m.as_u64()) {
+            if (items.len() as u64) > maximum {
+                errors.push(format!("{}: more than {} items", path, maximum));
+            }
+        }
+        match schema.get("items") {
+            Some(Value::Array(tuple)) => {
+                for (index, (item, item_schema)) in items.iter().zip(tuple).enumerate() {
+                    let item_path = format!("{}/{}", path, index);
+                    errors.extend(validate(item, item_schema, definitions, &item_path));
+                }
+            }
+            Some(item_schema) => {
+                for (index, item) in items.iter().enumerate() {
+                    let item_path = format!("{}/{}", path, index);
+                    errors.extend(validate(item, item_schema, definitions, &item_path));
+                }
+            }
+            None => (),
+        }
+    }
+    errors
+}
+
+/// Returns the errors of validating the JSON of a value against the schema of its type
+fn schema_errors<T: Serialize + JsonSchema>(value: &T) -> Vec<String> {
+    let schema = serde_json::to_value(json_schema::<T>()).unwrap();
+    let value = serde_json::to_value(value).unwrap();
+    validate(&value, &schema, &schema["definitions"], "")
+}
+
+/// Returns the JSON fixtures, one value of every operation
+fn operation_fixtures() -> Vec<Operation> {
+    vec![
+        Operation::from(SingleQubitGate::new(
+            0,
+            0.6.into(),
+            0.0.into(),
+            0.0.into(),
+            0.8.into(),
+            "phi".into(),
+        )),
+        Operation::from(RotateZ::new(0, 0.3.into())),
+        Operation::from(RotateX::new(1, "theta".into())),
+        Operation::from(RotateY::new(2, (-1e-12).into())),
+        Operation::from(PauliX::new(0)),
+        Operation::from(PauliY::new(0)),
+        Operation::from(PauliZ::new(0)),
+        Operation::from(SqrtPauliX::new(0)),
+        Operation::from(InvSqrtPauliX::new(0)),
+        Operation::from(Hadamard::new(0)),
+        Operation::from(SGate::new(0)),
+        Operation::from(TGate::new(0)),
+        Operation::from(RotateAroundSphericalAxis::new(
+            0,
+            0.5.into(),
+            0.3.into(),
+            1.2.into(),
+        )),
+        Operation::from(PragmaSetNumberOfMeasurements::new(100, "ro".to_string())),
+        Operation::from(PragmaSetStateVector::new(array![
+            Complex64::new(0.6, 0.0),
+            Complex64::new(0.0, -0.8)
+        ])),
+        Operation::from(PragmaSetDensityMatrix::new(array![
+            [Complex64::new(0.5, 0.0), Complex64::new(0.0, 0.5)],
+            [Complex64::new(0.0, -0.5), Complex64::new(0.5, 0.0)]
+        ])),
+        Operation::from(PragmaRepeatGate::new(3)),
+        Operation::from(PragmaOverrotation::new(
+            "RotateX".to_string(),
+            vec![0, 1],
+            0.03,
+            0.001,
+        )),
+        Operation::from(PragmaBoostNoise::new(1.5.into())),
+        Operation::from(PragmaStopParallelBlock::new(vec![], 0.0.into())),
+        Operation::from(PragmaGlobalPhase::new(CalculatorFloat::PI)),
+        Operation::from(PragmaSleep::new(vec![0, 3], "time".into())),
+        Operation::from(PragmaActiveReset::new(4)),
+        Operation::from(PragmaStartDecompositionBlock::new(vec![0, 1, 2], mapping())),
+        Operation::from(PragmaStopDecompositionBlock::new(vec![0, 1, 2])),
+        Operation::from(PragmaDamping::new(0, 0.005.into(), 0.02.into())),
+        Operation::from(PragmaDepolarising::new(0, 0.005.into(), 0.02.into())),
+        Operation::from(PragmaDephasing::new(0, 0.005.into(), 0.02.into())),
+        Operation::from(PragmaRandomNoise::new(
+            0,
+            0.005.into(),
+            0.02.into(),
+            0.01.into(),
+        )),
+        Operation::from(PragmaGeneralNoise::new(
+            0,
+            0.005.into(),
+            0.02.into(),
+            Array2::from_diag(&array![
+                Complex64::new(1.0, 0.0),
+                Complex64::new(1.0, 0.0),
+                Complex64::new(1.0, 0.0)
+            ]),
+        )),
+        Operation::from(PragmaConditional::new(
+            "flags".to_string(),
+            1,
+            nested_circuit(),
+        )),
+        Operation::from(PragmaConditionalPattern::new(
+            "flags".to_string(),
+            vec![(0, true), (2, false)],
+            nested_circuit(),
+        )),
+        Operation::from(PragmaLoop::new("repetitions".into(), nested_circuit())),
+        Operation::from(PragmaRepeatUntilSuccess::new(
+            "flags".to_string(),
+            1,
+            5,
+            nested_circuit(),
+        )),
+        Operation::from(CNOT::new(1, 0)),
+        Operation::from(SWAP::new(1, 0)),
+        Operation::from(ISwap::new(1, 0)),
+        Operation::from(FSwap::new(1, 0)),
+        Operation::from(SqrtISwap::new(1, 0)),
+        Operation::from(InvSqrtISwap::new(1, 0)),
+        Operation::from(XY::new(1, 0, 0.7.into())),
+        Operation::from(ControlledPhaseShift::new(1, 0, 0.7.into())),
+        Operation::from(ControlledPauliY::new(1, 0)),
+        Operation::from(ControlledPauliZ::new(1, 0)),
+        Operation::from(MolmerSorensenXX::new(1, 0)),
+        Operation::from(VariableMSXX::new(1, 0, 0.7.into())),
+        Operation::from(GivensRotation::new(1, 0, 0.7.into(), "phi".into())),
+        Operation::from(GivensRotationLittleEndian::new(
+            1,
+            0,
+            0.7.into(),
+            0.2.into(),
+        )),
+        Operation::from(Qsim::new(1, 0, 1.0.into(), 1.0.into(), (-1.0).into())),
+        Operation::from(Fsim::new(1, 0, 1.0.into(), 2.0.into(), (-1.0).into())),
+        Operation::from(SpinInteraction::new(
+            1,
+            0,
+            1.0.into(),
+            2.0.into(),
+            (-1.0).into(),
+        )),
+        Operation::from(Bogoliubov::new(1, 0, 1.0.into(), (-1.0).into())),
+        Operation::from(PMInteraction::new(1, 0, 0.7.into())),
+        Operation::from(ComplexPMInteraction::new(1, 0, 1.0.into(), (-1.0).into())),
+        Operation::from(PhaseShiftedControlledZ::new(
+            1,
+            0,
+            CalculatorFloat::FRAC_PI_4,
+        )),
+        Operation::from(
+            TwoQubitGate::from_unitary(1, 0, CNOT::new(0, 1).unitary_matrix().unwrap()).unwrap(),
+        ),
+        Operation::from(MultiQubitMS::new(vec![0, 2, 1], "theta".into())),
+        Operation::from(MultiQubitGate::new(
+            vec![1, 0],
+            array![
+                [Complex64::new(0.0, 1.0), Complex64::new(0.0, 0.0)],
+                [Complex64::new(0.0, 0.0), Complex64::new(-0.6, 0.8)]
+            ],
+        )),
+        Operation::from(Toffoli::new(vec![0, 1, 2])),
+        Operation::from(ControlledControlledPauliZ::new(vec![2, 0, 1])),
+        Operation::from(ControlledControlledPhaseShift::new(
+            vec![0, 1, 2],
+            CalculatorFloat::from("theta"),
+        )),
+        Operation::from(MultiControlledGate::new(
+            vec![0, 1, 2, 3],
+            CalculatorFloat::from(0.1),
+            CalculatorFloat::from("z1"),
+            CalculatorFloat::from(-0.3),
+            CalculatorFloat::from(0.4),
+        )),
+        Operation::from(PauliProductRotation::new(
+            vec![2, 0, 1],
+            vec![1, 2, 3],
+            CalculatorFloat::from("theta"),
+        )),
+        Operation::from(ClassicalNot::new(
+            "ro".to_string(),
+            1,
+            "flags".to_string(),
+            0,
+        )),
+        Operation::from(ClassicalCopy::new(
+            "ro".to_string(),
+            1,
+            "flags".to_string(),
+            0,
+        )),
+        Operation::from(ClassicalXor::new(
+            "ro".to_string(),
+            vec![0, 1, 3],
+            "flags".to_string(),
+            2,
+        )),
+        Operation::from(ClassicalAnd::new(
+            "ro".to_string(),
+            vec![0, 1],
+            "flags".to_string(),
+            2,
+        )),
+        Operation::from(ClassicalOr::new(
+            "ro".to_string(),
+            vec![],
+            "flags".to_string(),
+            2,
+        )),
+        Operation::from(ClassicalGreaterThan::new(
+            "x".to_string(),
+            1,
+            CalculatorFloat::from(0.5),
+            "flags".to_string(),
+            0,
+        )),
+        Operation::from(ClassicalLessThan::new(
+            "x".to_string(),
+            1,
+            CalculatorFloat::from("threshold"),
+            "flags".to_string(),
+            0,
+        )),
+        Operation::from(MeasureQubit::new(0, "ro".to_string(), 1)),
+        Operation::from(PragmaGetStateVector::new("ro".to_string(), None)),
+        Operation::from(PragmaGetDensityMatrix::new(
+            "ro".to_string(),
+            Some(nested_circuit()),
+        )),
+        Operation::from(PragmaGetOccupationProbability::new(
+            "ro".to_string(),
+            Some(Circuit::new()),
+        )),
+        Operation::from(PragmaGetPauliProduct::new(
+            mapping(),
+            "ro".to_string(),
+            nested_circuit(),
+        )),
+        Operation::from(PragmaRepeatedMeasurement::new(
+            "ro".to_string(),
+            Some(mapping()),
+            100,
+        )),
+        Operation::from(DefinitionFloat::new("values".to_string(), 2, true)),
+        Operation::from(DefinitionComplex::new(
+            "complex values".to_string(),
+            2,
+            false,
+        )),
+        Operation::from(DefinitionUsize::new("None".to_string(), 2, true)),
+        Operation::from(DefinitionBit::new("ro".to_string(), 2, true)),
+        Operation::from(InputSymbolic::new("theta".to_string(), 1.0 / 3.0)),
+    ]
+}
+
+/// Test that the JSON of every operation is valid for the schemas of Operation and Circuit
+#[test]
+fn operation_schema() {
+    for operation in operation_fixtures() {
+        let errors = schema_errors(&operation);
+        assert!(errors.is_empty(), "{}: {:?}", operation.hqslang(), errors);
+
+        let mut circuit = Circuit::new();
+        circuit.add_operation(operation.clone());
+        let mut conditional = Circuit::new();
+        conditional += PragmaConditional::new("flag".to_string(), 0, circuit.clone());
+        assert!(
+            schema_errors(&circuit).is_empty(),
+            "{}",
+            operation.hqslang()
+        );
+        assert!(
+            schema_errors(&conditional).is_empty(),
+            "{}",
+            operation.hqslang()
+        );
+    }
+}
+
+/// Test that the fixtures cover every operation of the schema, so that new operations are validated
+#[test]
+fn operation_fixtures_complete() {
+    let schema = serde_json::to_value(json_schema::<Operation>()).unwrap();
+    let mut names: Vec<String> = schema["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|variant| variant["required"].as_array().unwrap())
+        .filter_map(|name| name.as_str())
+        .filter(|name| *name != "DynOperation")
+        .map(|name| name.to_string())
+        .collect();
+    names.sort();
+    let mut fixtures: Vec<String> = operation_fixtures()
+        .iter()
+        .map(|operation| serde_json::to_value(operation).unwrap())
+        .flat_map(|value| {
+            value
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<String>>()
+        })
+        .collect();
+    fixtures.sort();
+    assert_eq!(fixtures, names);
+}
+
+/// Test that the schema has a definition for every operation
+#[test]
+fn operation_definitions() {
+    let schema = serde_json::to_value(json_schema::<Operation>()).unwrap();
+    let variants = schema["oneOf"].as_array().unwrap();
+    let names: Vec<&str> = variants
+        .iter()
+        .flat_map(|variant| variant["required"].as_array().unwrap())
+        .filter_map(|name| name.as_str())
+        .collect();
+    assert_eq!(names.len(), variants.len());
+    assert!(names.contains(&"PragmaConditional"));
+    assert!(names.contains(&"PhaseShiftedControlledZ"));
+    assert!(schema["definitions"]["CalculatorFloat"].is_object());
+}
+
+/// Test that invalid JSON is rejected by the schema
+#[test]
+fn invalid_json() {
+    let schema = serde_json::to_value(json_schema::<Operation>()).unwrap();
+    let definitions = &schema["definitions"];
+    let valid = json!({"RotateX": {"qubit": 0, "theta": "phi"}});
+    assert!(validate(&valid, &schema, definitions, "").is_empty());
+    for invalid in [
+        json!({"RotateX": {"qubit": 0, "theta": true}}),
+        json!({"RotateX": {"theta": 0.1}}),
+        json!({"RotateX": {"qubit": -1, "theta": 0.1}}),
+        json!({"Rotate": {"qubit": 0, "theta": 0.1}}),
+        json!({"PragmaSetStateVector": {"statevector": {"v": 1, "dim": [1, 2], "data": []}}}),
+    ]
+    .iter()
+    {
+        assert!(
+            !validate(invalid, &schema, definitions, "").is_empty(),
+            "{} is valid",
+            invalid
+        );
+    }
+}
+
+/// Test that the JSON of measurements is valid for their schemas
+#[test]
+fn measurement_schemas() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, "theta".into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 100);
+
+    let mut input = BasisRotationInput::new(2, false);
+    input.add_pauli_product("ro".to_string(), vec![0]).unwrap();
+    input
+        .add_pauli_product("ro".to_string(), vec![0, 1])
+        .unwrap();
+    let mut linear = HashMap::new();
+    linear.insert(0, 0.5);
+    input
+        .add_linear_exp_val("linear".to_string(), linear)
+        .unwrap();
+    input
+        .add_symbolic_exp_val("symbolic".to_string(), "pauli_product_1 * 2".into())
+        .unwrap();
+    let measurement = BasisRotation {
+        constant_circuit: Some(nested_circuit()),
+        circuits: vec![circuit.clone()],
+        input,
+    };
+    assert!(schema_errors(&measurement).is_empty());
+
+    let mut input = CheatedBasisRotationInput::new();
+    input.add_pauli_product("ro".to_string());
+    input
+        .add_symbolic_exp_val("symbolic".to_string(), 2.0.into())
+        .unwrap();
+    let measurement = CheatedBasisRotation {
+        constant_circuit: None,
+        circuits: vec![circuit.clone()],
+        input,
+    };
+    assert!(schema_errors(&measurement).is_empty());
+
+    let mut input = CheatedInput::new(1);
+    input
+        .add_operator_exp_val(
+            "operator".to_string(),
+            vec![
+                (0, 0, Complex64::new(1.0, 0.0)),
+                (1, 1, Complex64::new(0.0, -1.0)),
+            ],
+            "ro".to_string(),
+        )
+        .unwrap();
+    let measurement = Cheated {
+        constant_circuit: None,
+        circuits: vec![circuit.clone()],
+        input,
+    };
+    assert!(schema_errors(&measurement).is_empty());
+
+    let measurement = ClassicalRegister {
+        constant_circuit: Some(Circuit::new()),
+        circuits: vec![circuit],
+    };
+    assert!(schema_errors(&measurement).is_empty());
+}
+
+/// Test the pretty printed schema
+#[test]
+fn schema_string() {
+    let schema: Value = serde_json::from_str(&json_schema_string::<Circuit>()).unwrap();
+    assert_eq!(schema["title"], json!("Circuit"));
+    assert_eq!(schema["required"], json!(["definitions", "operations"]));
+}
@@ -18,3 +18,35 @@ mod measurements;
 
 #[cfg(test)]
 mod circuit;
+
+#[cfg(test)]
+mod unitary;
+
+#[cfg(test)]
+mod dag;
+
+#[cfg(test)]
+mod statistics;
+
+#[cfg(test)]
+mod validation;
+
+#[cfg(test)]
+mod passes;
+
+#[cfg(test)]
+mod formats;
+
+#[cfg(test)]
+mod histogram;
+
+#[cfg(test)]
+mod registers;
+
+#[cfg(test)]
+#[cfg(feature = "serialize")]
+mod serialization;
+
+#[cfg(test)]
+#[cfg(feature = "json_schema")]
+mod json_schema;
@@ -12,7 +12,7 @@
 
 //! Integration test for public API of cheated measurement
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use num_complex::Complex64;
 use roqoqo::prelude::*;
@@ -131,6 +131,59 @@ fn test_substitute_parameters_fail() {
     assert!(br_substitutes.is_err());
 }
 
+#[test]
+fn test_free_and_bind_parameters() {
+    let bri = CheatedInput::new(2);
+    let mut constant_circuit = Circuit::new();
+    constant_circuit += operations::InputSymbolic::new("phi".to_string(), 0.5);
+    constant_circuit += operations::RotateZ::new(0, "phi".into());
+    let mut circ1 = Circuit::new();
+    circ1 += operations::RotateX::new(0, "theta".into());
+    let mut circ2 = Circuit::new();
+    circ2 += operations::InputSymbolic::new("gamma".to_string(), 1.0);
+    circ2 += operations::RotateY::new(0, "gamma * theta".into());
+    let br = Cheated {
+        constant_circuit: Some(constant_circuit),
+        circuits: vec![circ1, circ2],
+        input: bri,
+    };
+    let free: HashSet<String> = ["phi", "theta", "gamma"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+    assert_eq!(br.free_parameters(), free);
+
+    let mut map: HashMap<String, f64> = HashMap::new();
+    map.insert("theta".to_string(), 2.0);
+    let bound = br.bind_parameters(map.clone()).unwrap();
+    assert_eq!(
+        bound.constant_circuit.as_ref().unwrap()[1],
+        operations::Operation::from(operations::RotateZ::new(0, 0.5.into()))
+    );
+    assert_eq!(
+        bound.circuits[1][1],
+        operations::Operation::from(operations::RotateY::new(0, 2.0.into()))
+    );
+
+    map.insert("phi".to_string(), 0.1);
+    let bound = br.bind_parameters(map).unwrap();
+    assert_eq!(
+        bound.constant_circuit.as_ref().unwrap()[1],
+        operations::Operation::from(operations::RotateZ::new(0, 0.1.into()))
+    );
+    assert!(bound.free_parameters().is_empty());
+
+    let mut map: HashMap<String, f64> = HashMap::new();
+    map.insert("teta".to_string(), 2.0);
+    assert_eq!(
+        br.bind_parameters(map),
+        Err(RoqoqoError::ParameterBindingError {
+            unbound: vec!["theta".to_string()],
+            extra: vec!["teta".to_string()],
+        })
+    );
+}
+
 #[test_case(vec![vec![Complex64::new(1.0,0.0), Complex64::new(0.0,0.0)]], 1.0, 0.0; "simple_state_diagonal")]
 #[test_case(vec![vec![Complex64::new(0.0,0.0), Complex64::new(0.0,1.0)]], -1.0, 0.0; "simple_state_2_diagonal")]
 #[test_case(vec![vec![Complex64::new(std::f64::consts::FRAC_1_SQRT_2, 0.0), Complex64::new(0.0,std::f64::consts::FRAC_1_SQRT_2)]], 0.0, 1.0; "simple_state_off_diagonal")]
@@ -20,3 +20,5 @@ mod cheated_measurement;
 pub use cheated_measurement::*;
 mod classical_register_measurement;
 pub use classical_register_measurement::*;
+mod parameter_shift_gradient;
+pub use parameter_shift_gradient::*;
@@ -0,0 +1,337 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for public API of parameter-shift gradient measurement
+
+use std::collections::HashMap;
+
+use num_complex::Complex64;
+use roqoqo::measurements::{
+    Cheated, CheatedBasisRotation, CheatedBasisRotationInput, CheatedInput, ParameterShiftGradient,
+};
+use roqoqo::operations::*;
+use roqoqo::prelude::*;
+use roqoqo::registers::ComplexOutputRegister;
+use roqoqo::unitary::circuit_unitary_on_qubits;
+use roqoqo::{Circuit, RoqoqoError};
+use test_case::test_case;
+
+/// Simulates the statevectors read out by the circuits of a measurement
+fn simulate<T: Measure>(
+    measurement: &T,
+    number_qubits: usize,
+) -> HashMap<String, ComplexOutputRegister> {
+    let qubits: Vec<usize> = (0..number_qubits).collect();
+    let mut registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
+    for circuit in measurement.circuits() {
+        let full_circuit = match measurement.constant_circuit() {
+            Some(constant_circuit) => constant_circuit.clone() + circuit,
+            None => circuit.clone(),
+        };
+        let mut gates = Circuit::new();
+        let mut readout: Option<String> = None;
+        for operation in full_circuit.operations().iter() {
+            match operation {
+                Operation::PragmaGetStateVector(op) => readout = Some(op.readout().clone()),
+                _ => gates.add_operation(operation.clone()),
+            }
+        }
+        let unitary = circuit_unitary_on_qubits(&gates, &qubits).unwrap();
+        registers
+            .entry(readout.unwrap())
+            .or_default()
+            .push(unitary.column(0).to_vec());
+    }
+    registers
+}
+
+/// Returns the expectation values of a measurement for the given parameters
+fn expectation_values(
+    measurement: &Cheated,
+    parameters: HashMap<String, f64>,
+    number_qubits: usize,
+) -> HashMap<String, f64> {
+    let substituted = measurement.substitute_parameters(parameters).unwrap();
+    let registers = simulate(&substituted, number_qubits);
+    substituted
+        .evaluate(HashMap::new(), HashMap::new(), registers)
+        .unwrap()
+        .unwrap()
+}
+
+/// Evaluates the gradient measurement on simulated registers
+fn gradient(
+    measurement: &Cheated,
+    parameters: HashMap<String, f64>,
+    number_qubits: usize,
+) -> HashMap<(String, String), f64> {
+    let gradient = ParameterShiftGradient::new(measurement.clone(), parameters).unwrap();
+    let registers = simulate(&gradient, number_qubits);
+    gradient
+        .evaluate(HashMap::new(), HashMap::new(), registers)
+        .unwrap()
+        .unwrap()
+}
+
+/// Returns the derivatives of the expectation values from central finite differences
+fn finite_difference(
+    measurement: &Cheated,
+    parameters: HashMap<String, f64>,
+    number_qubits: usize,
+) -> HashMap<(String, String), f64> {
+    let step = 1e-6;
+    let mut derivatives: HashMap<(String, String), f64> = HashMap::new();
+    for (parameter, value) in parameters.iter() {
+        let mut forward = parameters.clone();
+        forward.insert(parameter.clone(), value + step);
+        let mut backward = parameters.clone();
+        backward.insert(parameter.clone(), value - step);
+        let forward = expectation_values(measurement, forward, number_qubits);
+        let backward = expectation_values(measurement, backward, number_qubits);
+        for (name, forward_value) in forward.iter() {
+            derivatives.insert(
+                (name.clone(), parameter.clone()),
+                (forward_value - backward[name]) / (2.0 * step),
+            );
+        }
+    }
+    derivatives
+}
+
+/// Returns a cheated measurement of the Z operator on qubit 0
+fn z_measurement(
+    number_qubits: usize,
+    circuit: Circuit,
+    constant_circuit: Option<Circuit>,
+) -> Cheated {
+    let dimension = 2_usize.pow(number_qubits as u32);
+    let operator: Vec<(usize, usize, Complex64)> = (0..dimension)
+        .map(|index| {
+            let sign = if index % 2 == 0 { 1.0 } else { -1.0 };
+            (index, index, Complex64::new(sign, 0.0))
+        })
+        .collect();
+    let mut input = CheatedInput::new(number_qubits);
+    input
+        .add_operator_exp_val("z".to_string(), operator, "ro".to_string())
+        .unwrap();
+    let mut circuit = circuit;
+    circuit += DefinitionComplex::new("ro".to_string(), dimension, true);
+    circuit += PragmaGetStateVector::new("ro".to_string(), None);
+    Cheated {
+        constant_circuit,
+        circuits: vec![circuit],
+        input,
+    }
+}
+
+/// Asserts that the derivatives agree with the expected derivatives
+fn assert_derivatives_close(
+    derivatives: &HashMap<(String, String), f64>,
+    expected: &HashMap<(String, String), f64>,
+) {
+    assert_eq!(derivatives.len(), expected.len());
+    for (key, value) in expected.iter() {
+        assert!(
+            (derivatives[key] - value).abs() < 1e-6,
+            "{:?}: {} != {}",
+            key,
+            derivatives[key],
+            value
+        );
+    }
+}
+
+/// Test the two-term shift rule for a rotation
+#[test]
+fn test_rotate_x() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    let measurement = z_measurement(1, circuit, None);
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("theta".to_string(), 0.4);
+
+    let shift_gradient =
+        ParameterShiftGradient::new(measurement.clone(), parameters.clone()).unwrap();
+    assert_eq!(shift_gradient.number_shifted_circuits(), 2);
+    assert_eq!(shift_gradient.circuits().count(), 3);
+    assert!(shift_gradient.constant_circuit().is_none());
+
+    let derivatives = gradient(&measurement, parameters, 1);
+    let mut expected: HashMap<(String, String), f64> = HashMap::new();
+    expected.insert(("z".to_string(), "theta".to_string()), -(0.4_f64).sin());
+    assert_derivatives_close(&derivatives, &expected);
+}
+
+/// Test a parameter appearing in several gates and in the constant circuit
+#[test]
+fn test_multiple_occurrences() {
+    let mut constant_circuit = Circuit::new();
+    constant_circuit += RotateY::new(0, "theta".into());
+    constant_circuit += Hadamard::new(1);
+    let mut circuit = Circuit::new();
+    circuit += ControlledPhaseShift::new(1, 0, "phi".into());
+    circuit += VariableMSXX::new(0, 1, "theta".into());
+    circuit += RotateAroundSphericalAxis::new(0, "phi".into(), 0.3.into(), 0.7.into());
+    circuit += RotateZ::new(1, "theta".into());
+    circuit += RotateX::new(0, "phi".into());
+    let measurement = z_measurement(2, circuit, Some(constant_circuit));
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("theta".to_string(), 0.4);
+    parameters.insert("phi".to_string(), -1.1);
+
+    let shift_gradient =
+        ParameterShiftGradient::new(measurement.clone(), parameters.clone()).unwrap();
+    assert_eq!(shift_gradient.number_shifted_circuits(), 12);
+
+    let derivatives = gradient(&measurement, parameters.clone(), 2);
+    let expected = finite_difference(&measurement, parameters, 2);
+    assert_derivatives_close(&derivatives, &expected);
+}
+
+/// Test the generalized shift rule of the XY gate
+#[test]
+fn test_xy() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += RotateY::new(1, 0.3.into());
+    circuit += XY::new(0, 1, "theta".into());
+    circuit += Hadamard::new(0);
+    let measurement = z_measurement(2, circuit, None);
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("theta".to_string(), 0.9);
+
+    let shift_gradient =
+        ParameterShiftGradient::new(measurement.clone(), parameters.clone()).unwrap();
+    assert_eq!(shift_gradient.number_shifted_circuits(), 4);
+
+    let derivatives = gradient(&measurement, parameters.clone(), 2);
+    let expected = finite_difference(&measurement, parameters, 2);
+    assert_derivatives_close(&derivatives, &expected);
+}
+
+/// Test error for angles given by expressions of parameters instead of a single parameter
+#[test_case("2 * theta", "theta"; "scaled parameter")]
+#[test_case("theta * phi", "phi"; "product of parameters")]
+#[test_case("1/(phi-1) + theta", "phi"; "singular expression")]
+#[test_case("sqrt(theta)", "theta"; "not differentiable")]
+fn test_expression(angle: &str, name: &str) {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    circuit += RotateY::new(0, angle.into());
+    let measurement = z_measurement(1, circuit, None);
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("theta".to_string(), 0.0);
+    parameters.insert("phi".to_string(), 0.5);
+
+    assert_eq!(
+        ParameterShiftGradient::new(measurement, parameters),
+        Err(RoqoqoError::ParameterShiftNotApplicable {
+            name: name.to_string(),
+            hqslang: "RotateY"
+        })
+    );
+}
+
+/// Test that parameters not appearing in the circuits have vanishing derivatives
+#[test]
+fn test_unused_parameter() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    let measurement = z_measurement(1, circuit, None);
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("theta".to_string(), 0.0);
+    parameters.insert("unused".to_string(), 1.0);
+
+    let derivatives = gradient(&measurement, parameters, 1);
+    let mut expected: HashMap<(String, String), f64> = HashMap::new();
+    expected.insert(("z".to_string(), "theta".to_string()), 0.0);
+    expected.insert(("z".to_string(), "unused".to_string()), 0.0);
+    assert_derivatives_close(&derivatives, &expected);
+}
+
+/// Test error for parameters in gates without a shift rule
+#[test]
+fn test_not_applicable() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    circuit += GivensRotation::new(0, 1, "theta".into(), 0.0.into());
+    let measurement = z_measurement(2, circuit, None);
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("theta".to_string(), 0.0);
+
+    assert_eq!(
+        ParameterShiftGradient::new(measurement, parameters),
+        Err(RoqoqoError::ParameterShiftNotApplicable {
+            name: "theta".to_string(),
+            hqslang: "GivensRotation"
+        })
+    );
+}
+
+/// Test that incomplete measurements are reported
+#[test]
+fn test_incomplete_registers() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    let measurement = z_measurement(1, circuit, None);
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("theta".to_string(), 0.0);
+    let gradient = ParameterShiftGradient::new(measurement, parameters).unwrap();
+
+    let mut registers = simulate(&gradient, 1);
+    registers.remove("ro_parameter_shift_2");
+    assert_eq!(
+        gradient.evaluate(HashMap::new(), HashMap::new(), registers),
+        Err(RoqoqoError::MissingRegister {
+            name: "ro".to_string()
+        })
+    );
+}
+
+/// Test error for expectation values that are not linear in the measured Pauli products
+#[test]
+fn test_nonlinear_expectation_value() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    circuit += DefinitionFloat::new("ro".to_string(), 1, true);
+    circuit += PragmaGetPauliProduct::new(
+        vec![(0, 3)].into_iter().collect(),
+        "ro".to_string(),
+        Circuit::new(),
+    );
+    let mut input = CheatedBasisRotationInput::new();
+    let index = input.add_pauli_product("ro".to_string());
+    input
+        .add_linear_exp_val("z".to_string(), vec![(index, 1.0)].into_iter().collect())
+        .unwrap();
+    input
+        .add_symbolic_exp_val(
+            "z_squared".to_string(),
+            "pauli_product_0 * pauli_product_0".into(),
+        )
+        .unwrap();
+    let measurement = CheatedBasisRotation {
+        constant_circuit: None,
+        circuits: vec![circuit],
+        input,
+    };
+    let mut parameters: HashMap<String, f64> = HashMap::new();
+    parameters.insert("theta".to_string(), 0.4);
+
+    assert_eq!(
+        ParameterShiftGradient::new(measurement, parameters),
+        Err(RoqoqoError::NonlinearExpectationValue {
+            name: "z_squared".to_string()
+        })
+    );
+}
@@ -0,0 +1,164 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for public API of classical operations
+
+use qoqo_calculator::{Calculator, CalculatorError, CalculatorFloat};
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+use std::collections::HashMap;
+use test_case::test_case;
+
+fn register() -> Vec<bool> {
+    vec![true, false, true, true]
+}
+
+#[test_case(ClassicalXor::new("ro".to_string(), vec![0, 1], "out".to_string(), 0).evaluate(&register()), true; "xor")]
+#[test_case(ClassicalXor::new("ro".to_string(), vec![0, 2, 3], "out".to_string(), 0).evaluate(&register()), true; "parity odd")]
+#[test_case(ClassicalXor::new("ro".to_string(), vec![0, 2], "out".to_string(), 0).evaluate(&register()), false; "parity even")]
+#[test_case(ClassicalXor::new("ro".to_string(), vec![], "out".to_string(), 0).evaluate(&register()), false; "parity empty")]
+#[test_case(ClassicalAnd::new("ro".to_string(), vec![0, 2, 3], "out".to_string(), 0).evaluate(&register()), true; "and true")]
+#[test_case(ClassicalAnd::new("ro".to_string(), vec![0, 1], "out".to_string(), 0).evaluate(&register()), false; "and false")]
+#[test_case(ClassicalAnd::new("ro".to_string(), vec![], "out".to_string(), 0).evaluate(&register()), true; "and empty")]
+#[test_case(ClassicalOr::new("ro".to_string(), vec![1, 3], "out".to_string(), 0).evaluate(&register()), true; "or true")]
+#[test_case(ClassicalOr::new("ro".to_string(), vec![1, 1], "out".to_string(), 0).evaluate(&register()), false; "or false")]
+#[test_case(ClassicalOr::new("ro".to_string(), vec![], "out".to_string(), 0).evaluate(&register()), false; "or empty")]
+#[test_case(ClassicalNot::new("ro".to_string(), 1, "out".to_string(), 0).evaluate(&register()), true; "not")]
+#[test_case(ClassicalCopy::new("ro".to_string(), 2, "out".to_string(), 0).evaluate(&register()), true; "copy")]
+#[test_case(ClassicalGreaterThan::new("x".to_string(), 1, 0.5.into(), "out".to_string(), 0).evaluate(&[0.2, 0.7]), true; "greater true")]
+#[test_case(ClassicalGreaterThan::new("x".to_string(), 0, 0.5.into(), "out".to_string(), 0).evaluate(&[0.5, 0.7]), false; "greater equal")]
+#[test_case(ClassicalLessThan::new("x".to_string(), 0, 0.5.into(), "out".to_string(), 0).evaluate(&[0.2, 0.7]), true; "less true")]
+#[test_case(ClassicalLessThan::new("x".to_string(), 1, 0.5.into(), "out".to_string(), 0).evaluate(&[0.2, 0.7]), false; "less false")]
+fn test_classical_evaluate(result: Result<bool, RoqoqoError>, expected: bool) {
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_classical_evaluate_errors() {
+    let xor = ClassicalXor::new("ro".to_string(), vec![0, 4], "out".to_string(), 0);
+    assert_eq!(
+        xor.evaluate(&register()),
+        Err(RoqoqoError::ClassicalRegisterError {
+            name: "ro".to_string(),
+            msg: "Index 4 is out of range of register with length 4".to_string()
+        })
+    );
+    let greater = ClassicalGreaterThan::new("x".to_string(), 2, 0.5.into(), "out".to_string(), 0);
+    assert!(matches!(
+        greater.evaluate(&[0.1, 0.2]),
+        Err(RoqoqoError::ClassicalRegisterError { .. })
+    ));
+    let symbolic =
+        ClassicalLessThan::new("x".to_string(), 0, "threshold".into(), "out".to_string(), 0);
+    assert!(matches!(
+        symbolic.evaluate(&[0.1]),
+        Err(RoqoqoError::CalculatorError(
+            CalculatorError::FloatSymbolicNotConvertable { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_classical_traits() {
+    let xor = ClassicalXor::new("ro".to_string(), vec![0, 1], "syndrome".to_string(), 2);
+    assert_eq!(xor.register(), "ro");
+    assert_eq!(xor.indices(), &vec![0, 1]);
+    assert_eq!(xor.output_register(), "syndrome");
+    assert_eq!(xor.output_index(), &2);
+    assert_eq!(xor.hqslang(), "ClassicalXor");
+    assert_eq!(
+        xor.tags(),
+        &["Operation", "ClassicalOperation", "ClassicalXor"]
+    );
+    assert_eq!(xor.involved_qubits(), InvolvedQubits::None);
+    assert!(!xor.is_parametrized());
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(0, 1);
+    assert_eq!(xor.remap_qubits(&mapping), Ok(xor.clone()));
+    assert_eq!(
+        format!("{:?}", xor),
+        "ClassicalXor { register: \"ro\", indices: [0, 1], output_register: \"syndrome\", output_index: 2 }"
+    );
+
+    let operation: Operation = xor.clone().into();
+    assert_eq!(operation.involved_qubits(), InvolvedQubits::None);
+
+    let greater =
+        ClassicalGreaterThan::new("x".to_string(), 0, "threshold".into(), "out".to_string(), 1);
+    assert!(greater.is_parametrized());
+    let mut calculator = Calculator::new();
+    calculator.set_variable("threshold", 0.25);
+    let substituted = greater.substitute_parameters(&mut calculator).unwrap();
+    assert_eq!(substituted.threshold(), &CalculatorFloat::from(0.25));
+    assert_eq!(substituted.evaluate(&[0.5]), Ok(true));
+}
+
+#[test]
+fn test_conditional_pattern() {
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(1);
+    circuit += CNOT::new(1, 2);
+    let pragma =
+        PragmaConditionalPattern::new("syndrome".to_string(), vec![(0, true), (2, false)], circuit);
+    assert_eq!(pragma.condition(), &vec![(0, true), (2, false)]);
+    assert_eq!(pragma.condition_is_met(&[true, true, false]), Ok(true));
+    assert_eq!(pragma.condition_is_met(&[true, false, true]), Ok(false));
+    assert_eq!(pragma.condition_is_met(&[false, false, false]), Ok(false));
+    assert!(matches!(
+        pragma.condition_is_met(&[true, false]),
+        Err(RoqoqoError::ClassicalRegisterError { .. })
+    ));
+
+    let mut qubits = std::collections::HashSet::new();
+    qubits.insert(1);
+    qubits.insert(2);
+    assert_eq!(pragma.involved_qubits(), InvolvedQubits::Set(qubits));
+    assert_eq!(
+        pragma.tags(),
+        &["Operation", "PragmaOperation", "PragmaConditionalPattern"]
+    );
+
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(1, 3);
+    mapping.insert(2, 0);
+    let mut remapped_circuit = Circuit::new();
+    remapped_circuit += PauliX::new(3);
+    remapped_circuit += CNOT::new(3, 0);
+    assert_eq!(
+        pragma.remap_qubits(&mapping),
+        Ok(PragmaConditionalPattern::new(
+            "syndrome".to_string(),
+            vec![(0, true), (2, false)],
+            remapped_circuit
+        ))
+    );
+}
+
+#[test]
+fn test_conditional_pattern_substitute() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    let pragma = PragmaConditionalPattern::new("ro".to_string(), vec![(0, false)], circuit);
+    assert!(pragma.is_parametrized());
+    let mut calculator = Calculator::new();
+    calculator.set_variable("theta", 1.0);
+    let mut substituted_circuit = Circuit::new();
+    substituted_circuit += RotateX::new(0, 1.0.into());
+    assert_eq!(
+        pragma.substitute_parameters(&mut calculator),
+        Ok(PragmaConditionalPattern::new(
+            "ro".to_string(),
+            vec![(0, false)],
+            substituted_circuit
+        ))
+    );
+}
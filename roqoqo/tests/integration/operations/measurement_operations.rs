@@ -755,6 +755,25 @@ fn pragma_get_pauli_product_substitute_trait() {
     assert_eq!(result, test_gate)
 }
 
+/// Test PragmaGetPauliProduct Substitute trait returns errors of the nested circuit
+#[test]
+fn pragma_get_pauli_product_substitute_error() {
+    let mut qubit_paulis: HashMap<usize, usize> = HashMap::new();
+    qubit_paulis.insert(0, 1);
+    let mut circuit = Circuit::new();
+    circuit.add_operation(RotateX::new(0, CalculatorFloat::from("theta")));
+    let pragma = PragmaGetPauliProduct::new(qubit_paulis, String::from("ro"), circuit);
+
+    let mut substitution_dict: Calculator = Calculator::new();
+    let result = pragma.substitute_parameters(&mut substitution_dict);
+    assert!(result.is_err());
+
+    let mut qubit_mapping_test: HashMap<usize, usize> = HashMap::new();
+    qubit_mapping_test.insert(1, 2);
+    let result = pragma.remap_qubits(&qubit_mapping_test);
+    assert_eq!(result, Err(RoqoqoError::QubitMappingError { qubit: 0 }));
+}
+
 /// Test PragmaGetPauliProduct Serialization and Deserialization traits (readable)
 #[cfg(feature = "serialize")]
 #[test]
@@ -988,3 +1007,33 @@ fn pragma_repeated_measurement_serde_compact() {
         ],
     );
 }
+
+/// Test remapping qubit mappings of PragmaRepeatedMeasurement and PragmaGetPauliProduct with a permutation
+#[test]
+fn pragma_qubit_mapping_remap_permutation() {
+    let mut qubit_mapping_test: HashMap<usize, usize> = HashMap::new();
+    qubit_mapping_test.insert(0, 1);
+    qubit_mapping_test.insert(1, 0);
+
+    let mut measured: HashMap<usize, usize> = HashMap::new();
+    measured.insert(0, 0);
+    measured.insert(1, 1);
+    measured.insert(2, 2);
+    let mut measured_remapped: HashMap<usize, usize> = HashMap::new();
+    measured_remapped.insert(1, 0);
+    measured_remapped.insert(0, 1);
+    measured_remapped.insert(2, 2);
+    let pragma = PragmaRepeatedMeasurement::new(String::from("ro"), Some(measured.clone()), 2);
+    let result = pragma.remap_qubits(&qubit_mapping_test).unwrap();
+    assert_eq!(
+        result,
+        PragmaRepeatedMeasurement::new(String::from("ro"), Some(measured_remapped.clone()), 2)
+    );
+
+    let pragma = PragmaGetPauliProduct::new(measured, String::from("ro"), Circuit::new());
+    let result = pragma.remap_qubits(&qubit_mapping_test).unwrap();
+    assert_eq!(
+        result,
+        PragmaGetPauliProduct::new(measured_remapped, String::from("ro"), Circuit::new())
+    );
+}
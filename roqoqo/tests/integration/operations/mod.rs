@@ -10,6 +10,12 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use std::collections::HashSet;
+use test_case::test_case;
+
 mod define_operations;
 pub use define_operations::*;
 
@@ -38,3 +44,25 @@ fn test_involved_qubits_clone() {
     let helper = iq != iq3;
     assert!(helper);
 }
+
+mod classical_operations;
+pub use classical_operations::*;
+
+// Test free_parameters of the Substitute trait reads the symbolic parameters of operations and nested circuits
+#[test_case(Operation::from(RotateX::new(0, CalculatorFloat::from("2 * theta + phi"))), &["phi", "theta"]; "RotateX")]
+#[test_case(Operation::from(RotateX::new(0, CalculatorFloat::from(1.0))), &[]; "constant RotateX")]
+#[test_case(Operation::from(DefinitionFloat::new("theta".to_string(), 1, false)), &[]; "DefinitionFloat")]
+#[test_case(Operation::from(PragmaLoop::new(CalculatorFloat::from("n"), circuit_with_symbol("theta"))), &["n", "theta"]; "PragmaLoop")]
+#[test_case(Operation::from(PragmaConditional::new("Str(\"a\")".to_string(), 0, circuit_with_symbol("theta"))), &["theta"]; "PragmaConditional")]
+#[test_case(Operation::from(PragmaGetStateVector::new("ro".to_string(), Some(circuit_with_symbol("theta")))), &["theta"]; "PragmaGetStateVector")]
+#[test_case(Operation::from(PragmaGetStateVector::new("ro".to_string(), None)), &[]; "PragmaGetStateVector without circuit")]
+fn test_free_parameters(operation: Operation, expected: &[&str]) {
+    let expected: HashSet<String> = expected.iter().map(|name| name.to_string()).collect();
+    assert_eq!(operation.free_parameters(), expected);
+}
+
+fn circuit_with_symbol(symbol: &str) -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, CalculatorFloat::from(symbol));
+    circuit
+}
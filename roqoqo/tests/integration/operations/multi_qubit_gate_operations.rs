@@ -14,12 +14,16 @@
 
 use std::collections::{HashMap, HashSet};
 
-use ndarray::array;
+use nalgebra as na;
+use ndarray::{array, Array2};
 use num_complex::Complex64;
 use qoqo_calculator::Calculator;
 use qoqo_calculator::CalculatorFloat;
 use roqoqo::operations::*;
-use roqoqo::Circuit;
+use roqoqo::statistics::CircuitStatistics;
+use roqoqo::unitary::circuit_unitary_on_qubits;
+use roqoqo::{Circuit, RoqoqoError};
+use std::convert::TryFrom;
 use test_case::test_case;
 /// Test circuit function of MultiQubitMolmerSorensen
 #[test_case(vec![0,1]; "two_qubit")]
@@ -296,3 +300,561 @@ fn test_rotatex_powercf(theta: CalculatorFloat, power: CalculatorFloat) {
     assert_eq!(power_gate, test_gate);
     assert_eq!(power_gate.theta(), test_gate.theta());
 }
+
+/// Returns a pseudo random unitary matrix acting on a number of qubits
+fn random_unitary(number_qubits: usize, seed: u64) -> Array2<Complex64> {
+    let dimension = 2_usize.pow(number_qubits as u32);
+    let mut state = seed;
+    let mut random = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1_u64 << 53) as f64 - 0.5
+    };
+    let matrix = na::DMatrix::from_fn(dimension, dimension, |_, _| {
+        Complex64::new(random(), random())
+    });
+    let q = matrix.qr().q();
+    Array2::from_shape_fn((dimension, dimension), |(row, column)| q[(row, column)])
+}
+
+/// Returns the unitary of a permutation of basis states
+fn permutation_matrix(permutation: &[usize]) -> Array2<Complex64> {
+    let dimension = permutation.len();
+    Array2::from_shape_fn((dimension, dimension), |(row, column)| {
+        if permutation[column] == row {
+            Complex64::new(1.0, 0.0)
+        } else {
+            Complex64::new(0.0, 0.0)
+        }
+    })
+}
+
+/// Test that the decomposition of MultiQubitGate reproduces its unitary including the global phase
+#[test_case(vec![3], random_unitary(1, 1); "single qubit")]
+#[test_case(vec![0, 1], random_unitary(2, 2); "two qubits")]
+#[test_case(vec![0, 1, 2], random_unitary(3, 3); "three qubits")]
+#[test_case(vec![2, 0, 1], random_unitary(3, 4); "three unordered qubits")]
+#[test_case(vec![0, 1, 2, 3], random_unitary(4, 5); "four qubits")]
+#[test_case(vec![0, 1, 2], Array2::eye(8); "identity")]
+#[test_case(vec![1, 0], permutation_matrix(&[0, 1, 3, 2]); "cnot")]
+#[test_case(vec![0, 1], permutation_matrix(&[0, 2, 1, 3]); "swap")]
+#[test_case(vec![0, 1, 2], permutation_matrix(&[0, 1, 2, 3, 4, 5, 7, 6]); "toffoli")]
+#[test_case(vec![0, 1, 2], permutation_matrix(&[1, 2, 3, 4, 5, 6, 7, 0]); "increment")]
+#[test_case(vec![0, 1], Array2::from_diag(&array![Complex64::new(0.0, 1.0), Complex64::new(-1.0, 0.0), Complex64::new(1.0, 0.0), Complex64::new(0.0, -1.0)]); "diagonal")]
+fn test_multi_qubit_gate_circuit(qubits: Vec<usize>, matrix: Array2<Complex64>) {
+    let gate = MultiQubitGate::from_unitary(qubits.clone(), matrix.clone()).unwrap();
+    let circuit = gate.circuit();
+    for operation in circuit.iter() {
+        assert!(
+            matches!(
+                operation,
+                Operation::RotateY(_)
+                    | Operation::RotateZ(_)
+                    | Operation::CNOT(_)
+                    | Operation::PragmaGlobalPhase(_)
+            ),
+            "{:?}",
+            operation
+        );
+    }
+    // circuit_unitary_on_qubits expects the least significant qubit first
+    let reversed: Vec<usize> = qubits.iter().rev().cloned().collect();
+    let decomposed = circuit_unitary_on_qubits(&circuit, &reversed).unwrap();
+    let difference = decomposed - matrix;
+    assert!(
+        difference.iter().all(|x| x.norm() < 1e-10),
+        "{}",
+        difference
+    );
+}
+
+/// Test the unitary matrix and the checks of MultiQubitGate
+#[test]
+fn test_multi_qubit_gate_unitary() {
+    let matrix = random_unitary(2, 7);
+    let gate = MultiQubitGate::from_unitary(vec![0, 1], matrix.clone()).unwrap();
+    assert_eq!(gate.unitary_matrix().unwrap(), matrix);
+    assert_eq!(gate.matrix(), &matrix);
+    assert_eq!(gate, MultiQubitGate::new(vec![0, 1], matrix.clone()));
+
+    assert_eq!(
+        MultiQubitGate::from_unitary(vec![0, 1, 2], matrix.clone()),
+        Err(RoqoqoError::MismatchedUnitaryDimension {
+            rows: 4,
+            columns: 4,
+            number_qubits: 3
+        })
+    );
+    let not_unitary = matrix.mapv(|x| x * 1.1);
+    assert!(matches!(
+        MultiQubitGate::from_unitary(vec![0, 1], not_unitary.clone()),
+        Err(RoqoqoError::NonUnitaryMatrix { .. })
+    ));
+    let gate = MultiQubitGate::new(vec![0, 1], not_unitary);
+    assert!(matches!(
+        gate.unitary_matrix(),
+        Err(RoqoqoError::NonUnitaryMatrix { .. })
+    ));
+    let gate = MultiQubitGate::new(vec![0, 1], Array2::zeros((4, 2)));
+    assert!(matches!(
+        gate.unitary_matrix(),
+        Err(RoqoqoError::MismatchedUnitaryDimension { .. })
+    ));
+}
+
+/// Test the Operate, InvolveQubits and Substitute traits of MultiQubitGate
+#[test]
+fn test_multi_qubit_gate_traits() {
+    let matrix = random_unitary(3, 8);
+    let gate = MultiQubitGate::new(vec![0, 1, 2], matrix.clone());
+    assert_eq!(gate.hqslang(), "MultiQubitGate");
+    assert_eq!(
+        gate.tags(),
+        &[
+            "Operation",
+            "GateOperation",
+            "MultiQubitGateOperation",
+            "MultiQubitGate",
+        ]
+    );
+    assert!(!gate.is_parametrized());
+    assert_eq!(gate.qubits(), &vec![0, 1, 2]);
+    let comp_set: HashSet<usize> = [0, 1, 2].iter().cloned().collect();
+    assert_eq!(gate.involved_qubits(), InvolvedQubits::Set(comp_set));
+    assert!(format!("{:?}", gate).contains("MultiQubitGate"));
+
+    let mut calc = Calculator::new();
+    assert_eq!(gate.substitute_parameters(&mut calc).unwrap(), gate);
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(0, 2);
+    mapping.insert(1, 0);
+    mapping.insert(2, 1);
+    let remapped = gate.remap_qubits(&mapping).unwrap();
+    assert_eq!(remapped, MultiQubitGate::new(vec![2, 0, 1], matrix));
+    mapping.remove(&2);
+    assert!(gate.remap_qubits(&mapping).is_err());
+
+    let operation = Operation::from(gate.clone());
+    assert_eq!(
+        MultiQubitGateOperation::try_from(operation).unwrap(),
+        MultiQubitGateOperation::from(gate)
+    );
+}
+
+/// Returns the diagonal matrix with all entries one except for the last entry
+fn last_phase_matrix(dimension: usize, last: Complex64) -> Array2<Complex64> {
+    let mut matrix: Array2<Complex64> = Array2::eye(dimension);
+    matrix[(dimension - 1, dimension - 1)] = last;
+    matrix
+}
+
+/// Test the unitary matrices of the multi-controlled gates against the known matrices
+#[test_case(Toffoli::new(vec![0, 1, 2]).into(), permutation_matrix(&[0, 1, 2, 3, 4, 5, 7, 6]); "toffoli")]
+#[test_case(ControlledControlledPauliZ::new(vec![0, 1, 2]).into(), last_phase_matrix(8, Complex64::new(-1.0, 0.0)); "ccz")]
+#[test_case(ControlledControlledPhaseShift::new(vec![0, 1, 2], 0.3.into()).into(), last_phase_matrix(8, Complex64::from_polar(1.0, 0.3)); "ccphase")]
+#[test_case(MultiControlledGate::pauli_x(vec![0, 1, 2]).into(), permutation_matrix(&[0, 1, 2, 3, 4, 5, 7, 6]); "pauli_x")]
+#[test_case(MultiControlledGate::pauli_x(vec![0]).into(), permutation_matrix(&[1, 0]); "pauli_x_without_controls")]
+#[test_case(MultiControlledGate::pauli_z(vec![0, 1, 2, 3]).into(), last_phase_matrix(16, Complex64::new(-1.0, 0.0)); "pauli_z")]
+#[test_case(MultiControlledGate::phase_shift(vec![0, 1], 0.3.into()).into(), last_phase_matrix(4, Complex64::from_polar(1.0, 0.3)); "phase_shift")]
+fn test_multi_controlled_unitary(gate: MultiQubitGateOperation, matrix: Array2<Complex64>) {
+    let difference = gate.unitary_matrix().unwrap() - matrix;
+    assert!(
+        difference.iter().all(|x| x.norm() < 1e-10),
+        "{}",
+        difference
+    );
+}
+
+/// Test that the ancilla-free decompositions of the multi-controlled gates reproduce their unitaries
+#[test_case(Toffoli::new(vec![0, 1, 2]).into(); "toffoli")]
+#[test_case(Toffoli::new(vec![2, 0, 1]).into(); "toffoli_unordered")]
+#[test_case(ControlledControlledPauliZ::new(vec![1, 2, 0]).into(); "ccz")]
+#[test_case(ControlledControlledPhaseShift::new(vec![0, 2, 1], 0.7.into()).into(); "ccphase")]
+#[test_case(MultiControlledGate::new(vec![3], 0.1.into(), 0.2.into(), 0.3.into(), 0.4.into()).into(); "no_control")]
+#[test_case(MultiControlledGate::new(vec![1, 0], 0.5.into(), (-0.2).into(), 1.3.into(), 0.4.into()).into(); "one_control")]
+#[test_case(MultiControlledGate::new(vec![0, 2, 1], 0.5.into(), (-0.2).into(), 1.3.into(), 0.4.into()).into(); "two_controls")]
+#[test_case(MultiControlledGate::new(vec![3, 0, 1, 2], 0.5.into(), (-0.2).into(), 1.3.into(), 0.4.into()).into(); "three_controls")]
+#[test_case(MultiControlledGate::new(vec![0, 1, 2, 3, 4], (-0.9).into(), 0.8.into(), 2.1.into(), (-0.6).into()).into(); "four_controls")]
+#[test_case(MultiControlledGate::pauli_x(vec![0, 1, 2, 3]).into(); "pauli_x")]
+#[test_case(MultiControlledGate::pauli_z(vec![0, 1, 2, 3]).into(); "pauli_z")]
+#[test_case(MultiControlledGate::phase_shift(vec![0, 1, 2, 3], 0.3.into()).into(); "phase_shift")]
+#[test_case(MultiControlledGate::from_unitary(vec![2, 0, 1], random_unitary(1, 9)).unwrap().into(); "from_unitary")]
+fn test_multi_controlled_circuit(gate: MultiQubitGateOperation) {
+    let circuit = gate.circuit();
+    for operation in circuit.iter() {
+        assert!(
+            SingleQubitGateOperation::try_from(operation.clone()).is_ok()
+                || TwoQubitGateOperation::try_from(operation.clone()).is_ok()
+                || matches!(operation, Operation::PragmaGlobalPhase(_)),
+            "{:?}",
+            operation
+        );
+    }
+    // circuit_unitary_on_qubits expects the least significant qubit first
+    let reversed: Vec<usize> = gate.qubits().iter().rev().cloned().collect();
+    let decomposed = circuit_unitary_on_qubits(&circuit, &reversed).unwrap();
+    let difference = decomposed - gate.unitary_matrix().unwrap();
+    assert!(
+        difference.iter().all(|x| x.norm() < 1e-10),
+        "{}",
+        difference
+    );
+}
+
+/// Test that the decomposition with ancilla qubits reproduces the unitary and returns the ancillas to |0>
+#[test_case(vec![0, 1, 2, 3], vec![4]; "three_controls")]
+#[test_case(vec![4, 0, 1, 2, 3], vec![5, 6]; "four_controls")]
+#[test_case(vec![0, 1, 2, 3, 4, 5], vec![6, 7, 8, 9]; "five_controls_more_ancillas")]
+fn test_multi_controlled_circuit_with_ancillas(qubits: Vec<usize>, ancillas: Vec<usize>) {
+    let gate = MultiControlledGate::new(
+        qubits.clone(),
+        0.5.into(),
+        (-0.2).into(),
+        1.3.into(),
+        0.4.into(),
+    );
+    let circuit = gate.circuit_with_ancillas(&ancillas).unwrap();
+    assert!(circuit.len() < gate.circuit().len());
+    let used_ancillas = &ancillas[..qubits.len() - 3];
+    let mut all_qubits: Vec<usize> = qubits.iter().chain(used_ancillas.iter()).cloned().collect();
+    all_qubits.reverse();
+    let decomposed = circuit_unitary_on_qubits(&circuit, &all_qubits).unwrap();
+    let matrix = gate.unitary_matrix().unwrap();
+    let ancilla_dimension = 2_usize.pow(used_ancillas.len() as u32);
+    for row in 0..matrix.nrows() {
+        for column in 0..matrix.ncols() {
+            let value = decomposed[(row * ancilla_dimension, column * ancilla_dimension)];
+            assert!(
+                (value - matrix[(row, column)]).norm() < 1e-10,
+                "{} {}",
+                row,
+                column
+            );
+        }
+    }
+}
+
+/// Test the errors of the decomposition with ancilla qubits
+#[test]
+fn test_multi_controlled_circuit_with_ancillas_errors() {
+    let gate = MultiControlledGate::pauli_x(vec![0, 1, 2, 3, 4]);
+    assert!(matches!(
+        gate.circuit_with_ancillas(&[5]),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+    assert!(matches!(
+        gate.circuit_with_ancillas(&[5, 2]),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+    assert!(matches!(
+        gate.circuit_with_ancillas(&[5, 5]),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+    assert!(gate.circuit_with_ancillas(&[5, 6, 2]).is_ok());
+    let gate = MultiControlledGate::pauli_x(vec![]);
+    assert!(matches!(
+        gate.circuit_with_ancillas(&[5]),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+    assert!(matches!(
+        gate.unitary_matrix(),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+    assert!(gate.circuit().is_empty());
+    let gate = MultiControlledGate::pauli_x(vec![0, 1, 2]);
+    assert_eq!(gate.circuit_with_ancillas(&[]).unwrap(), gate.circuit());
+}
+
+/// Test the traits of the multi-controlled gates
+#[test]
+fn test_multi_controlled_traits() {
+    let toffoli = Toffoli::new(vec![0, 1, 2]);
+    assert_eq!(toffoli.hqslang(), "Toffoli");
+    assert_eq!(
+        toffoli.tags(),
+        &[
+            "Operation",
+            "GateOperation",
+            "MultiQubitGateOperation",
+            "Toffoli",
+        ]
+    );
+    assert!(!toffoli.is_parametrized());
+    let comp_set: HashSet<usize> = [0, 1, 2].iter().cloned().collect();
+    assert_eq!(toffoli.involved_qubits(), InvolvedQubits::Set(comp_set));
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(0, 2);
+    mapping.insert(1, 0);
+    mapping.insert(2, 1);
+    assert_eq!(
+        toffoli.remap_qubits(&mapping).unwrap(),
+        Toffoli::new(vec![2, 0, 1])
+    );
+    assert!(matches!(
+        Toffoli::new(vec![0, 1]).unitary_matrix(),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+    assert!(matches!(
+        ControlledControlledPauliZ::new(vec![0, 1, 2, 3]).unitary_matrix(),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+    assert_eq!(
+        ControlledControlledPauliZ::new(vec![0, 1, 2])
+            .remap_qubits(&mapping)
+            .unwrap(),
+        ControlledControlledPauliZ::new(vec![2, 0, 1])
+    );
+
+    let ccphase = ControlledControlledPhaseShift::new(vec![0, 1, 2], "theta".into());
+    assert_eq!(ccphase.hqslang(), "ControlledControlledPhaseShift");
+    assert!(ccphase.tags().contains(&"Rotation"));
+    assert!(ccphase.is_parametrized());
+    assert_eq!(ccphase.theta(), &CalculatorFloat::from("theta"));
+    assert_eq!(
+        ccphase.powercf(2.0.into()).theta(),
+        &(CalculatorFloat::from(2.0) * "theta")
+    );
+    let mut calc = Calculator::new();
+    calc.set_variable("theta", 0.4);
+    let substituted = ccphase.substitute_parameters(&mut calc).unwrap();
+    assert_eq!(
+        substituted,
+        ControlledControlledPhaseShift::new(vec![0, 1, 2], 0.4.into())
+    );
+    assert_eq!(
+        ccphase.circuit().substitute_parameters(&mut calc).unwrap(),
+        substituted.circuit()
+    );
+
+    let gate = MultiControlledGate::new(
+        vec![2, 1, 0],
+        "phase".into(),
+        0.2.into(),
+        "y".into(),
+        0.4.into(),
+    );
+    assert_eq!(gate.hqslang(), "MultiControlledGate");
+    assert!(gate.is_parametrized());
+    assert_eq!(gate.phase(), &CalculatorFloat::from("phase"));
+    assert_eq!(gate.z1(), &CalculatorFloat::from(0.2));
+    assert_eq!(gate.y(), &CalculatorFloat::from("y"));
+    assert_eq!(gate.z2(), &CalculatorFloat::from(0.4));
+    assert!(gate.unitary_matrix().is_err());
+    calc.set_variable("phase", 0.3);
+    calc.set_variable("y", -1.1);
+    let substituted = gate.substitute_parameters(&mut calc).unwrap();
+    assert!(!substituted.is_parametrized());
+    let circuit = gate.circuit().substitute_parameters(&mut calc).unwrap();
+    let decomposed = circuit_unitary_on_qubits(&circuit, &[0, 1, 2]).unwrap();
+    let difference = decomposed - substituted.unitary_matrix().unwrap();
+    assert!(difference.iter().all(|x| x.norm() < 1e-10));
+    assert_eq!(
+        gate.remap_qubits(&mapping).unwrap().qubits(),
+        &vec![1, 0, 2]
+    );
+    assert!(matches!(
+        MultiControlledGate::from_unitary(vec![0, 1], random_unitary(2, 3)),
+        Err(RoqoqoError::MismatchedUnitaryDimension { .. })
+    ));
+
+    let operation = Operation::from(gate.clone());
+    assert_eq!(
+        MultiQubitGateOperation::try_from(operation).unwrap(),
+        MultiQubitGateOperation::from(gate)
+    );
+}
+
+/// Returns exp(-i theta/2 P) for the Pauli product P with the first Pauli on the most significant qubit
+fn pauli_product_exponential(paulis: &[usize], theta: f64) -> Array2<Complex64> {
+    let zero = Complex64::new(0.0, 0.0);
+    let one = Complex64::new(1.0, 0.0);
+    let i = Complex64::new(0.0, 1.0);
+    let single = [
+        array![[one, zero], [zero, one]],
+        array![[zero, one], [one, zero]],
+        array![[zero, -i], [i, zero]],
+        array![[one, zero], [zero, -one]],
+    ];
+    let mut product: Array2<Complex64> = array![[one]];
+    for pauli in paulis {
+        let factor = &single[*pauli];
+        let (rows, columns) = product.dim();
+        product = Array2::from_shape_fn((2 * rows, 2 * columns), |(row, column)| {
+            product[(row / 2, column / 2)] * factor[(row % 2, column % 2)]
+        });
+    }
+    let dimension = product.nrows();
+    Array2::<Complex64>::eye(dimension).mapv(|x| x * (theta / 2.0).cos())
+        - product.mapv(|x| x * i * (theta / 2.0).sin())
+}
+
+/// Test the unitary matrix of PauliProductRotation against the exponential of the Pauli product
+#[test_case(vec![0], vec![1]; "x")]
+#[test_case(vec![0], vec![2]; "y")]
+#[test_case(vec![0], vec![3]; "z")]
+#[test_case(vec![0], vec![0]; "identity")]
+#[test_case(vec![0, 1], vec![1, 3]; "xz")]
+#[test_case(vec![2, 0, 1], vec![2, 0, 1]; "yix")]
+#[test_case(vec![0, 1, 2, 3], vec![3, 2, 1, 2]; "zyxy")]
+fn test_pauli_product_rotation_unitary(qubits: Vec<usize>, paulis: Vec<usize>) {
+    let rotation = PauliProductRotation::new(qubits, paulis.clone(), 0.7.into());
+    let difference = rotation.unitary_matrix().unwrap() - pauli_product_exponential(&paulis, 0.7);
+    assert!(
+        difference.iter().all(|x| x.norm() < 1e-10),
+        "{}",
+        difference
+    );
+}
+
+/// Test that the single-qubit PauliProductRotations are the rotations around the axes
+#[test]
+fn test_pauli_product_rotation_single_qubit() {
+    let theta = CalculatorFloat::from(1.3);
+    for (pauli, rotation) in [
+        (1, RotateX::new(0, theta.clone()).unitary_matrix().unwrap()),
+        (2, RotateY::new(0, theta.clone()).unitary_matrix().unwrap()),
+        (3, RotateZ::new(0, theta.clone()).unitary_matrix().unwrap()),
+    ]
+    .iter()
+    {
+        let product = PauliProductRotation::new(vec![0], vec![*pauli], theta.clone());
+        let difference = product.unitary_matrix().unwrap() - rotation;
+        assert!(difference.iter().all(|x| x.norm() < 1e-10));
+    }
+}
+
+/// Test that both layouts of the decomposition reproduce the unitary of PauliProductRotation
+#[test_case(vec![0], vec![1]; "x")]
+#[test_case(vec![0], vec![0]; "identity")]
+#[test_case(vec![1, 0], vec![2, 3]; "yz")]
+#[test_case(vec![2, 0, 1], vec![2, 0, 1]; "yix")]
+#[test_case(vec![0, 1, 2, 3], vec![3, 2, 1, 2]; "zyxy")]
+#[test_case(vec![4, 0, 3, 1, 2], vec![1, 1, 3, 2, 3]; "xxzyz")]
+#[test_case(vec![0, 1, 2, 3, 4, 5, 6], vec![3, 3, 0, 3, 3, 3, 3]; "seven_qubits")]
+fn test_pauli_product_rotation_circuit(qubits: Vec<usize>, paulis: Vec<usize>) {
+    let rotation = PauliProductRotation::new(qubits.clone(), paulis, (-0.4).into());
+    let reversed: Vec<usize> = qubits.iter().rev().cloned().collect();
+    let matrix = rotation.unitary_matrix().unwrap();
+    for layout in [PauliProductLayout::Ladder, PauliProductLayout::Tree].iter() {
+        let circuit = rotation.circuit_with_layout(*layout);
+        let decomposed = circuit_unitary_on_qubits(&circuit, &reversed).unwrap();
+        let difference = decomposed - &matrix;
+        assert!(
+            difference.iter().all(|x| x.norm() < 1e-10),
+            "{:?} {}",
+            layout,
+            difference
+        );
+    }
+    assert_eq!(
+        rotation.circuit(),
+        rotation.circuit_with_layout(PauliProductLayout::Ladder)
+    );
+}
+
+/// Test that the tree layout computes the parity with logarithmic depth
+#[test]
+fn test_pauli_product_rotation_layout_depth() {
+    let qubits: Vec<usize> = (0..8).collect();
+    let rotation = PauliProductRotation::new(qubits, vec![3; 8], "theta".into());
+    let ladder = CircuitStatistics::from(&rotation.circuit_with_layout(PauliProductLayout::Ladder));
+    let tree = CircuitStatistics::from(&rotation.circuit_with_layout(PauliProductLayout::Tree));
+    assert_eq!(ladder.operation_counts["CNOT"], 14);
+    assert_eq!(tree.operation_counts["CNOT"], 14);
+    assert_eq!(ladder.depth, 15);
+    assert_eq!(tree.depth, 7);
+}
+
+/// Test the traits of PauliProductRotation
+#[test]
+fn test_pauli_product_rotation_traits() {
+    let mut qubit_paulis: HashMap<usize, usize> = HashMap::new();
+    qubit_paulis.insert(2, 1);
+    qubit_paulis.insert(0, 3);
+    qubit_paulis.insert(1, 2);
+    let rotation = PauliProductRotation::from_qubit_paulis(&qubit_paulis, "theta".into());
+    assert_eq!(rotation.qubits(), &vec![0, 1, 2]);
+    assert_eq!(rotation.paulis(), &vec![3, 2, 1]);
+    assert_eq!(rotation.qubit_paulis(), qubit_paulis);
+    assert_eq!(rotation.hqslang(), "PauliProductRotation");
+    assert_eq!(
+        rotation.tags(),
+        &[
+            "Operation",
+            "GateOperation",
+            "MultiQubitGateOperation",
+            "Rotation",
+            "PauliProductRotation",
+        ]
+    );
+    assert!(rotation.is_parametrized());
+    let comp_set: HashSet<usize> = [0, 1, 2].iter().cloned().collect();
+    assert_eq!(rotation.involved_qubits(), InvolvedQubits::Set(comp_set));
+    assert!(rotation.unitary_matrix().is_err());
+
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(0, 1);
+    mapping.insert(1, 2);
+    mapping.insert(2, 0);
+    let remapped = rotation.remap_qubits(&mapping).unwrap();
+    assert_eq!(
+        remapped,
+        PauliProductRotation::new(vec![1, 2, 0], vec![3, 2, 1], "theta".into())
+    );
+
+    let mut calc = Calculator::new();
+    calc.set_variable("theta", 0.3);
+    let substituted = rotation.substitute_parameters(&mut calc).unwrap();
+    assert_eq!(substituted.theta(), &CalculatorFloat::from(0.3));
+    assert_eq!(
+        rotation.circuit().substitute_parameters(&mut calc).unwrap(),
+        substituted.circuit()
+    );
+    assert_eq!(
+        rotation.powercf(2.0.into()),
+        PauliProductRotation::new(
+            vec![0, 1, 2],
+            vec![3, 2, 1],
+            CalculatorFloat::from(2.0) * "theta"
+        )
+    );
+
+    assert!(matches!(
+        PauliProductRotation::new(vec![0, 1], vec![3], 0.3.into()).unitary_matrix(),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+    assert!(matches!(
+        PauliProductRotation::new(vec![0], vec![4], 0.3.into()).unitary_matrix(),
+        Err(RoqoqoError::InvalidQubits { .. })
+    ));
+
+    let operation = Operation::from(rotation.clone());
+    assert_eq!(
+        Rotation::try_from(operation.clone()).unwrap(),
+        Rotation::from(rotation.clone())
+    );
+    assert_eq!(
+        MultiQubitGateOperation::try_from(operation).unwrap(),
+        MultiQubitGateOperation::from(rotation)
+    );
+}
+
+/// Test the overrotation of PauliProductRotation
+#[test]
+#[cfg(feature = "overrotate")]
+fn test_pauli_product_rotation_overrotate() {
+    let rotation = PauliProductRotation::new(vec![0, 1], vec![1, 3], 0.5.into());
+    let overrotated = rotation.overrotate(&1.0, &0.5);
+    assert_eq!(overrotated.qubits(), rotation.qubits());
+    assert_eq!(overrotated.paulis(), rotation.paulis());
+    assert_ne!(overrotated.theta(), rotation.theta());
+
+    let mut circuit = Circuit::new();
+    circuit += PragmaOverrotation::new("PauliProductRotation".to_string(), vec![0, 1], 1.0, 0.5);
+    circuit += rotation.clone();
+    let overrotated_circuit = circuit.overrotate().unwrap();
+    assert_eq!(overrotated_circuit.len(), 1);
+    let overrotated = PauliProductRotation::try_from(overrotated_circuit[0].clone()).unwrap();
+    assert_ne!(overrotated.theta(), rotation.theta());
+}
@@ -2994,6 +2994,23 @@ fn pragma_conditional_substitute_trait() {
     assert_eq!(result, test_gate);
 }
 
+/// Test PragmaConditional Substitute trait returns errors of the nested circuit
+#[test]
+fn pragma_conditional_substitute_error() {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(RotateX::new(0, CalculatorFloat::from("theta")));
+    let pragma = PragmaConditional::new(String::from("ro"), 1, circuit);
+
+    let mut substitution_dict: Calculator = Calculator::new();
+    let result = pragma.substitute_parameters(&mut substitution_dict);
+    assert!(result.is_err());
+
+    let mut qubit_mapping_test: HashMap<usize, usize> = HashMap::new();
+    qubit_mapping_test.insert(1, 2);
+    let result = pragma.remap_qubits(&qubit_mapping_test);
+    assert_eq!(result, Err(RoqoqoError::QubitMappingError { qubit: 0 }));
+}
+
 /// Test PragmaConditional Serialization and Deserialization traits (readable)
 #[cfg(feature = "serialize")]
 #[test]
@@ -3059,3 +3076,185 @@ fn pragma_conditional_serde_compact() {
         ],
     );
 }
+
+/// Test PragmaLoop inputs and involved qubits
+#[test]
+fn pragma_loop_inputs_qubits() {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(PauliX::new(0));
+    let pragma = PragmaLoop::new(CalculatorFloat::from(3), circuit.clone());
+
+    // Test inputs are correct
+    assert_eq!(pragma.repetitions(), &CalculatorFloat::from(3));
+    assert_eq!(pragma.circuit(), &circuit);
+
+    // Test InvolveQubits trait
+    let mut qubits: HashSet<usize> = HashSet::new();
+    qubits.insert(0);
+    assert_eq!(pragma.involved_qubits(), InvolvedQubits::Set(qubits));
+}
+
+/// Test PragmaLoop Operate trait
+#[test]
+fn pragma_loop_operate_trait() {
+    let pragma = PragmaLoop::new(CalculatorFloat::from(3), Circuit::default());
+
+    // (1) Test tags function
+    let tags: &[&str; 3] = &["Operation", "PragmaOperation", "PragmaLoop"];
+    assert_eq!(pragma.tags(), tags);
+
+    // (2) Test hqslang function
+    assert_eq!(pragma.hqslang(), String::from("PragmaLoop"));
+
+    // (3) Test is_parametrized function
+    assert!(!pragma.is_parametrized());
+    let pragma = PragmaLoop::new(CalculatorFloat::from("n"), Circuit::default());
+    assert!(pragma.is_parametrized());
+}
+
+/// Test PragmaLoop Substitute trait
+#[test]
+fn pragma_loop_substitute_trait() {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(RotateX::new(0, CalculatorFloat::from("theta")));
+    let pragma = PragmaLoop::new(CalculatorFloat::from("n"), circuit);
+
+    // (1) Substitute parameters function
+    let mut substitution_dict: Calculator = Calculator::new();
+    substitution_dict.set_variable("n", 2.0);
+    substitution_dict.set_variable("theta", 0.5);
+    let mut substituted_circuit = Circuit::new();
+    substituted_circuit.add_operation(RotateX::new(0, CalculatorFloat::from(0.5)));
+    let result = pragma
+        .substitute_parameters(&mut substitution_dict)
+        .unwrap();
+    assert_eq!(
+        result,
+        PragmaLoop::new(CalculatorFloat::from(2), substituted_circuit)
+    );
+
+    // (2) Remap qubits function
+    let mut qubit_mapping_test: HashMap<usize, usize> = HashMap::new();
+    qubit_mapping_test.insert(0, 2);
+    let mut remapped_circuit = Circuit::new();
+    remapped_circuit.add_operation(RotateX::new(2, CalculatorFloat::from("theta")));
+    let result = pragma.remap_qubits(&qubit_mapping_test).unwrap();
+    assert_eq!(
+        result,
+        PragmaLoop::new(CalculatorFloat::from("n"), remapped_circuit)
+    );
+}
+
+/// Test unrolling PragmaLoop
+#[test]
+fn pragma_loop_unroll() {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(Hadamard::new(0));
+    circuit.add_operation(CNOT::new(0, 1));
+    let pragma = PragmaLoop::new(CalculatorFloat::from(2), circuit.clone());
+    assert_eq!(pragma.number_repetitions(), Ok(2));
+    assert_eq!(pragma.unroll(), Ok(circuit.clone() + circuit.clone()));
+
+    let pragma = PragmaLoop::new(CalculatorFloat::from(0), circuit.clone());
+    assert_eq!(pragma.unroll(), Ok(Circuit::new()));
+
+    let pragma = PragmaLoop::new(CalculatorFloat::from(1.5), circuit.clone());
+    assert_eq!(
+        pragma.unroll(),
+        Err(RoqoqoError::InvalidRepetitions { repetitions: 1.5 })
+    );
+    let pragma = PragmaLoop::new(CalculatorFloat::from(-1), circuit.clone());
+    assert_eq!(
+        pragma.number_repetitions(),
+        Err(RoqoqoError::InvalidRepetitions { repetitions: -1.0 })
+    );
+    let pragma = PragmaLoop::new(CalculatorFloat::from("n"), circuit);
+    assert!(matches!(
+        pragma.unroll(),
+        Err(RoqoqoError::CalculatorError(_))
+    ));
+}
+
+/// Test PragmaRepeatUntilSuccess inputs and involved qubits
+#[test]
+fn pragma_repeat_until_success_inputs_qubits() {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(PauliX::new(0));
+    circuit.add_operation(MeasureQubit::new(1, String::from("ro"), 0));
+    let pragma = PragmaRepeatUntilSuccess::new(String::from("ro"), 0, 5, circuit.clone());
+
+    // Test inputs are correct
+    assert_eq!(pragma.condition_register(), &String::from("ro"));
+    assert_eq!(pragma.condition_index(), &0_usize);
+    assert_eq!(pragma.max_iterations(), &5_usize);
+    assert_eq!(pragma.circuit(), &circuit);
+
+    // Test InvolveQubits trait
+    let mut qubits: HashSet<usize> = HashSet::new();
+    qubits.insert(0);
+    qubits.insert(1);
+    assert_eq!(pragma.involved_qubits(), InvolvedQubits::Set(qubits));
+
+    // Test Operate trait
+    let tags: &[&str; 3] = &["Operation", "PragmaOperation", "PragmaRepeatUntilSuccess"];
+    assert_eq!(pragma.tags(), tags);
+    assert_eq!(pragma.hqslang(), String::from("PragmaRepeatUntilSuccess"));
+    assert!(!pragma.is_parametrized());
+}
+
+/// Test PragmaRepeatUntilSuccess Substitute trait
+#[test]
+fn pragma_repeat_until_success_substitute_trait() {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(RotateY::new(0, CalculatorFloat::from("theta")));
+    let pragma = PragmaRepeatUntilSuccess::new(String::from("ro"), 1, 3, circuit);
+
+    // (1) Substitute parameters function
+    let mut substitution_dict: Calculator = Calculator::new();
+    substitution_dict.set_variable("theta", 0.5);
+    let mut substituted_circuit = Circuit::new();
+    substituted_circuit.add_operation(RotateY::new(0, CalculatorFloat::from(0.5)));
+    let result = pragma
+        .substitute_parameters(&mut substitution_dict)
+        .unwrap();
+    assert_eq!(
+        result,
+        PragmaRepeatUntilSuccess::new(String::from("ro"), 1, 3, substituted_circuit)
+    );
+
+    // (2) Remap qubits function
+    let mut qubit_mapping_test: HashMap<usize, usize> = HashMap::new();
+    qubit_mapping_test.insert(0, 4);
+    let mut remapped_circuit = Circuit::new();
+    remapped_circuit.add_operation(RotateY::new(4, CalculatorFloat::from("theta")));
+    let result = pragma.remap_qubits(&qubit_mapping_test).unwrap();
+    assert_eq!(
+        result,
+        PragmaRepeatUntilSuccess::new(String::from("ro"), 1, 3, remapped_circuit)
+    );
+}
+
+/// Test unrolling PragmaRepeatUntilSuccess into nested PragmaConditional operations
+#[test]
+fn pragma_repeat_until_success_unroll() {
+    let mut circuit = Circuit::new();
+    circuit.add_operation(Hadamard::new(0));
+    circuit.add_operation(MeasureQubit::new(0, String::from("ro"), 0));
+
+    let pragma = PragmaRepeatUntilSuccess::new(String::from("ro"), 0, 0, circuit.clone());
+    assert_eq!(pragma.unroll(), Circuit::new());
+
+    let pragma = PragmaRepeatUntilSuccess::new(String::from("ro"), 0, 1, circuit.clone());
+    assert_eq!(pragma.unroll(), circuit);
+
+    let pragma = PragmaRepeatUntilSuccess::new(String::from("ro"), 0, 3, circuit.clone());
+    let mut third = circuit.clone();
+    third.add_operation(PragmaConditional::new(
+        String::from("ro"),
+        0,
+        circuit.clone(),
+    ));
+    let mut expected = circuit.clone();
+    expected.add_operation(PragmaConditional::new(String::from("ro"), 0, third));
+    assert_eq!(pragma.unroll(), expected);
+}
@@ -17,6 +17,7 @@ use ndarray::{array, Array2};
 use num_complex::Complex64;
 use qoqo_calculator::{Calculator, CalculatorFloat};
 use roqoqo::operations::*;
+use roqoqo::unitary::circuit_unitary_on_qubits;
 use roqoqo::Circuit;
 use roqoqo::RoqoqoError;
 use roqoqo::RoqoqoError::QubitMappingError;
@@ -132,6 +133,30 @@ fn kak_sigma_matrix(
     array
 }
 
+// helper function to build a general TwoQubitGate with one variable interaction parameter
+fn kak_gate(control: usize, target: usize, kz: CalculatorFloat) -> TwoQubitGate {
+    TwoQubitGate::new(
+        control,
+        target,
+        CalculatorFloat::from(0.1),
+        CalculatorFloat::from(0.2),
+        CalculatorFloat::from(-0.4),
+        kz,
+        CalculatorFloat::from(0.5),
+        CalculatorFloat::from(0.6),
+        CalculatorFloat::from(0.7),
+        CalculatorFloat::from(-0.8),
+        CalculatorFloat::from(0.9),
+        CalculatorFloat::from(1.0),
+        CalculatorFloat::from(1.1),
+        CalculatorFloat::from(-1.2),
+        CalculatorFloat::from(1.3),
+        CalculatorFloat::from(1.4),
+        CalculatorFloat::from(1.5),
+        CalculatorFloat::from(-1.6),
+    )
+}
+
 //
 // Test KaK decompositions for Two Qubit Gates
 //
@@ -298,6 +323,7 @@ fn test_kakdecomposition(gate: TwoQubitGateOperation) {
 #[test_case(GateOperation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ_pi_4")]
 #[test_case(GateOperation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::ZERO)); "PhaseShiftedControlledZ_zero")]
 #[test_case(GateOperation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::from(1.33))); "PhaseShiftedControlledZ_1_33")]
+#[test_case(GateOperation::from(kak_gate(0, 1, CalculatorFloat::from(-0.7))); "TwoQubitGate")]
 fn test_twoqubitgates_unitarity(gate: GateOperation) {
     let result: Result<Array2<Complex64>, RoqoqoError> = gate.unitary_matrix();
     let result_array: Array2<Complex64> = result.unwrap();
@@ -338,6 +364,7 @@ fn test_twoqubitgates_unitarity(gate: GateOperation) {
 #[test_case(Operation::from(PMInteraction::new(0, 1, CalculatorFloat::PI)); "PMInteraction")]
 #[test_case(Operation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "ComplexPMInteraction")]
 #[test_case(Operation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ_pi_4")]
+#[test_case(Operation::from(kak_gate(0, 1, CalculatorFloat::from(0.3))); "TwoQubitGate")]
 fn test_twoqubitgates_clone(gate1: Operation) {
     let gate2 = gate1.clone();
     assert_eq!(gate2, gate1);
@@ -364,6 +391,7 @@ fn test_twoqubitgates_clone(gate1: Operation) {
 #[test_case(TwoQubitGateOperation::from(PMInteraction::new(0, 1, CalculatorFloat::PI)); "PMInteraction")]
 #[test_case(TwoQubitGateOperation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "ComplexPMInteraction")]
 #[test_case(TwoQubitGateOperation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ")]
+#[test_case(TwoQubitGateOperation::from(kak_gate(0, 1, CalculatorFloat::from(0.3))); "TwoQubitGate")]
 fn test_qubits_twoqubitgates(gate: TwoQubitGateOperation) {
     let control: &usize = &gate.control();
     assert_eq!(control, &0);
@@ -397,6 +425,7 @@ fn test_qubits_twoqubitgates(gate: TwoQubitGateOperation) {
 #[test_case(Operation::from(PMInteraction::new(0, 1, CalculatorFloat::PI)); "PMInteraction")]
 #[test_case(Operation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "ComplexPMInteraction")]
 #[test_case(Operation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ_pi_4")]
+#[test_case(Operation::from(kak_gate(0, 1, CalculatorFloat::from(0.3))); "TwoQubitGate")]
 fn test_is_parametrized_false(gate: Operation) {
     let bool_parameter = gate.is_parametrized();
     assert!(!bool_parameter);
@@ -414,6 +443,7 @@ fn test_is_parametrized_false(gate: Operation) {
 #[test_case(Operation::from(PMInteraction::new(0, 1, CalculatorFloat::from("parameter"))); "PMInteraction")]
 #[test_case(Operation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from("parameter1"), CalculatorFloat::from("parameter2"))); "ComplexPMInteraction")]
 #[test_case(Operation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::from("phi"))); "PhaseShiftedControlledZ_pi_4")]
+#[test_case(Operation::from(kak_gate(0, 1, CalculatorFloat::from("phi"))); "TwoQubitGate")]
 fn test_is_parametrized_true(gate: Operation) {
     let bool_parameter = gate.is_parametrized();
     assert!(bool_parameter);
@@ -443,6 +473,7 @@ fn test_is_parametrized_true(gate: Operation) {
 #[test_case("PMInteraction", Operation::from(PMInteraction::new(0, 1, CalculatorFloat::PI)); "PMInteraction")]
 #[test_case("ComplexPMInteraction", Operation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "ComplexPMInteraction")]
 #[test_case("PhaseShiftedControlledZ", Operation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::from("phi"))); "PhaseShiftedControlledZ")]
+#[test_case("TwoQubitGate", Operation::from(kak_gate(0, 1, CalculatorFloat::from("phi"))); "TwoQubitGate")]
 fn test_twoqubitgateoperations_hqslang(name: &'static str, gate: Operation) {
     assert!(!gate.hqslang().is_empty());
     assert_eq!(gate.hqslang(), name);
@@ -511,6 +542,9 @@ fn test_twoqubitgateoperations_hqslang(name: &'static str, gate: Operation) {
 #[test_case(
     GateOperation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::PI)),
     GateOperation::from(PhaseShiftedControlledZ::new(1, 0, CalculatorFloat::PI)); "PhaseShiftedControlledZ")]
+#[test_case(
+    GateOperation::from(kak_gate(0, 1, CalculatorFloat::PI)),
+    GateOperation::from(kak_gate(1, 0, CalculatorFloat::PI)); "TwoQubitGate")]
 fn remap_qubits_result(gate: GateOperation, test_gate: GateOperation) {
     let mut qubit_mapping: HashMap<usize, usize> = HashMap::new();
     qubit_mapping.insert(0, 1);
@@ -540,6 +574,7 @@ fn remap_qubits_result(gate: GateOperation, test_gate: GateOperation) {
 #[test_case(GateOperation::from(PMInteraction::new(0, 1, CalculatorFloat::PI)); "PMInteraction")]
 #[test_case(GateOperation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "ComplexPMInteraction")]
 #[test_case(GateOperation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ")]
+#[test_case(GateOperation::from(kak_gate(0, 1, CalculatorFloat::FRAC_PI_4)); "TwoQubitGate")]
 fn remap_qubits_error0(gate: GateOperation) {
     let qubit_mapping: HashMap<usize, usize> = HashMap::new();
     let result = gate.remap_qubits(&qubit_mapping);
@@ -567,6 +602,7 @@ fn remap_qubits_error0(gate: GateOperation) {
 #[test_case(GateOperation::from(PMInteraction::new(0, 1, CalculatorFloat::PI)); "PMInteraction")]
 #[test_case(GateOperation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "ComplexPMInteraction")]
 #[test_case(GateOperation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ")]
+#[test_case(GateOperation::from(kak_gate(0, 1, CalculatorFloat::FRAC_PI_4)); "TwoQubitGate")]
 fn remap_qubits_error1(gate: GateOperation) {
     let mut qubit_mapping: HashMap<usize, usize> = HashMap::new();
     qubit_mapping.insert(0, 2);
@@ -932,6 +968,7 @@ fn test_rotate_powercf(gate: Rotation, gate2: Rotation) {
 #[test_case(Operation::from(ControlledPauliY::new(0, 1)); "ControlledPauliY")]
 #[test_case(Operation::from(Bogoliubov::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(-1.0))); "Bogoliubov")]
 #[test_case(Operation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::FRAC_PI_4)); "PhaseShiftedControlledZ")]
+#[test_case(Operation::from(kak_gate(0, 1, CalculatorFloat::FRAC_PI_4)); "TwoQubitGate")]
 fn test_ineffective_substitute_parameters(gate: Operation) {
     let mut substitution_dict: Calculator = Calculator::new();
     substitution_dict.set_variable("theta", 0.0);
@@ -964,6 +1001,8 @@ fn test_ineffective_substitute_parameters(gate: Operation) {
             Operation::from(Bogoliubov::new(0, 1, CalculatorFloat::ZERO, CalculatorFloat::from(-1.0))); "Bogoliubov")]
 #[test_case(Operation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::from("theta"))),
             Operation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::ZERO)); "PhaseShiftedControlledZ")]
+#[test_case(Operation::from(kak_gate(0, 1, CalculatorFloat::from("theta"))),
+            Operation::from(kak_gate(0, 1, CalculatorFloat::ZERO)); "TwoQubitGate")]
 fn test_substitute_parameters(gate: Operation, gate2: Operation) {
     let mut substitution_dict: Calculator = Calculator::new();
     substitution_dict.set_variable("theta", 0.0);
@@ -986,6 +1025,7 @@ fn test_substitute_parameters(gate: Operation, gate2: Operation) {
 #[test_case(Operation::from(ComplexPMInteraction::new(0, 1, CalculatorFloat::from("theta"), CalculatorFloat::from(-1.0))); "ComplexPMInteraction")]
 #[test_case(Operation::from(Bogoliubov::new(0, 1, CalculatorFloat::from("theta"), CalculatorFloat::from(-1.0))); "Bogoliubov")]
 #[test_case(Operation::from(PhaseShiftedControlledZ::new(0, 1, CalculatorFloat::from("theta"))); "PhaseShiftedControlledZ")]
+#[test_case(Operation::from(kak_gate(0, 1, CalculatorFloat::from("theta"))); "TwoQubitGate")]
 fn test_substitute_parameters_error(gate: Operation) {
     let mut substitution_dict: Calculator = Calculator::new();
     substitution_dict.set_variable("error", 0.0);
@@ -1118,3 +1158,161 @@ fn test_kakdecomposition_debug() {
     let message = "KakDecomposition { global_phase: Float(1.0), k_vector: [Float(0.0), Float(0.0), Float(0.0)], circuit_before: None, circuit_after: None }";
     assert_eq!(format!("{:?}", gate), message);
 }
+
+// helper function returning a pseudo random 4x4 unitary matrix
+fn random_unitary(seed: u64) -> Array2<Complex64> {
+    let mut state = seed;
+    let mut random = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1_u64 << 53) as f64 - 0.5
+    };
+    let matrix = na::DMatrix::from_fn(4, 4, |_, _| Complex64::new(random(), random()));
+    let q = matrix.qr().q();
+    Array2::from_shape_fn((4, 4), |(row, column)| q[(row, column)])
+}
+
+/// Test that TwoQubitGate::from_unitary reproduces the matrix including the global phase
+#[test_case(random_unitary(1); "random_1")]
+#[test_case(random_unitary(2); "random_2")]
+#[test_case(random_unitary(3); "random_3")]
+#[test_case(Array2::eye(4); "identity")]
+#[test_case(CNOT::new(0, 1).unitary_matrix().unwrap(); "CNOT")]
+#[test_case(CNOT::new(1, 0).unitary_matrix().unwrap().mapv(|x| x * Complex64::new(0.0, 1.0)); "CNOT_phase")]
+#[test_case(SWAP::new(0, 1).unitary_matrix().unwrap(); "SWAP")]
+#[test_case(ISwap::new(0, 1).unitary_matrix().unwrap(); "ISwap")]
+#[test_case(ControlledPauliZ::new(0, 1).unitary_matrix().unwrap(); "ControlledPauliZ")]
+#[test_case(ControlledPhaseShift::new(0, 1, CalculatorFloat::from(0.3)).unitary_matrix().unwrap(); "ControlledPhaseShift")]
+#[test_case(Fsim::new(0, 1, CalculatorFloat::from(1.0), CalculatorFloat::from(2.0), CalculatorFloat::from(-1.0)).unitary_matrix().unwrap(); "Fsim")]
+#[test_case(kak_gate(0, 1, CalculatorFloat::from(0.3)).unitary_matrix().unwrap(); "TwoQubitGate")]
+#[test_case(MolmerSorensenXX::new(0, 1).unitary_matrix().unwrap(); "MolmerSorensenXX")]
+#[test_case(SqrtISwap::new(0, 1).unitary_matrix().unwrap(); "SqrtISwap")]
+#[test_case(kak_gate(0, 1, CalculatorFloat::from(0.0)).unitary_matrix().unwrap(); "TwoQubitGate_kz_zero")]
+#[test_case(array![
+    [Complex64::new(0.0, 1.0), Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0)],
+    [Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0), Complex64::new(-1.0, 0.0), Complex64::new(0.0, 0.0)],
+    [Complex64::new(0.0, 0.0), Complex64::new(0.0, -1.0), Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0)],
+    [Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)],
+]; "phased_swap")]
+fn test_two_qubit_gate_from_unitary(matrix: Array2<Complex64>) {
+    let gate = TwoQubitGate::from_unitary(2, 5, matrix.clone()).unwrap();
+    assert_eq!(gate.control(), &2);
+    assert_eq!(gate.target(), &5);
+    let reconstructed = gate.unitary_matrix().unwrap();
+    for (value, expected) in reconstructed.iter().zip(matrix.iter()) {
+        assert!((value - expected).norm() < 1e-10, "{}", reconstructed);
+    }
+
+    // The KAK decomposition reproduces the unitary matrix
+    let kak = gate.kak_decomposition();
+    let qubits = [5, 2];
+    let before = circuit_unitary_on_qubits(&kak.circuit_before.unwrap(), &qubits).unwrap();
+    let after = circuit_unitary_on_qubits(&kak.circuit_after.unwrap(), &qubits).unwrap();
+    let entangling = kak_sigma_matrix(
+        kak.k_vector[0].clone() * (-1.0),
+        kak.k_vector[1].clone() * (-1.0),
+        kak.k_vector[2].clone() * (-1.0),
+    );
+    let phase = Complex64::new(0.0, f64::try_from(kak.global_phase).unwrap()).exp();
+    let decomposed = after.dot(&entangling).dot(&before).mapv(|x| x * phase);
+    for (value, expected) in decomposed.iter().zip(matrix.iter()) {
+        assert!((value - expected).norm() < 1e-10, "{}", decomposed);
+    }
+}
+
+/// Test the errors and the accessors of TwoQubitGate
+#[test]
+fn test_two_qubit_gate_errors_and_accessors() {
+    assert_eq!(
+        TwoQubitGate::from_unitary(0, 1, Array2::eye(8)),
+        Err(RoqoqoError::MismatchedUnitaryDimension {
+            rows: 8,
+            columns: 8,
+            number_qubits: 2
+        })
+    );
+    assert!(matches!(
+        TwoQubitGate::from_unitary(0, 1, Array2::eye(4).mapv(|x: Complex64| x * 2.0)),
+        Err(RoqoqoError::NonUnitaryMatrix { .. })
+    ));
+
+    let gate = kak_gate(0, 1, CalculatorFloat::from("theta"));
+    assert!(gate.unitary_matrix().is_err());
+    assert_eq!(
+        gate.k_vector(),
+        [
+            CalculatorFloat::from(0.2),
+            CalculatorFloat::from(-0.4),
+            CalculatorFloat::from("theta")
+        ]
+    );
+    let kak = gate.kak_decomposition();
+    assert_eq!(kak.global_phase, CalculatorFloat::from(0.1));
+    assert_eq!(kak.k_vector, gate.k_vector());
+    let mut circuit_before = Circuit::new();
+    circuit_before += RotateZ::new(0, CalculatorFloat::from(0.5));
+    circuit_before += RotateY::new(0, CalculatorFloat::from(0.6));
+    circuit_before += RotateZ::new(0, CalculatorFloat::from(0.7));
+    circuit_before += RotateZ::new(1, CalculatorFloat::from(-0.8));
+    circuit_before += RotateY::new(1, CalculatorFloat::from(0.9));
+    circuit_before += RotateZ::new(1, CalculatorFloat::from(1.0));
+    assert_eq!(kak.circuit_before, Some(circuit_before));
+    assert_eq!(
+        gate.tags(),
+        &[
+            "Operation",
+            "GateOperation",
+            "TwoQubitGateOperation",
+            "TwoQubitGate",
+        ]
+    );
+}
+
+/// Test the conversions of the TwoQubitGate stored boxed in the operation enums
+#[test]
+fn test_two_qubit_gate_enum_conversions() {
+    let gate = kak_gate(0, 1, CalculatorFloat::from("theta"));
+    let operation = Operation::from(gate.clone());
+    assert_eq!(TwoQubitGate::try_from(operation.clone()), Ok(gate.clone()));
+    let two_qubit_gate_operation = TwoQubitGateOperation::try_from(operation.clone()).unwrap();
+    assert_eq!(two_qubit_gate_operation.control(), &0);
+    assert_eq!(two_qubit_gate_operation.target(), &1);
+    assert_eq!(two_qubit_gate_operation.hqslang(), "TwoQubitGate");
+    assert!(two_qubit_gate_operation.is_parametrized());
+    assert_eq!(
+        two_qubit_gate_operation.kak_decomposition(),
+        gate.kak_decomposition()
+    );
+    assert_eq!(Operation::from(two_qubit_gate_operation), operation);
+    // The variants of the operation enums store the gate boxed
+    match operation.clone() {
+        Operation::TwoQubitGate(boxed) => assert_eq!(TwoQubitGate::from(boxed), gate),
+        _ => panic!("Unexpected variant"),
+    }
+
+    let mut calculator = Calculator::new();
+    calculator.set_variable("theta", 0.3);
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(0, 1);
+    mapping.insert(1, 0);
+    assert_eq!(
+        operation.substitute_parameters(&mut calculator).unwrap(),
+        Operation::from(kak_gate(0, 1, CalculatorFloat::from(0.3)))
+    );
+    assert_eq!(
+        operation.remap_qubits(&mapping).unwrap(),
+        Operation::from(kak_gate(1, 0, CalculatorFloat::from("theta")))
+    );
+    assert_eq!(
+        GateOperation::try_from(operation)
+            .unwrap()
+            .substitute_parameters(&mut calculator)
+            .unwrap()
+            .unitary_matrix()
+            .unwrap(),
+        kak_gate(0, 1, CalculatorFloat::from(0.3))
+            .unitary_matrix()
+            .unwrap()
+    );
+}
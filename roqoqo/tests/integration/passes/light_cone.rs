@@ -0,0 +1,153 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ndarray::array;
+use num_complex::Complex64;
+use roqoqo::operations::*;
+use roqoqo::passes::backward_light_cone;
+use roqoqo::Circuit;
+use std::collections::HashMap;
+
+/// Test that operations on unmeasured qubits are removed
+#[test]
+fn measure_qubit() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += Hadamard::new(0);
+    circuit += PauliX::new(2);
+    circuit += CNOT::new(0, 1);
+    circuit += CNOT::new(2, 3);
+    circuit += PragmaGlobalPhase::new(0.5.into());
+    circuit += MeasureQubit::new(1, "ro".to_string(), 0);
+    circuit += RotateX::new(1, 0.1.into());
+
+    let mut reduced = Circuit::new();
+    reduced += DefinitionBit::new("ro".to_string(), 1, true);
+    reduced += Hadamard::new(0);
+    reduced += CNOT::new(0, 1);
+    reduced += PragmaGlobalPhase::new(0.5.into());
+    reduced += MeasureQubit::new(1, "ro".to_string(), 0);
+    assert_eq!(backward_light_cone(&circuit, None), reduced);
+}
+
+/// Test restricting the light cone to selected readout registers
+#[test]
+fn selected_registers() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("a".to_string(), 1, true);
+    circuit += DefinitionBit::new("b".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PauliY::new(1);
+    circuit += MeasureQubit::new(0, "a".to_string(), 0);
+    circuit += MeasureQubit::new(1, "b".to_string(), 0);
+
+    let mut reduced = Circuit::new();
+    reduced += DefinitionBit::new("a".to_string(), 1, true);
+    reduced += DefinitionBit::new("b".to_string(), 1, true);
+    reduced += PauliY::new(1);
+    reduced += MeasureQubit::new(1, "b".to_string(), 0);
+    assert_eq!(
+        backward_light_cone(&circuit, Some(&["b".to_string()])),
+        reduced
+    );
+    assert_eq!(backward_light_cone(&circuit, None), circuit);
+
+    // A measurement writing an unneeded register is kept when it acts on a qubit in the light cone
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "a".to_string(), 0);
+    circuit += MeasureQubit::new(0, "b".to_string(), 0);
+    assert_eq!(
+        backward_light_cone(&circuit, Some(&["b".to_string()])),
+        circuit
+    );
+}
+
+/// Test the qubit mapping of PragmaRepeatedMeasurement
+#[test]
+fn repeated_measurement() {
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(0, 0);
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0);
+    circuit += PauliX::new(1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), Some(mapping.clone()), 10);
+
+    let mut reduced = Circuit::new();
+    reduced += PauliX::new(0);
+    reduced += PragmaRepeatedMeasurement::new("ro".to_string(), Some(mapping), 10);
+    assert_eq!(backward_light_cone(&circuit, None), reduced);
+
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0);
+    circuit += PauliX::new(1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 10);
+    assert_eq!(backward_light_cone(&circuit, None), circuit);
+}
+
+/// Test that registers read by conditional operations extend the light cone
+#[test]
+fn conditional() {
+    let mut conditional_circuit = Circuit::new();
+    conditional_circuit += PauliX::new(0);
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(2);
+    circuit += Hadamard::new(3);
+    circuit += MeasureQubit::new(2, "c".to_string(), 0);
+    circuit += MeasureQubit::new(3, "d".to_string(), 0);
+    circuit += PragmaConditional::new("c".to_string(), 0, conditional_circuit.clone());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+
+    let mut reduced = Circuit::new();
+    reduced += Hadamard::new(2);
+    reduced += MeasureQubit::new(2, "c".to_string(), 0);
+    reduced += PragmaConditional::new("c".to_string(), 0, conditional_circuit);
+    reduced += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert_eq!(
+        backward_light_cone(&circuit, Some(&["ro".to_string()])),
+        reduced
+    );
+}
+
+/// Test that operations acting on all qubits put all qubits into the light cone
+#[test]
+fn all_qubits() {
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(3);
+    circuit +=
+        PragmaSetStateVector::new(array![Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)]);
+    circuit += PauliX::new(1);
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+
+    let mut reduced = Circuit::new();
+    reduced += PauliX::new(3);
+    reduced +=
+        PragmaSetStateVector::new(array![Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)]);
+    reduced += PauliX::new(0);
+    reduced += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert_eq!(backward_light_cone(&circuit, None), reduced);
+}
+
+/// Test that only operations without qubits remain when nothing is measured
+#[test]
+fn no_measurement() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("fl".to_string(), 1, false);
+    circuit += PauliX::new(0);
+    circuit += PragmaGlobalPhase::new(0.5.into());
+
+    let mut reduced = Circuit::new();
+    reduced += DefinitionFloat::new("fl".to_string(), 1, false);
+    reduced += PragmaGlobalPhase::new(0.5.into());
+    assert_eq!(backward_light_cone(&circuit, None), reduced);
+}
@@ -0,0 +1,137 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ndarray::{array, Array1, Array2};
+use num_complex::Complex64;
+use roqoqo::operations::*;
+use roqoqo::passes::{compact_qubits, used_qubits};
+use roqoqo::{Circuit, RoqoqoError};
+use std::collections::HashMap;
+
+/// Test compaction of sparse qubit indices
+#[test]
+fn compact_sparse_qubits() {
+    let mut inner = Circuit::new();
+    inner += PauliX::new(42);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += Hadamard::new(7);
+    circuit += CNOT::new(7, 42);
+    circuit += PragmaConditional::new("ro".to_string(), 0, inner);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 1);
+    circuit += PragmaGlobalPhase::new(0.2.into());
+    assert_eq!(used_qubits(&circuit), vec![0, 7, 42]);
+
+    let (compacted, inverse_mapping) = compact_qubits(&circuit).unwrap();
+    let mut inner = Circuit::new();
+    inner += PauliX::new(2);
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("ro".to_string(), 2, true);
+    expected += Hadamard::new(1);
+    expected += CNOT::new(1, 2);
+    expected += PragmaConditional::new("ro".to_string(), 0, inner);
+    expected += MeasureQubit::new(0, "ro".to_string(), 1);
+    expected += PragmaGlobalPhase::new(0.2.into());
+    assert_eq!(compacted, expected);
+
+    let mut expected_inverse: HashMap<usize, usize> = HashMap::new();
+    expected_inverse.insert(0, 0);
+    expected_inverse.insert(1, 7);
+    expected_inverse.insert(2, 42);
+    assert_eq!(inverse_mapping, expected_inverse);
+    assert_eq!(compacted.remap_qubits(&inverse_mapping).unwrap(), circuit);
+}
+
+/// Test that qubit mappings of PragmaRepeatedMeasurement are updated
+#[test]
+fn repeated_measurement_mapping() {
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(3, 0);
+    mapping.insert(5, 1);
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(5);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), Some(mapping), 10);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 10);
+    assert_eq!(used_qubits(&circuit), vec![3, 5]);
+
+    let (compacted, _) = compact_qubits(&circuit).unwrap();
+    let mut new_mapping: HashMap<usize, usize> = HashMap::new();
+    new_mapping.insert(0, 0);
+    new_mapping.insert(1, 1);
+    let mut expected = Circuit::new();
+    expected += PauliX::new(1);
+    expected += PragmaRepeatedMeasurement::new("ro".to_string(), Some(new_mapping), 10);
+    expected += PragmaRepeatedMeasurement::new("ro".to_string(), None, 10);
+    assert_eq!(compacted, expected);
+}
+
+/// Test that set states are reduced to the used qubits
+#[test]
+fn set_states() {
+    // Qubit 0 in |+>, qubit 1 in |0>, qubit 2 in |1>
+    let amplitude = Complex64::new(1.0 / 2.0_f64.sqrt(), 0.0);
+    let mut statevector: Array1<Complex64> = Array1::zeros(8);
+    statevector[4] = amplitude;
+    statevector[5] = amplitude;
+    let density_matrix: Array2<Complex64> = Array2::from_shape_fn((8, 8), |(row, column)| {
+        statevector[row] * statevector[column].conj()
+    });
+    let mut circuit = Circuit::new();
+    circuit += PragmaSetStateVector::new(statevector);
+    circuit += PragmaSetDensityMatrix::new(density_matrix);
+    circuit += PauliX::new(0);
+    circuit += PauliX::new(2);
+
+    let (compacted, _) = compact_qubits(&circuit).unwrap();
+    let zero = Complex64::new(0.0, 0.0);
+    let half = Complex64::new(0.5, 0.0);
+    let mut expected = Circuit::new();
+    expected += PragmaSetStateVector::new(array![zero, zero, amplitude, amplitude]);
+    expected += PragmaSetDensityMatrix::new(array![
+        [zero, zero, zero, zero],
+        [zero, zero, zero, zero],
+        [zero, zero, half, half],
+        [zero, zero, half, half],
+    ]);
+    expected += PauliX::new(0);
+    expected += PauliX::new(1);
+    for (operation, expected_operation) in compacted.iter().zip(expected.iter()) {
+        match (operation, expected_operation) {
+            (Operation::PragmaSetStateVector(x), Operation::PragmaSetStateVector(y)) => {
+                for (a, b) in x.statevector().iter().zip(y.statevector().iter()) {
+                    assert!((a - b).norm() < 1e-10);
+                }
+            }
+            (Operation::PragmaSetDensityMatrix(x), Operation::PragmaSetDensityMatrix(y)) => {
+                for (a, b) in x.density_matrix().iter().zip(y.density_matrix().iter()) {
+                    assert!((a - b).norm() < 1e-10);
+                }
+            }
+            _ => assert_eq!(operation, expected_operation),
+        }
+    }
+    assert_eq!(compacted.len(), expected.len());
+}
+
+/// Test error when a set state has population on a qubit that is not used
+#[test]
+fn set_state_unused_population() {
+    let zero = Complex64::new(0.0, 0.0);
+    let one = Complex64::new(1.0, 0.0);
+    let mut circuit = Circuit::new();
+    circuit += PragmaSetStateVector::new(array![zero, zero, one, zero]);
+    circuit += PauliX::new(0);
+    assert_eq!(
+        compact_qubits(&circuit),
+        Err(RoqoqoError::QubitMappingError { qubit: 1 })
+    );
+}
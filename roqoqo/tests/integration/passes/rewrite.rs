@@ -0,0 +1,269 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ndarray::array;
+use num_complex::Complex64;
+use qoqo_calculator::{Calculator, CalculatorFloat};
+use roqoqo::operations::*;
+use roqoqo::passes::{
+    apply_rewrite_rules, cancellation_rules, clifford_rules, rotation_merge_rules, RewriteMatch,
+    RewriteRule,
+};
+use roqoqo::unitary::circuits_equivalent;
+use roqoqo::{Circuit, RoqoqoError};
+use std::collections::HashMap;
+
+fn merge_rotate_z() -> RewriteRule {
+    let mut pattern = Circuit::new();
+    pattern += RotateZ::new(0, "a".into());
+    pattern += RotateZ::new(0, "b".into());
+    let mut replacement = Circuit::new();
+    replacement += RotateZ::new(0, "a + b".into());
+    RewriteRule::new("merge_rotate_z".to_string(), pattern, replacement).unwrap()
+}
+
+/// Test matching through operations on disjoint qubits
+#[test]
+fn match_commuting_operations() {
+    let rule = merge_rotate_z();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += Hadamard::new(3);
+    circuit += RotateZ::new(3, 0.1.into());
+    circuit += CNOT::new(0, 1);
+    circuit += RotateZ::new(3, 0.2.into());
+    circuit += MeasureQubit::new(3, "ro".to_string(), 0);
+
+    let rewrite_match = rule.find_match(&circuit).unwrap();
+    assert_eq!(rewrite_match.indices, vec![1, 3]);
+    assert_eq!(rewrite_match.qubits.get(&0), Some(&3));
+    assert_eq!(
+        rewrite_match.parameters.get("b"),
+        Some(&CalculatorFloat::from(0.2))
+    );
+
+    let rewritten = rule.apply(&circuit, &rewrite_match).unwrap();
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("ro".to_string(), 1, true);
+    expected += Hadamard::new(3);
+    expected += RotateZ::new(3, 0.1.into());
+    expected += CNOT::new(0, 1);
+    expected += MeasureQubit::new(3, "ro".to_string(), 0);
+    assert_eq!(rewritten.definitions(), expected.definitions());
+    assert_eq!(rewritten.len(), expected.len());
+    match &rewritten[2] {
+        Operation::RotateZ(op) => {
+            assert_eq!(op.qubit(), &3);
+            assert!((op.theta().float().unwrap() - 0.3).abs() < 1e-12);
+        }
+        _ => panic!("Unexpected operation {:?}", rewritten[2]),
+    }
+    assert_eq!(rewritten[3], expected[3]);
+    assert_eq!(rewritten[4], expected[4]);
+}
+
+/// Test that operations on the same qubits block matches
+#[test]
+fn blocked_matches() {
+    let rule = merge_rotate_z();
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 0.1.into());
+    circuit += CNOT::new(1, 0);
+    circuit += RotateZ::new(0, 0.2.into());
+    assert_eq!(rule.find_match(&circuit), None);
+
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 0.1.into());
+    circuit +=
+        PragmaSetStateVector::new(array![Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)]);
+    circuit += RotateZ::new(0, 0.2.into());
+    assert_eq!(rule.find_match(&circuit), None);
+
+    // The search continues after a blocked partial match
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 0.1.into());
+    circuit += CNOT::new(1, 0);
+    circuit += RotateZ::new(0, 0.2.into());
+    circuit += RotateZ::new(0, 0.4.into());
+    let rewrite_match = rule.find_match(&circuit).unwrap();
+    assert_eq!(rewrite_match.indices, vec![2, 3]);
+}
+
+/// Test substitution of symbolic angles into the replacement
+#[test]
+fn symbolic_angles() {
+    let rule = merge_rotate_z();
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, "theta".into());
+    circuit += RotateZ::new(0, 0.5.into());
+    let (rewritten, number_rewrites) = apply_rewrite_rules(&circuit, &[rule], 10).unwrap();
+    assert_eq!(number_rewrites, 1);
+    let mut calculator = Calculator::new();
+    calculator.set_variable("theta", 1.0);
+    let substituted = rewritten.substitute_parameters(&mut calculator).unwrap();
+    let mut expected = Circuit::new();
+    expected += RotateZ::new(0, 1.5.into());
+    assert_eq!(substituted, expected);
+}
+
+/// Test that repeated symbols and distinct wildcard qubits have to match consistently
+#[test]
+fn consistent_bindings() {
+    let mut pattern = Circuit::new();
+    pattern += RotateX::new(0, "a".into());
+    pattern += RotateX::new(0, "a".into());
+    let mut replacement = Circuit::new();
+    replacement += RotateX::new(0, "2 * a".into());
+    let rule = RewriteRule::new("double".to_string(), pattern, replacement).unwrap();
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, 0.1.into());
+    circuit += RotateX::new(0, 0.2.into());
+    assert_eq!(rule.find_match(&circuit), None);
+    circuit += RotateX::new(0, 0.2.into());
+    assert_eq!(rule.find_match(&circuit).unwrap().indices, vec![1, 2]);
+
+    let rules = cancellation_rules();
+    let mut circuit = Circuit::new();
+    circuit += CNOT::new(0, 1);
+    circuit += CNOT::new(1, 0);
+    assert_eq!(apply_rewrite_rules(&circuit, &rules, 10).unwrap().1, 0);
+    circuit += CNOT::new(1, 0);
+    let (rewritten, _) = apply_rewrite_rules(&circuit, &rules, 10).unwrap();
+    let mut expected = Circuit::new();
+    expected += CNOT::new(0, 1);
+    assert_eq!(rewritten, expected);
+}
+
+/// Test guards restricting the matches a rule is applied to
+#[test]
+fn guards() {
+    fn large_angle(rewrite_match: &RewriteMatch) -> bool {
+        match rewrite_match.parameters.get("a") {
+            Some(CalculatorFloat::Float(x)) => x.abs() > 1.0,
+            _ => false,
+        }
+    }
+    let mut pattern = Circuit::new();
+    pattern += RotateY::new(0, "a".into());
+    let mut replacement = Circuit::new();
+    replacement += RotateY::new(0, "a - 6.283185307179586".into());
+    replacement += PragmaGlobalPhase::new(std::f64::consts::PI.into());
+    let rule = RewriteRule::new("wrap".to_string(), pattern, replacement)
+        .unwrap()
+        .with_guard(large_angle);
+    let mut circuit = Circuit::new();
+    circuit += RotateY::new(0, 0.5.into());
+    assert_eq!(rule.find_match(&circuit), None);
+    circuit += RotateY::new(1, 4.0.into());
+    assert_eq!(rule.find_match(&circuit).unwrap().indices, vec![1]);
+
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, 0.0.into());
+    circuit += RotateZ::new(1, 0.1.into());
+    circuit += RotateZ::new(1, "theta".into());
+    let (rewritten, _) = apply_rewrite_rules(&circuit, &rotation_merge_rules(), 10).unwrap();
+    assert_eq!(rewritten.len(), 1);
+}
+
+/// Test applying rules to a fixpoint and limiting the number of rewrites
+#[test]
+fn fixpoint_and_budget() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += PauliX::new(1);
+    circuit += Hadamard::new(0);
+    circuit += Hadamard::new(0);
+    circuit += PauliX::new(1);
+    circuit += Hadamard::new(0);
+    circuit += PauliZ::new(2);
+    let rules = cancellation_rules();
+    let (rewritten, number_rewrites) = apply_rewrite_rules(&circuit, &rules, 100).unwrap();
+    assert_eq!(number_rewrites, 3);
+    let mut expected = Circuit::new();
+    expected += PauliZ::new(2);
+    assert_eq!(rewritten, expected);
+
+    let (rewritten, number_rewrites) = apply_rewrite_rules(&circuit, &rules, 1).unwrap();
+    assert_eq!(number_rewrites, 1);
+    assert_eq!(rewritten.len(), 5);
+}
+
+/// Test errors for invalid rules
+#[test]
+fn invalid_rules() {
+    let mut pattern = Circuit::new();
+    pattern += RotateZ::new(0, "a".into());
+    let mut replacement = Circuit::new();
+    replacement += RotateZ::new(1, "a".into());
+    assert!(matches!(
+        RewriteRule::new("qubit".to_string(), pattern.clone(), replacement),
+        Err(RoqoqoError::RewriteRuleError { .. })
+    ));
+    let mut replacement = Circuit::new();
+    replacement += RotateZ::new(0, "b".into());
+    assert!(matches!(
+        RewriteRule::new("symbol".to_string(), pattern, replacement),
+        Err(RoqoqoError::RewriteRuleError { .. })
+    ));
+    let mut pattern = Circuit::new();
+    pattern += RotateZ::new(0, "2 * a".into());
+    assert!(matches!(
+        RewriteRule::new("expression".to_string(), pattern, Circuit::new()),
+        Err(RoqoqoError::RewriteRuleError { .. })
+    ));
+    assert!(matches!(
+        RewriteRule::new("empty".to_string(), Circuit::new(), Circuit::new()),
+        Err(RoqoqoError::RewriteRuleError { .. })
+    ));
+}
+
+/// Test that the built-in rules replace patterns with equivalent circuits
+#[test]
+fn builtin_rules_equivalent() {
+    let mut rules = cancellation_rules();
+    rules.extend(rotation_merge_rules());
+    rules.extend(clifford_rules());
+    for rule in rules.iter() {
+        let mut calculator = Calculator::new();
+        let value = if rule.name().starts_with("remove_zero") {
+            0.0
+        } else {
+            0.3
+        };
+        calculator.set_variable("a", value);
+        calculator.set_variable("b", 0.7);
+        let pattern = rule
+            .pattern()
+            .substitute_parameters(&mut calculator)
+            .unwrap();
+        let replacement = rule
+            .replacement()
+            .substitute_parameters(&mut calculator)
+            .unwrap();
+        let mut permutation: HashMap<usize, usize> = HashMap::new();
+        permutation.insert(0, 0);
+        permutation.insert(1, 1);
+        assert!(
+            circuits_equivalent(&pattern, &replacement, 1e-10, Some(&permutation)).unwrap(),
+            "{}",
+            rule.name()
+        );
+        assert_eq!(
+            apply_rewrite_rules(&pattern, std::slice::from_ref(rule), 1)
+                .unwrap()
+                .1,
+            1,
+            "{}",
+            rule.name()
+        );
+    }
+}
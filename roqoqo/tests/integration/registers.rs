@@ -0,0 +1,168 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the output registers container
+
+use num_complex::Complex64;
+use roqoqo::operations::*;
+use roqoqo::registers::*;
+use roqoqo::{Circuit, RoqoqoError};
+use std::collections::HashMap;
+
+/// Returns a circuit defining a bit output register, a float output register and a complex register
+fn example_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += DefinitionFloat::new("fl".to_string(), 1, true);
+    circuit += DefinitionComplex::new("co".to_string(), 1, false);
+    circuit
+}
+
+/// Test typed access, appending runs and counting shots
+#[test]
+fn append_and_access() {
+    let mut registers = OutputRegisters::new();
+    assert!(registers.is_empty());
+    assert_eq!(
+        registers.set_bit_register("ro".to_string(), vec![vec![true, false]]),
+        None
+    );
+    registers.set_float_register("fl".to_string(), vec![vec![0.5]]);
+    assert!(!registers.is_empty());
+
+    let mut other = OutputRegisters::new();
+    other.set_bit_register("ro".to_string(), vec![vec![false, false], vec![true, true]]);
+    other.set_complex_register("co".to_string(), vec![vec![Complex64::new(0.0, 1.0)]]);
+    registers.append(other);
+
+    assert_eq!(
+        registers.bit_register("ro"),
+        Some(&vec![
+            vec![true, false],
+            vec![false, false],
+            vec![true, true]
+        ])
+    );
+    assert_eq!(registers.float_register("fl"), Some(&vec![vec![0.5]]));
+    assert_eq!(
+        registers.complex_register("co"),
+        Some(&vec![vec![Complex64::new(0.0, 1.0)]])
+    );
+    assert_eq!(registers.bit_register("fl"), None);
+    assert_eq!(registers.bit_registers().len(), 1);
+    assert_eq!(registers.float_registers().len(), 1);
+    assert_eq!(registers.complex_registers().len(), 1);
+
+    assert_eq!(registers.number_shots("ro"), Some(3));
+    assert_eq!(registers.number_shots("co"), Some(1));
+    assert_eq!(registers.number_shots("missing"), None);
+    let mut shot_counts: HashMap<String, usize> = HashMap::new();
+    shot_counts.insert("ro".to_string(), 3);
+    shot_counts.insert("fl".to_string(), 1);
+    shot_counts.insert("co".to_string(), 1);
+    assert_eq!(registers.shot_counts(), shot_counts);
+
+    assert_eq!(
+        registers.set_float_register("fl".to_string(), vec![]),
+        Some(vec![vec![0.5]])
+    );
+}
+
+/// Test validating output registers against the definitions of a circuit
+#[test]
+fn validate() {
+    let circuit = example_circuit();
+    let mut registers = OutputRegisters::new();
+    registers.set_bit_register("ro".to_string(), vec![vec![true, false]]);
+    registers.set_float_register("fl".to_string(), vec![vec![0.5], vec![1.0]]);
+    assert_eq!(registers.validate(&circuit), Ok(()));
+
+    let mut missing = registers.clone();
+    missing.set_float_register("fl".to_string(), vec![]);
+    assert_eq!(missing.validate(&circuit), Ok(()));
+    let missing: OutputRegisters = (
+        registers.bit_registers().clone(),
+        HashMap::new(),
+        HashMap::new(),
+    )
+        .into();
+    assert_eq!(
+        missing.validate(&circuit),
+        Err(RoqoqoError::MissingRegister {
+            name: "fl".to_string()
+        })
+    );
+
+    let mut undefined = registers.clone();
+    undefined.set_bit_register("other".to_string(), vec![vec![true]]);
+    assert_eq!(
+        undefined.validate(&circuit),
+        Err(RoqoqoError::OutputRegisterMismatch {
+            name: "other".to_string(),
+            msg: "The register is not defined in the circuit".to_string()
+        })
+    );
+
+    let mut wrong_type = registers.clone();
+    wrong_type.set_float_register("co".to_string(), vec![vec![1.0]]);
+    assert_eq!(
+        wrong_type.validate(&circuit),
+        Err(RoqoqoError::OutputRegisterMismatch {
+            name: "co".to_string(),
+            msg: "The register is a float register but is defined as a complex register"
+                .to_string()
+        })
+    );
+
+    let mut wrong_length = registers;
+    wrong_length.set_bit_register("ro".to_string(), vec![vec![true, false], vec![true]]);
+    assert_eq!(
+        wrong_length.validate(&circuit),
+        Err(RoqoqoError::OutputRegisterMismatch {
+            name: "ro".to_string(),
+            msg: "Run 1 has 1 values but the register is defined with length 2".to_string()
+        })
+    );
+}
+
+/// Test the conversion from and to the tuple of register maps
+#[test]
+fn tuple_conversion() {
+    let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
+    bit_registers.insert("ro".to_string(), vec![vec![true]]);
+    let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
+    float_registers.insert("fl".to_string(), vec![vec![0.5]]);
+    let complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
+    let tuple = (bit_registers, float_registers, complex_registers);
+
+    let registers = OutputRegisters::from(tuple.clone());
+    assert_eq!(registers.bit_register("ro"), Some(&vec![vec![true]]));
+    assert_eq!(registers.float_register("fl"), Some(&vec![vec![0.5]]));
+    let converted: (
+        HashMap<String, BitOutputRegister>,
+        HashMap<String, FloatOutputRegister>,
+        HashMap<String, ComplexOutputRegister>,
+    ) = registers.into();
+    assert_eq!(converted, tuple);
+}
+
+/// Test the serialization round trip of output registers
+#[cfg(feature = "serialize")]
+#[test]
+fn serde_round_trip() {
+    let mut registers = OutputRegisters::new();
+    registers.set_bit_register("ro".to_string(), vec![vec![true, false]]);
+    registers.set_complex_register("co".to_string(), vec![vec![Complex64::new(1.0, 2.0)]]);
+    let json = serde_json::to_string(&registers).unwrap();
+    let deserialized: OutputRegisters = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, registers);
+}
@@ -0,0 +1,242 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the versioned serialization
+
+use roqoqo::measurements::{CheatedBasisRotation, CheatedBasisRotationInput, ClassicalRegister};
+use roqoqo::operations::*;
+use roqoqo::serialization::*;
+use roqoqo::{Circuit, RoqoqoError, ROQOQO_VERSION};
+use serde_json::{json, Value};
+
+/// Test the envelope of a serialized circuit and the round trip
+#[test]
+fn circuit_round_trip() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += RotateX::new(0, "theta".into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+
+    let json = to_versioned_json(&circuit).unwrap();
+    let value: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["roqoqo_version"], json!(ROQOQO_VERSION));
+    assert_eq!(value["minimum_roqoqo_version"], json!("0.1.0"));
+    assert_eq!(value["data"], serde_json::to_value(&circuit).unwrap());
+
+    let deserialized: Circuit = from_versioned_json(&json).unwrap();
+    assert_eq!(deserialized, circuit);
+}
+
+/// Test that the minimum version is determined by the operations used
+#[test]
+fn minimum_version() {
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0);
+    circuit += MultiQubitMS::new(vec![0, 1], 0.1.into());
+    assert_eq!(circuit.minimum_supported_roqoqo_version(), (0, 1, 0));
+
+    let mut conditional = Circuit::new();
+    conditional += PhaseShiftedControlledZ::new(0, 1, 0.1.into());
+    circuit += PragmaConditional::new("ro".to_string(), 0, conditional.clone());
+    assert_eq!(circuit.minimum_supported_roqoqo_version(), (0, 5, 0));
+
+    let measurement = ClassicalRegister {
+        constant_circuit: Some(conditional),
+        circuits: vec![Circuit::new()],
+    };
+    assert_eq!(measurement.minimum_supported_roqoqo_version(), (0, 5, 0));
+    let json = to_versioned_json(&measurement).unwrap();
+    let value: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["minimum_roqoqo_version"], json!("0.5.0"));
+    let deserialized: ClassicalRegister = from_versioned_json(&json).unwrap();
+    assert_eq!(deserialized, measurement);
+}
+
+/// Test that data requiring a newer roqoqo version is rejected
+#[test]
+fn incompatible_version() {
+    let json = json!({
+        "roqoqo_version": "99.0.0",
+        "minimum_roqoqo_version": "99.0.0",
+        "data": {"definitions": [], "operations": []}
+    })
+    .to_string();
+    let result: Result<Circuit, RoqoqoError> = from_versioned_json(&json);
+    assert_eq!(
+        result,
+        Err(RoqoqoError::IncompatibleVersion {
+            data_version: "99.0.0".to_string(),
+            minimum_version: "99.0.0".to_string(),
+            library_version: ROQOQO_VERSION.to_string(),
+        })
+    );
+
+    let json = json!({
+        "roqoqo_version": "99.0.0",
+        "minimum_roqoqo_version": "0.1.0",
+        "data": {"definitions": [], "operations": []}
+    })
+    .to_string();
+    let result: Result<Circuit, RoqoqoError> = from_versioned_json(&json);
+    assert_eq!(result, Ok(Circuit::new()));
+
+    let json = json!({
+        "roqoqo_version": "latest",
+        "minimum_roqoqo_version": "0.1.0",
+        "data": {}
+    })
+    .to_string();
+    let result: Result<Circuit, RoqoqoError> = from_versioned_json(&json);
+    assert!(matches!(
+        result,
+        Err(RoqoqoError::SerializationError { .. })
+    ));
+}
+
+/// Test renaming operations and fields for data written by older versions
+#[test]
+fn migrations() {
+    let mut registry = MigrationRegistry::new();
+    registry.register(Migration::RenameField {
+        version: (0, 3, 0),
+        operation: "RotateX".to_string(),
+        old_name: "angle".to_string(),
+        new_name: "theta".to_string(),
+    });
+    registry.register(Migration::RenameOperation {
+        version: (0, 2, 0),
+        old_name: "XRotation".to_string(),
+        new_name: "RotateX".to_string(),
+    });
+    let old_data = json!({
+        "definitions": [],
+        "operations": [{"XRotation": {"qubit": 0, "angle": 0.5}}]
+    });
+    let mut expected = Circuit::new();
+    expected += RotateX::new(0, 0.5.into());
+
+    let json = json!({
+        "roqoqo_version": "0.1.0",
+        "minimum_roqoqo_version": "0.1.0",
+        "data": old_data.clone()
+    })
+    .to_string();
+    let circuit: Circuit = registry.from_versioned_json(&json).unwrap();
+    assert_eq!(circuit, expected);
+
+    // Data without an envelope is migrated with all migrations
+    let circuit: Circuit = registry.from_versioned_json(&old_data.to_string()).unwrap();
+    assert_eq!(circuit, expected);
+
+    // Migrations older than the data are not applied
+    let json = json!({
+        "roqoqo_version": "0.2.0",
+        "minimum_roqoqo_version": "0.1.0",
+        "data": old_data
+    })
+    .to_string();
+    let result: Result<Circuit, RoqoqoError> = registry.from_versioned_json(&json);
+    assert!(matches!(
+        result,
+        Err(RoqoqoError::SerializationError { .. })
+    ));
+}
+
+/// Test that migrations only rename serialized operations
+#[test]
+fn migrations_nested_operations() {
+    let mut registry = MigrationRegistry::new();
+    registry.register(Migration::RenameOperation {
+        version: (0, 2, 0),
+        old_name: "XRotation".to_string(),
+        new_name: "RotateX".to_string(),
+    });
+    registry.register(Migration::RenameField {
+        version: (0, 3, 0),
+        operation: "RotateX".to_string(),
+        old_name: "angle".to_string(),
+        new_name: "theta".to_string(),
+    });
+
+    let mut conditional = Circuit::new();
+    conditional += RotateX::new(0, 0.5.into());
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(1, 0.2.into());
+    circuit += PragmaConditional::new("ro".to_string(), 0, conditional);
+    let mut input = CheatedBasisRotationInput::new();
+    let index = input.add_pauli_product("XRotation".to_string());
+    input
+        .add_linear_exp_val(
+            "XRotation".to_string(),
+            vec![(index, 1.0)].into_iter().collect(),
+        )
+        .unwrap();
+    let measurement = CheatedBasisRotation {
+        constant_circuit: Some(circuit.clone()),
+        circuits: vec![circuit],
+        input,
+    };
+
+    let old_data = serde_json::to_string(&measurement)
+        .unwrap()
+        .replace("\"RotateX\"", "\"XRotation\"")
+        .replace("\"theta\"", "\"angle\"");
+    let migrated: CheatedBasisRotation = registry.from_versioned_json(&old_data).unwrap();
+    assert_eq!(migrated, measurement);
+
+    let old_operation = json!({"XRotation": {"qubit": 0, "angle": 0.5}}).to_string();
+    let migrated: Operation = registry.from_versioned_json(&old_operation).unwrap();
+    assert_eq!(migrated, RotateX::new(0, 0.5.into()).into());
+}
+
+/// Test custom migrations
+#[test]
+fn custom_migration() {
+    fn add_definitions(value: &mut Value) -> Result<(), RoqoqoError> {
+        match value.as_object_mut() {
+            Some(map) => {
+                map.insert("definitions".to_string(), json!([]));
+                Ok(())
+            }
+            None => Err(RoqoqoError::SerializationError {
+                msg: "Circuit is not an object".to_string(),
+            }),
+        }
+    }
+    let mut registry = MigrationRegistry::new();
+    registry.register(Migration::Custom {
+        version: (0, 2, 0),
+        description: "Add definitions".to_string(),
+        migrate: add_definitions,
+    });
+    let json = json!({"operations": []}).to_string();
+    let circuit: Circuit = registry.from_versioned_json(&json).unwrap();
+    assert_eq!(circuit, Circuit::new());
+
+    let result: Result<Circuit, RoqoqoError> = registry.from_versioned_json("[]");
+    assert_eq!(
+        result,
+        Err(RoqoqoError::SerializationError {
+            msg:
+                "Migration 'Add definitions' failed: Serialization failed. Circuit is not an object"
+                    .to_string()
+        })
+    );
+}
+
+/// Test parsing of versions
+#[test]
+fn versions() {
+    assert_eq!(parse_version("0.5.0"), Ok((0, 5, 0)));
+    assert_eq!(parse_version("1.2.3-alpha.1"), Ok((1, 2, 3)));
+    assert!(parse_version("1.2").is_err());
+}
@@ -0,0 +1,177 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::operations::*;
+use roqoqo::statistics::CircuitStatistics;
+use roqoqo::Circuit;
+#[cfg(feature = "serialize")]
+use serde_test::{assert_tokens, Configure, Token};
+use std::collections::BTreeMap;
+
+/// Test depth, critical path and gate counts
+#[test]
+fn depth_and_counts() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0); // 0
+    circuit += TGate::new(1); // 1
+    circuit += CNOT::new(0, 1); // 2
+    circuit += RotateZ::new(2, 0.3.into()); // 3
+    circuit += TGate::new(2); // 4
+    circuit += ControlledPauliZ::new(1, 2); // 5
+    circuit += CNOT::new(0, 2); // 6
+    circuit += PragmaGlobalPhase::new(0.1.into()); // 7
+    let statistics = CircuitStatistics::from(&circuit);
+
+    assert_eq!(statistics.number_operations, 8);
+    assert_eq!(statistics.number_gates, 7);
+    assert_eq!(statistics.depth, 4);
+    assert_eq!(statistics.critical_path, vec![0, 2, 5, 6]);
+    assert_eq!(statistics.two_qubit_depth, 3);
+    assert_eq!(statistics.t_count, 2);
+    assert_eq!(statistics.clifford_count, 4);
+    assert_eq!(statistics.non_clifford_count, 3);
+    assert_eq!(statistics.number_measurements, 0);
+    assert_eq!(statistics.qubits, vec![0, 1, 2]);
+    assert!(!statistics.involves_all_qubits);
+
+    let mut gate_counts_per_qubit: BTreeMap<usize, usize> = BTreeMap::new();
+    gate_counts_per_qubit.insert(0, 3);
+    gate_counts_per_qubit.insert(1, 3);
+    gate_counts_per_qubit.insert(2, 4);
+    assert_eq!(statistics.gate_counts_per_qubit, gate_counts_per_qubit);
+
+    assert_eq!(statistics.operation_counts.get("CNOT"), Some(&2));
+    assert_eq!(statistics.operation_counts.get("TGate"), Some(&2));
+    assert_eq!(
+        statistics.operation_counts.get("PragmaGlobalPhase"),
+        Some(&1)
+    );
+    assert_eq!(statistics.operation_counts.get("PauliX"), None);
+}
+
+/// Test measurements, register footprint and operations acting on all qubits
+#[test]
+fn measurements_and_registers() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += DefinitionFloat::new("fl".to_string(), 2, false);
+    circuit += DefinitionComplex::new("co".to_string(), 4, true);
+    circuit += DefinitionUsize::new("us".to_string(), 1, false);
+    circuit += PauliX::new(4);
+    circuit += MeasureQubit::new(4, "ro".to_string(), 0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 100);
+    let statistics = CircuitStatistics::from(circuit);
+
+    assert_eq!(statistics.number_operations, 3);
+    assert_eq!(statistics.number_measurements, 2);
+    assert_eq!(statistics.qubits, vec![4]);
+    assert!(statistics.involves_all_qubits);
+    assert_eq!(statistics.bit_registers.get("ro"), Some(&3));
+    assert_eq!(statistics.float_registers.get("fl"), Some(&2));
+    assert_eq!(statistics.complex_registers.get("co"), Some(&4));
+    assert_eq!(statistics.usize_registers.get("us"), Some(&1));
+    assert_eq!(statistics.operation_counts.get("DefinitionBit"), Some(&1));
+    assert_eq!(statistics.depth, 1);
+}
+
+/// Test that only gate operations contribute to the depth and critical path
+#[test]
+fn depth_counts_gates() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += Hadamard::new(0); // 0
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0); // 1
+    circuit += PragmaActiveReset::new(0); // 2
+    circuit += PragmaGlobalPhase::new(0.1.into()); // 3
+    circuit += Hadamard::new(0); // 4
+    let statistics = CircuitStatistics::from(&circuit);
+
+    assert_eq!(statistics.depth, 2);
+    assert_eq!(statistics.critical_path, vec![0, 4]);
+    assert_eq!(statistics.number_measurements, 1);
+}
+
+/// Test that MolmerSorensenXX is counted as a Clifford gate
+#[test]
+fn clifford_molmer_sorensen() {
+    let mut circuit = Circuit::new();
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += VariableMSXX::new(0, 1, 0.3.into());
+    let statistics = CircuitStatistics::from(&circuit);
+
+    assert_eq!(statistics.clifford_count, 1);
+    assert_eq!(statistics.non_clifford_count, 1);
+}
+
+/// Test collection of symbolic parameters
+#[test]
+fn symbolic_parameters() {
+    let mut inner = Circuit::new();
+    inner += RotateX::new(0, "gamma".into());
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += RotateZ::new(0, "theta".into());
+    circuit += RotateX::new(1, CalculatorFloat::from("2 * theta + sin(phi)"));
+    circuit += RotateY::new(1, 0.5.into());
+    circuit += PragmaConditional::new("ro".to_string(), 0, inner);
+    let statistics = CircuitStatistics::from(&circuit);
+    assert_eq!(
+        statistics.symbolic_parameters,
+        vec!["gamma".to_string(), "phi".to_string(), "theta".to_string()]
+    );
+
+    let statistics = CircuitStatistics::from(Circuit::new());
+    assert!(statistics.symbolic_parameters.is_empty());
+    assert_eq!(statistics.depth, 0);
+    assert_eq!(statistics.two_qubit_depth, 0);
+}
+
+/// Test serialization of the statistics of an empty circuit
+#[cfg(feature = "serialize")]
+#[test]
+fn serde_readable() {
+    let statistics = CircuitStatistics::from(Circuit::new());
+    let mut tokens = vec![Token::Struct {
+        name: "CircuitStatistics",
+        len: 18,
+    }];
+    for (field, token) in [
+        ("number_operations", Token::U64(0)),
+        ("number_gates", Token::U64(0)),
+        ("depth", Token::U64(0)),
+        ("two_qubit_depth", Token::U64(0)),
+        ("operation_counts", Token::Map { len: Some(0) }),
+        ("gate_counts_per_qubit", Token::Map { len: Some(0) }),
+        ("critical_path", Token::Seq { len: Some(0) }),
+        ("t_count", Token::U64(0)),
+        ("clifford_count", Token::U64(0)),
+        ("non_clifford_count", Token::U64(0)),
+        ("number_measurements", Token::U64(0)),
+        ("symbolic_parameters", Token::Seq { len: Some(0) }),
+        ("qubits", Token::Seq { len: Some(0) }),
+        ("involves_all_qubits", Token::Bool(false)),
+        ("bit_registers", Token::Map { len: Some(0) }),
+        ("float_registers", Token::Map { len: Some(0) }),
+        ("complex_registers", Token::Map { len: Some(0) }),
+        ("usize_registers", Token::Map { len: Some(0) }),
+    ] {
+        tokens.push(Token::Str(field));
+        match token {
+            Token::Map { .. } => tokens.extend([token, Token::MapEnd]),
+            Token::Seq { .. } => tokens.extend([token, Token::SeqEnd]),
+            _ => tokens.push(token),
+        }
+    }
+    tokens.push(Token::StructEnd);
+    assert_tokens(&statistics.readable(), &tokens);
+}
@@ -0,0 +1,236 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ndarray::Array2;
+use num_complex::Complex64;
+use qoqo_calculator::{CalculatorError, CalculatorFloat};
+use roqoqo::operations::*;
+use roqoqo::unitary::{
+    circuit_unitary, circuit_unitary_on_qubits, circuits_equivalent, equal_up_to_global_phase,
+};
+use roqoqo::{Circuit, RoqoqoError};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use test_case::test_case;
+
+fn kron(first: &Array2<Complex64>, second: &Array2<Complex64>) -> Array2<Complex64> {
+    let (n1, m1) = (first.nrows(), first.ncols());
+    let (n2, m2) = (second.nrows(), second.ncols());
+    Array2::from_shape_fn((n1 * n2, m1 * m2), |(i, j)| {
+        first[(i / n2, j / m2)] * second[(i % n2, j % m2)]
+    })
+}
+
+fn assert_close(first: &Array2<Complex64>, second: &Array2<Complex64>) {
+    assert_eq!(first.shape(), second.shape());
+    for (a, b) in first.iter().zip(second.iter()) {
+        assert!((a - b).norm() < 1e-10, "{} != {}", a, b);
+    }
+}
+
+/// Test that the unitary of a circuit with a single gate is the unitary of the gate
+#[test_case(GateOperation::from(PauliX::new(0)); "PauliX")]
+#[test_case(GateOperation::from(Hadamard::new(0)); "Hadamard")]
+#[test_case(GateOperation::from(RotateY::new(0, 0.3.into())); "RotateY")]
+#[test_case(GateOperation::from(CNOT::new(1, 0)); "CNOT")]
+#[test_case(GateOperation::from(ISwap::new(1, 0)); "ISwap")]
+#[test_case(GateOperation::from(XY::new(1, 0, 0.4.into())); "XY")]
+fn single_gate_unitary(gate: GateOperation) {
+    let mut circuit = Circuit::new();
+    circuit += gate.clone();
+    let (qubits, unitary) = circuit_unitary(&circuit).unwrap();
+    assert_eq!(qubits.len(), unitary.nrows().trailing_zeros() as usize);
+    assert_close(&unitary, &gate.unitary_matrix().unwrap());
+}
+
+/// Test that the lowest qubit is the least significant qubit
+#[test]
+fn qubit_ordering() {
+    let mut circuit = Circuit::new();
+    circuit += CNOT::new(0, 1);
+    let (qubits, unitary) = circuit_unitary(&circuit).unwrap();
+    assert_eq!(qubits, vec![0, 1]);
+    // |q1=0, q0=1> is mapped to |q1=1, q0=1>
+    assert_eq!(unitary[(3, 1)], Complex64::new(1.0, 0.0));
+    assert_eq!(unitary[(1, 3)], Complex64::new(1.0, 0.0));
+    assert_eq!(unitary[(0, 0)], Complex64::new(1.0, 0.0));
+    assert_eq!(unitary[(2, 2)], Complex64::new(1.0, 0.0));
+}
+
+/// Test embedding of gates acting on sparse qubit indices
+#[test]
+fn sparse_qubits() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(3);
+    circuit += PauliY::new(7);
+    let (qubits, unitary) = circuit_unitary(&circuit).unwrap();
+    assert_eq!(qubits, vec![3, 7]);
+    let expected = kron(
+        &PauliY::new(7).unitary_matrix().unwrap(),
+        &Hadamard::new(3).unitary_matrix().unwrap(),
+    );
+    assert_close(&unitary, &expected);
+}
+
+/// Test the product of consecutive gates
+#[test]
+fn gate_product() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, 0.2.into());
+    circuit += RotateZ::new(0, 0.7.into());
+    let (_, unitary) = circuit_unitary(&circuit).unwrap();
+    let expected = RotateZ::new(0, 0.7.into())
+        .unitary_matrix()
+        .unwrap()
+        .dot(&RotateX::new(0, 0.2.into()).unitary_matrix().unwrap());
+    assert_close(&unitary, &expected);
+}
+
+/// Test unitary on explicitly given qubits
+#[test]
+fn unitary_on_qubits() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(1);
+    let unitary = circuit_unitary_on_qubits(&circuit, &[0, 1]).unwrap();
+    let expected = kron(
+        &PauliX::new(1).unitary_matrix().unwrap(),
+        &Array2::<Complex64>::eye(2),
+    );
+    assert_close(&unitary, &expected);
+
+    let error = circuit_unitary_on_qubits(&circuit, &[0]);
+    assert_eq!(error, Err(RoqoqoError::QubitMappingError { qubit: 1 }));
+}
+
+/// Test global phase pragma is applied to unitary
+#[test]
+fn global_phase() {
+    let mut circuit = Circuit::new();
+    circuit += PauliZ::new(0);
+    circuit += PragmaGlobalPhase::new(CalculatorFloat::FRAC_PI_2);
+    let (_, unitary) = circuit_unitary(&circuit).unwrap();
+    let expected = PauliZ::new(0)
+        .unitary_matrix()
+        .unwrap()
+        .mapv(|x| x * Complex64::new(0.0, 1.0));
+    assert_close(&unitary, &expected);
+}
+
+/// Test errors for operations that are not unitary
+#[test]
+fn non_unitary_errors() {
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert_eq!(
+        circuit_unitary(&circuit),
+        Err(RoqoqoError::OperationNotUnitary {
+            hqslang: "MeasureQubit"
+        })
+    );
+
+    let mut circuit = Circuit::new();
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 10);
+    assert_eq!(
+        circuit_unitary(&circuit),
+        Err(RoqoqoError::OperationNotUnitary {
+            hqslang: "PragmaRepeatedMeasurement"
+        })
+    );
+
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    assert_eq!(
+        circuit_unitary(&circuit),
+        Err(RoqoqoError::CalculatorError(
+            CalculatorError::FloatSymbolicNotConvertable {
+                val: "theta".to_string()
+            }
+        ))
+    );
+}
+
+/// Test equivalence of circuits up to a global phase
+#[test]
+fn equivalence() {
+    let mut cnot = Circuit::new();
+    cnot += CNOT::new(0, 1);
+    let mut decomposed = Circuit::new();
+    decomposed += Hadamard::new(1);
+    decomposed += ControlledPauliZ::new(0, 1);
+    decomposed += Hadamard::new(1);
+    assert!(circuits_equivalent(&cnot, &decomposed, 1e-10, None).unwrap());
+
+    let mut rotation = Circuit::new();
+    rotation += RotateZ::new(0, PI.into());
+    let mut pauli = Circuit::new();
+    pauli += PauliZ::new(0);
+    assert!(circuits_equivalent(&rotation, &pauli, 1e-10, None).unwrap());
+
+    let mut other = Circuit::new();
+    other += PauliX::new(0);
+    assert!(!circuits_equivalent(&other, &pauli, 1e-10, None).unwrap());
+
+    // Circuits acting on different qubits are compared on the union of qubits
+    let mut shifted = Circuit::new();
+    shifted += PauliZ::new(1);
+    assert!(!circuits_equivalent(&shifted, &pauli, 1e-10, None).unwrap());
+
+    // Tolerance is respected
+    let mut almost = Circuit::new();
+    almost += RotateZ::new(0, (PI + 1e-6).into());
+    assert!(!circuits_equivalent(&almost, &pauli, 1e-10, None).unwrap());
+    assert!(circuits_equivalent(&almost, &pauli, 1e-5, None).unwrap());
+}
+
+/// Test equivalence of circuits up to a permutation of the output qubits
+#[test]
+fn equivalence_permutation() {
+    let mut first = Circuit::new();
+    first += Hadamard::new(0);
+    first += CNOT::new(0, 1);
+    first += RotateX::new(1, 0.3.into());
+    let mut second = first.clone();
+    second += SWAP::new(0, 1);
+
+    assert!(!circuits_equivalent(&first, &second, 1e-10, None).unwrap());
+    let mut permutation: HashMap<usize, usize> = HashMap::new();
+    permutation.insert(0, 1);
+    permutation.insert(1, 0);
+    assert!(circuits_equivalent(&first, &second, 1e-10, Some(&permutation)).unwrap());
+
+    let mut invalid: HashMap<usize, usize> = HashMap::new();
+    invalid.insert(0, 1);
+    assert_eq!(
+        circuits_equivalent(&first, &second, 1e-10, Some(&invalid)),
+        Err(RoqoqoError::QubitMappingError { qubit: 0 })
+    );
+}
+
+/// Test equality of matrices up to global phase
+#[test]
+fn matrix_equal_up_to_global_phase() {
+    let first = Hadamard::new(0).unitary_matrix().unwrap();
+    let second = first.mapv(|x| x * Complex64::new(0.0, 1.0).exp());
+    assert!(equal_up_to_global_phase(&first, &second, 1e-10));
+    assert!(!equal_up_to_global_phase(
+        &first,
+        &Array2::<Complex64>::eye(2),
+        1e-10
+    ));
+    assert!(!equal_up_to_global_phase(
+        &first,
+        &Array2::<Complex64>::eye(4),
+        1e-10
+    ));
+}
@@ -0,0 +1,265 @@
+// Copyright © 2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the static validation of circuits
+
+use ndarray::{array, Array1, Array2};
+use num_complex::Complex64;
+use roqoqo::operations::*;
+use roqoqo::validation::*;
+use roqoqo::Circuit;
+use std::collections::HashMap;
+use test_case::test_case;
+
+/// Returns the kinds and severities of the diagnostics with their operation indices
+fn kinds(circuit: &Circuit) -> Vec<(usize, Severity, DiagnosticKind)> {
+    validate_circuit(circuit)
+        .into_iter()
+        .map(|diagnostic| (diagnostic.index, diagnostic.severity, diagnostic.kind))
+        .collect()
+}
+
+/// Test that a valid circuit has no diagnostics
+#[test]
+fn valid_circuit() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += DefinitionComplex::new("state".to_string(), 4, true);
+    circuit += InputSymbolic::new("theta".to_string(), 0.5);
+    circuit += RotateX::new(0, "theta".into());
+    circuit += CNOT::new(0, 1);
+    circuit += PragmaGetStateVector::new("state".to_string(), None);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let mut conditional = Circuit::new();
+    conditional += PauliX::new(1);
+    circuit += PragmaConditional::new("ro".to_string(), 0, conditional);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 100);
+    assert_eq!(validate_circuit(&circuit), vec![]);
+}
+
+/// Test measurements into undefined registers, registers of the wrong type and past the register length
+#[test_case(MeasureQubit::new(0, "missing".to_string(), 0).into(), DiagnosticKind::UndefinedRegister; "undefined")]
+#[test_case(MeasureQubit::new(0, "fl".to_string(), 0).into(), DiagnosticKind::WrongRegisterType; "wrong type")]
+#[test_case(MeasureQubit::new(0, "ro".to_string(), 2).into(), DiagnosticKind::RegisterIndexOutOfRange; "index")]
+#[test_case(PragmaGetStateVector::new("ro".to_string(), None).into(), DiagnosticKind::WrongRegisterType; "state vector")]
+#[test_case(PragmaGetOccupationProbability::new("missing".to_string(), None).into(), DiagnosticKind::UndefinedRegister; "occupation")]
+#[test_case(PragmaRepeatedMeasurement::new("ro".to_string(), None, 10).into(), DiagnosticKind::RegisterIndexOutOfRange; "repeated")]
+#[test_case(ClassicalXor::new("fl".to_string(), vec![0], "ro".to_string(), 1).into(), DiagnosticKind::WrongRegisterType; "classical input type")]
+#[test_case(ClassicalAnd::new("ro".to_string(), vec![0, 2], "ro".to_string(), 1).into(), DiagnosticKind::RegisterIndexOutOfRange; "classical input index")]
+#[test_case(ClassicalCopy::new("ro".to_string(), 0, "missing".to_string(), 0).into(), DiagnosticKind::UndefinedRegister; "classical output")]
+#[test_case(ClassicalGreaterThan::new("ro".to_string(), 0, 0.5.into(), "ro".to_string(), 1).into(), DiagnosticKind::WrongRegisterType; "comparison input type")]
+#[test_case(ClassicalLessThan::new("fl".to_string(), 0, 0.5.into(), "fl".to_string(), 0).into(), DiagnosticKind::WrongRegisterType; "comparison output type")]
+fn readout_errors(operation: Operation, kind: DiagnosticKind) {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += DefinitionFloat::new("fl".to_string(), 1, true);
+    circuit += PauliX::new(2);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += PragmaGetPauliProduct::new(HashMap::new(), "fl".to_string(), Circuit::new());
+    circuit.add_operation(operation);
+    assert_eq!(kinds(&circuit), vec![(5, Severity::Error, kind)]);
+}
+
+/// Test the mapping of repeated measurements
+#[test]
+fn repeated_measurement_mapping() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PauliX::new(3);
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    mapping.insert(3, 1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), Some(mapping.clone()), 10);
+    assert_eq!(kinds(&circuit), vec![]);
+
+    mapping.insert(0, 2);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), Some(mapping), 10);
+    let diagnostics = validate_circuit(&circuit);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "Index 2 is out of range of register ro with length 2"
+    );
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "error at operation 1: Index 2 is out of range of register ro with length 2"
+    );
+}
+
+/// Test conditions on undefined or unwritten registers and errors inside the conditional circuit
+#[test]
+fn conditional() {
+    let mut inner = Circuit::new();
+    inner += MeasureQubit::new(0, "other".to_string(), 0);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PragmaConditional::new("ro".to_string(), 0, Circuit::new());
+    circuit += PragmaConditional::new("missing".to_string(), 0, inner);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += PragmaConditional::new("ro".to_string(), 1, Circuit::new());
+    assert_eq!(
+        kinds(&circuit),
+        vec![
+            (
+                1,
+                Severity::Warning,
+                DiagnosticKind::UnwrittenConditionRegister
+            ),
+            (2, Severity::Error, DiagnosticKind::UndefinedRegister),
+            (2, Severity::Error, DiagnosticKind::UndefinedRegister),
+            (4, Severity::Error, DiagnosticKind::RegisterIndexOutOfRange),
+        ]
+    );
+}
+
+/// Test conditions on several bits computed with classical operations
+#[test]
+fn conditional_pattern() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += DefinitionBit::new("syndrome".to_string(), 2, false);
+    circuit +=
+        PragmaConditionalPattern::new("syndrome".to_string(), vec![(0, true)], Circuit::new());
+    for qubit in 0..3 {
+        circuit += MeasureQubit::new(qubit, "ro".to_string(), qubit);
+    }
+    circuit += ClassicalXor::new("ro".to_string(), vec![0, 1], "syndrome".to_string(), 0);
+    circuit += ClassicalXor::new("ro".to_string(), vec![1, 2], "syndrome".to_string(), 1);
+    let mut correction = Circuit::new();
+    correction += MeasureQubit::new(0, "ro".to_string(), 3);
+    circuit += PragmaConditionalPattern::new(
+        "syndrome".to_string(),
+        vec![(0, true), (1, false)],
+        correction,
+    );
+    assert_eq!(
+        kinds(&circuit),
+        vec![
+            (
+                2,
+                Severity::Warning,
+                DiagnosticKind::UnwrittenConditionRegister
+            ),
+            (8, Severity::Error, DiagnosticKind::RegisterIndexOutOfRange),
+        ]
+    );
+}
+
+/// Test loops with invalid repetitions and exit conditions that are never written
+#[test]
+fn loops() {
+    let mut attempt = Circuit::new();
+    attempt += Hadamard::new(0);
+    attempt += MeasureQubit::new(0, "ro".to_string(), 0);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += DefinitionBit::new("flag".to_string(), 1, false);
+    circuit += PragmaRepeatUntilSuccess::new("ro".to_string(), 0, 5, attempt.clone());
+    circuit += PragmaRepeatUntilSuccess::new("flag".to_string(), 0, 5, attempt.clone());
+    circuit += PragmaRepeatUntilSuccess::new("ro".to_string(), 1, 5, attempt.clone());
+    circuit += PragmaLoop::new(2.5.into(), attempt.clone());
+    circuit += PragmaLoop::new("repetitions".into(), attempt);
+    assert_eq!(
+        kinds(&circuit),
+        vec![
+            (
+                3,
+                Severity::Warning,
+                DiagnosticKind::UnwrittenConditionRegister
+            ),
+            (4, Severity::Error, DiagnosticKind::RegisterIndexOutOfRange),
+            (5, Severity::Error, DiagnosticKind::InvalidRepetitions),
+        ]
+    );
+}
+
+/// Test operations acting on qubits after a repeated measurement
+#[test]
+fn operation_after_repeated_measurement() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), None, 10);
+    circuit += PragmaGlobalPhase::new(1.0.into());
+    circuit += Hadamard::new(0);
+    let diagnostics = validate_circuit(&circuit);
+    assert_eq!(
+        kinds(&circuit),
+        vec![(
+            4,
+            Severity::Error,
+            DiagnosticKind::OperationAfterRepeatedMeasurement
+        )]
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "Hadamard acts on qubits after the PragmaRepeatedMeasurement at operation 2"
+    );
+}
+
+/// Test duplicate and unused definitions
+#[test]
+fn definitions() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += DefinitionFloat::new("ro".to_string(), 1, true);
+    circuit += DefinitionUsize::new("unused".to_string(), 1, false);
+    circuit += InputSymbolic::new("theta".to_string(), 0.0);
+    circuit += InputSymbolic::new("phi".to_string(), 0.0);
+    circuit += RotateZ::new(0, "phi".into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert_eq!(
+        kinds(&circuit),
+        vec![
+            (1, Severity::Error, DiagnosticKind::DuplicateDefinition),
+            (2, Severity::Warning, DiagnosticKind::UnusedDefinition),
+            (3, Severity::Warning, DiagnosticKind::UnusedDefinition),
+            (6, Severity::Error, DiagnosticKind::WrongRegisterType),
+        ]
+    );
+}
+
+/// Test the dimensions of state vectors and density matrices
+#[test_case(Array1::zeros(4), vec![]; "state vector")]
+#[test_case(Array1::zeros(8), vec![]; "larger state vector")]
+#[test_case(Array1::zeros(2), vec![DiagnosticKind::StateDimensionMismatch]; "small state vector")]
+#[test_case(Array1::zeros(6), vec![DiagnosticKind::StateDimensionMismatch]; "not power of two")]
+fn state_vector_dimension(statevector: Array1<Complex64>, expected: Vec<DiagnosticKind>) {
+    let mut circuit = Circuit::new();
+    circuit += PragmaSetStateVector::new(statevector);
+    circuit += CNOT::new(0, 1);
+    let diagnostics: Vec<DiagnosticKind> = validate_circuit(&circuit)
+        .into_iter()
+        .map(|diagnostic| diagnostic.kind)
+        .collect();
+    assert_eq!(diagnostics, expected);
+}
+
+/// Test the dimensions of density matrices
+#[test]
+fn density_matrix_dimension() {
+    let zero = Complex64::new(0.0, 0.0);
+    let mut circuit = Circuit::new();
+    circuit += PragmaSetDensityMatrix::new(array![[zero, zero], [zero, zero]]);
+    circuit += PauliX::new(0);
+    assert_eq!(validate_circuit(&circuit), vec![]);
+
+    let mut circuit = Circuit::new();
+    circuit += PragmaSetDensityMatrix::new(Array2::zeros((2, 4)));
+    let diagnostics = validate_circuit(&circuit);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "Density matrix is not square but 2x4"
+    );
+}